@@ -5,7 +5,7 @@ System Management Endpoints
 Endpoints for system health monitoring, cleanup, and maintenance.
 """
 
-from fastapi import APIRouter, HTTPException, BackgroundTasks
+from fastapi import APIRouter, HTTPException, BackgroundTasks, Request
 from pydantic import BaseModel
 from typing import Dict, Any, List
 import logging
@@ -82,6 +82,23 @@ async def check_system_health():
         raise HTTPException(status_code=500, detail=f"Health check failed: {str(e)}")
 
 
+@router.post("/shutdown")
+async def request_graceful_shutdown(request: Request):
+    """
+    Ask the server to stop gracefully: uvicorn finishes in-flight requests and
+    runs the shutdown handlers (flush, close databases) before exiting.
+    Used by the desktop shell before it falls back to killing the process.
+    """
+    server = getattr(request.app.state, "uvicorn_server", None)
+    if server is None:
+        logger.warning("🛑 SHUTDOWN REQUEST ignored – server handle not available")
+        return {"status": "unsupported"}
+
+    logger.info("🛑 SHUTDOWN REQUEST – stopping server gracefully")
+    server.should_exit = True
+    return {"status": "shutting_down"}
+
+
 @router.post("/cleanup", response_model=CleanupResponse)
 async def perform_system_cleanup(background_tasks: BackgroundTasks):
     """
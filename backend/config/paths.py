@@ -44,8 +44,16 @@ def project_root() -> Path:
 def app_data_root() -> Path:
     """
     Stable root for user data in frozen mode: LOCALAPPDATA\\Plattera\\Data.
+    The desktop shell passes PLATTERA_DATA_DIR when the user picked a custom
+    data location during onboarding.
     In dev this is not used for dossiers/PLSS so behavior stays as-is.
     """
+    override = os.environ.get("PLATTERA_DATA_DIR")
+    if override:
+        root = Path(override)
+        root.mkdir(parents=True, exist_ok=True)
+        return root
+
     local_appdata = os.environ.get("LOCALAPPDATA") or os.path.expanduser(r"~\AppData\Local")
     root = Path(local_appdata) / "Plattera" / "Data"
     root.mkdir(parents=True, exist_ok=True)
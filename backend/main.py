@@ -7,6 +7,8 @@ Entry point for the Plattera backend API server.
 
 import uvicorn
 import logging
+import os
+import hmac
 import sys
 import asyncio
 from fastapi import FastAPI, Request
@@ -26,7 +28,7 @@ except Exception:
     pass
 
 from api.router import api_router
-from services.logging_service import init_logging
+from services.logging_service import init_logging, correlation_id_var
 from utils.health_monitor import get_health_monitor
 from pipelines.mapping.georeference.georeference_service import GeoreferenceService
 from services.registry import get_registry
@@ -117,23 +119,6 @@ app = FastAPI(
     redoc_url="/redoc"
 )
 
-# Add CORS middleware – restrict to Tauri desktop and localhost dev by default
-# This works without any .env and is agnostic to the frontend port.
-# Allow:
-# - tauri://localhost        (older Tauri origin)
-# - http(s)://tauri.localhost  (Tauri v2 WebView origin on Windows)
-# - http(s)://localhost / 127.0.0.1 : any port
-SAFE_LOCAL_REGEX = r"^(tauri://localhost|https?://tauri\.localhost|https?://(localhost|127\.0\.0\.1)(:\d+)?)$"
-
-app.add_middleware(
-    CORSMiddleware,
-    allow_origins=[],              # use regex instead
-    allow_origin_regex=SAFE_LOCAL_REGEX,
-    allow_credentials=True,
-    allow_methods=["*"],
-    allow_headers=["*"],
-)
-
 # Mount static images (originals and processed)
 try:
     images_root = dossiers_images_root()
@@ -228,6 +213,50 @@ async def shutdown_event():
     except Exception as e:
         logger.error(f"❌ Error during shutdown: {e}")
 
+# Shared secret set by the desktop shell. When present, callers must send it
+# in X-Plattera-Token (or as ?plattera_token= where the header can't be set,
+# e.g. EventSource). The webview gets it from the shell or goes through the
+# shell's backend_request. Identity and liveness endpoints stay open so the
+# shell can tell a Plattera backend (and whether it requires auth) from other
+# software, and static images stay open because <img> loads send no headers.
+SHELL_AUTH_TOKEN = os.environ.get("PLATTERA_AUTH_TOKEN") or None
+AUTH_EXEMPT_PATHS = {"/", "/api/health"}
+
+@app.middleware("http")
+async def shell_auth_middleware(request: Request, call_next):
+    """Reject callers that don't present the shell's shared secret"""
+    if (
+        SHELL_AUTH_TOKEN is None
+        or request.method == "OPTIONS"
+        or request.url.path in AUTH_EXEMPT_PATHS
+        or request.url.path.startswith("/static/")
+    ):
+        return await call_next(request)
+    supplied = (
+        request.headers.get("X-Plattera-Token")
+        or request.query_params.get("plattera_token")
+        or ""
+    )
+    if hmac.compare_digest(supplied.encode(), SHELL_AUTH_TOKEN.encode()):
+        return await call_next(request)
+    logger.warning(f"🔒 Rejected unauthenticated {request.method} {request.url.path}")
+    return JSONResponse(status_code=401, content={"error": "unauthorized"})
+
+@app.middleware("http")
+async def correlation_id_middleware(request: Request, call_next):
+    """Tag log lines with the caller's X-Correlation-ID and echo it back"""
+    cid = request.headers.get("X-Correlation-ID")
+    if not cid:
+        return await call_next(request)
+    token = correlation_id_var.set(cid)
+    try:
+        logger.info(f"🔗 TRACE [cid={cid}] {request.method} {request.url.path}")
+        response = await call_next(request)
+        response.headers["X-Correlation-ID"] = cid
+        return response
+    finally:
+        correlation_id_var.reset(token)
+
 @app.middleware("http")
 async def add_process_time_header(request: Request, call_next):
     """Add processing time header to responses"""
@@ -237,6 +266,26 @@ async def add_process_time_header(request: Request, call_next):
     response.headers["X-Process-Time"] = str(process_time)
     return response
 
+# Add CORS middleware – restrict to Tauri desktop and localhost dev by default
+# Added after the other middleware so it is the outermost layer: responses
+# they produce themselves (a 401 from the auth check) still carry CORS
+# headers, and the webview sees the status instead of a network error.
+# This works without any .env and is agnostic to the frontend port.
+# Allow:
+# - tauri://localhost        (older Tauri origin)
+# - http(s)://tauri.localhost  (Tauri v2 WebView origin on Windows)
+# - http(s)://localhost / 127.0.0.1 : any port
+SAFE_LOCAL_REGEX = r"^(tauri://localhost|https?://tauri\.localhost|https?://(localhost|127\.0\.0\.1)(:\d+)?)$"
+
+app.add_middleware(
+    CORSMiddleware,
+    allow_origins=[],              # use regex instead
+    allow_origin_regex=SAFE_LOCAL_REGEX,
+    allow_credentials=True,
+    allow_methods=["*"],
+    allow_headers=["*"],
+)
+
 @app.exception_handler(Exception)
 async def global_exception_handler(request: Request, exc: Exception):
     """Global exception handler with cleanup"""
@@ -262,7 +311,9 @@ async def root():
     """Root endpoint"""
     return {
         "message": "Plattera API v2.0",
+        "version": app.version,
         "status": "running",
+        "auth_required": SHELL_AUTH_TOKEN is not None,
         "docs": "/docs",
         "health": "/api/health"
     }
@@ -271,11 +322,23 @@ if __name__ == "__main__":
     logger.info("🔧 Starting Plattera API Server in development mode")
     
     # Configure uvicorn settings
-    uvicorn.run(
+    config = uvicorn.Config(
         app,
-        host="127.0.0.1",
-        port=8000,
+        # The desktop shell may force loopback when it finds the server exposed.
+        host=os.environ.get("PLATTERA_BACKEND_HOST", "127.0.0.1"),
+        # The desktop shell picks the port (e.g. warm-standby restarts).
+        port=int(os.environ.get("PLATTERA_BACKEND_PORT", "8000")),
         reload=False,  # ← This will fix it
         log_level="info",
         access_log=False,  # Disable per-request access log spam
-    )
\ No newline at end of file
+    )
+    # Named pipe / Unix socket instead of TCP when the shell asks for it.
+    from utils import local_transport
+    server = local_transport.create_server(config)
+    # Exposed so /api/shutdown can stop the server gracefully (the desktop
+    # shell uses it before falling back to a hard kill).
+    app.state.uvicorn_server = server
+    # Exit together with the desktop app on Linux/macOS (no Job Object there).
+    from utils import parent_watch
+    parent_watch.install(on_parent_exit=lambda: setattr(server, "should_exit", True))
+    local_transport.run(server)
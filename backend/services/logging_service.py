@@ -1,3 +1,4 @@
+import contextvars
 import logging
 import os
 from logging.handlers import RotatingFileHandler
@@ -10,6 +11,21 @@ LOG_LEVEL = os.getenv("LOG_LEVEL", "INFO").upper()
 RING_BUFFER_SIZE = int(os.getenv("RING_BUFFER_SIZE", "2000"))
 LOG_FILE = os.path.join(LOG_DIR, "app.log")
 
+# Correlation ID of the request being handled (set by the HTTP middleware in
+# main.py from the desktop shell's X-Correlation-ID header).
+correlation_id_var: contextvars.ContextVar[str | None] = contextvars.ContextVar(
+    "correlation_id", default=None
+)
+
+
+class CorrelationIdFilter(logging.Filter):
+    """Expose the active correlation ID to formatters as %(cid)s."""
+
+    def filter(self, record: logging.LogRecord) -> bool:
+        cid = correlation_id_var.get()
+        record.cid = f" [cid={cid}]" if cid else ""
+        return True
+
 
 class RingBufferHandler(logging.Handler):
     def __init__(self, maxlen: int = 2000):
@@ -49,7 +65,7 @@ def get_ring_handler() -> RingBufferHandler:
 def init_logging():
     os.makedirs(LOG_DIR, exist_ok=True)
 
-    fmt = logging.Formatter("%(asctime)s %(levelname)s %(name)s: %(message)s")
+    fmt = logging.Formatter("%(asctime)s %(levelname)s %(name)s: %(message)s%(cid)s")
 
     root = logging.getLogger()
     # Preserve any level previously set by the app; otherwise, apply env level
@@ -64,6 +80,7 @@ def init_logging():
         encoding="utf-8",
     )
     file_handler.setFormatter(fmt)
+    file_handler.addFilter(CorrelationIdFilter())
     root.addHandler(file_handler)
 
     # In-memory ring buffer (default WARNING+)
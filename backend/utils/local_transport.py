@@ -0,0 +1,124 @@
+"""
+Local Transport
+===============
+
+Lets the desktop shell talk to the backend over a Unix domain socket (Linux,
+macOS) or a named pipe (Windows) instead of a loopback TCP port, so no other
+user or process on the machine can reach the API through the network stack.
+
+The shell opts in by passing the socket path / pipe name in
+PLATTERA_BACKEND_SOCKET. Without it the server binds TCP exactly as before.
+
+- Unix: the socket file is created with owner-only permissions and removed
+  again when the server stops.
+- Windows: uvicorn has no named pipe support, so the server's startup is
+  overridden to serve the HTTP protocol on the proactor loop's pipe server.
+"""
+
+import asyncio
+import logging
+import os
+import socket
+import sys
+from typing import List, Optional
+
+import uvicorn
+
+logger = logging.getLogger(__name__)
+
+SOCKET_ENV = "PLATTERA_BACKEND_SOCKET"
+
+
+def socket_path() -> Optional[str]:
+    """The socket path / pipe name requested by the shell, if any."""
+    return os.environ.get(SOCKET_ENV) or None
+
+
+class _PipeListener:
+    """Gives a proactor pipe server the close()/wait_closed() pair uvicorn expects."""
+
+    def __init__(self, pipe_servers: List) -> None:
+        self._pipe_servers = pipe_servers
+
+    def close(self) -> None:
+        for pipe_server in self._pipe_servers:
+            pipe_server.close()
+
+    async def wait_closed(self) -> None:
+        return None
+
+
+class _NamedPipeServer(uvicorn.Server):
+    """uvicorn server listening on a Windows named pipe."""
+
+    def __init__(self, config: uvicorn.Config, pipe_name: str) -> None:
+        super().__init__(config)
+        self.pipe_name = pipe_name
+
+    async def startup(self, sockets=None) -> None:
+        await self.lifespan.startup()
+        if self.lifespan.should_exit:
+            self.should_exit = True
+            return
+
+        config = self.config
+        loop = asyncio.get_running_loop()
+
+        def create_protocol(_loop=None):
+            return config.http_protocol_class(
+                config=config,
+                server_state=self.server_state,
+                app_state=self.lifespan.state,
+                _loop=_loop,
+            )
+
+        pipe_servers = await loop.start_serving_pipe(create_protocol, self.pipe_name)
+        self.servers = [_PipeListener(pipe_servers)]
+        logger.info(f"🔌 LOCAL_TRANSPORT ► serving on named pipe {self.pipe_name}")
+        self.started = True
+
+
+def create_server(config: uvicorn.Config) -> uvicorn.Server:
+    """A server for `config`, bound to the shell's named pipe when one was requested on Windows."""
+    path = socket_path()
+    if path and sys.platform == "win32":
+        return _NamedPipeServer(config, path)
+    return uvicorn.Server(config)
+
+
+def _bind_unix_socket(path: str) -> socket.socket:
+    if os.path.exists(path):
+        os.unlink(path)
+    sock = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+    # Owner-only from the moment the file exists (no chmod race).
+    old_umask = os.umask(0o177)
+    try:
+        sock.bind(path)
+    finally:
+        os.umask(old_umask)
+    sock.listen(2048)
+    sock.setblocking(False)
+    return sock
+
+
+def run(server: uvicorn.Server) -> None:
+    """Run `server` on the requested local socket, or on TCP when none was requested."""
+    path = socket_path()
+    if not path or isinstance(server, _NamedPipeServer):
+        server.run()
+        return
+    if sys.platform == "win32" or not hasattr(socket, "AF_UNIX"):
+        logger.warning(f"⚠️ LOCAL_TRANSPORT ► {SOCKET_ENV} ignored: no Unix sockets here; using TCP")
+        server.run()
+        return
+
+    sock = _bind_unix_socket(path)
+    logger.info(f"🔌 LOCAL_TRANSPORT ► serving on unix socket {path}")
+    try:
+        server.run(sockets=[sock])
+    finally:
+        sock.close()
+        try:
+            os.unlink(path)
+        except OSError:
+            pass
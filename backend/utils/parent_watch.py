@@ -0,0 +1,119 @@
+"""
+Parent Watch
+============
+
+Unix counterpart of the desktop shell's Windows Job Object: makes sure the
+backend cannot outlive the app that started it.
+
+The shell passes its pid in PLATTERA_PARENT_PID. When set (and not on
+Windows, where the Job Object already covers this) we:
+
+- move into our own process group, so the backend and any workers it starts
+  can be signalled together without touching the shell's group;
+- on Linux, ask the kernel for SIGTERM when our direct parent dies
+  (PR_SET_PDEATHSIG) – covers the PyInstaller bootloader being killed;
+- watch the app process itself (pidfd on Linux, kqueue NOTE_EXIT on macOS,
+  polling elsewhere) and stop when it exits.
+"""
+
+import ctypes
+import logging
+import os
+import select
+import signal
+import sys
+import threading
+import time
+from typing import Callable, Optional
+
+logger = logging.getLogger(__name__)
+
+PARENT_PID_ENV = "PLATTERA_PARENT_PID"
+PR_SET_PDEATHSIG = 1
+# Time the graceful stop gets before the process exits hard.
+EXIT_GRACE_SECONDS = 5.0
+
+
+def _set_pdeathsig() -> None:
+    try:
+        libc = ctypes.CDLL("libc.so.6", use_errno=True)
+        if libc.prctl(PR_SET_PDEATHSIG, signal.SIGTERM, 0, 0, 0) != 0:
+            logger.warning(f"⚠️ PARENT_WATCH ► prctl(PR_SET_PDEATHSIG) failed: errno {ctypes.get_errno()}")
+    except Exception as e:
+        logger.warning(f"⚠️ PARENT_WATCH ► prctl unavailable: {e}")
+
+
+def _wait_for_exit(pid: int) -> None:
+    """Block until process `pid` exits."""
+    if sys.platform.startswith("linux") and hasattr(os, "pidfd_open"):
+        try:
+            fd = os.pidfd_open(pid)
+            try:
+                select.select([fd], [], [])
+                return
+            finally:
+                os.close(fd)
+        except OSError:
+            pass
+    if sys.platform == "darwin" and hasattr(select, "kqueue"):
+        try:
+            kq = select.kqueue()
+            try:
+                ev = select.kevent(
+                    pid,
+                    filter=select.KQ_FILTER_PROC,
+                    flags=select.KQ_EV_ADD | select.KQ_EV_ONESHOT,
+                    fflags=select.KQ_NOTE_EXIT,
+                )
+                kq.control([ev], 1, None)
+                return
+            finally:
+                kq.close()
+        except OSError:
+            pass
+    # Fallback: poll for the pid going away.
+    while True:
+        try:
+            os.kill(pid, 0)
+        except ProcessLookupError:
+            return
+        except PermissionError:
+            pass
+        time.sleep(2.0)
+
+
+def install(on_parent_exit: Optional[Callable[[], None]] = None) -> None:
+    """
+    Start watching the app process named in PLATTERA_PARENT_PID. `on_parent_exit`
+    should start a graceful stop; the process exits hard after a grace period
+    regardless.
+    """
+    raw = os.environ.get(PARENT_PID_ENV)
+    if not raw or os.name == "nt":
+        return
+    try:
+        parent_pid = int(raw)
+    except ValueError:
+        logger.warning(f"⚠️ PARENT_WATCH ► ignoring invalid {PARENT_PID_ENV}={raw!r}")
+        return
+
+    try:
+        os.setpgrp()
+    except OSError as e:
+        logger.debug(f"PARENT_WATCH ► setpgrp failed: {e}")
+    if sys.platform.startswith("linux"):
+        _set_pdeathsig()
+
+    def watch() -> None:
+        _wait_for_exit(parent_pid)
+        logger.warning(f"🛑 PARENT_WATCH ► app process {parent_pid} exited – stopping backend")
+        if on_parent_exit is not None:
+            try:
+                on_parent_exit()
+            except Exception as e:
+                logger.error(f"❌ PARENT_WATCH ► graceful stop failed: {e}")
+        time.sleep(EXIT_GRACE_SECONDS)
+        os._exit(0)
+
+    threading.Thread(target=watch, name="parent-watch", daemon=True).start()
+    logger.info(f"👀 PARENT_WATCH ► watching app process {parent_pid}")
@@ -14,10 +14,15 @@ import { ToastProvider } from '../src/components/ui/ToastProvider'
 import { ApiKeyModal } from '../src/components/ApiKeyModal'
 import { LogsButton } from '../src/components/logs/LogsButton'
 import { installGlobalLogCapture } from '../src/services/logging/logStore'
+import { backendUrl, connectBackend, installBackendFetch } from '@/services/backendClient';
 
 export default function App({ Component, pageProps }: AppProps) {
   const [showKeyModal, setShowKeyModal] = useState(false)
   useEffect(() => {
+    // Backend requests carry the shell's token and wait until it is known
+    installBackendFetch()
+    const connected = connectBackend()
+
     // Fire-and-forget dossier prewarm on app mount (read-only)
     try { startDossierPreload(); } catch {}
 
@@ -26,7 +31,7 @@ export default function App({ Component, pageProps }: AppProps) {
       const delays = [500, 1000, 1500, 2500, 4000, 6000]
       for (let i = 0; i < delays.length; i++) {
         try {
-          const res = await fetch('http://127.0.0.1:8000/config/key-status')
+          const res = await fetch(backendUrl('/config/key-status'))
           if (res.ok) {
             const data = await res.json().catch(() => ({}))
             if (!data?.hasKey) setShowKeyModal(true)
@@ -40,8 +45,10 @@ export default function App({ Component, pageProps }: AppProps) {
       try {
         if ((window as any).__TAURI__) {
           const { invoke } = await import('@tauri-apps/api/core')
-          await invoke('start_backend')
+          // Tell the shell the bundle mounted (otherwise it opens the recovery console)
+          invoke('report_ui_ready').catch(() => {})
         }
+        await connected
       } catch {}
       checkKeyStatus()
     })()
@@ -1,7 +1,6 @@
-import React, { useState, useEffect } from 'react'
+import React, { useState, useEffect, useRef } from 'react'
 import Link from 'next/link'
 import { invoke } from '@tauri-apps/api/core'
-import { relaunch } from '@tauri-apps/plugin-process'
 import { ApiKeyModal } from '../src/components/ApiKeyModal'
 import TextBatchProcessor from '../src/components/TextBatchProcessor'
 import ImageBatchProcessor from '../src/components/ImageBatchProcessor'
@@ -84,6 +83,141 @@ const App: React.FC = () => {
     setActiveWorkspace('text-to-schema')
   }
 
+  const checkForUpdates = async () => {
+    // If an update is already downloaded and waiting for restart,
+    // treat this as a shortcut to reopen the "Update ready" dialog.
+    if (isUpdateReady) {
+      setUpdaterDialog({
+        open: true,
+        title: 'Update ready',
+        message: 'Restart Plattera to finish applying the update.',
+        mode: 'update-ready'
+      })
+      return
+    }
+
+    if (isCheckingUpdate || isDownloadingUpdate) return
+    try {
+      setIsCheckingUpdate(true)
+      const { check } = await import('@tauri-apps/plugin-updater')
+      const update = await check()
+      if (update?.available) {
+        // Remember that an update is available and show a confirmation dialog
+        setPendingUpdate(update)
+        setHasUpdateAvailable(true)
+
+        const version = update.version || 'unknown'
+        const body = (update.body as string | undefined) || ''
+        const messageLines = [
+          `A new version is available.`,
+          ``,
+          `Latest version: ${version}`,
+          body ? `\nRelease notes:\n${body}` : ''
+        ].join('\n')
+
+        setUpdaterDialog({
+          open: true,
+          title: 'Update available',
+          message: messageLines,
+          mode: 'update-available'
+        })
+      } else {
+        setPendingUpdate(null)
+        setHasUpdateAvailable(false)
+        setUpdaterDialog({
+          open: true,
+          title: 'No updates available',
+          message: 'You are up to date.',
+          mode: 'info'
+        })
+      }
+    } catch (e: any) {
+      // Surface the underlying updater error so we can diagnose configuration issues.
+      // NOTE: In plain browser dev (npm run dev), this will always fail because Tauri APIs are unavailable.
+      // We care primarily about Tauri dev and installed builds here.
+      // eslint-disable-next-line no-console
+      console.error('Updater error', e)
+      const message =
+        (e && (e.message || (typeof e.toString === 'function' && e.toString()))) ||
+        'Unknown updater error (see devtools console for details)'
+      setUpdaterDialog({
+        open: true,
+        title: 'Updater check failed',
+        message: `Updater check failed:\n${message}`,
+        mode: 'info'
+      })
+    } finally {
+      setIsCheckingUpdate(false)
+    }
+  }
+
+  // The menu's and tray's "Check for Updates" items run the same check from anywhere.
+  const checkForUpdatesRef = useRef(checkForUpdates)
+  checkForUpdatesRef.current = checkForUpdates
+  useEffect(() => {
+    let unlisten: (() => void) | undefined
+    import('@tauri-apps/api/event')
+      .then(({ listen }) =>
+        listen<{ action: string }>('menu-action', e => {
+          if (e.payload.action === 'check_for_updates') checkForUpdatesRef.current()
+        })
+      )
+      .then(u => { unlisten = u })
+      .catch(() => {})
+    return () => unlisten?.()
+  }, [])
+
+  // Background update download run by the shell: progress, ready, failure.
+  useEffect(() => {
+    const unlisteners: Array<() => void> = []
+    let cancelled = false
+    import('@tauri-apps/api/event')
+      .then(async ({ listen }) => {
+        const subs = [
+          await listen<any>('update-download-progress', ({ payload }) => {
+            setIsDownloadingUpdate(true)
+            setUpdateProgress({
+              percent: typeof payload?.percent === 'number' ? payload.percent : null,
+              downloaded: Number(payload?.downloaded ?? 0),
+              total: typeof payload?.total === 'number' ? payload.total : null,
+            })
+          }),
+          await listen<any>('update-ready', ({ payload }) => {
+            setIsDownloadingUpdate(false)
+            setUpdateProgress(null)
+            setIsUpdateReady(true)
+            setPendingUpdate(null)
+            setHasUpdateAvailable(false)
+            setUpdaterDialog({
+              open: true,
+              title: 'Update ready',
+              message: payload?.install_on_exit
+                ? 'Restart Plattera now to apply the update, or keep working: it installs when you quit.'
+                : 'Restart Plattera to finish applying the update.',
+              mode: 'update-ready'
+            })
+          }),
+          await listen<any>('update-download-failed', ({ payload }) => {
+            setIsDownloadingUpdate(false)
+            setUpdateProgress(null)
+            setUpdaterDialog({
+              open: true,
+              title: 'Update failed',
+              message: payload?.error || 'The update download failed.',
+              mode: 'info'
+            })
+          }),
+        ]
+        if (cancelled) subs.forEach(u => u())
+        else unlisteners.push(...subs)
+      })
+      .catch(() => {})
+    return () => {
+      cancelled = true
+      unlisteners.forEach(u => u())
+    }
+  }, [])
+
   const handleExitToHome = () => {
     setMode('home')
     setActiveWorkspace(null)
@@ -150,73 +284,7 @@ const App: React.FC = () => {
                 Set / Update API Key
               </button>
               <button
-                onClick={async () => {
-                  // If an update is already downloaded and waiting for restart,
-                  // treat this as a shortcut to reopen the "Update ready" dialog.
-                  if (isUpdateReady) {
-                    setUpdaterDialog({
-                      open: true,
-                      title: 'Update ready',
-                      message: 'Restart Plattera to finish applying the update.',
-                      mode: 'update-ready'
-                    })
-                    return
-                  }
-
-                  if (isCheckingUpdate || isDownloadingUpdate) return
-                  try {
-                    setIsCheckingUpdate(true)
-                    const { check } = await import('@tauri-apps/plugin-updater')
-                    const update = await check()
-                    if (update?.available) {
-                      // Remember that an update is available and show a confirmation dialog
-                      setPendingUpdate(update)
-                      setHasUpdateAvailable(true)
-
-                      const version = update.version || 'unknown'
-                      const body = (update.body as string | undefined) || ''
-                      const messageLines = [
-                        `A new version is available.`,
-                        ``,
-                        `Latest version: ${version}`,
-                        body ? `\nRelease notes:\n${body}` : ''
-                      ].join('\n')
-
-                      setUpdaterDialog({
-                        open: true,
-                        title: 'Update available',
-                        message: messageLines,
-                        mode: 'update-available'
-                      })
-                    } else {
-                      setPendingUpdate(null)
-                      setHasUpdateAvailable(false)
-                      setUpdaterDialog({
-                        open: true,
-                        title: 'No updates available',
-                        message: 'You are up to date.',
-                        mode: 'info'
-                      })
-                    }
-                  } catch (e: any) {
-                    // Surface the underlying updater error so we can diagnose configuration issues.
-                    // NOTE: In plain browser dev (npm run dev), this will always fail because Tauri APIs are unavailable.
-                    // We care primarily about Tauri dev and installed builds here.
-                    // eslint-disable-next-line no-console
-                    console.error('Updater error', e)
-                    const message =
-                      (e && (e.message || (typeof e.toString === 'function' && e.toString()))) ||
-                      'Unknown updater error (see devtools console for details)'
-                    setUpdaterDialog({
-                      open: true,
-                      title: 'Updater check failed',
-                      message: `Updater check failed:\n${message}`,
-                      mode: 'info'
-                    })
-                  } finally {
-                    setIsCheckingUpdate(false)
-                  }
-                }}
+                onClick={checkForUpdates}
                 style={{
                   display: 'inline-block',
                   marginLeft: 12,
@@ -357,73 +425,35 @@ const App: React.FC = () => {
                         if (isDownloadingUpdate) return
                         setIsDownloadingUpdate(true)
                         setUpdateProgress(null)
-                        // Kick off download/install and surface updater progress
-                        // events into a simple percent display so users can see
-                        // that the update is actively downloading.
-                        await pendingUpdate.downloadAndInstall((event: any) => {
-                          try {
-                            const kind = event?.event
-                            if (kind === 'Started') {
-                              const rawTotal = Number(event?.data?.contentLength ?? 0)
-                              const total = Number.isFinite(rawTotal) && rawTotal > 0 ? rawTotal : null
-                              setUpdateProgress({ percent: total ? 0 : null, downloaded: 0, total })
-                            } else if (kind === 'Progress') {
-                              const rawChunk = Number(event?.data?.chunkLength ?? 0)
-                              const chunk = Number.isFinite(rawChunk) && rawChunk > 0 ? rawChunk : 0
-                              setUpdateProgress(prev => {
-                                const prevDownloaded = prev?.downloaded ?? 0
-                                const downloaded = prevDownloaded + chunk
-                                let total = prev?.total ?? null
-
-                                // If we didn't have a valid total yet, try to pick it up
-                                // from this progress event (some implementations repeat
-                                // contentLength on Progress).
-                                if (!total) {
-                                  const rawTotal = Number(event?.data?.contentLength ?? 0)
-                                  if (Number.isFinite(rawTotal) && rawTotal > 0) {
-                                    total = rawTotal
-                                  }
-                                }
-
-                                let percent: number | null = null
-                                if (total && total > 0) {
-                                  percent = Math.min(100, Math.round((downloaded / total) * 100))
-                                }
-
-                                return { downloaded, total, percent }
-                              })
-                            } else if (kind === 'Finished') {
-                              setUpdateProgress(prev => {
-                                if (!prev) return { downloaded: 0, total: null, percent: 100 }
-                                return { ...prev, percent: 100 }
-                              })
-                            }
-                          } catch {
-                            // Swallow progress parsing issues; they are non-fatal.
-                          }
+                        // The shell downloads and verifies the package in the background
+                        // and reports progress via `update-download-progress`; the
+                        // dialog can be closed while it runs.
+                        const version = await invoke<string | null>('start_update_download', {
+                          background: false,
                         })
-                        setUpdaterDialog({
-                          open: true,
-                          title: 'Update ready',
-                          message: 'Restart Plattera to finish applying the update.',
-                          mode: 'update-ready'
-                        })
-                        setIsUpdateReady(true)
-                        setPendingUpdate(null)
-                        setHasUpdateAvailable(false)
+                        if (!version) {
+                          setIsDownloadingUpdate(false)
+                          setPendingUpdate(null)
+                          setHasUpdateAvailable(false)
+                          setUpdaterDialog({
+                            open: true,
+                            title: 'No updates available',
+                            message: 'You are running the latest version of Plattera.',
+                            mode: 'info'
+                          })
+                        }
                       } catch (e: any) {
                         // eslint-disable-next-line no-console
-                        console.error('Download/install failed', e)
+                        console.error('Starting update download failed', e)
                         const msg =
                           (e && (e.message || (typeof e.toString === 'function' && e.toString()))) ||
-                          'Unknown updater install error'
+                          String(e || 'Unknown updater download error')
                         setUpdaterDialog({
                           open: true,
                           title: 'Update failed',
                           message: msg,
                           mode: 'info'
                         })
-                      } finally {
                         setIsDownloadingUpdate(false)
                         setUpdateProgress(null)
                       }
@@ -448,16 +478,12 @@ const App: React.FC = () => {
                   <button
                     onClick={() => {
                       // Leave hasUpdateAvailable = true so the main button keeps its badge.
+                      // A running download carries on in the background.
                       setUpdaterDialog({ open: false, title: '', message: '', mode: 'info' })
                     }}
-                    disabled={isDownloadingUpdate}
-                    style={{
-                      padding: '8px 14px',
-                      opacity: isDownloadingUpdate ? 0.6 : 1,
-                      cursor: isDownloadingUpdate ? 'default' : 'pointer'
-                    }}
+                    style={{ padding: '8px 14px' }}
                   >
-                    Later
+                    {isDownloadingUpdate ? 'Continue in background' : 'Later'}
                   </button>
                 </>
               )}
@@ -474,19 +500,19 @@ const App: React.FC = () => {
                           title: 'Restarting…',
                           message: 'Relaunching Plattera to finish applying the update.',
                         }))
-                        await relaunch()
+                        // Stops the backend, runs the installer and restarts the app.
+                        await invoke('install_update')
                       } catch (e: any) {
                         // eslint-disable-next-line no-console
-                        console.error('Relaunch failed', e)
+                        console.error('Update install failed', e)
                         const msg =
                           (e && (e.message || (typeof e.toString === 'function' && e.toString()))) ||
-                          'Unknown relaunch error'
+                          String(e || 'Unknown install error')
                         setUpdaterDialog({
                           open: true,
-                          title: 'Update applied, restart failed',
+                          title: 'Update install failed',
                           message:
-                            'The update was installed successfully, but automatic restart failed.\n' +
-                            'Please close Plattera and reopen it manually.\n\nDetails:\n' +
+                            'The update could not be installed. Plattera keeps running the current version.\n\nDetails:\n' +
                             msg,
                           mode: 'info',
                         })
@@ -507,7 +533,11 @@ const App: React.FC = () => {
                     {isRelaunching ? 'Restarting…' : 'Restart to finish update'}
                   </button>
                   <button
-                    onClick={() => setUpdaterDialog({ open: false, title: '', message: '', mode: 'info' })}
+                    onClick={() => {
+                      setUpdaterDialog({ open: false, title: '', message: '', mode: 'info' })
+                      // Install when the app quits rather than interrupting now.
+                      invoke('set_install_on_exit', { enabled: true }).catch(() => {})
+                    }}
                     disabled={isRelaunching}
                     style={{
                       padding: '8px 14px',
@@ -515,7 +545,7 @@ const App: React.FC = () => {
                       cursor: isRelaunching ? 'default' : 'pointer',
                     }}
                   >
-                    Later
+                    Install when I quit
                   </button>
                 </>
               )}
@@ -1,3 +1,27 @@
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+/// Record the SHA-256 of the backend sidecar being bundled so the app can
+/// verify it before every launch (see `src/sidecar_integrity.rs`).
+fn write_sidecar_manifest() {
+  let target = std::env::var("TARGET").unwrap_or_default();
+  let suffix = if target.contains("windows") { ".exe" } else { "" };
+  let sidecar = PathBuf::from(format!("bin/plattera-backend-{}{}", target, suffix));
+  println!("cargo:rerun-if-changed={}", sidecar.display());
+
+  let entry = match std::fs::read(&sidecar) {
+    Ok(bytes) => {
+      let hash: String = Sha256::digest(&bytes).iter().map(|b| format!("{:02x}", b)).collect();
+      format!("{{\"plattera-backend\":{{\"sha256\":\"{}\",\"size\":{}}}}}", hash, bytes.len())
+    }
+    // No sidecar (tauri_build reports it); an empty manifest skips the check.
+    Err(_) => "{}".to_string(),
+  };
+  let out = PathBuf::from(std::env::var("OUT_DIR").unwrap()).join("sidecar-manifest.json");
+  std::fs::write(out, entry).expect("write sidecar manifest");
+}
+
 fn main() {
+  write_sidecar_manifest();
   tauri_build::build()
 }
@@ -0,0 +1,32 @@
+// Shared secret between the shell and the backend it spawns. A random token
+// is generated once per app run, handed to the backend through the
+// environment and attached to the shell's requests to that child (see
+// `endpoint::is_spawned`), so the backend can turn away other local processes
+// hitting its loopback port. No other listener ever sees it.
+
+use crate::endpoint;
+use rand::RngCore;
+use std::sync::OnceLock;
+
+pub const HEADER: &str = "X-Plattera-Token";
+/// Environment variable the backend reads the expected token from.
+pub const TOKEN_ENV: &str = "PLATTERA_AUTH_TOKEN";
+
+static TOKEN: OnceLock<String> = OnceLock::new();
+
+/// This run's token (generated on first use).
+pub fn token() -> &'static str {
+    TOKEN.get_or_init(|| {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    })
+}
+
+/// Token for backend calls the webview makes itself (the header name is
+/// `X-Plattera-Token`, or `?plattera_token=` where headers can't be set).
+/// `None` when the active backend isn't one this run spawned.
+#[tauri::command]
+pub async fn get_backend_token(app_handle: tauri::AppHandle) -> Result<Option<String>, String> {
+    Ok(endpoint::active_is_spawned(&app_handle).then(|| token().to_string()))
+}
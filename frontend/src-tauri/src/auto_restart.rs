@@ -0,0 +1,193 @@
+// Automatic backend restarts with a countdown. Triggers (the memory limit
+// below, health checks) never restart the backend outright: they announce
+// the restart with `backend://restart-pending` ticks and give the user the
+// countdown to call `cancel_pending_restart()`, so a long transcription is
+// never cut off without warning.
+
+use crate::{backend_lifecycle, correlation, maintenance, settings, telemetry, BackendProcess};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use sysinfo::{Pid, ProcessRefreshKind, System};
+use tauri::Manager;
+
+const MEMORY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoRestartSettings {
+    /// Warning period before an automatic restart goes ahead.
+    pub countdown_secs: u64,
+    /// Restart the backend once its resident memory exceeds this many MB.
+    /// `None` disables the check.
+    pub memory_limit_mb: Option<u64>,
+}
+
+impl Default for AutoRestartSettings {
+    fn default() -> Self {
+        AutoRestartSettings {
+            countdown_secs: 30,
+            memory_limit_mb: None,
+        }
+    }
+}
+
+struct Pending {
+    id: u64,
+    cancelled: Arc<AtomicBool>,
+}
+
+pub struct PendingRestart(Mutex<Option<Pending>>);
+
+impl PendingRestart {
+    pub fn new() -> Self {
+        PendingRestart(Mutex::new(None))
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct RestartPendingPayload {
+    id: u64,
+    reason: String,
+    countdown_secs: u64,
+    remaining_secs: u64,
+}
+
+#[derive(Clone, Serialize)]
+struct RestartResolvedPayload {
+    id: u64,
+    reason: String,
+}
+
+/// Announce an automatic restart and perform it after the countdown unless
+/// cancelled. Returns false if another restart is already pending.
+pub fn schedule(app_handle: &tauri::AppHandle, reason: String) -> bool {
+    if maintenance::active(app_handle) {
+        log::info!(
+            "AUTO_RESTART ► not restarting during maintenance mode: {}",
+            reason
+        );
+        return false;
+    }
+    let countdown_secs = settings::current(app_handle).auto_restart.countdown_secs;
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    {
+        let pending = app_handle.state::<PendingRestart>();
+        let mut guard = pending.0.lock().unwrap();
+        if guard.is_some() {
+            return false;
+        }
+        *guard = Some(Pending {
+            id,
+            cancelled: cancelled.clone(),
+        });
+    }
+    log::warn!(
+        "AUTO_RESTART ► backend restart in {}s: {}",
+        countdown_secs,
+        reason
+    );
+
+    let handle = app_handle.clone();
+    thread::spawn(move || {
+        let _op = correlation::begin("restart");
+        for remaining in (1..=countdown_secs).rev() {
+            if cancelled.load(Ordering::SeqCst) {
+                break;
+            }
+            correlation::emit(
+                &handle,
+                "backend://restart-pending",
+                RestartPendingPayload {
+                    id,
+                    reason: reason.clone(),
+                    countdown_secs,
+                    remaining_secs: remaining,
+                },
+            );
+            thread::sleep(Duration::from_secs(1));
+        }
+
+        handle.state::<PendingRestart>().0.lock().unwrap().take();
+        let payload = RestartResolvedPayload { id, reason };
+        if cancelled.load(Ordering::SeqCst) {
+            log::info!("AUTO_RESTART ► restart {} cancelled", id);
+            correlation::emit(&handle, "backend://restart-cancelled", payload);
+            return;
+        }
+
+        correlation::emit(&handle, "backend://restart-started", payload);
+        telemetry::record(
+            &handle,
+            "backend_restart",
+            serde_json::json!({ "trigger": "auto_restart" }),
+        );
+        match backend_lifecycle::restart_backend(&handle) {
+            Ok(msg) => log::info!("AUTO_RESTART ► {}", msg),
+            Err(e) => log::error!("AUTO_RESTART ► restart failed: {}", e),
+        }
+    });
+    true
+}
+
+/// Cancel the pending automatic restart, if any.
+#[tauri::command]
+pub async fn cancel_pending_restart(app_handle: tauri::AppHandle) -> Result<bool, String> {
+    let pending = app_handle.state::<PendingRestart>();
+    let guard = pending.0.lock().unwrap();
+    match guard.as_ref() {
+        Some(p) => {
+            p.cancelled.store(true, Ordering::SeqCst);
+            log::info!("AUTO_RESTART ► cancel requested for restart {}", p.id);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Watch the tracked backend's resident memory and schedule a restart when
+/// it passes the configured limit.
+pub fn spawn_memory_monitor(app_handle: tauri::AppHandle) {
+    thread::spawn(move || {
+        let mut sys = System::new();
+        loop {
+            thread::sleep(MEMORY_CHECK_INTERVAL);
+            let Some(limit_mb) = settings::current(&app_handle).auto_restart.memory_limit_mb else {
+                continue;
+            };
+            if maintenance::active(&app_handle) {
+                continue;
+            }
+            let pid = app_handle
+                .state::<BackendProcess>()
+                .0
+                .lock()
+                .unwrap()
+                .as_ref()
+                .map(|c| c.pid());
+            let Some(pid) = pid else {
+                continue;
+            };
+
+            let pid = Pid::from_u32(pid);
+            sys.refresh_process_specifics(pid, ProcessRefreshKind::new().with_memory());
+            let Some(used_mb) = sys.process(pid).map(|p| p.memory() / (1024 * 1024)) else {
+                continue;
+            };
+            if used_mb > limit_mb {
+                schedule(
+                    &app_handle,
+                    format!(
+                        "backend memory {} MB exceeds the {} MB limit",
+                        used_mb, limit_mb
+                    ),
+                );
+            }
+        }
+    });
+}
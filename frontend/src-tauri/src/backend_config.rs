@@ -0,0 +1,188 @@
+// Extra environment variables and command-line flags for the backend, kept
+// in settings (`backend_launch`) so support can tune a user's backend (e.g.
+// `--log-level debug` or a feature flag) without a rebuild. They apply from
+// the next spawn, sidecar and dev Python fallback alike; the running backend
+// is left alone. A profile's own `backend.env` wins over the global one for
+// the same variable. Variables the shell sets itself (listen address, port,
+// socket, token, data folder) are refused here and in profiles, and
+// `spawn_env` layers them over these, so even settings saved before a name
+// was reserved can't replace them. A `--host` flag can still move the listener; a
+// backend found listening beyond loopback may be restarted without it
+// (`loopback_bind.rs`).
+
+use crate::{auth, endpoint, loopback_bind, profiles, settings, unix_pgroup};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackendLaunchSettings {
+    pub env: BTreeMap<String, String>,
+    /// Appended to the backend's command line.
+    pub args: Vec<String>,
+}
+
+/// Variables owned by the shell.
+pub const RESERVED_ENV: &[&str] = &[
+    endpoint::HOST_ENV,
+    endpoint::PORT_ENV,
+    endpoint::SOCKET_ENV,
+    auth::TOKEN_ENV,
+    unix_pgroup::PARENT_PID_ENV,
+    settings::DATA_DIR_ENV,
+];
+
+fn is_reserved(name: &str) -> bool {
+    RESERVED_ENV.iter().any(|r| r.eq_ignore_ascii_case(name))
+}
+
+/// Refuse malformed names, NUL in values, and variables the shell owns.
+pub fn validate_env(env: &BTreeMap<String, String>) -> Result<(), String> {
+    for name in env.keys() {
+        if name.is_empty() || name.contains('=') || name.contains('\0') {
+            return Err(format!(
+                "'{}' is not a valid environment variable name",
+                name
+            ));
+        }
+        if is_reserved(name) {
+            return Err(format!(
+                "{} is set by the app and cannot be overridden",
+                name
+            ));
+        }
+    }
+    if env.values().any(|v| v.contains('\0')) {
+        return Err("backend environment must not contain NUL".into());
+    }
+    Ok(())
+}
+
+impl BackendLaunchSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        validate_env(&self.env)?;
+        if self.args.iter().any(|v| v.contains('\0')) {
+            return Err("backend arguments must not contain NUL".into());
+        }
+        Ok(())
+    }
+}
+
+/// Extra environment for the next backend spawn.
+pub fn env(app_handle: &tauri::AppHandle) -> BTreeMap<String, String> {
+    settings::current(app_handle).backend_launch.env
+}
+
+/// Environment for a backend spawn on `port`: the launch settings, the
+/// active profile's variables, and the shell's own on top.
+pub fn spawn_env(app_handle: &tauri::AppHandle, port: u16) -> Vec<(String, String)> {
+    let mut owned = vec![
+        (endpoint::PORT_ENV, port.to_string()),
+        (endpoint::HOST_ENV, endpoint::LOOPBACK_HOST.to_string()),
+        (auth::TOKEN_ENV, auth::token().to_string()),
+    ];
+    owned.extend(unix_pgroup::child_env());
+    layer_env(&env(app_handle), &profiles::backend_env(app_handle), &owned)
+}
+
+/// `profile` over `global`, then `owned` last. Reserved names are taken
+/// from `owned` only.
+fn layer_env(
+    global: &BTreeMap<String, String>,
+    profile: &BTreeMap<String, String>,
+    owned: &[(&str, String)],
+) -> Vec<(String, String)> {
+    let mut merged = global.clone();
+    merged.extend(profile.clone());
+    merged.retain(|name, _| !is_reserved(name));
+    merged
+        .into_iter()
+        .chain(
+            owned
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone())),
+        )
+        .collect()
+}
+
+/// Extra command-line flags for the next backend spawn.
+pub fn args(app_handle: &tauri::AppHandle) -> Vec<String> {
+    let args = settings::current(app_handle).backend_launch.args;
+    if loopback_bind::forced() {
+        loopback_bind::strip_host_args(args)
+    } else {
+        args
+    }
+}
+
+/// Replace the extra backend environment and flags. Takes effect on the
+/// next backend start.
+#[tauri::command]
+pub async fn configure_backend(
+    app_handle: tauri::AppHandle,
+    env: BTreeMap<String, String>,
+    args: Vec<String>,
+) -> Result<BackendLaunchSettings, String> {
+    let launch = BackendLaunchSettings { env, args };
+    launch.validate()?;
+    log::info!(
+        "BACKEND_CONFIG ► {} variable(s) {:?}, args {:?} (applies on next start)",
+        launch.env.len(),
+        launch.env.keys().collect::<Vec<_>>(),
+        launch.args
+    );
+    settings::update(&app_handle, |s| s.backend_launch = launch.clone())?;
+    Ok(launch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn launch_and_profile_env_cannot_shadow_shell_variables() {
+        let global = map(&[(endpoint::PORT_ENV, "1"), ("PLATTERA_FEATURE", "global")]);
+        let profile = map(&[
+            (endpoint::HOST_ENV, "0.0.0.0"),
+            ("plattera_auth_token", "guessed"),
+            ("PLATTERA_FEATURE", "profile"),
+        ]);
+        let owned = [
+            (endpoint::PORT_ENV, "8000".to_string()),
+            (endpoint::HOST_ENV, endpoint::LOOPBACK_HOST.to_string()),
+            (auth::TOKEN_ENV, "secret".to_string()),
+        ];
+
+        let env = layer_env(&global, &profile, &owned);
+        let user_set: Vec<&str> = env[..env.len() - owned.len()]
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(user_set, ["PLATTERA_FEATURE"]);
+        assert_eq!(env[0].1, "profile");
+        for (name, value) in &owned {
+            let seen: Vec<&String> = env
+                .iter()
+                .filter(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v)
+                .collect();
+            assert_eq!(seen, [value]);
+        }
+    }
+
+    #[test]
+    fn reserved_names_are_refused_in_any_case() {
+        for name in [endpoint::HOST_ENV, "plattera_backend_port", auth::TOKEN_ENV] {
+            let err = validate_env(&map(&[(name, "x")])).unwrap_err();
+            assert!(err.contains("cannot be overridden"), "{}", err);
+        }
+        assert!(validate_env(&map(&[("PLATTERA_FEATURE", "on")])).is_ok());
+    }
+}
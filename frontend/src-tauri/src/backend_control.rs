@@ -0,0 +1,88 @@
+// Explicit backend control from the frontend. `restart_backend` always does
+// a cold restart through the normal shutdown path (cleanup, graceful stop,
+// port and file-lock checks), e.g. after changing backend settings or
+// clearing caches, and announces the new backend like a launch:
+// `backend-starting`, then `backend-ready` or `backend-failed`, with the
+// health probes recorded in the readiness report.
+//
+// `stop_backend` lets power users stop the backend on purpose (to free
+// memory while idle, or before running a database tool by hand). The app
+// keeps running with the backend in the `stopped` state: the supervisor
+// doesn't bring it back and `start_backend` from a reloaded webview leaves
+// it alone. `resume_backend` starts it again, announced like a restart.
+
+use crate::backend_state::{self, BackendState};
+use crate::{backend_lifecycle, correlation, readiness, startup, supervisor};
+
+/// Run `spawn` off the async runtime, wait for the backend to become
+/// healthy and announce each step through the backend state.
+async fn bring_up<F>(
+    app_handle: &tauri::AppHandle,
+    op: &'static str,
+    spawn: F,
+) -> Result<String, String>
+where
+    F: FnOnce(&tauri::AppHandle) -> Result<String, String> + Send + 'static,
+{
+    readiness::begin(app_handle);
+    backend_state::set_starting(app_handle);
+    let handle = app_handle.clone();
+    let spawned = tauri::async_runtime::spawn_blocking(move || {
+        let _op = correlation::begin(op);
+        supervisor::reset(&handle);
+        spawn(&handle)
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|r| r);
+    let result = match spawned {
+        Ok(msg) => startup::wait_until_healthy(app_handle).await.map(|_| msg),
+        Err(e) => Err(e),
+    };
+    match &result {
+        Ok(msg) => {
+            log::info!("BACKEND_CONTROL ► {} finished: {}", op, msg);
+            backend_state::set_ready(app_handle);
+        }
+        Err(e) => backend_state::set_failed(app_handle, e),
+    }
+    result
+}
+
+/// Stop the backend and start a fresh one; resolves once it is healthy.
+#[tauri::command]
+pub async fn restart_backend(app_handle: tauri::AppHandle) -> Result<String, String> {
+    bring_up(&app_handle, "restart", |handle| {
+        backend_lifecycle::shutdown_backend_for_restart(handle);
+        crate::spawn_backend(handle)
+    })
+    .await
+}
+
+/// Stop the backend and leave it stopped until `resume_backend`.
+#[tauri::command]
+pub async fn stop_backend(app_handle: tauri::AppHandle) -> Result<(), String> {
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let _op = correlation::begin("stop");
+        backend_lifecycle::shutdown_backend_for_stop(&handle);
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    backend_state::set_stopped(&app_handle);
+    Ok(())
+}
+
+/// Start a backend stopped with `stop_backend`; resolves once it is healthy.
+#[tauri::command]
+pub async fn resume_backend(app_handle: tauri::AppHandle) -> Result<String, String> {
+    bring_up(&app_handle, "start", crate::spawn_backend).await
+}
+
+/// Whether the user stopped the backend on purpose.
+pub fn stopped_by_user(app_handle: &tauri::AppHandle) -> bool {
+    matches!(
+        backend_state::current(app_handle),
+        BackendState::Stopped { .. }
+    )
+}
@@ -0,0 +1,512 @@
+// Frontend → backend request proxy. `backend_request` forwards a method,
+// path, body and headers to the local backend through one shared async
+// client, so the webview never needs to know the port, every call gets the
+// same timeouts and retry rule, and headers the shell must add (correlation
+// ID, auth token) are attached in a single place. The auth token only goes
+// to a backend this run spawned (`endpoint::is_spawned`); `probe_port` asks
+// an unknown listener who it is without it. Job start responses are handed
+// to the task registry. Shell-side calls to the active backend go through
+// `call` / `call_blocking`, which follow the configured transport (TCP or a
+// local socket, see `endpoint.rs`); `call_port` reaches a backend on a
+// specific TCP port, e.g. a warm standby before the endpoint moves to it;
+// `open_stream` opens the event stream the event bridge relays.
+// The shell makes no blocking loopback requests of its own; the blocking
+// forms wait on the async client through `block_on`, which outbound
+// requests from blocking code (`proxy::client_builder`) use as well.
+
+use crate::{auth, correlation, endpoint, tasks, trace};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{mpsc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::Manager;
+use tokio::runtime::RuntimeFlavor;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+/// Extra wait in the blocking forms beyond the request timeout, so a caller
+/// on an exit path is never held up by a runtime that has stopped polling.
+const BLOCKING_GRACE: Duration = Duration::from_secs(1);
+/// Headers the client computes itself; caller-supplied values are dropped.
+const RESERVED_HEADERS: &[&str] = &["host", "content-length", "connection", "x-plattera-token"];
+
+pub struct BackendClient {
+    tcp: reqwest::Client,
+    /// Client bound to the backend's local socket, keyed by socket name.
+    local: Mutex<Option<(String, reqwest::Client)>>,
+}
+
+impl BackendClient {
+    pub fn new() -> Self {
+        BackendClient {
+            tcp: build_client(None),
+            local: Mutex::new(None),
+        }
+    }
+}
+
+fn build_client(socket: Option<&str>) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        // The backend is on loopback; a system proxy must never see it.
+        .no_proxy();
+    if let Some(socket) = socket {
+        #[cfg(unix)]
+        {
+            builder = builder.unix_socket(socket);
+        }
+        #[cfg(windows)]
+        {
+            builder = builder.windows_named_pipe(socket);
+        }
+    }
+    builder.build().unwrap_or_else(|e| {
+        log::warn!(
+            "BACKEND_HTTP ► client builder failed ({}); using defaults",
+            e
+        );
+        reqwest::Client::new()
+    })
+}
+
+/// The shared client for the active backend's transport.
+pub fn client(app_handle: &tauri::AppHandle) -> reqwest::Client {
+    let state = app_handle.state::<BackendClient>();
+    let Some(socket) = endpoint::socket(app_handle) else {
+        return state.tcp.clone();
+    };
+    let mut local = state.local.lock().unwrap();
+    match local.as_ref() {
+        Some((name, client)) if *name == socket => client.clone(),
+        _ => {
+            let client = build_client(Some(&socket));
+            *local = Some((socket, client.clone()));
+            client
+        }
+    }
+}
+
+/// Reply to a shell-side backend call. Non-2xx statuses are replies too.
+#[derive(Debug)]
+pub struct BackendReply {
+    pub status: reqwest::StatusCode,
+    /// Parsed JSON, or the raw text when the body isn't JSON.
+    pub body: Value,
+}
+
+/// Attach the auth token when the target is a backend this run spawned.
+fn authorize(req: reqwest::RequestBuilder, spawned: bool) -> reqwest::RequestBuilder {
+    if spawned {
+        req.header(auth::HEADER, auth::token())
+    } else {
+        req
+    }
+}
+
+async fn send(
+    client: reqwest::Client,
+    url: String,
+    method: reqwest::Method,
+    body: Option<&Value>,
+    timeout: Duration,
+    origin: Option<&str>,
+    spawned: bool,
+) -> Result<BackendReply, String> {
+    let mut req = authorize(
+        client.request(method.clone(), &url).timeout(timeout),
+        spawned,
+    );
+    if let Some(origin) = origin {
+        let cid = correlation::request_id();
+        log::info!("TRACE ► [cid={}] {} {} {}", cid, origin, method, url);
+        req = req.header(trace::HEADER, cid);
+    }
+    if let Some(body) = body {
+        req = req.json(body);
+    }
+    let res = req.send().await.map_err(|e| e.to_string())?;
+    read_reply(res).await
+}
+
+async fn read_reply(res: reqwest::Response) -> Result<BackendReply, String> {
+    let status = res.status();
+    let text = res.text().await.map_err(|e| e.to_string())?;
+    let body = serde_json::from_str(&text).unwrap_or(Value::String(text));
+    Ok(BackendReply { status, body })
+}
+
+/// Call the active backend, with the auth token when we spawned it. `origin`
+/// names the caller in the trace log and adds a correlation ID; frequent
+/// polls pass `None` to keep the log quiet.
+pub async fn call(
+    app_handle: &tauri::AppHandle,
+    method: reqwest::Method,
+    path: &str,
+    body: Option<&Value>,
+    timeout: Duration,
+    origin: Option<&str>,
+) -> Result<BackendReply, String> {
+    let url = endpoint::url(app_handle, path);
+    let spawned = endpoint::active_is_spawned(app_handle);
+    send(
+        client(app_handle),
+        url,
+        method,
+        body,
+        timeout,
+        origin,
+        spawned,
+    )
+    .await
+}
+
+/// POST a multipart form (an upload chunk) to the active backend.
+pub async fn post_multipart(
+    app_handle: &tauri::AppHandle,
+    path: &str,
+    form: reqwest::multipart::Form,
+    timeout: Duration,
+) -> Result<BackendReply, String> {
+    let req = client(app_handle)
+        .post(endpoint::url(app_handle, path))
+        .timeout(timeout);
+    let res = authorize(req, endpoint::active_is_spawned(app_handle))
+        .multipart(form)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    read_reply(res).await
+}
+
+/// Call the backend on TCP `port`, whichever backend is active. The token
+/// is attached only when this run spawned the backend on that port.
+pub async fn call_port(
+    app_handle: &tauri::AppHandle,
+    port: u16,
+    method: reqwest::Method,
+    path: &str,
+    body: Option<&Value>,
+    timeout: Duration,
+) -> Result<BackendReply, String> {
+    let url = format!("{}{}", endpoint::base_url_for(port), path);
+    let client = app_handle.state::<BackendClient>().tcp.clone();
+    let spawned = endpoint::is_spawned(app_handle, port);
+    send(client, url, method, body, timeout, None, spawned).await
+}
+
+/// GET `path` from whatever listens on TCP `port` without the auth token,
+/// to learn whether it is a Plattera backend at all.
+pub async fn probe_port(
+    app_handle: &tauri::AppHandle,
+    port: u16,
+    path: &str,
+    timeout: Duration,
+) -> Result<BackendReply, String> {
+    let url = format!("{}{}", endpoint::base_url_for(port), path);
+    let client = app_handle.state::<BackendClient>().tcp.clone();
+    send(
+        client,
+        url,
+        reqwest::Method::GET,
+        None,
+        timeout,
+        None,
+        false,
+    )
+    .await
+}
+
+async fn open_get(
+    app_handle: &tauri::AppHandle,
+    path: &str,
+    accept: Option<&str>,
+) -> Result<reqwest::Response, String> {
+    let mut req = authorize(
+        client(app_handle).get(endpoint::url(app_handle, path)),
+        endpoint::active_is_spawned(app_handle),
+    );
+    if let Some(accept) = accept {
+        req = req.header(reqwest::header::ACCEPT, accept);
+    }
+    let res = req.send().await.map_err(|e| e.to_string())?;
+    if !res.status().is_success() {
+        return Err(format!("{} returned {}", path, res.status()));
+    }
+    Ok(res)
+}
+
+/// Open a long-lived GET (an event stream) on the active backend. Only
+/// connecting is time-limited; the caller reads the body as it arrives.
+pub async fn open_stream(
+    app_handle: &tauri::AppHandle,
+    path: &str,
+) -> Result<reqwest::Response, String> {
+    open_get(app_handle, path, Some("text/event-stream")).await
+}
+
+/// Open a GET for a file the backend generates; the caller streams the body
+/// to disk instead of buffering it.
+pub async fn open_download(
+    app_handle: &tauri::AppHandle,
+    path: &str,
+) -> Result<reqwest::Response, String> {
+    open_get(app_handle, path, None).await
+}
+
+/// Run a request on the async runtime and wait for it here, for plain
+/// threads and `spawn_blocking`; gives up shortly after `timeout`. Called
+/// on a runtime worker, the wait goes through `block_in_place`, which hands
+/// the worker's other tasks to the rest of the pool first, so the request
+/// can't end up queued behind its own wait.
+pub fn block_on<T, F>(timeout: Duration, future: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: Future<Output = Result<T, String>> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    tauri::async_runtime::spawn(async move {
+        let _ = tx.send(future.await);
+    });
+    let wait = move || match rx.recv_timeout(timeout + BLOCKING_GRACE) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => Err("request timed out".to_string()),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err("request was dropped".to_string()),
+    };
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(wait)
+        }
+        _ => wait(),
+    }
+}
+
+/// Blocking form of `call`.
+pub fn call_blocking(
+    app_handle: &tauri::AppHandle,
+    method: reqwest::Method,
+    path: &str,
+    body: Option<&Value>,
+    timeout: Duration,
+    origin: Option<&str>,
+) -> Result<BackendReply, String> {
+    let handle = app_handle.clone();
+    let path = path.to_string();
+    let body = body.cloned();
+    let origin = origin.map(str::to_string);
+    block_on(timeout, async move {
+        call(
+            &handle,
+            method,
+            &path,
+            body.as_ref(),
+            timeout,
+            origin.as_deref(),
+        )
+        .await
+    })
+}
+
+/// Blocking form of `call_port`.
+pub fn call_port_blocking(
+    app_handle: &tauri::AppHandle,
+    port: u16,
+    method: reqwest::Method,
+    path: &str,
+    timeout: Duration,
+) -> Result<BackendReply, String> {
+    let handle = app_handle.clone();
+    let path = path.to_string();
+    block_on(timeout, async move {
+        call_port(&handle, port, method, &path, None, timeout).await
+    })
+}
+
+/// Blocking form of `probe_port`.
+pub fn probe_port_blocking(
+    app_handle: &tauri::AppHandle,
+    port: u16,
+    path: &str,
+    timeout: Duration,
+) -> Result<BackendReply, String> {
+    let handle = app_handle.clone();
+    let path = path.to_string();
+    block_on(timeout, async move {
+        probe_port(&handle, port, &path, timeout).await
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendResponse {
+    pub status: u16,
+    pub ok: bool,
+    pub headers: HashMap<String, String>,
+    /// Parsed JSON when the backend answered with JSON, otherwise the text.
+    pub body: Value,
+    pub correlation_id: String,
+    pub attempts: u32,
+    pub duration_ms: u64,
+}
+
+fn parse_method(method: &str) -> Result<reqwest::Method, String> {
+    reqwest::Method::from_bytes(method.trim().to_ascii_uppercase().as_bytes())
+        .map_err(|_| format!("invalid HTTP method: {}", method))
+}
+
+/// Only backend-relative paths are accepted so the proxy can't be pointed at
+/// another host.
+fn validate_path(path: &str) -> Result<(), String> {
+    if !path.starts_with('/') || path.starts_with("//") || path.contains("://") {
+        return Err(format!(
+            "backend path must be relative to the backend root: {}",
+            path
+        ));
+    }
+    Ok(())
+}
+
+fn is_reserved_header(name: &str) -> bool {
+    RESERVED_HEADERS.contains(&name.to_ascii_lowercase().as_str())
+}
+
+/// Connection failures are retried for any method since the request never
+/// reached the backend; timeouts only for methods that are safe to repeat.
+fn should_retry(method: &reqwest::Method, err: &reqwest::Error) -> bool {
+    if err.is_connect() {
+        return true;
+    }
+    err.is_timeout() && method.is_idempotent()
+}
+
+#[tauri::command]
+pub async fn backend_request(
+    app_handle: tauri::AppHandle,
+    method: String,
+    path: String,
+    body: Option<Value>,
+    headers: Option<HashMap<String, String>>,
+    timeout_ms: Option<u64>,
+) -> Result<BackendResponse, String> {
+    let method = parse_method(&method)?;
+    validate_path(&path)?;
+    let timeout = timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_TIMEOUT);
+    let client = client(&app_handle);
+    let cid = correlation::request_id();
+    let start = Instant::now();
+
+    let mut attempt = 0;
+    let response = loop {
+        attempt += 1;
+        // Resolve the URL per attempt: a warm-standby restart may have moved
+        // the backend between tries.
+        let url = endpoint::url(&app_handle, &path);
+        if attempt == 1 {
+            log::info!("TRACE ► [cid={}] webview {} {}", cid, method, url);
+        }
+        let mut req = authorize(
+            client
+                .request(method.clone(), &url)
+                .timeout(timeout)
+                .header(trace::HEADER, &cid),
+            endpoint::active_is_spawned(&app_handle),
+        );
+        for (name, value) in headers.iter().flatten() {
+            if is_reserved_header(name) {
+                continue;
+            }
+            req = req.header(name.as_str(), value.as_str());
+        }
+        if let Some(body) = &body {
+            req = req.json(body);
+        }
+
+        match req.send().await {
+            Ok(res) => break res,
+            Err(e) if attempt < MAX_ATTEMPTS && should_retry(&method, &e) => {
+                let delay = RETRY_BASE_DELAY * 2u32.pow(attempt - 1);
+                log::warn!(
+                    "BACKEND_HTTP ► [cid={}] {} {} failed (attempt {}/{}): {}; retrying in {}ms",
+                    cid,
+                    method,
+                    path,
+                    attempt,
+                    MAX_ATTEMPTS,
+                    e,
+                    delay.as_millis()
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => {
+                log::warn!(
+                    "BACKEND_HTTP ► [cid={}] {} {} failed: {}",
+                    cid,
+                    method,
+                    path,
+                    e
+                );
+                return Err(format!("{} {} failed: {}", method, path, e));
+            }
+        }
+    };
+
+    let status = response.status();
+    let headers: HashMap<String, String> = response
+        .headers()
+        .iter()
+        .filter_map(|(k, v)| Some((k.as_str().to_string(), v.to_str().ok()?.to_string())))
+        .collect();
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("{} {}: could not read response: {}", method, path, e))?;
+    let body = serde_json::from_str(&text).unwrap_or(Value::String(text));
+    if status.is_success() {
+        tasks::observe_response(&app_handle, method.as_str(), &path, &body);
+    }
+
+    Ok(BackendResponse {
+        status: status.as_u16(),
+        ok: status.is_success(),
+        headers,
+        body,
+        correlation_id: cid,
+        attempts: attempt,
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_must_stay_on_the_backend() {
+        for path in ["/api/health", "/api/dossiers?limit=20", "/"] {
+            assert!(validate_path(path).is_ok(), "{}", path);
+        }
+        for path in [
+            "api/health",
+            "",
+            "//evil.example/api",
+            "http://evil.example/api",
+            "/api/fetch?url=https://evil.example",
+        ] {
+            assert!(validate_path(path).is_err(), "{}", path);
+        }
+    }
+
+    #[test]
+    fn caller_cannot_set_client_owned_headers() {
+        for name in ["Host", "content-length", "Connection", "X-Plattera-Token"] {
+            assert!(is_reserved_header(name), "{}", name);
+        }
+        for name in ["Accept", "X-Requested-With", "If-None-Match"] {
+            assert!(!is_reserved_header(name), "{}", name);
+        }
+    }
+}
@@ -1,16 +1,131 @@
-use crate::{cleanup_via_http, port_in_use, BackendProcess};
+use crate::backend_mode::{self, BackendMode};
+use crate::backend_pid::{self, ProcessIdentity};
+use crate::{
+    backend_http, cleanup_via_http, correlation, data_encryption, endpoint, port_in_use, portable,
+    settings, standby, supervisor, unix_pgroup, BackendProcess,
+};
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::thread;
 use std::time::{Duration, Instant};
-use tauri::path::BaseDirectory;
 use tauri::Manager;
 
+const SHUTDOWN_REPORT_FILE: &str = "shutdown_report.json";
+
+/// Launch knobs for the backend, editable through `update_settings`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LifecycleSettings {
+    /// Port tried first; any free loopback port is used when it is taken.
+    pub preferred_port: u16,
+    /// Timeout of each startup health probe.
+    pub health_timeout_ms: u64,
+    /// Waits between failed startup health probes; one probe per entry
+    /// unless `health_attempts` asks for more.
+    pub health_retry_delays_ms: Vec<u64>,
+    /// Startup health probes; further probes reuse the last delay. `None`
+    /// makes one probe per `health_retry_delays_ms` entry.
+    pub health_attempts: Option<u32>,
+    /// Overall limit for the backend to come up, across all probes.
+    pub startup_timeout_ms: u64,
+    /// Restart a backend found listening beyond loopback with its listen
+    /// address forced to 127.0.0.1 (see `loopback_bind.rs`).
+    pub enforce_loopback_bind: bool,
+}
+
+impl Default for LifecycleSettings {
+    fn default() -> Self {
+        LifecycleSettings {
+            preferred_port: endpoint::DEFAULT_PORT,
+            health_timeout_ms: 8_000,
+            health_retry_delays_ms: vec![500, 1_000, 1_500, 2_500],
+            health_attempts: None,
+            startup_timeout_ms: 60_000,
+            enforce_loopback_bind: false,
+        }
+    }
+}
+
+impl LifecycleSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.preferred_port == 0 {
+            return Err("lifecycle.preferred_port must be a port number".into());
+        }
+        if self.health_retry_delays_ms.is_empty() {
+            return Err("lifecycle.health_retry_delays_ms needs at least one entry".into());
+        }
+        if self.health_timeout_ms == 0 {
+            return Err("lifecycle.health_timeout_ms must be greater than zero".into());
+        }
+        if self.health_attempts == Some(0) {
+            return Err("lifecycle.health_attempts must be at least 1".into());
+        }
+        if self.startup_timeout_ms == 0 {
+            return Err("lifecycle.startup_timeout_ms must be greater than zero".into());
+        }
+        Ok(())
+    }
+}
+
+/// Time limits for one shutdown run.
+struct ShutdownLimits {
+    cleanup_ms: u64,
+    grace_ms: u64,
+    invariants_ms: u64,
+}
+
+impl ShutdownLimits {
+    fn normal(app_handle: &tauri::AppHandle) -> Self {
+        ShutdownLimits {
+            cleanup_ms: 1_500,
+            grace_ms: settings::current(app_handle).backend_shutdown_grace_ms,
+            invariants_ms: 10_000,
+        }
+    }
+
+    /// Windows gives each app roughly five seconds after WM_ENDSESSION
+    /// before it is terminated; stay well inside that.
+    fn session_end() -> Self {
+        ShutdownLimits {
+            cleanup_ms: 500,
+            grace_ms: 1_500,
+            invariants_ms: 1_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShutdownStep {
+    pub name: String,
+    pub duration_ms: u64,
+    pub ok: bool,
+    pub detail: Option<String>,
+}
+
+/// What the last shutdown did, written to AppLocalData on every run so
+/// update failures can be investigated after the fact.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShutdownReport {
+    /// "update", "exit", "restart", "stop" or "session_end".
+    pub reason: String,
+    pub started_at: String,
+    pub total_ms: u64,
+    pub port: u16,
+    pub steps: Vec<ShutdownStep>,
+    pub port_freed: bool,
+    /// `None` when the exe lock was not probed (normal exits, non-Windows).
+    pub exe_unlocked: Option<bool>,
+    /// Strongest measure that was needed: "none", "http_cleanup",
+    /// "graceful", "kill" or "timeout" (invariants never held).
+    pub escalation: String,
+}
+
 /// Best-effort shutdown routine for the updater path. The goal is to release
 /// the backend's port and file lock before the NSIS installer runs so updates
 /// don't fail with "file in use" errors.
 pub fn shutdown_backend_for_update(app_handle: &tauri::AppHandle) {
-    log::info!("UPDATER_SHUTDOWN ► requested backend shutdown (update install)");
-    shutdown_backend_inner(app_handle, true);
+    log::info!("BACKEND_LIFECYCLE ► requested backend shutdown (update install)");
+    shutdown_backend_inner(app_handle, true, "update", ShutdownLimits::normal(app_handle));
 }
 
 /// Best-effort shutdown routine for normal exits (window close, Ctrl+C). This
@@ -18,55 +133,197 @@ pub fn shutdown_backend_for_update(app_handle: &tauri::AppHandle) {
 /// rename-based lock probe to avoid any chance of leaving the installed
 /// backend exe in an unexpected name if a second rename were to fail.
 pub fn shutdown_backend_for_exit(app_handle: &tauri::AppHandle) {
-    log::info!("UPDATER_SHUTDOWN ► requested backend shutdown (normal exit)");
-    shutdown_backend_inner(app_handle, false);
+    log::info!("BACKEND_LIFECYCLE ► requested backend shutdown (normal exit)");
+    shutdown_backend_inner(app_handle, false, "exit", ShutdownLimits::normal(app_handle));
 }
 
-fn shutdown_backend_inner(app_handle: &tauri::AppHandle, check_file_lock: bool) {
+/// Abbreviated, time-bounded shutdown for OS-initiated exits (logoff,
+/// system shutdown, SIGTERM). Same steps as a normal exit with short waits,
+/// so the backend still gets to flush and close its databases before the OS
+/// kills the process tree.
+pub fn shutdown_backend_for_session_end(app_handle: &tauri::AppHandle) {
+    log::info!("BACKEND_LIFECYCLE ► requested backend shutdown (session end)");
+    shutdown_backend_inner(app_handle, false, "session_end", ShutdownLimits::session_end());
+}
 
-    // 1) Ask the backend to perform its own cleanup (flush, close DBs, etc.).
-    cleanup_via_http(1_500);
+/// Normal shutdown ahead of a deliberate cold restart (`restart_backend`
+/// command): same cleanup, graceful stop and port/lock checks as an exit.
+pub fn shutdown_backend_for_restart(app_handle: &tauri::AppHandle) {
+    log::info!("BACKEND_LIFECYCLE ► requested backend shutdown (restart)");
+    shutdown_backend_inner(app_handle, false, "restart", ShutdownLimits::normal(app_handle));
+}
+
+/// Normal shutdown for a user-requested stop (`stop_backend`); the app
+/// keeps running without a backend.
+pub fn shutdown_backend_for_stop(app_handle: &tauri::AppHandle) {
+    log::info!("BACKEND_LIFECYCLE ► requested backend shutdown (stop)");
+    shutdown_backend_inner(app_handle, false, "stop", ShutdownLimits::normal(app_handle));
+}
 
-    // 2) Kill the child process we spawned, if any.
+/// Shared restart entry point (tray, settings changes, recovery actions).
+/// Uses a warm standby when enabled so open views never lose the backend;
+/// otherwise, or if the standby fails to come up, stops the backend through
+/// the normal shutdown path and spawns a fresh one.
+pub fn restart_backend(app_handle: &tauri::AppHandle) -> Result<String, String> {
+    let _op = correlation::begin("restart");
+    log::info!("BACKEND_LIFECYCLE ► requested backend restart");
+    let tracked = app_handle.state::<BackendProcess>().0.lock().unwrap().is_some();
+    if tracked && settings::current(app_handle).warm_standby_restarts {
+        match standby::restart_with_standby(app_handle) {
+            Ok(msg) => return Ok(msg),
+            Err(e) => log::warn!("BACKEND_LIFECYCLE ► warm restart failed ({}); restarting cold", e),
+        }
+    }
+    shutdown_backend_inner(app_handle, false, "restart", ShutdownLimits::normal(app_handle));
+    supervisor::reset(app_handle);
+    crate::spawn_backend(app_handle)
+}
+
+fn shutdown_backend_inner(
+    app_handle: &tauri::AppHandle,
+    check_file_lock: bool,
+    reason: &str,
+    limits: ShutdownLimits,
+) {
+    let _op = correlation::begin("shutdown");
+    let started_at = chrono::Local::now().to_rfc3339();
+    let overall = Instant::now();
+    let mut steps = Vec::new();
+
+    let port = endpoint::port(app_handle);
+    let port_was_in_use = port_in_use(port);
+
+    // A backend this run didn't spawn (`external` mode, or one found already
+    // running and attached to) belongs to someone else: it is neither
+    // cleaned up nor stopped, only forgotten.
+    if backend_mode::current(app_handle) == BackendMode::External
+        || !endpoint::active_is_spawned(app_handle)
     {
-        let backend = app_handle.state::<BackendProcess>();
-        let mut guard = backend.0.lock().unwrap();
-        if let Some(child) = guard.take() {
-            log::info!("UPDATER_SHUTDOWN ► killing tracked backend child");
-            let _ = child.kill();
+        app_handle.state::<BackendProcess>().0.lock().unwrap().take();
+        backend_pid::take(app_handle);
+        log::info!(
+            "BACKEND_LIFECYCLE ► backend on port {} was not started by the app; leaving it running",
+            port
+        );
+        write_shutdown_report(
+            app_handle,
+            &ShutdownReport {
+                reason: reason.to_string(),
+                started_at,
+                total_ms: overall.elapsed().as_millis() as u64,
+                port,
+                steps: vec![ShutdownStep {
+                    name: "detach".to_string(),
+                    duration_ms: 0,
+                    ok: true,
+                    detail: Some("not started by the app".to_string()),
+                }],
+                port_freed: !port_was_in_use,
+                exe_unlocked: None,
+                escalation: "none".to_string(),
+            },
+        );
+        return;
+    }
+
+    // 1) Ask the backend to perform its own cleanup (flush, close DBs, etc.).
+    let step = Instant::now();
+    let cleanup_ok = request_cleanup(app_handle, port, limits.cleanup_ms);
+    steps.push(ShutdownStep {
+        name: "http_cleanup".to_string(),
+        duration_ms: step.elapsed().as_millis() as u64,
+        ok: cleanup_ok,
+        detail: None,
+    });
+
+    // 2) Take the child out of state first so its exit isn't treated as a
+    //    crash, then give it a graceful stop before any hard kill.
+    let child = app_handle.state::<BackendProcess>().0.lock().unwrap().take();
+    let identity = backend_pid::take(app_handle);
+    let identity = identity.as_ref();
+    let grace = Duration::from_millis(limits.grace_ms);
+    let step = Instant::now();
+    let mut graceful = false;
+    if backend_alive(identity, port) {
+        let detail = request_graceful_stop(app_handle, identity);
+        let deadline = Instant::now() + grace;
+        while backend_alive(identity, port) && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(100));
         }
+        graceful = !backend_alive(identity, port);
+        steps.push(ShutdownStep {
+            name: "graceful_stop".to_string(),
+            duration_ms: step.elapsed().as_millis() as u64,
+            ok: graceful,
+            detail: Some(detail),
+        });
     }
 
-    // 3) Wait for invariants: port must be free and (on Windows, update path)
+    // 3) Hard-kill the child we spawned if it is still around.
+    let step = Instant::now();
+    let killed = match child {
+        Some(child) if !graceful || backend_alive(identity, port) => {
+            log::info!("BACKEND_LIFECYCLE ► killing tracked backend child");
+            let pid = child.pid();
+            let result = child.kill();
+            // Take any workers in the backend's process group with it (Unix).
+            if unix_pgroup::kill_group(pid) {
+                log::debug!("BACKEND_LIFECYCLE ► killed process group {}", pid);
+            }
+            Some((pid, result))
+        }
+        _ => None,
+    };
+    if let Some((pid, result)) = &killed {
+        steps.push(ShutdownStep {
+            name: "kill_child".to_string(),
+            duration_ms: step.elapsed().as_millis() as u64,
+            ok: result.is_ok(),
+            detail: Some(match result {
+                Ok(()) => format!("pid {}", pid),
+                Err(e) => format!("pid {}: {}", pid, e),
+            }),
+        });
+    }
+
+    // 4) Wait for invariants: port must be free and (on Windows, update path)
     //    binary should be unlocked for overwrite.
-    const TIMEOUT_MS: u64 = 10_000;
     const POLL_MS: u64 = 250;
     let start = Instant::now();
+    let mut port_freed;
+    let mut exe_unlocked = None;
+    let mut timed_out = false;
 
     loop {
         let elapsed = start.elapsed();
-        if elapsed.as_millis() as u64 >= TIMEOUT_MS {
+        port_freed = !port_in_use(port);
+        if check_file_lock {
+            exe_unlocked = Some(backend_exe_unlocked(app_handle));
+        }
+
+        if elapsed.as_millis() as u64 >= limits.invariants_ms {
             log::warn!(
-                "UPDATER_SHUTDOWN ► timeout ({:?}) waiting for backend shutdown; proceeding anyway",
+                "BACKEND_LIFECYCLE ► timeout ({:?}) waiting for backend shutdown; proceeding anyway",
                 elapsed
             );
+            timed_out = true;
             break;
         }
 
         let mut all_clear = true;
 
-        if port_in_use(8000) {
+        if !port_freed {
             all_clear = false;
-            log::debug!("UPDATER_SHUTDOWN ► port 8000 still in use; waiting…");
+            log::debug!("BACKEND_LIFECYCLE ► port {} still in use; waiting…", port);
         }
 
-        if check_file_lock && !backend_exe_unlocked(app_handle) {
+        if exe_unlocked == Some(false) {
             all_clear = false;
         }
 
         if all_clear {
             log::info!(
-                "UPDATER_SHUTDOWN ► backend shutdown verified in {:?} (check_file_lock={})",
+                "BACKEND_LIFECYCLE ► backend shutdown verified in {:?} (check_file_lock={})",
                 elapsed,
                 check_file_lock
             );
@@ -75,22 +332,189 @@ fn shutdown_backend_inner(app_handle: &tauri::AppHandle, check_file_lock: bool)
 
         thread::sleep(Duration::from_millis(POLL_MS));
     }
+    steps.push(ShutdownStep {
+        name: "wait_invariants".to_string(),
+        duration_ms: start.elapsed().as_millis() as u64,
+        ok: !timed_out,
+        detail: None,
+    });
+    endpoint::forget_spawned(app_handle, port);
+
+    // 5) Re-seal encrypted data folders now that the backend has let go of
+    //    them. A restart skips this (the backend comes straight back), so
+    //    does a backend that may still be writing, and so does the end of an
+    //    OS session, whose grace period can't cover it; the next launch
+    //    finishes it instead.
+    if reason != "restart" && reason != "session_end" && !timed_out {
+        let step = Instant::now();
+        let result = data_encryption::reseal(app_handle);
+        if let Err(e) = &result {
+            log::error!("BACKEND_LIFECYCLE ► could not reseal data folders: {}", e);
+        }
+        steps.push(ShutdownStep {
+            name: "reseal".to_string(),
+            duration_ms: step.elapsed().as_millis() as u64,
+            ok: result.is_ok(),
+            detail: result.err(),
+        });
+    }
+
+    let escalation = if timed_out {
+        "timeout"
+    } else if killed.is_some() {
+        "kill"
+    } else if graceful {
+        "graceful"
+    } else if port_was_in_use {
+        "http_cleanup"
+    } else {
+        "none"
+    };
+    write_shutdown_report(
+        app_handle,
+        &ShutdownReport {
+            reason: reason.to_string(),
+            started_at,
+            total_ms: overall.elapsed().as_millis() as u64,
+            port,
+            steps,
+            port_freed,
+            exe_unlocked,
+            escalation: escalation.to_string(),
+        },
+    );
+}
+
+/// Whether the backend is still running: by process identity when we
+/// spawned it, otherwise by whether anything still listens on its port.
+fn backend_alive(identity: Option<&ProcessIdentity>, port: u16) -> bool {
+    match identity {
+        Some(identity) => identity.is_alive(),
+        None => port_in_use(port),
+    }
+}
 
-    // 4) Best-effort cleanup of any legacy artifacts can be added here if needed.
+/// Ask the backend to stop on its own: SIGTERM on Unix (uvicorn shuts down
+/// cleanly on it), the `/api/shutdown` RPC on Windows where a console
+/// signal can't be delivered to the sidecar.
+#[cfg(unix)]
+fn request_graceful_stop(
+    app_handle: &tauri::AppHandle,
+    identity: Option<&ProcessIdentity>,
+) -> String {
+    // A pid that no longer names the backend must not be signalled.
+    if let Some(pid) = identity.filter(|id| id.is_alive()).map(|id| id.pid) {
+        let sent = std::process::Command::new("kill")
+            .args(["-TERM", &pid.to_string()])
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false);
+        if sent {
+            log::info!("BACKEND_LIFECYCLE ► sent SIGTERM to backend pid {}", pid);
+            return format!("SIGTERM pid {}", pid);
+        }
+    }
+    shutdown_rpc(app_handle)
+}
+
+#[cfg(not(unix))]
+fn request_graceful_stop(
+    app_handle: &tauri::AppHandle,
+    _identity: Option<&ProcessIdentity>,
+) -> String {
+    shutdown_rpc(app_handle)
+}
+
+fn shutdown_rpc(app_handle: &tauri::AppHandle) -> String {
+    let result = backend_http::call_blocking(
+        app_handle,
+        reqwest::Method::POST,
+        "/api/shutdown",
+        None,
+        Duration::from_millis(1_500),
+        None,
+    );
+    match result.and_then(|reply| {
+        if reply.status.is_success() {
+            Ok(())
+        } else {
+            Err(reply.status.to_string())
+        }
+    }) {
+        Ok(()) => {
+            log::info!("BACKEND_LIFECYCLE ► requested graceful shutdown via RPC");
+            "shutdown rpc".to_string()
+        }
+        Err(e) => {
+            log::debug!("BACKEND_LIFECYCLE ► shutdown rpc failed: {}", e);
+            format!("shutdown rpc failed: {}", e)
+        }
+    }
+}
+
+/// `/api/cleanup` over the active transport.
+pub fn request_cleanup(app_handle: &tauri::AppHandle, port: u16, timeout_ms: u64) -> bool {
+    if endpoint::socket(app_handle).is_none() {
+        return cleanup_via_http(app_handle, port, timeout_ms);
+    }
+    backend_http::call_blocking(
+        app_handle,
+        reqwest::Method::POST,
+        "/api/cleanup",
+        None,
+        Duration::from_millis(timeout_ms * 2),
+        None,
+    )
+    .map(|reply| reply.status.is_success())
+    .unwrap_or(false)
+}
+
+fn shutdown_report_path(app_handle: &tauri::AppHandle) -> Option<std::path::PathBuf> {
+    portable::resolve(app_handle, SHUTDOWN_REPORT_FILE).ok()
+}
+
+fn write_shutdown_report(app_handle: &tauri::AppHandle, report: &ShutdownReport) {
+    let Some(path) = shutdown_report_path(app_handle) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    match serde_json::to_string_pretty(report) {
+        Ok(raw) => {
+            if let Err(e) = fs::write(&path, raw) {
+                log::warn!("BACKEND_LIFECYCLE ► could not write report at {:?}: {}", path, e);
+            }
+        }
+        Err(e) => log::warn!("BACKEND_LIFECYCLE ► could not serialize report: {}", e),
+    }
+}
+
+/// The report written by the most recent shutdown, if any.
+#[tauri::command]
+pub async fn get_last_shutdown_report(
+    app_handle: tauri::AppHandle,
+) -> Result<Option<ShutdownReport>, String> {
+    let Some(path) = shutdown_report_path(&app_handle) else {
+        return Ok(None);
+    };
+    match fs::read_to_string(&path) {
+        Ok(raw) => serde_json::from_str(&raw)
+            .map(Some)
+            .map_err(|e| format!("invalid shutdown report: {}", e)),
+        Err(_) => Ok(None),
+    }
 }
 
 #[cfg(windows)]
 fn backend_exe_unlocked(app_handle: &tauri::AppHandle) -> bool {
     // Probe by attempting a rename‑and‑restore of the backend executable.
     // If either rename fails, we treat the file as still locked.
-    let path = match app_handle
-        .path()
-        .resolve("plattera-backend.exe", BaseDirectory::AppLocalData)
-    {
+    let path = match portable::backend_exe(app_handle, "plattera-backend.exe") {
         Ok(p) => p,
         Err(e) => {
             log::debug!(
-                "UPDATER_SHUTDOWN ► could not resolve backend exe path: {}",
+                "BACKEND_LIFECYCLE ► could not resolve backend exe path: {}",
                 e
             );
             return true;
@@ -110,13 +534,13 @@ fn backend_exe_unlocked(app_handle: &tauri::AppHandle) -> bool {
             // rename succeeded (i.e. the file wasn't locked).
             if let Err(err) = fs::rename(&probe_path, &path) {
                 log::warn!(
-                    "UPDATER_SHUTDOWN ► rename back from probe failed at {:?}: {}",
+                    "BACKEND_LIFECYCLE ► rename back from probe failed at {:?}: {}",
                     probe_path,
                     err
                 );
             } else {
                 log::debug!(
-                    "UPDATER_SHUTDOWN ► backend exe appears rename‑unlocked at {:?}",
+                    "BACKEND_LIFECYCLE ► backend exe appears rename‑unlocked at {:?}",
                     path
                 );
             }
@@ -124,7 +548,7 @@ fn backend_exe_unlocked(app_handle: &tauri::AppHandle) -> bool {
         }
         Err(err) => {
             log::debug!(
-                "UPDATER_SHUTDOWN ► backend exe still locked at {:?} (rename failed): {}",
+                "BACKEND_LIFECYCLE ► backend exe still locked at {:?} (rename failed): {}",
                 path,
                 err
             );
@@ -0,0 +1,283 @@
+// Persistent copy of the backend's stdout/stderr. Lines are appended to
+// rotating files under AppLocalData/logs/backend_output; a file is rotated
+// once it exceeds the size limit or gets too old, and the oldest files are
+// pruned past the configured retention. The most recent lines are also
+// kept in memory for the in-app log viewer (`tail_logs`).
+
+use crate::{portable, settings};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tauri::Manager;
+
+const LOG_DIR: &str = "logs/backend_output";
+const FILE_PREFIX: &str = "backend-";
+/// Lines kept in the in-memory ring buffer.
+const RECENT_LINES: usize = 2_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackendLogSettings {
+    pub enabled: bool,
+    /// Rotate once the current file grows past this size.
+    pub max_file_bytes: u64,
+    /// Rotate once the current file is older than this.
+    pub max_file_age_hours: u64,
+    /// Number of files kept, including the current one.
+    pub retain_files: usize,
+}
+
+impl Default for BackendLogSettings {
+    fn default() -> Self {
+        BackendLogSettings {
+            enabled: true,
+            max_file_bytes: 5_000_000,
+            max_file_age_hours: 24,
+            retain_files: 10,
+        }
+    }
+}
+
+struct CurrentFile {
+    file: File,
+    opened_at: SystemTime,
+    bytes: u64,
+}
+
+#[derive(Default)]
+struct WriterState {
+    current: Option<CurrentFile>,
+    /// Settings captured when the current file was opened.
+    cfg: Option<BackendLogSettings>,
+    /// Latest lines, formatted as in the files, oldest first.
+    recent: VecDeque<String>,
+}
+
+pub struct BackendLogWriter(Mutex<WriterState>);
+
+impl BackendLogWriter {
+    pub fn new() -> Self {
+        BackendLogWriter(Mutex::new(WriterState::default()))
+    }
+}
+
+pub fn log_dir(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    portable::resolve(app_handle, LOG_DIR).ok()
+}
+
+/// Log files, oldest first.
+fn log_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.is_file()
+                        && p.file_name()
+                            .map(|n| n.to_string_lossy().starts_with(FILE_PREFIX))
+                            .unwrap_or(false)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    // Names embed the creation time, so lexical order is age order.
+    files.sort();
+    files
+}
+
+fn prune(dir: &Path, keep: usize) {
+    let files = log_files(dir);
+    let excess = files.len().saturating_sub(keep.max(1));
+    for old in files.into_iter().take(excess) {
+        if let Err(e) = fs::remove_file(&old) {
+            log::debug!("BACKEND_LOG ► could not prune {:?}: {}", old, e);
+        }
+    }
+}
+
+fn open_new(app_handle: &tauri::AppHandle, cfg: &BackendLogSettings) -> Option<CurrentFile> {
+    let dir = log_dir(app_handle)?;
+    if let Err(e) = fs::create_dir_all(&dir) {
+        log::warn!("BACKEND_LOG ► could not create {:?}: {}", dir, e);
+        return None;
+    }
+    // Leave room for the file about to be created.
+    prune(&dir, cfg.retain_files.saturating_sub(1));
+
+    let name = format!(
+        "{}{}.log",
+        FILE_PREFIX,
+        chrono::Local::now().format("%Y%m%d-%H%M%S%.3f")
+    );
+    let path = dir.join(name);
+    match OpenOptions::new().create(true).append(true).open(&path) {
+        Ok(file) => Some(CurrentFile {
+            file,
+            opened_at: SystemTime::now(),
+            bytes: 0,
+        }),
+        Err(e) => {
+            log::warn!("BACKEND_LOG ► could not open {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+fn needs_rotation(current: &CurrentFile, cfg: &BackendLogSettings) -> bool {
+    let max_age = Duration::from_secs(cfg.max_file_age_hours.max(1) * 3600);
+    current.bytes >= cfg.max_file_bytes
+        || current
+            .opened_at
+            .elapsed()
+            .map(|age| age >= max_age)
+            .unwrap_or(false)
+}
+
+/// Append one line of backend output. Failures are swallowed: losing a log
+/// line must never affect the backend.
+pub fn append(app_handle: &tauri::AppHandle, stream: &str, pid: u32, line: &str) {
+    let writer = app_handle.state::<BackendLogWriter>();
+    let mut state = writer.0.lock().unwrap();
+
+    let rotate = match (&state.current, &state.cfg) {
+        (Some(current), Some(cfg)) => needs_rotation(current, cfg),
+        _ => true,
+    };
+    if rotate {
+        let cfg = settings::current(app_handle).backend_log;
+        state.current = if cfg.enabled {
+            open_new(app_handle, &cfg)
+        } else {
+            None
+        };
+        state.cfg = Some(cfg);
+    }
+
+    let entry = format!(
+        "{} [{} pid={}] {}",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+        stream,
+        pid,
+        line
+    );
+    if let Some(current) = state.current.as_mut() {
+        if writeln!(current.file, "{}", entry).is_ok() {
+            current.bytes += entry.len() as u64 + 1;
+        }
+    }
+    if state.recent.len() >= RECENT_LINES {
+        state.recent.pop_front();
+    }
+    state.recent.push_back(entry);
+}
+
+/// Close the current file (e.g. so the log folder can be cleared); the next
+/// line opens a fresh one.
+pub fn close(app_handle: &tauri::AppHandle) {
+    let writer = app_handle.state::<BackendLogWriter>();
+    let mut state = writer.0.lock().unwrap();
+    state.current = None;
+    state.cfg = None;
+}
+
+/// The last `n` lines of backend output seen by this launch.
+pub fn recent(app_handle: &tauri::AppHandle, n: usize) -> Vec<String> {
+    let writer = app_handle.state::<BackendLogWriter>();
+    let state = writer.0.lock().unwrap();
+    let skip = state.recent.len().saturating_sub(n);
+    state.recent.iter().skip(skip).cloned().collect()
+}
+
+/// The last `n` lines the backend wrote to stderr.
+pub fn recent_stderr(app_handle: &tauri::AppHandle, n: usize) -> Vec<String> {
+    let writer = app_handle.state::<BackendLogWriter>();
+    let state = writer.0.lock().unwrap();
+    let mut lines: Vec<String> = state
+        .recent
+        .iter()
+        .rev()
+        .filter(|l| l.contains(" [stderr pid="))
+        .take(n)
+        .cloned()
+        .collect();
+    lines.reverse();
+    lines
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogFileInfo {
+    pub name: String,
+    pub path: String,
+    pub size_bytes: u64,
+    /// RFC 3339 modification time.
+    pub modified: Option<String>,
+}
+
+/// Open a file or folder with the OS default handler.
+pub fn open_path(path: &Path) -> Result<(), String> {
+    #[cfg(windows)]
+    let mut cmd = std::process::Command::new("explorer");
+    #[cfg(target_os = "macos")]
+    let mut cmd = std::process::Command::new("open");
+    #[cfg(not(any(windows, target_os = "macos")))]
+    let mut cmd = std::process::Command::new("xdg-open");
+
+    cmd.arg(path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("could not open {}: {}", path.display(), e))
+}
+
+/// Backend output log files, newest first.
+#[tauri::command]
+pub async fn list_backend_logs(app_handle: tauri::AppHandle) -> Result<Vec<LogFileInfo>, String> {
+    let dir = log_dir(&app_handle).ok_or("could not resolve backend log folder")?;
+    let mut files: Vec<LogFileInfo> = log_files(&dir)
+        .into_iter()
+        .filter_map(|path| {
+            let meta = fs::metadata(&path).ok()?;
+            Some(LogFileInfo {
+                name: path.file_name()?.to_string_lossy().into_owned(),
+                path: path.to_string_lossy().into_owned(),
+                size_bytes: meta.len(),
+                modified: meta
+                    .modified()
+                    .ok()
+                    .map(|t| chrono::DateTime::<chrono::Local>::from(t).to_rfc3339()),
+            })
+        })
+        .collect();
+    files.reverse();
+    Ok(files)
+}
+
+/// Open one backend log file by name, or the log folder when `name` is None.
+#[tauri::command]
+pub async fn open_backend_log(
+    app_handle: tauri::AppHandle,
+    name: Option<String>,
+) -> Result<(), String> {
+    let dir = log_dir(&app_handle).ok_or("could not resolve backend log folder")?;
+    let target = match name {
+        Some(name) => {
+            if name.contains(['/', '\\']) || name.contains("..") {
+                return Err(format!("invalid log file name '{}'", name));
+            }
+            let path = dir.join(&name);
+            if !path.is_file() {
+                return Err(format!("log file '{}' not found", name));
+            }
+            path
+        }
+        None => {
+            fs::create_dir_all(&dir).map_err(|e| format!("create {:?}: {}", dir, e))?;
+            dir
+        }
+    };
+    open_path(&target)
+}
@@ -0,0 +1,92 @@
+// Which backend the shell runs. `sidecar` spawns the bundled binary and
+// only falls back to the dev Python backend when that spawn fails;
+// `dev-python` goes straight to the Python backend from the source tree;
+// `external` spawns nothing and attaches to a backend the user runs
+// themselves at `external_backend` (host and port). `set_backend_mode`
+// stores the choice and restarts the backend under it.
+
+use crate::{backend_lifecycle, endpoint, settings};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackendMode {
+    #[default]
+    Sidecar,
+    DevPython,
+    External,
+}
+
+/// Where an `external` backend listens.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalBackend {
+    pub host: String,
+    pub port: u16,
+}
+
+impl Default for ExternalBackend {
+    fn default() -> Self {
+        ExternalBackend {
+            host: endpoint::LOOPBACK_HOST.to_string(),
+            port: endpoint::DEFAULT_PORT,
+        }
+    }
+}
+
+impl ExternalBackend {
+    pub fn validate(&self) -> Result<(), String> {
+        let host = self.host.trim();
+        if host.is_empty() || host.contains(['/', ' ', '?', '#', '@']) {
+            return Err(format!(
+                "'{}' is not a valid external backend host",
+                self.host
+            ));
+        }
+        if self.port == 0 {
+            return Err("external_backend.port must be greater than zero".into());
+        }
+        Ok(())
+    }
+}
+
+pub fn current(app_handle: &tauri::AppHandle) -> BackendMode {
+    settings::current(app_handle).backend_mode
+}
+
+/// The configured external backend, when in `external` mode.
+pub fn external(app_handle: &tauri::AppHandle) -> Option<ExternalBackend> {
+    let settings = settings::current(app_handle);
+    (settings.backend_mode == BackendMode::External).then_some(settings.external_backend)
+}
+
+/// Switch the backend mode (and, for `external`, where to attach) and
+/// restart the backend under it.
+#[tauri::command]
+pub async fn set_backend_mode(
+    app_handle: tauri::AppHandle,
+    mode: BackendMode,
+    external: Option<ExternalBackend>,
+) -> Result<String, String> {
+    if let Some(external) = &external {
+        external.validate()?;
+    }
+    let before = settings::current(&app_handle);
+    if before.backend_mode == mode && external.is_none() {
+        return Ok(format!("Backend mode is already {:?}", mode));
+    }
+    log::info!(
+        "BACKEND_MODE ► switching {:?} → {:?}",
+        before.backend_mode,
+        mode
+    );
+    settings::update(&app_handle, |s| {
+        s.backend_mode = mode;
+        if let Some(external) = external {
+            s.external_backend = external;
+        }
+    })?;
+    tauri::async_runtime::spawn_blocking(move || backend_lifecycle::restart_backend(&app_handle))
+        .await
+        .map_err(|e| e.to_string())?
+}
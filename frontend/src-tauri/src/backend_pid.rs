@@ -0,0 +1,135 @@
+// Identity of the backend process the shell tracks. A pid alone is not safe
+// to act on later: once the process has exited the OS may hand the number to
+// something unrelated. Next to the pid we keep the process name and start
+// time sysinfo reports right after spawn (for the sidecar and the dev Python
+// fallback alike), and pid-based actions during shutdown (SIGTERM, liveness
+// checks) first verify that the pid still names that same process.
+//
+// The identity is also written to `backend.pid` under AppLocalData (the
+// portable folder in portable mode), so a later run can recognise a backend
+// a crashed shell left behind. Every reader and writer goes through
+// `pid_file_path`; the legacy repo-relative `backend/.server.pid` is moved
+// there by a data migration.
+
+use crate::portable;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use sysinfo::{Pid, ProcessRefreshKind, System};
+use tauri::Manager;
+
+pub const PID_FILE: &str = "backend.pid";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessIdentity {
+    pub pid: u32,
+    pub name: String,
+    /// Seconds since the Unix epoch, as reported by the OS.
+    pub start_time: u64,
+}
+
+impl ProcessIdentity {
+    /// Identity of the running process `pid`, if there is one.
+    pub fn capture(pid: u32) -> Option<Self> {
+        let mut sys = System::new();
+        let id = Pid::from_u32(pid);
+        if !sys.refresh_process_specifics(id, ProcessRefreshKind::new()) {
+            return None;
+        }
+        sys.process(id).map(|p| ProcessIdentity {
+            pid,
+            name: p.name().to_string(),
+            start_time: p.start_time(),
+        })
+    }
+
+    /// Whether the pid still belongs to this process (not a reused number).
+    pub fn is_alive(&self) -> bool {
+        Self::capture(self.pid).as_ref() == Some(self)
+    }
+}
+
+/// Identity of the child in `BackendProcess`.
+pub struct BackendIdentity(Mutex<Option<ProcessIdentity>>);
+
+impl BackendIdentity {
+    pub fn new() -> Self {
+        BackendIdentity(Mutex::new(None))
+    }
+}
+
+pub fn pid_file_path(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    portable::resolve(app_handle, PID_FILE).ok()
+}
+
+pub fn write_record(path: &Path, identity: &ProcessIdentity) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("create {:?}: {}", parent, e))?;
+    }
+    let raw = serde_json::to_string_pretty(identity).map_err(|e| e.to_string())?;
+    fs::write(path, raw).map_err(|e| format!("write {:?}: {}", path, e))
+}
+
+/// Identity left in the pid file, e.g. by a run that crashed.
+pub fn read_record(app_handle: &tauri::AppHandle) -> Option<ProcessIdentity> {
+    let raw = fs::read_to_string(pid_file_path(app_handle)?).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+pub fn remove_record(app_handle: &tauri::AppHandle) {
+    if let Some(path) = pid_file_path(app_handle) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Record the newly tracked backend child.
+pub fn track(app_handle: &tauri::AppHandle, pid: u32) {
+    let identity = ProcessIdentity::capture(pid);
+    match &identity {
+        Some(id) => {
+            log::info!(
+                "BACKEND_PID ► tracking pid {} ({}, started {})",
+                id.pid,
+                id.name,
+                id.start_time
+            );
+            if let Some(path) = pid_file_path(app_handle) {
+                if let Err(e) = write_record(&path, id) {
+                    log::warn!("BACKEND_PID ► could not write pid file: {}", e);
+                }
+            }
+        }
+        None => {
+            log::warn!(
+                "BACKEND_PID ► pid {} exited before it could be identified",
+                pid
+            );
+            remove_record(app_handle);
+        }
+    }
+    *app_handle.state::<BackendIdentity>().0.lock().unwrap() = identity;
+}
+
+/// Forget the tracked identity if it belongs to `pid`.
+pub fn untrack(app_handle: &tauri::AppHandle, pid: u32) {
+    let state = app_handle.state::<BackendIdentity>();
+    let mut current = state.0.lock().unwrap();
+    if current.as_ref().map(|id| id.pid) == Some(pid) {
+        *current = None;
+        remove_record(app_handle);
+    }
+}
+
+/// Take the tracked identity, e.g. when shutdown takes the child out of
+/// state.
+pub fn take(app_handle: &tauri::AppHandle) -> Option<ProcessIdentity> {
+    let identity = app_handle
+        .state::<BackendIdentity>()
+        .0
+        .lock()
+        .unwrap()
+        .take();
+    remove_record(app_handle);
+    identity
+}
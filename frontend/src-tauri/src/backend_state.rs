@@ -0,0 +1,143 @@
+// Backend state as the frontend should render it. The startup sequence
+// (`startup.rs`) moves it from `idle` through `starting` to `ready` or
+// `failed`, and the crash supervisor sends it back to `starting` (restart
+// scheduled) or `failed` (given up). Once ready, the health watchdog
+// (`health_watchdog.rs`) moves it between `ready`, `degraded` and
+// `unreachable`; `stop_backend` parks it in `stopped` (see
+// `backend_control.rs`). Every transition is emitted as `backend-<state>`
+// (with a `reason` where there is one), so the UI can follow along instead
+// of polling; a window that loads late asks `get_backend_state` for the
+// current value.
+
+use crate::{correlation, endpoint};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::Manager;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum BackendState {
+    /// Nothing has been started yet.
+    Idle,
+    Starting {
+        since: String,
+    },
+    Ready {
+        port: u16,
+        since: String,
+    },
+    /// Answers, but slowly or only intermittently.
+    Degraded {
+        port: u16,
+        reason: String,
+        since: String,
+    },
+    /// Was ready, but has stopped answering.
+    Unreachable {
+        reason: String,
+        since: String,
+    },
+    Failed {
+        reason: String,
+        since: String,
+    },
+    /// Stopped on purpose with `stop_backend`.
+    Stopped {
+        since: String,
+    },
+}
+
+pub struct BackendStateCell(Mutex<BackendState>);
+
+impl BackendStateCell {
+    pub fn new() -> Self {
+        BackendStateCell(Mutex::new(BackendState::Idle))
+    }
+}
+
+fn now() -> String {
+    chrono::Local::now().to_rfc3339()
+}
+
+fn transition(app_handle: &tauri::AppHandle, event: &str, state: BackendState) {
+    *app_handle.state::<BackendStateCell>().0.lock().unwrap() = state.clone();
+    correlation::emit(app_handle, event, state);
+}
+
+pub fn set_starting(app_handle: &tauri::AppHandle) {
+    transition(
+        app_handle,
+        "backend-starting",
+        BackendState::Starting { since: now() },
+    );
+}
+
+pub fn set_ready(app_handle: &tauri::AppHandle) {
+    let port = endpoint::port(app_handle);
+    log::info!("BACKEND_STATE ► ready on port {}", port);
+    transition(
+        app_handle,
+        "backend-ready",
+        BackendState::Ready { port, since: now() },
+    );
+}
+
+pub fn set_degraded(app_handle: &tauri::AppHandle, reason: &str) {
+    log::warn!("BACKEND_STATE ► degraded: {}", reason);
+    transition(
+        app_handle,
+        "backend-degraded",
+        BackendState::Degraded {
+            port: endpoint::port(app_handle),
+            reason: reason.to_string(),
+            since: now(),
+        },
+    );
+}
+
+pub fn set_unreachable(app_handle: &tauri::AppHandle, reason: &str) {
+    log::warn!("BACKEND_STATE ► unreachable: {}", reason);
+    transition(
+        app_handle,
+        "backend-unreachable",
+        BackendState::Unreachable {
+            reason: reason.to_string(),
+            since: now(),
+        },
+    );
+}
+
+pub fn set_failed(app_handle: &tauri::AppHandle, reason: &str) {
+    log::warn!("BACKEND_STATE ► failed: {}", reason);
+    transition(
+        app_handle,
+        "backend-failed",
+        BackendState::Failed {
+            reason: reason.to_string(),
+            since: now(),
+        },
+    );
+}
+
+pub fn set_stopped(app_handle: &tauri::AppHandle) {
+    log::info!("BACKEND_STATE ► stopped by the user");
+    transition(
+        app_handle,
+        "backend-stopped",
+        BackendState::Stopped { since: now() },
+    );
+}
+
+pub fn current(app_handle: &tauri::AppHandle) -> BackendState {
+    app_handle
+        .state::<BackendStateCell>()
+        .0
+        .lock()
+        .unwrap()
+        .clone()
+}
+
+#[tauri::command]
+pub async fn get_backend_state(app_handle: tauri::AppHandle) -> Result<BackendState, String> {
+    Ok(current(&app_handle))
+}
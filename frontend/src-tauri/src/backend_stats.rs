@@ -0,0 +1,149 @@
+// Resource monitoring of the backend process for the performance HUD. A
+// sampler thread reads the tracked backend's CPU, resident memory and open
+// handle count every few seconds, keeps the last few minutes in memory and
+// emits each sample as `backend-stats`. The history restarts whenever a
+// different backend process is tracked.
+
+use crate::BackendProcess;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use sysinfo::{Pid, ProcessRefreshKind, System};
+use tauri::{Emitter, Manager};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(3);
+/// Samples kept (six minutes at the interval above).
+const HISTORY_LEN: usize = 120;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsSample {
+    pub at: String,
+    pub pid: u32,
+    /// Percent of one core; can exceed 100 on multi-core machines.
+    pub cpu_percent: f32,
+    pub rss_bytes: u64,
+    pub virtual_bytes: u64,
+    /// Open handles (Windows) or file descriptors (Linux); `None` elsewhere.
+    pub handles: Option<u64>,
+}
+
+pub struct BackendStats(Mutex<VecDeque<StatsSample>>);
+
+impl BackendStats {
+    pub fn new() -> Self {
+        BackendStats(Mutex::new(VecDeque::new()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendStatsReport {
+    pub current: Option<StatsSample>,
+    /// Oldest first.
+    pub history: Vec<StatsSample>,
+    pub interval_ms: u64,
+    /// Logical CPUs, to scale `cpu_percent` to the whole machine.
+    pub cpu_count: usize,
+}
+
+#[cfg(windows)]
+fn handle_count(pid: u32) -> Option<u64> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        GetProcessHandleCount, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process == 0 {
+            return None;
+        }
+        let mut count = 0u32;
+        let ok = GetProcessHandleCount(process, &mut count);
+        CloseHandle(process);
+        (ok != 0).then_some(count as u64)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn handle_count(pid: u32) -> Option<u64> {
+    std::fs::read_dir(format!("/proc/{}/fd", pid))
+        .ok()
+        .map(|entries| entries.count() as u64)
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn handle_count(_pid: u32) -> Option<u64> {
+    None
+}
+
+fn tracked_pid(app_handle: &tauri::AppHandle) -> Option<u32> {
+    app_handle
+        .state::<BackendProcess>()
+        .0
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|c| c.pid())
+}
+
+/// Start sampling on a dedicated thread.
+pub fn spawn_sampler(app_handle: tauri::AppHandle) {
+    thread::spawn(move || {
+        let mut sys = System::new();
+        loop {
+            thread::sleep(SAMPLE_INTERVAL);
+            let Some(pid) = tracked_pid(&app_handle) else {
+                continue;
+            };
+            // CPU usage is measured between two refreshes of the same
+            // `System`, so the first sample of a new process reads 0.
+            let id = Pid::from_u32(pid);
+            sys.refresh_process_specifics(id, ProcessRefreshKind::new().with_cpu().with_memory());
+            let Some(process) = sys.process(id) else {
+                continue;
+            };
+            let sample = StatsSample {
+                at: chrono::Local::now().to_rfc3339(),
+                pid,
+                cpu_percent: process.cpu_usage(),
+                rss_bytes: process.memory(),
+                virtual_bytes: process.virtual_memory(),
+                handles: handle_count(pid),
+            };
+            {
+                let stats = app_handle.state::<BackendStats>();
+                let mut history = stats.0.lock().unwrap();
+                if history.back().is_some_and(|s| s.pid != pid) {
+                    history.clear();
+                }
+                if history.len() >= HISTORY_LEN {
+                    history.pop_front();
+                }
+                history.push_back(sample.clone());
+            }
+            let _ = app_handle.emit("backend-stats", &sample);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_backend_stats(app_handle: tauri::AppHandle) -> Result<BackendStatsReport, String> {
+    let history: Vec<StatsSample> = app_handle
+        .state::<BackendStats>()
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .cloned()
+        .collect();
+    let mut sys = System::new();
+    sys.refresh_cpu();
+    Ok(BackendStatsReport {
+        current: history.last().cloned(),
+        history,
+        interval_ms: SAMPLE_INTERVAL.as_millis() as u64,
+        cpu_count: sys.cpus().len(),
+    })
+}
@@ -0,0 +1,520 @@
+// Backend-only updates. Shipping a full NSIS installer for a Python fix is
+// heavy, so the backend can be patched on its own: `releases/backend-latest.json`
+// lists a sidecar build per platform, which is downloaded next to the
+// installed one and checked for size, SHA-256 and a minisign signature made
+// with the app updater's key before anything is touched.
+//
+// The swap works like the updater's lock probe: with the backend stopped
+// the installed binary is renamed aside (which only succeeds once nothing
+// holds it) and the new one renamed into place. The old binary stays as
+// `<name>.previous`; a patched backend that doesn't come up healthy is
+// rolled back right away, and `rollback_backend_update` undoes a patch
+// later. The installed patch is recorded so the sidecar integrity check
+// accepts it, and the record is dropped once a newer app ships its own
+// sidecar. Version pins and the update channel apply as for app updates.
+
+use crate::backend_lifecycle::shutdown_backend_for_update;
+use crate::{
+    backend_http, correlation, health, portable, proxy, runtime_info, sidecar_fallback,
+    sidecar_integrity, storage, telemetry, updates, version_pin,
+};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+const BACKEND_FEED: &str =
+    "https://raw.githubusercontent.com/bwanedead/Plattera/main/releases/backend-latest.json";
+const UPDATE_DIR: &str = "backend_update";
+const RECORD_FILE: &str = "installed.json";
+/// Budget for a patched backend to report healthy before it is rolled back.
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(90);
+/// Refuse feeds pointing at something far larger than a sidecar.
+const MAX_DOWNLOAD_BYTES: u64 = 1024 * 1024 * 1024;
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+static BUSY: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Deserialize)]
+struct BackendFeed {
+    version: String,
+    #[serde(default)]
+    notes: String,
+    /// Oldest app the build works with (API/settings compatibility).
+    #[serde(default)]
+    min_app_version: Option<String>,
+    platforms: HashMap<String, BackendAsset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BackendAsset {
+    url: String,
+    sha256: String,
+    size: u64,
+    /// Base64 minisign signature, as produced by `tauri signer sign`.
+    signature: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendUpdateInfo {
+    pub version: String,
+    pub current: String,
+    pub notes: String,
+    pub size: u64,
+}
+
+/// A backend patch on top of the bundled sidecar.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledPatch {
+    pub version: String,
+    pub sha256: String,
+    pub size: u64,
+    /// App version the patch was installed under.
+    pub app_version: String,
+    pub installed_at: String,
+    /// The patch this one replaced, restored on rollback.
+    #[serde(default)]
+    pub replaced: Option<Box<InstalledPatch>>,
+}
+
+#[derive(Clone, Serialize)]
+struct RolledBackPayload {
+    version: String,
+    reason: String,
+}
+
+fn update_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    portable::resolve(app_handle, UPDATE_DIR).map_err(|e| e.to_string())
+}
+
+fn read_record(app_handle: &tauri::AppHandle) -> Option<InstalledPatch> {
+    let raw = fs::read_to_string(update_dir(app_handle).ok()?.join(RECORD_FILE)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn write_record(
+    app_handle: &tauri::AppHandle,
+    record: Option<&InstalledPatch>,
+) -> Result<(), String> {
+    let dir = update_dir(app_handle)?;
+    let path = dir.join(RECORD_FILE);
+    match record {
+        Some(record) => {
+            fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            let json = serde_json::to_string_pretty(record).map_err(|e| e.to_string())?;
+            fs::write(&path, json).map_err(|e| e.to_string())
+        }
+        None => match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        },
+    }
+}
+
+/// The backend patch in effect for this app version, if any.
+pub fn installed_patch(app_handle: &tauri::AppHandle) -> Option<InstalledPatch> {
+    let current = app_handle.package_info().version.to_string();
+    read_record(app_handle).filter(|r| r.app_version == current)
+}
+
+fn previous_path(sidecar: &Path) -> PathBuf {
+    let name = sidecar.file_name().unwrap_or_default().to_string_lossy();
+    sidecar.with_file_name(format!("{}.previous", name))
+}
+
+/// Startup: a patch recorded under another app version was replaced by that
+/// version's own sidecar; forget it.
+pub fn reconcile(app_handle: &tauri::AppHandle) {
+    let Some(record) = read_record(app_handle) else {
+        return;
+    };
+    if installed_patch(app_handle).is_some() {
+        log::info!("BACKEND_UPDATE ► running backend patch {}", record.version);
+        return;
+    }
+    log::info!(
+        "BACKEND_UPDATE ► dropping backend patch {} (installed under app {})",
+        record.version,
+        record.app_version
+    );
+    let _ = write_record(app_handle, None);
+    if let Some(sidecar) = sidecar_fallback::bundled_sidecar_path() {
+        let _ = fs::remove_file(previous_path(&sidecar));
+    }
+}
+
+fn platform_key() -> String {
+    let os = match std::env::consts::OS {
+        "macos" => "darwin",
+        other => other,
+    };
+    format!("{}-{}", os, std::env::consts::ARCH)
+}
+
+fn parse_version(v: &str) -> Option<semver::Version> {
+    semver::Version::parse(v.trim().trim_start_matches('v')).ok()
+}
+
+fn current_backend_version(app_handle: &tauri::AppHandle) -> String {
+    installed_patch(app_handle)
+        .map(|p| p.version)
+        .or_else(|| runtime_info::probe_backend_version(app_handle))
+        .unwrap_or_else(|| app_handle.package_info().version.to_string())
+}
+
+/// The update to offer, if any. Blocking.
+fn find_update(
+    app_handle: &tauri::AppHandle,
+) -> Result<Option<(BackendFeed, BackendAsset)>, String> {
+    let timeout = Duration::from_millis(15_000);
+    let req = proxy::client_builder(app_handle, BACKEND_FEED)
+        .connect_timeout(Duration::from_millis(5_000))
+        .timeout(timeout)
+        .build()
+        .map_err(|e| e.to_string())?
+        .get(BACKEND_FEED);
+    let feed: BackendFeed = backend_http::block_on(timeout, async move {
+        let res = req
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(|e| format!("backend update feed unavailable: {}", e))?;
+        res.json()
+            .await
+            .map_err(|e| format!("backend update feed unreadable: {}", e))
+    })?;
+
+    let Some(remote) = parse_version(&feed.version) else {
+        return Err(format!(
+            "backend update feed has an invalid version '{}'",
+            feed.version
+        ));
+    };
+    let app_version = app_handle.package_info().version.clone();
+    if let Some(min) = feed.min_app_version.as_deref().and_then(parse_version) {
+        if app_version < min {
+            log::info!(
+                "BACKEND_UPDATE ► backend {} needs app {} or newer (running {})",
+                remote,
+                min,
+                app_version
+            );
+            return Ok(None);
+        }
+    }
+    let current = current_backend_version(app_handle);
+    let newer = parse_version(&current).map_or(true, |c| remote > c);
+    let allowed = newer
+        && updates::channel_allows(app_handle, &remote)
+        && version_pin::allow_app_update(app_handle, &current, &feed.version, newer);
+    if !allowed {
+        return Ok(None);
+    }
+    let Some(asset) = feed.platforms.get(&platform_key()).cloned() else {
+        log::info!(
+            "BACKEND_UPDATE ► no backend {} build for {}",
+            remote,
+            platform_key()
+        );
+        return Ok(None);
+    };
+    Ok(Some((feed, asset)))
+}
+
+fn updater_pubkey(app_handle: &tauri::AppHandle) -> Result<String, String> {
+    app_handle
+        .config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|u| u.get("pubkey"))
+        .and_then(|k| k.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| "no updater public key configured".to_string())
+}
+
+fn base64_text(value: &str) -> Result<String, String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(value.trim())
+        .map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+fn verify_signature(data: &[u8], signature: &str, pubkey: &str) -> Result<(), String> {
+    let key = minisign_verify::PublicKey::decode(&base64_text(pubkey)?)
+        .map_err(|e| format!("invalid updater key: {}", e))?;
+    let signature = minisign_verify::Signature::decode(&base64_text(signature)?)
+        .map_err(|e| format!("invalid signature: {}", e))?;
+    key.verify(data, &signature, true)
+        .map_err(|e| format!("signature check failed: {}", e))
+}
+
+/// Download `asset` to `dest` and verify it. Blocking.
+fn download(
+    app_handle: &tauri::AppHandle,
+    asset: &BackendAsset,
+    dest: &Path,
+    pubkey: &str,
+) -> Result<(), String> {
+    if asset.size > MAX_DOWNLOAD_BYTES {
+        return Err(format!(
+            "backend build is implausibly large ({} bytes)",
+            asset.size
+        ));
+    }
+    let req = proxy::client_builder(app_handle, &asset.url)
+        .connect_timeout(Duration::from_millis(10_000))
+        .timeout(DOWNLOAD_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?
+        .get(&asset.url);
+    let size = asset.size;
+    let data = backend_http::block_on(DOWNLOAD_TIMEOUT, async move {
+        let mut res = req
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(|e| format!("download failed: {}", e))?;
+        let mut data = Vec::with_capacity(size as usize);
+        // Stop one byte past the expected size; that is enough to reject it.
+        while let Some(chunk) = res
+            .chunk()
+            .await
+            .map_err(|e| format!("download failed: {}", e))?
+        {
+            data.extend_from_slice(&chunk);
+            if data.len() as u64 > size {
+                break;
+            }
+        }
+        Ok(data)
+    })?;
+    if data.len() as u64 != asset.size {
+        return Err(format!(
+            "downloaded {} bytes, expected {}",
+            data.len(),
+            asset.size
+        ));
+    }
+    verify_signature(&data, &asset.signature, pubkey)?;
+    fs::write(dest, &data).map_err(|e| format!("cannot write {}: {}", dest.display(), e))?;
+    let hash = sidecar_integrity::sha256_file(dest)?;
+    if !hash.eq_ignore_ascii_case(&asset.sha256) {
+        let _ = fs::remove_file(dest);
+        return Err(format!(
+            "SHA-256 mismatch (expected {}, got {})",
+            asset.sha256, hash
+        ));
+    }
+    Ok(())
+}
+
+/// Rename with retries: the stopped backend (or an antivirus scan) may hold
+/// the file for a moment.
+fn rename_retrying(from: &Path, to: &Path) -> io::Result<()> {
+    let mut attempt = 0;
+    loop {
+        match fs::rename(from, to) {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt >= 20 => return Err(e),
+            Err(_) => {
+                attempt += 1;
+                thread::sleep(Duration::from_millis(250));
+            }
+        }
+    }
+}
+
+/// Move the installed sidecar aside and `incoming` into its place.
+fn swap_in(sidecar: &Path, incoming: &Path) -> Result<(), String> {
+    let previous = previous_path(sidecar);
+    let _ = fs::remove_file(&previous);
+    rename_retrying(sidecar, &previous)
+        .map_err(|e| format!("backend binary is still in use ({})", e))?;
+    if let Err(e) = fs::rename(incoming, sidecar) {
+        let _ = rename_retrying(&previous, sidecar);
+        return Err(format!("cannot move new backend into place: {}", e));
+    }
+    Ok(())
+}
+
+/// Put `<name>.previous` back in place of the installed sidecar.
+fn restore_previous(sidecar: &Path) -> Result<(), String> {
+    let previous = previous_path(sidecar);
+    if !previous.is_file() {
+        return Err("no previous backend binary to restore".to_string());
+    }
+    let rejected = sidecar.with_file_name(format!(
+        "{}.rejected",
+        sidecar.file_name().unwrap_or_default().to_string_lossy()
+    ));
+    let _ = fs::remove_file(&rejected);
+    rename_retrying(sidecar, &rejected)
+        .map_err(|e| format!("backend binary is still in use ({})", e))?;
+    rename_retrying(&previous, sidecar).map_err(|e| e.to_string())?;
+    let _ = fs::remove_file(&rejected);
+    Ok(())
+}
+
+fn roll_back(app_handle: &tauri::AppHandle, sidecar: &Path, reason: &str) -> Result<(), String> {
+    let _op = correlation::begin("backend_rollback");
+    let record = read_record(app_handle);
+    let version = record
+        .as_ref()
+        .map(|r| r.version.clone())
+        .unwrap_or_default();
+    log::warn!(
+        "BACKEND_UPDATE ► rolling back backend {}: {}",
+        version,
+        reason
+    );
+    shutdown_backend_for_update(app_handle);
+    let restored = restore_previous(sidecar);
+    if restored.is_ok() {
+        let replaced = record.and_then(|r| r.replaced).map(|b| *b);
+        let _ = write_record(app_handle, replaced.as_ref());
+        correlation::emit(
+            app_handle,
+            "backend-update-rolled-back",
+            RolledBackPayload {
+                version,
+                reason: reason.to_string(),
+            },
+        );
+        telemetry::record(
+            app_handle,
+            "update_outcome",
+            serde_json::json!({ "kind": "backend", "outcome": "rolled_back" }),
+        );
+    }
+    let _ = crate::spawn_backend(app_handle);
+    restored
+}
+
+fn install(app_handle: &tauri::AppHandle) -> Result<String, String> {
+    let _op = correlation::begin("backend_update");
+    let (feed, asset) = find_update(app_handle)?.ok_or("no backend update available")?;
+    let sidecar = sidecar_fallback::bundled_sidecar_path()
+        .filter(|p| p.is_file())
+        .ok_or("no bundled backend to update (dev build?)")?;
+    let incoming = sidecar.with_file_name(format!(
+        "{}.incoming",
+        sidecar.file_name().unwrap_or_default().to_string_lossy()
+    ));
+
+    // The incoming binary sits next to the installed one and the previous.
+    storage::check(
+        app_handle,
+        "backend_update",
+        &sidecar,
+        asset.size.saturating_mul(2) + storage::MARGIN,
+    )?;
+
+    log::info!("BACKEND_UPDATE ► downloading backend {}", feed.version);
+    // Downloaded next to the sidecar so the final rename stays on one volume.
+    if let Err(e) = download(app_handle, &asset, &incoming, &updater_pubkey(app_handle)?) {
+        let _ = fs::remove_file(&incoming);
+        return Err(e);
+    }
+
+    shutdown_backend_for_update(app_handle);
+    if let Err(e) = swap_in(&sidecar, &incoming) {
+        let _ = fs::remove_file(&incoming);
+        let _ = crate::spawn_backend(app_handle);
+        return Err(e);
+    }
+    let record = InstalledPatch {
+        version: feed.version.clone(),
+        sha256: asset.sha256.to_ascii_lowercase(),
+        size: asset.size,
+        app_version: app_handle.package_info().version.to_string(),
+        installed_at: chrono::Local::now().to_rfc3339(),
+        replaced: installed_patch(app_handle).map(Box::new),
+    };
+    write_record(app_handle, Some(&record))?;
+    log::info!(
+        "BACKEND_UPDATE ► backend {} installed; verifying",
+        feed.version
+    );
+
+    if let Err(e) = crate::spawn_backend(app_handle) {
+        roll_back(app_handle, &sidecar, &format!("failed to start: {}", e))?;
+        return Err(format!(
+            "backend {} failed to start and was rolled back",
+            feed.version
+        ));
+    }
+    if !health::wait_until_healthy(app_handle, VERIFY_TIMEOUT) {
+        roll_back(app_handle, &sidecar, "not healthy after the update")?;
+        return Err(format!(
+            "backend {} did not become healthy and was rolled back",
+            feed.version
+        ));
+    }
+    log::info!("BACKEND_UPDATE ► backend {} verified", feed.version);
+    correlation::emit(app_handle, "backend-update-installed", &feed.version);
+    telemetry::record(
+        app_handle,
+        "update_outcome",
+        serde_json::json!({ "kind": "backend", "outcome": "verified" }),
+    );
+    Ok(feed.version)
+}
+
+/// Run `f` unless another backend update operation is in progress.
+fn exclusive<T>(f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    if BUSY.swap(true, Ordering::SeqCst) {
+        return Err("a backend update is already in progress".into());
+    }
+    let result = f();
+    BUSY.store(false, Ordering::SeqCst);
+    result
+}
+
+#[tauri::command]
+pub async fn check_backend_update(
+    app_handle: tauri::AppHandle,
+) -> Result<Option<BackendUpdateInfo>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let found = find_update(&app_handle)?;
+        Ok(found.map(|(feed, asset)| BackendUpdateInfo {
+            current: current_backend_version(&app_handle),
+            version: feed.version,
+            notes: feed.notes,
+            size: asset.size,
+        }))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Download, verify and swap in the latest backend; rolls back on its own if
+/// the new backend doesn't come up. Returns the installed version.
+#[tauri::command]
+pub async fn install_backend_update(app_handle: tauri::AppHandle) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || exclusive(|| install(&app_handle)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Undo the last backend patch.
+#[tauri::command]
+pub async fn rollback_backend_update(app_handle: tauri::AppHandle) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        exclusive(|| {
+            if installed_patch(&app_handle).is_none() {
+                return Err("no backend update to roll back".into());
+            }
+            let sidecar = sidecar_fallback::bundled_sidecar_path()
+                .ok_or("could not resolve the backend binary")?;
+            roll_back(&app_handle, &sidecar, "requested by user")
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,722 @@
+// Backups of the user's data. `create_backup` asks the backend to
+// checkpoint (the same `/api/cleanup` the shutdown path uses: flush and
+// close databases), then streams the backend data folder (dossiers, PLSS)
+// and the shell settings into a timestamped zip at a path the user picked.
+// Folders kept encrypted (`data_encryption`) are archived from their
+// decrypted runtime copy while the backend has them open, in place of the
+// sealed file, which lags behind until the next reseal; the archive holds
+// them in plain form and a restore seals them again on the next start.
+// Files are copied through the zip writer in chunks, never read whole, and
+// `backup-progress` events report how far along it is, so multi-GB data
+// neither freezes the UI nor exhausts memory. The archive is written under
+// a `.partial` name and renamed once complete.
+//
+// Every archive carries `manifest.json` (format, data schema, versions,
+// file count). `restore_backup` validates it before touching anything; an
+// archive from a newer format or another data schema is refused with
+// `IncompatibleBackup`, also emitted as `incompatible-backup`. The data is
+// extracted into a staging folder next to the data root while the backend
+// still runs; only the swap happens with the backend stopped: the live
+// folder is renamed aside, the staged one renamed into place, and the old
+// one deleted once that succeeded. The sidecar is then started again.
+// Shell settings in the archive are not restored: they hold machine-specific
+// values (data location, ports) that may not fit this install.
+
+use crate::backend_lifecycle::shutdown_backend_for_exit;
+use crate::{
+    backend_lifecycle, correlation, data_encryption, endpoint, paths, portable, runtime_info,
+    settings, spawn_backend, storage, supervisor,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Version of the archive layout.
+pub const BACKUP_FORMAT: u32 = 1;
+/// Version of the data layout inside `data/` (dossiers_data, plss). Bumped
+/// when the backend changes its on-disk layout incompatibly.
+pub const DATA_SCHEMA: u32 = 1;
+pub const MANIFEST_NAME: &str = "manifest.json";
+/// Archive folder holding the backend data root.
+pub const DATA_PREFIX: &str = "data/";
+/// Archive folder holding shell state.
+pub const SHELL_PREFIX: &str = "shell/";
+/// Shell files included in a backup.
+const SHELL_FILES: &[&str] = &["settings.json"];
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+const CLEANUP_TIMEOUT_MS: u64 = 5_000;
+/// Already-compressed formats are stored instead of deflated again.
+const STORED_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "webp", "tif", "tiff", "pdf", "zip", "gz", "7z", "docx", "xlsx",
+];
+
+static BUSY: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format: u32,
+    pub data_schema: u32,
+    pub app_version: String,
+    pub backend_version: Option<String>,
+    pub created_at: String,
+    pub files: u64,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupProgress {
+    /// "scanning", "archiving", "finishing" or "done"; for a restore
+    /// "validating", "extracting", "swapping" or "done".
+    pub phase: &'static str,
+    pub files_done: u64,
+    pub files_total: u64,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub current: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupResult {
+    pub path: String,
+    pub files: u64,
+    pub bytes: u64,
+    pub archive_bytes: u64,
+    pub checkpointed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreResult {
+    pub files: u64,
+    pub bytes: u64,
+    pub created_at: String,
+    pub backup_app_version: String,
+    /// Whether the backend came back up after the swap.
+    pub backend_restarted: bool,
+}
+
+/// Typed error for an archive this version can't restore.
+#[derive(Debug, Clone, Serialize)]
+pub struct IncompatibleBackup {
+    pub path: String,
+    pub backup_format: u32,
+    pub backup_data_schema: u32,
+    pub backup_app_version: String,
+    pub supported_format: u32,
+    pub supported_data_schema: u32,
+}
+
+impl fmt::Display for IncompatibleBackup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.backup_format > self.supported_format {
+            write!(
+                f,
+                "backup was made by a newer version (Plattera {}, format {}); this version reads format {}",
+                self.backup_app_version, self.backup_format, self.supported_format
+            )
+        } else {
+            write!(
+                f,
+                "backup holds data schema {} (Plattera {}); this version uses schema {}",
+                self.backup_data_schema, self.backup_app_version, self.supported_data_schema
+            )
+        }
+    }
+}
+
+/// Run `f` unless another backup or restore is in progress.
+pub fn exclusive<T>(f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    if BUSY.swap(true, Ordering::SeqCst) {
+        return Err("a backup or restore is already in progress".into());
+    }
+    let result = f();
+    BUSY.store(false, Ordering::SeqCst);
+    result
+}
+
+/// Files below `root` as (archive name, path, size), in a stable order.
+fn collect(root: &Path, prefix: &str, out: &mut Vec<(String, PathBuf, u64)>) {
+    let Ok(entries) = fs::read_dir(paths::extended(root)) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.flatten().collect();
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        let Ok(meta) = fs::symlink_metadata(entry.path()) else {
+            continue;
+        };
+        if meta.is_dir() {
+            collect(&entry.path(), &format!("{}/", name), out);
+        } else if meta.is_file() {
+            out.push((name, entry.path(), meta.len()));
+        }
+    }
+}
+
+/// Files of the data root to archive. A folder in `encrypted` that is linked
+/// to its decrypted copy under `runtime_root` is archived from there, and
+/// its sealed file is left out.
+fn collect_data(
+    data_root: &Path,
+    runtime_root: &Path,
+    encrypted: &[String],
+) -> Vec<(String, PathBuf, u64)> {
+    let mut files = Vec::new();
+    collect(data_root, DATA_PREFIX, &mut files);
+    for name in encrypted {
+        if !data_encryption::is_link(&data_root.join(name)) {
+            continue;
+        }
+        let sealed = data_encryption::sealed_path(data_root, name);
+        files.retain(|f| f.1 != sealed);
+        collect(
+            &runtime_root.join(name),
+            &format!("{}{}/", DATA_PREFIX, name),
+            &mut files,
+        );
+    }
+    files
+}
+
+pub fn options_for(name: &str) -> SimpleFileOptions {
+    let stored = Path::new(name)
+        .extension()
+        .map(|e| STORED_EXTENSIONS.contains(&e.to_string_lossy().to_ascii_lowercase().as_str()))
+        .unwrap_or(false);
+    SimpleFileOptions::default()
+        .compression_method(if stored {
+            CompressionMethod::Stored
+        } else {
+            CompressionMethod::Deflated
+        })
+        .large_file(true)
+}
+
+/// `path` itself, or a timestamped archive inside it when it is a folder.
+fn target_path(path: &str) -> Result<PathBuf, String> {
+    let path = paths::plain(Path::new(path.trim()));
+    if !path.is_absolute() {
+        return Err(format!("{} is not an absolute path", path.display()));
+    }
+    if path.is_dir() {
+        return Ok(path.join(format!(
+            "plattera-backup-{}.zip",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        )));
+    }
+    Ok(path)
+}
+
+fn create(app_handle: &tauri::AppHandle, path: &str) -> Result<BackupResult, String> {
+    let target = target_path(path)?;
+    let folder = target.parent().ok_or("backup path has no folder")?;
+    let check = paths::check(&folder.to_string_lossy());
+    if !check.ok {
+        return Err(format!(
+            "cannot write the backup there: {}",
+            check.problems.join("; ")
+        ));
+    }
+    let data_root = settings::backend_data_dir(app_handle)?;
+    if target.starts_with(&data_root) {
+        return Err("the backup can't be saved inside the data folder it backs up".into());
+    }
+
+    let emit = |progress: BackupProgress| {
+        let _ = app_handle.emit("backup-progress", &progress);
+    };
+    emit(BackupProgress {
+        phase: "scanning",
+        files_done: 0,
+        files_total: 0,
+        bytes_done: 0,
+        bytes_total: 0,
+        current: None,
+    });
+    // Flush and close the backend's databases so the files are consistent.
+    let checkpointed = backend_lifecycle::request_cleanup(
+        app_handle,
+        endpoint::port(app_handle),
+        CLEANUP_TIMEOUT_MS,
+    );
+    if !checkpointed {
+        log::warn!("BACKUP ► backend did not confirm a checkpoint; backing up as-is");
+    }
+
+    let encrypted = settings::current(app_handle).data_encryption.folders;
+    let mut files = collect_data(
+        &data_root,
+        &data_encryption::runtime_root(app_handle)?,
+        &encrypted,
+    );
+    for name in SHELL_FILES {
+        if let Ok(p) = portable::resolve(app_handle, name) {
+            if let Ok(meta) = fs::metadata(&p) {
+                files.push((format!("{}{}", SHELL_PREFIX, name), p, meta.len()));
+            }
+        }
+    }
+    let bytes_total: u64 = files.iter().map(|f| f.2).sum();
+    let files_total = files.len() as u64;
+    storage::check(app_handle, "backup", &target, bytes_total + storage::MARGIN)?;
+    log::info!(
+        "BACKUP ► archiving {} file(s), {} bytes from {:?} to {:?}",
+        files_total,
+        bytes_total,
+        data_root,
+        target
+    );
+
+    let partial = target.with_extension("zip.partial");
+    let result = (|| -> Result<(u64, u64), String> {
+        let file = File::create(paths::extended(&partial))
+            .map_err(|e| format!("cannot create {}: {}", partial.display(), e))?;
+        let mut zip = ZipWriter::new(BufWriter::new(file));
+        let manifest = BackupManifest {
+            format: BACKUP_FORMAT,
+            data_schema: DATA_SCHEMA,
+            app_version: app_handle.package_info().version.to_string(),
+            backend_version: runtime_info::probe_backend_version(app_handle),
+            created_at: chrono::Local::now().to_rfc3339(),
+            files: files_total,
+            bytes: bytes_total,
+        };
+        zip.start_file(MANIFEST_NAME, SimpleFileOptions::default())
+            .map_err(|e| e.to_string())?;
+        zip.write_all(&serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?)
+            .map_err(|e| e.to_string())?;
+
+        let mut files_done = 0u64;
+        let mut bytes_done = 0u64;
+        let mut last_emit = Instant::now();
+        let mut buf = vec![0u8; 256 * 1024];
+        for (name, path, _) in &files {
+            // A file that vanished since the scan is skipped, not fatal.
+            let Ok(mut src) = File::open(paths::extended(path)) else {
+                log::warn!("BACKUP ► skipped unreadable {:?}", path);
+                continue;
+            };
+            zip.start_file(name.as_str(), options_for(name))
+                .map_err(|e| format!("cannot add {}: {}", name, e))?;
+            loop {
+                let n = match src.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => n,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(format!("cannot read {}: {}", path.display(), e)),
+                };
+                zip.write_all(&buf[..n])
+                    .map_err(|e| format!("cannot write archive: {}", e))?;
+                bytes_done += n as u64;
+                if last_emit.elapsed() >= PROGRESS_INTERVAL {
+                    last_emit = Instant::now();
+                    emit(BackupProgress {
+                        phase: "archiving",
+                        files_done,
+                        files_total,
+                        bytes_done,
+                        bytes_total,
+                        current: Some(name.clone()),
+                    });
+                }
+            }
+            files_done += 1;
+        }
+        emit(BackupProgress {
+            phase: "finishing",
+            files_done,
+            files_total,
+            bytes_done,
+            bytes_total,
+            current: None,
+        });
+        zip.finish()
+            .and_then(|w| w.into_inner().map_err(|e| e.into_error().into()))
+            .map_err(|e| format!("cannot finish archive: {}", e))?;
+        Ok((files_done, bytes_done))
+    })();
+
+    let (files_done, bytes_done) = match result {
+        Ok(done) => done,
+        Err(e) => {
+            let _ = fs::remove_file(paths::extended(&partial));
+            log::error!("BACKUP ► failed: {}", e);
+            return Err(e);
+        }
+    };
+    fs::rename(paths::extended(&partial), paths::extended(&target))
+        .map_err(|e| format!("cannot finalize {}: {}", target.display(), e))?;
+    let archive_bytes = fs::metadata(paths::extended(&target))
+        .map(|m| m.len())
+        .unwrap_or(0);
+    emit(BackupProgress {
+        phase: "done",
+        files_done,
+        files_total,
+        bytes_done,
+        bytes_total,
+        current: None,
+    });
+    log::info!(
+        "BACKUP ► wrote {:?} ({} file(s), {} bytes archived to {})",
+        target,
+        files_done,
+        bytes_done,
+        archive_bytes
+    );
+    Ok(BackupResult {
+        path: target.to_string_lossy().into_owned(),
+        files: files_done,
+        bytes: bytes_done,
+        archive_bytes,
+        checkpointed,
+    })
+}
+
+/// Back up the data folder and settings to `path` (a .zip file, or a
+/// folder to put a timestamped one in). Progress arrives as
+/// `backup-progress` events.
+#[tauri::command]
+pub async fn create_backup(
+    app_handle: tauri::AppHandle,
+    path: String,
+) -> Result<BackupResult, String> {
+    tauri::async_runtime::spawn_blocking(move || run(&app_handle, &path))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// `create_backup` for shell callers (pipelines). Blocking.
+pub fn run(app_handle: &tauri::AppHandle, path: &str) -> Result<BackupResult, String> {
+    exclusive(|| create(app_handle, path))
+}
+
+fn read_manifest<R: Read + io::Seek>(zip: &mut ZipArchive<R>) -> Result<BackupManifest, String> {
+    let mut entry = zip
+        .by_name(MANIFEST_NAME)
+        .map_err(|_| "not a Plattera backup: manifest.json is missing".to_string())?;
+    let mut text = String::new();
+    entry
+        .read_to_string(&mut text)
+        .map_err(|e| format!("cannot read manifest: {}", e))?;
+    serde_json::from_str(&text).map_err(|e| format!("invalid manifest: {}", e))
+}
+
+/// Archives from a newer format or a different data schema can't be read.
+fn compatibility(path: &Path, manifest: &BackupManifest) -> Result<(), IncompatibleBackup> {
+    if manifest.format <= BACKUP_FORMAT && manifest.data_schema == DATA_SCHEMA {
+        return Ok(());
+    }
+    Err(IncompatibleBackup {
+        path: path.to_string_lossy().into_owned(),
+        backup_format: manifest.format,
+        backup_data_schema: manifest.data_schema,
+        backup_app_version: manifest.app_version.clone(),
+        supported_format: BACKUP_FORMAT,
+        supported_data_schema: DATA_SCHEMA,
+    })
+}
+
+/// Refuse archives `compatibility` rejects; the error is logged and emitted
+/// as `incompatible-backup`.
+fn validate(
+    app_handle: &tauri::AppHandle,
+    path: &Path,
+    manifest: &BackupManifest,
+) -> Result<(), String> {
+    let Err(e) = compatibility(path, manifest) else {
+        return Ok(());
+    };
+    log::warn!("BACKUP ► {}", e);
+    let _ = app_handle.emit("incompatible-backup", &e);
+    Err(e.to_string())
+}
+
+/// Extract the `data/` entries of `zip` into `staging`.
+fn extract<R: Read + io::Seek>(
+    app_handle: &tauri::AppHandle,
+    zip: &mut ZipArchive<R>,
+    staging: &Path,
+    manifest: &BackupManifest,
+) -> Result<(u64, u64), String> {
+    fs::create_dir_all(paths::extended(staging))
+        .map_err(|e| format!("cannot create {}: {}", staging.display(), e))?;
+    let mut files_done = 0u64;
+    let mut bytes_done = 0u64;
+    let mut last_emit = Instant::now();
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(|e| e.to_string())?;
+        // `enclosed_name` rejects absolute paths and `..` components.
+        let Some(name) = entry.enclosed_name() else {
+            return Err(format!("backup contains an unsafe path: {}", entry.name()));
+        };
+        let Ok(rel) = name.strip_prefix(DATA_PREFIX.trim_end_matches('/')) else {
+            continue;
+        };
+        let dest = staging.join(rel);
+        if entry.is_dir() {
+            fs::create_dir_all(paths::extended(&dest)).map_err(|e| e.to_string())?;
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(paths::extended(parent)).map_err(|e| e.to_string())?;
+        }
+        let mut out = File::create(paths::extended(&dest))
+            .map_err(|e| format!("cannot create {}: {}", dest.display(), e))?;
+        bytes_done += io::copy(&mut entry, &mut out)
+            .map_err(|e| format!("cannot extract {}: {}", name.display(), e))?;
+        files_done += 1;
+        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+            last_emit = Instant::now();
+            let _ = app_handle.emit(
+                "restore-progress",
+                &BackupProgress {
+                    phase: "extracting",
+                    files_done,
+                    files_total: manifest.files,
+                    bytes_done,
+                    bytes_total: manifest.bytes,
+                    current: Some(name.to_string_lossy().into_owned()),
+                },
+            );
+        }
+    }
+    Ok((files_done, bytes_done))
+}
+
+/// Put `staging` in place of `live`. The live folder is moved aside first so
+/// a failed second rename can be undone.
+fn swap(live: &Path, staging: &Path) -> Result<(), String> {
+    let aside = live.with_extension("pre-restore");
+    let _ = fs::remove_dir_all(paths::extended(&aside));
+    let had_live = paths::extended(live).exists();
+    if had_live {
+        fs::rename(paths::extended(live), paths::extended(&aside))
+            .map_err(|e| format!("cannot move the current data aside: {}", e))?;
+    }
+    if let Err(e) = fs::rename(paths::extended(staging), paths::extended(live)) {
+        if had_live {
+            let _ = fs::rename(paths::extended(&aside), paths::extended(live));
+        }
+        return Err(format!("cannot move the restored data into place: {}", e));
+    }
+    if had_live {
+        if let Err(e) = fs::remove_dir_all(paths::extended(&aside)) {
+            log::warn!(
+                "BACKUP ► could not remove previous data at {:?}: {}",
+                aside,
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+fn restore(app_handle: &tauri::AppHandle, path: &str) -> Result<RestoreResult, String> {
+    let _op = correlation::begin("restore");
+    let archive = paths::plain(Path::new(path.trim()));
+    let progress = |phase: &'static str, manifest: Option<&BackupManifest>| {
+        let _ = app_handle.emit(
+            "restore-progress",
+            &BackupProgress {
+                phase,
+                files_done: if phase == "done" {
+                    manifest.map_or(0, |m| m.files)
+                } else {
+                    0
+                },
+                files_total: manifest.map_or(0, |m| m.files),
+                bytes_done: if phase == "done" {
+                    manifest.map_or(0, |m| m.bytes)
+                } else {
+                    0
+                },
+                bytes_total: manifest.map_or(0, |m| m.bytes),
+                current: None,
+            },
+        );
+    };
+    progress("validating", None);
+    let file = File::open(paths::extended(&archive))
+        .map_err(|e| format!("cannot open {}: {}", archive.display(), e))?;
+    let mut zip = ZipArchive::new(BufReader::new(file))
+        .map_err(|e| format!("{} is not a valid backup archive: {}", archive.display(), e))?;
+    let manifest = read_manifest(&mut zip)?;
+    validate(app_handle, &archive, &manifest)?;
+
+    let data_root = settings::backend_data_dir(app_handle)?;
+    let staging = data_root.with_extension("restore-staging");
+    storage::check(
+        app_handle,
+        "restore",
+        &staging,
+        manifest.bytes + storage::MARGIN,
+    )?;
+    log::warn!(
+        "BACKUP ► restoring {:?} (created {}, Plattera {}) into {:?}",
+        archive,
+        manifest.created_at,
+        manifest.app_version,
+        data_root
+    );
+
+    let _ = fs::remove_dir_all(paths::extended(&staging));
+    let (files, bytes) = match extract(app_handle, &mut zip, &staging, &manifest) {
+        Ok(done) => done,
+        Err(e) => {
+            let _ = fs::remove_dir_all(paths::extended(&staging));
+            log::error!("BACKUP ► restore failed while extracting: {}", e);
+            return Err(e);
+        }
+    };
+
+    progress("swapping", Some(&manifest));
+    // The backend must not hold or write the data folder during the swap.
+    shutdown_backend_for_exit(app_handle);
+    let swapped = swap(&data_root, &staging);
+    if swapped.is_err() {
+        let _ = fs::remove_dir_all(paths::extended(&staging));
+    }
+    supervisor::reset(app_handle);
+    let backend_restarted = match spawn_backend(app_handle) {
+        Ok(_) => true,
+        Err(e) => {
+            log::error!("BACKUP ► backend did not restart after restore: {}", e);
+            false
+        }
+    };
+    if let Err(e) = swapped {
+        log::error!("BACKUP ► restore failed: {}", e);
+        return Err(e);
+    }
+
+    progress("done", Some(&manifest));
+    log::info!("BACKUP ► restored {} file(s), {} bytes", files, bytes);
+    Ok(RestoreResult {
+        files,
+        bytes,
+        created_at: manifest.created_at,
+        backup_app_version: manifest.app_version,
+        backend_restarted,
+    })
+}
+
+/// Replace the data folder with the contents of the backup at `path`. The
+/// backend is stopped for the swap and started again. Progress arrives as
+/// `restore-progress` events.
+#[tauri::command]
+pub async fn restore_backup(
+    app_handle: tauri::AppHandle,
+    path: String,
+) -> Result<RestoreResult, String> {
+    tauri::async_runtime::spawn_blocking(move || exclusive(|| restore(&app_handle, &path)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(format: u32, data_schema: u32) -> BackupManifest {
+        BackupManifest {
+            format,
+            data_schema,
+            app_version: "9.9.9".to_string(),
+            backend_version: None,
+            created_at: "2026-01-01T00:00:00+00:00".to_string(),
+            files: 0,
+            bytes: 0,
+        }
+    }
+
+    #[test]
+    fn current_and_older_formats_are_restored() {
+        let path = Path::new("backup.zip");
+        assert!(compatibility(path, &manifest(BACKUP_FORMAT, DATA_SCHEMA)).is_ok());
+        assert!(compatibility(path, &manifest(0, DATA_SCHEMA)).is_ok());
+    }
+
+    #[test]
+    fn newer_format_or_other_schema_is_refused() {
+        let path = Path::new("backup.zip");
+        let newer = compatibility(path, &manifest(BACKUP_FORMAT + 1, DATA_SCHEMA)).unwrap_err();
+        assert!(newer.to_string().contains("newer version"), "{}", newer);
+        let schema = compatibility(path, &manifest(BACKUP_FORMAT, DATA_SCHEMA + 1)).unwrap_err();
+        assert!(schema.to_string().contains("data schema"), "{}", schema);
+    }
+
+    #[test]
+    fn manifest_without_backend_version_still_parses() {
+        let parsed: BackupManifest = serde_json::from_str(
+            r#"{"format":1,"data_schema":1,"app_version":"1.0.0",
+                "created_at":"2026-01-01T00:00:00+00:00","files":3,"bytes":42}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.backend_version, None);
+        assert!(compatibility(Path::new("old.zip"), &parsed).is_ok());
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("plattera-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unsealed_folder_is_archived_from_its_runtime_copy() {
+        let root = scratch("backup-unsealed");
+        let data = root.join("data");
+        let runtime = root.join("unsealed");
+        fs::create_dir_all(data.join("plss")).unwrap();
+        fs::write(data.join("plss/index.json"), "{}").unwrap();
+        fs::write(
+            data_encryption::sealed_path(&data, "dossiers_data"),
+            "stale",
+        )
+        .unwrap();
+        fs::create_dir_all(runtime.join("dossiers_data/d1")).unwrap();
+        fs::write(runtime.join("dossiers_data/d1/dossier.json"), "{}").unwrap();
+        std::os::unix::fs::symlink(runtime.join("dossiers_data"), data.join("dossiers_data"))
+            .unwrap();
+
+        let files = collect_data(&data, &runtime, &["dossiers_data".to_string()]);
+        let mut names: Vec<&str> = files.iter().map(|f| f.0.as_str()).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            ["data/dossiers_data/d1/dossier.json", "data/plss/index.json"]
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn sealed_folder_is_archived_as_is_when_not_open() {
+        let root = scratch("backup-sealed");
+        let data = root.join("data");
+        fs::create_dir_all(&data).unwrap();
+        fs::write(
+            data_encryption::sealed_path(&data, "dossiers_data"),
+            "sealed",
+        )
+        .unwrap();
+
+        let files = collect_data(
+            &data,
+            &root.join("unsealed"),
+            &["dossiers_data".to_string()],
+        );
+        let names: Vec<&str> = files.iter().map(|f| f.0.as_str()).collect();
+        assert_eq!(names, ["data/dossiers_data.sealed"]);
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,73 @@
+// Capability negotiation, so the frontend can feature-gate UI on what is
+// actually there instead of parsing version strings. `get_capabilities`
+// combines the shell's registered commands (`SHELL_COMMANDS`, generated with
+// the invoke handler) with the feature list the backend advertises at
+// `/api/capabilities`. A backend that predates that endpoint, or isn't
+// reachable, reports `backend_features: null` rather than an empty list,
+// so the two cases can be told apart.
+
+use crate::{backend_http, SHELL_COMMANDS};
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Capabilities {
+    pub app_version: String,
+    /// Names the webview can `invoke`, sorted.
+    pub commands: Vec<String>,
+    /// Features the backend advertises; `None` when it couldn't be asked.
+    pub backend_features: Option<Vec<String>>,
+}
+
+/// Command names as the webview invokes them (the last path segment).
+pub fn shell_commands() -> Vec<String> {
+    let mut names: Vec<String> = SHELL_COMMANDS
+        .iter()
+        .filter_map(|c| c.rsplit("::").next())
+        .map(|c| c.trim().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+/// `{"features": [...]}` or a bare list of strings.
+fn parse_features(body: &Value) -> Option<Vec<String>> {
+    let list = body.get("features").unwrap_or(body).as_array()?;
+    Some(
+        list.iter()
+            .filter_map(|f| f.as_str().map(str::to_string))
+            .collect(),
+    )
+}
+
+async fn backend_features(app_handle: &tauri::AppHandle) -> Option<Vec<String>> {
+    let reply = backend_http::call(
+        app_handle,
+        reqwest::Method::GET,
+        "/api/capabilities",
+        None,
+        Duration::from_millis(3_000),
+        None,
+    )
+    .await
+    .map_err(|e| log::info!("CAPABILITIES ► backend not asked: {}", e))
+    .ok()?;
+    if !reply.status.is_success() {
+        log::info!(
+            "CAPABILITIES ► backend returned {} for /api/capabilities",
+            reply.status
+        );
+        return None;
+    }
+    parse_features(&reply.body)
+}
+
+#[tauri::command]
+pub async fn get_capabilities(app_handle: tauri::AppHandle) -> Result<Capabilities, String> {
+    Ok(Capabilities {
+        app_version: app_handle.package_info().version.to_string(),
+        commands: shell_commands(),
+        backend_features: backend_features(&app_handle).await,
+    })
+}
@@ -0,0 +1,274 @@
+// Clipboard bridge. Text read or written through these commands is cleaned
+// first: tracking parameters (`utm_*`, `fbclid`, `gclid`, ...) are stripped
+// from any http(s) links and line endings are normalized (LF towards the
+// frontend, the platform's own towards the OS). Images go through PNG files
+// rather than the webview: `paste_clipboard_image` writes the clipboard
+// bitmap into a `file_drop.rs` quarantine batch, so the returned path can be
+// handed to `import_dropped_files` like a dropped scan.
+//
+// The OS clipboard is owned by one thread for the life of the app. On X11 the
+// owner has to stay alive to serve what it copied, and on macOS the handle may
+// not move between threads.
+
+use crate::file_drop;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::Path;
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use tauri::Manager;
+
+/// Query parameters removed from links, matched case-insensitively.
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "igshid", "mc_cid",
+    "mc_eid", "_hsenc", "_hsmi", "mkt_tok", "ref_src",
+];
+const TRACKING_PREFIXES: &[&str] = &["utm_"];
+
+type Job = Box<dyn FnOnce(Result<&mut arboard::Clipboard, String>) + Send>;
+
+/// Channel to the clipboard thread.
+pub struct ClipboardBridge(Mutex<Sender<Job>>);
+
+impl ClipboardBridge {
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::channel::<Job>();
+        std::thread::spawn(move || {
+            let mut clipboard = arboard::Clipboard::new().map_err(|e| e.to_string());
+            if let Err(e) = &clipboard {
+                log::warn!("CLIPBOARD ► unavailable: {}", e);
+            }
+            for job in rx {
+                job(clipboard.as_mut().map_err(|e| e.clone()));
+            }
+        });
+        ClipboardBridge(Mutex::new(tx))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClipboardImage {
+    /// PNG in the quarantine folder; pass to `import_dropped_files`.
+    pub path: String,
+    pub width: usize,
+    pub height: usize,
+    pub size: u64,
+}
+
+/// Run `f` on the clipboard thread and wait for its result.
+async fn with_clipboard<T, F>(app_handle: &tauri::AppHandle, f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut arboard::Clipboard) -> Result<T, String> + Send + 'static,
+{
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let job: Job = Box::new(move |clipboard| {
+        let _ = tx.send(clipboard.and_then(f));
+    });
+    app_handle
+        .state::<ClipboardBridge>()
+        .0
+        .lock()
+        .unwrap()
+        .send(job)
+        .map_err(|_| "clipboard thread is gone".to_string())?;
+    rx.await
+        .map_err(|_| "clipboard thread is gone".to_string())?
+}
+
+fn clipboard_error(e: arboard::Error) -> String {
+    match e {
+        arboard::Error::ContentNotAvailable => {
+            "the clipboard holds no content of that kind".to_string()
+        }
+        e => e.to_string(),
+    }
+}
+
+fn is_tracking_param(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    TRACKING_PARAMS.contains(&key.as_str()) || TRACKING_PREFIXES.iter().any(|p| key.starts_with(p))
+}
+
+/// `url` without tracking parameters; the fragment is kept.
+fn clean_url(url: &str) -> Cow<'_, str> {
+    let (before_fragment, fragment) = match url.find('#') {
+        Some(i) => url.split_at(i),
+        None => (url, ""),
+    };
+    let Some((base, query)) = before_fragment.split_once('?') else {
+        return Cow::Borrowed(url);
+    };
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter(|pair| !is_tracking_param(pair.split('=').next().unwrap_or("")))
+        .collect();
+    let mut cleaned = base.to_string();
+    if !kept.is_empty() {
+        cleaned.push('?');
+        cleaned.push_str(&kept.join("&"));
+    }
+    cleaned.push_str(fragment);
+    Cow::Owned(cleaned)
+}
+
+fn find_url(text: &str) -> Option<usize> {
+    [text.find("https://"), text.find("http://")]
+        .into_iter()
+        .flatten()
+        .min()
+}
+
+/// Strip tracking parameters from every http(s) link in `text`.
+pub fn strip_tracking(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = find_url(rest) {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let end = tail
+            .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | '\''))
+            .unwrap_or(tail.len());
+        out.push_str(&clean_url(&tail[..end]));
+        rest = &tail[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn to_lf(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+fn to_native(text: &str) -> String {
+    let text = to_lf(text);
+    if cfg!(windows) {
+        text.replace('\n', "\r\n")
+    } else {
+        text
+    }
+}
+
+fn encode_png(path: &Path, image: &arboard::ImageData) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("create {:?}: {}", path, e))?;
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(file),
+        image.width as u32,
+        image.height as u32,
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&image.bytes))
+        .map_err(|e| format!("encode {:?}: {}", path, e))
+}
+
+/// Decode a PNG into the RGBA layout the clipboard expects.
+fn decode_png(path: &Path) -> Result<arboard::ImageData<'static>, String> {
+    let file = File::open(path).map_err(|e| format!("cannot open {}: {}", path.display(), e))?;
+    let mut decoder = png::Decoder::new(file);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder
+        .read_info()
+        .map_err(|e| format!("{} is not a readable PNG: {}", path.display(), e))?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buf).map_err(|e| e.to_string())?;
+    let pixels = &buf[..frame.buffer_size()];
+    let rgba: Vec<u8> = match frame.color_type {
+        png::ColorType::Rgba => pixels.to_vec(),
+        png::ColorType::Rgb => pixels
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => pixels
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Grayscale => pixels.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        png::ColorType::Indexed => return Err("indexed PNGs are not supported".to_string()),
+    };
+    Ok(arboard::ImageData {
+        width: frame.width as usize,
+        height: frame.height as usize,
+        bytes: Cow::Owned(rgba),
+    })
+}
+
+/// Clipboard text, sanitized, with LF line endings.
+#[tauri::command]
+pub async fn read_clipboard_text(app_handle: tauri::AppHandle) -> Result<String, String> {
+    let text = with_clipboard(&app_handle, |c| c.get_text().map_err(clipboard_error)).await?;
+    Ok(strip_tracking(&to_lf(&text)))
+}
+
+/// Put `text` on the clipboard, sanitized. Returns what was written.
+#[tauri::command]
+pub async fn write_clipboard_text(
+    app_handle: tauri::AppHandle,
+    text: String,
+) -> Result<String, String> {
+    let text = strip_tracking(&to_native(&text));
+    let written = text.clone();
+    with_clipboard(&app_handle, move |c| {
+        c.set_text(written).map_err(clipboard_error)
+    })
+    .await?;
+    Ok(text)
+}
+
+/// Save the clipboard bitmap as a PNG in a new quarantine batch.
+#[tauri::command]
+pub async fn paste_clipboard_image(app_handle: tauri::AppHandle) -> Result<ClipboardImage, String> {
+    let image = with_clipboard(&app_handle, |c| c.get_image().map_err(clipboard_error)).await?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let (_, dir) = file_drop::new_batch(&app_handle)?;
+        let name = format!(
+            "clipboard-{}.png",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        );
+        let path = dir.join(name);
+        encode_png(&path, &image)?;
+        let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        log::info!(
+            "CLIPBOARD ► pasted {}x{} image to {}",
+            image.width,
+            image.height,
+            path.display()
+        );
+        Ok(ClipboardImage {
+            path: path.to_string_lossy().into_owned(),
+            width: image.width,
+            height: image.height,
+            size,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Put the PNG at `path` on the clipboard as an image.
+#[tauri::command]
+pub async fn write_clipboard_image(
+    app_handle: tauri::AppHandle,
+    path: String,
+) -> Result<(), String> {
+    let path = Path::new(path.trim()).to_path_buf();
+    let is_png = path
+        .extension()
+        .map(|e| e.eq_ignore_ascii_case("png"))
+        .unwrap_or(false);
+    if !is_png {
+        return Err(format!("{} is not a PNG", path.display()));
+    }
+    let image = tauri::async_runtime::spawn_blocking(move || decode_png(&path))
+        .await
+        .map_err(|e| e.to_string())??;
+    with_clipboard(&app_handle, move |c| {
+        c.set_image(image).map_err(clipboard_error)
+    })
+    .await
+}
@@ -0,0 +1,278 @@
+// Unsaved-changes close guard. A window close is held back while the
+// frontend is asked (via `close-requested`) whether it is safe to go; only
+// after it confirms, or fails to answer in time, does the shell persist the
+// session, shut the backend down and destroy the window. This keeps an
+// in-flight save from being cut off by the backend being killed underneath it.
+// An app exit also lists the backend jobs still running (`tasks.rs`), so the
+// frontend can warn before they are cut off.
+// With dossier windows open, closing the main window asks every one of them
+// at once and the app exits only once all agree; they share one deadline,
+// which a `wait` answer from any of them extends. A close that would ask a
+// window already answering another close is refused, not stacked.
+//
+// With `close_to_tray` set, closing the main window only hides it and the
+// backend stays warm; the app then exits from the tray's Quit, File → Quit
+// (`quit`, which still runs the guard) or the end of the OS session.
+
+use crate::backend_lifecycle::shutdown_backend_for_exit;
+use crate::tasks::{self, BackendTask};
+use crate::{dossier_windows, session, settings, updates};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{CloseRequestApi, Emitter, Manager};
+
+/// How long to wait for the frontend's first answer before closing anyway.
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a `wait` answer (user looking at a save dialog) holds the close.
+const WAIT_EXTENSION: Duration = Duration::from_secs(300);
+const MAIN_WINDOW: &str = "main";
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CloseDecision {
+    /// Nothing unsaved (or saved now): close.
+    Proceed,
+    /// User chose to stay: abort the close.
+    Cancel,
+    /// Still resolving (e.g. a save dialog is open); a final answer follows.
+    Wait,
+}
+
+#[derive(Clone, Serialize)]
+struct CloseRequestedPayload {
+    window: String,
+    timeout_ms: u64,
+    /// The whole app is about to exit, not just this window.
+    app_exit: bool,
+    /// Backend jobs an exit would cut off, so the frontend can warn.
+    active_tasks: Vec<BackendTask>,
+}
+
+/// A window's answer, tagged with its label.
+type Answer = (String, CloseDecision);
+
+pub struct CloseGuard {
+    /// Open close requests by window label; all windows asked by one close
+    /// share a channel.
+    pending: Mutex<HashMap<String, Sender<Answer>>>,
+    confirmed: AtomicBool,
+    /// An explicit quit is in progress; don't hide to the tray.
+    quitting: AtomicBool,
+}
+
+impl CloseGuard {
+    pub fn new() -> Self {
+        CloseGuard {
+            pending: Mutex::new(HashMap::new()),
+            confirmed: AtomicBool::new(false),
+            quitting: AtomicBool::new(false),
+        }
+    }
+}
+
+/// Windows whose frontend answers `close-requested`.
+fn answers_close(label: &str) -> bool {
+    label == MAIN_WINDOW || dossier_windows::is_dossier_window(label)
+}
+
+/// Wait for every window in `labels` to answer, all under one deadline;
+/// windows that haven't answered by then count as proceed.
+fn await_decisions(labels: &[String], rx: Receiver<Answer>) -> bool {
+    let mut waiting: HashSet<&str> = labels.iter().map(String::as_str).collect();
+    let mut deadline = Instant::now() + CONFIRM_TIMEOUT;
+    while !waiting.is_empty() {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        match rx.recv_timeout(timeout) {
+            Ok((label, CloseDecision::Wait)) => {
+                log::info!("CLOSE_GUARD ► '{}' asked to wait", label);
+                deadline = deadline.max(Instant::now() + WAIT_EXTENSION);
+            }
+            Ok((label, CloseDecision::Proceed)) => {
+                waiting.remove(label.as_str());
+            }
+            Ok((label, CloseDecision::Cancel)) => {
+                log::info!("CLOSE_GUARD ► close cancelled by '{}'", label);
+                return false;
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let mut silent: Vec<&str> = waiting.into_iter().collect();
+                silent.sort_unstable();
+                log::warn!(
+                    "CLOSE_GUARD ► no answer from {:?} in time; closing anyway",
+                    silent
+                );
+                return true;
+            }
+            Err(RecvTimeoutError::Disconnected) => return true,
+        }
+    }
+    true
+}
+
+/// Handle `WindowEvent::CloseRequested`. Closing the main window exits the
+/// app and waits for every window that answers; closing a dossier window
+/// waits only for that window and leaves the backend running. Other
+/// secondary windows (recovery console) close straight away.
+pub fn on_close_requested(window: &tauri::Window, api: &CloseRequestApi) {
+    let app_handle = window.app_handle().clone();
+    let guard = app_handle.state::<CloseGuard>();
+    if guard.confirmed.load(Ordering::SeqCst) || !answers_close(window.label()) {
+        return;
+    }
+    api.prevent_close();
+
+    let app_exit = window.label() == MAIN_WINDOW;
+    if app_exit
+        && !guard.quitting.load(Ordering::SeqCst)
+        && settings::current(&app_handle).close_to_tray
+    {
+        log::info!("CLOSE_GUARD ► hiding main window to the tray");
+        let _ = window.hide();
+        let _ = app_handle.emit("hidden-to-tray", ());
+        return;
+    }
+    let labels: Vec<String> = if app_exit {
+        app_handle
+            .webview_windows()
+            .into_keys()
+            .filter(|label| answers_close(label))
+            .collect()
+    } else {
+        vec![window.label().to_string()]
+    };
+    let (tx, rx) = mpsc::channel();
+    {
+        let mut pending = guard.pending.lock().unwrap();
+        // A window already answering a close (this one again, or a dossier
+        // window's own close during an exit) keeps that request; this one
+        // is refused rather than taking its answer away.
+        if let Some(busy) = labels.iter().find(|label| pending.contains_key(*label)) {
+            log::info!(
+                "CLOSE_GUARD ► '{}' is already answering a close; ignoring close of '{}'",
+                busy,
+                window.label()
+            );
+            // A quit refused over a dossier window's close must not leave
+            // a later main-window close quitting instead of hiding.
+            if busy != MAIN_WINDOW {
+                guard.quitting.store(false, Ordering::SeqCst);
+            }
+            return;
+        }
+        for label in &labels {
+            pending.insert(label.clone(), tx.clone());
+        }
+    }
+    drop(tx);
+
+    log::info!(
+        "CLOSE_GUARD ► close requested for '{}'; asking {} window(s)",
+        window.label(),
+        labels.len()
+    );
+    let active_tasks = if app_exit {
+        tasks::snapshot(&app_handle)
+    } else {
+        Vec::new()
+    };
+    for label in &labels {
+        let _ = app_handle.emit_to(
+            label.as_str(),
+            "close-requested",
+            CloseRequestedPayload {
+                window: window.label().to_string(),
+                timeout_ms: CONFIRM_TIMEOUT.as_millis() as u64,
+                app_exit,
+                active_tasks: active_tasks.clone(),
+            },
+        );
+    }
+
+    let window = window.clone();
+    thread::spawn(move || {
+        // Every window is asked at once; the close goes ahead only when all
+        // of them agree.
+        let proceed = await_decisions(&labels, rx);
+
+        let guard = app_handle.state::<CloseGuard>();
+        {
+            let mut pending = guard.pending.lock().unwrap();
+            for label in &labels {
+                pending.remove(label);
+            }
+        }
+        if !proceed {
+            guard.quitting.store(false, Ordering::SeqCst);
+            return;
+        }
+        if !app_exit {
+            log::info!("CLOSE_GUARD ► closing '{}'", window.label());
+            let _ = window.destroy();
+            return;
+        }
+
+        guard.confirmed.store(true, Ordering::SeqCst);
+        log::info!("Window close confirmed - running backend shutdown routine");
+        session::persist(&app_handle);
+        if !updates::install_on_exit(&app_handle) {
+            shutdown_backend_for_exit(&app_handle);
+        }
+        for (label, other) in app_handle.webview_windows() {
+            if label != MAIN_WINDOW {
+                let _ = other.destroy();
+            }
+        }
+        let _ = window.destroy();
+    });
+}
+
+/// The app exit has been confirmed and its windows are being torn down.
+pub fn exiting(app_handle: &tauri::AppHandle) -> bool {
+    app_handle
+        .try_state::<CloseGuard>()
+        .is_some_and(|guard| guard.confirmed.load(Ordering::SeqCst))
+}
+
+/// Quit the app through the close guard, even with `close_to_tray` set.
+pub fn quit(app_handle: &tauri::AppHandle) {
+    let Some(window) = app_handle.get_webview_window(MAIN_WINDOW) else {
+        return;
+    };
+    app_handle
+        .state::<CloseGuard>()
+        .quitting
+        .store(true, Ordering::SeqCst);
+    // A hidden window still answers `close-requested`; show it so any
+    // unsaved-changes prompt is visible.
+    let _ = window.show();
+    let _ = window.close();
+}
+
+/// Frontend answer to a `close-requested` event, for the calling window.
+#[tauri::command]
+pub async fn respond_to_close_request(
+    app_handle: tauri::AppHandle,
+    window: tauri::Window,
+    decision: CloseDecision,
+) -> Result<(), String> {
+    let guard = app_handle.state::<CloseGuard>();
+    let pending = guard.pending.lock().unwrap();
+    // Older frontends answer from the main window only; with a single
+    // request open, that answer is unambiguous.
+    let entry = pending.get_key_value(window.label()).or_else(|| {
+        (pending.len() == 1)
+            .then(|| pending.iter().next())
+            .flatten()
+    });
+    match entry {
+        Some((label, tx)) => tx
+            .send((label.clone(), decision))
+            .map_err(|e| e.to_string()),
+        None => Err("no close request is pending".into()),
+    }
+}
@@ -0,0 +1,65 @@
+// App/backend compatibility gate. The shell is built against a range of
+// backend versions; a backend outside it (e.g. an old sidecar left behind by
+// a partial update) would fail in confusing ways once the UI starts talking
+// to it. The startup sequence checks the version the backend reports right
+// after its first successful health check; on a mismatch it emits
+// `version-mismatch` and keeps the UI behind the splash's failure page,
+// whose recovery console offers repair and updates. A backend that doesn't
+// report a version can't be judged and is let through with a warning.
+
+use crate::runtime_info;
+use serde::Serialize;
+
+/// Backend versions this shell works with.
+pub const SUPPORTED_BACKEND: &str = ">=2.0.0, <3.0.0";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionCheck {
+    pub app_version: String,
+    pub backend_version: Option<String>,
+    pub supported: &'static str,
+    pub compatible: bool,
+}
+
+fn parse_version(v: &str) -> Option<semver::Version> {
+    semver::Version::parse(v.trim().trim_start_matches('v')).ok()
+}
+
+/// Compare the running backend's version with `SUPPORTED_BACKEND`. Blocking.
+pub fn check(app_handle: &tauri::AppHandle) -> VersionCheck {
+    let backend_version = runtime_info::probe_backend_version(app_handle);
+    let req = semver::VersionReq::parse(SUPPORTED_BACKEND).expect("valid version range");
+    let compatible = match backend_version.as_deref() {
+        Some(v) => parse_version(v).map(|v| req.matches(&v)).unwrap_or(false),
+        None => {
+            log::warn!("COMPAT ► backend did not report a version; skipping the check");
+            true
+        }
+    };
+    VersionCheck {
+        app_version: app_handle.package_info().version.to_string(),
+        backend_version,
+        supported: SUPPORTED_BACKEND,
+        compatible,
+    }
+}
+
+/// Shown on the splash failure page.
+pub fn mismatch_message(check: &VersionCheck) -> String {
+    format!(
+        "This version of Plattera ({}) needs a backend in the range {}, but found {}. \
+         The installation may be incomplete; repair it or install the latest update.",
+        check.app_version,
+        check.supported,
+        check.backend_version.as_deref().unwrap_or("unknown")
+    )
+}
+
+#[tauri::command]
+pub async fn check_version_compatibility(
+    app_handle: tauri::AppHandle,
+) -> Result<VersionCheck, String> {
+    tauri::async_runtime::spawn_blocking(move || check(&app_handle))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,225 @@
+// Online/offline monitor. A background thread periodically checks whether
+// the update endpoint (`plugins.updater.endpoints`) and any cloud services
+// listed in the `connectivity` setting can be reached, and emits
+// `connectivity-changed` whenever the picture changes. Any HTTP answer
+// counts as reachable, including error statuses; only connection failures
+// and timeouts count as unreachable.
+//
+// Until the first round has finished the app is treated as online. While
+// offline, background update checks stand down instead of filling the log
+// with connection errors.
+
+use crate::{backend_http, proxy, settings};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+const MIN_INTERVAL_SECS: u64 = 10;
+const PROBE_CONNECT_TIMEOUT: Duration = Duration::from_millis(5_000);
+const PROBE_TIMEOUT: Duration = Duration::from_millis(10_000);
+/// Time to sleep between looks at the settings while the monitor is off.
+const IDLE_TICK: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConnectivitySettings {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    /// Extra http(s) URLs to watch besides the update endpoint.
+    pub services: Vec<String>,
+}
+
+impl Default for ConnectivitySettings {
+    fn default() -> Self {
+        ConnectivitySettings {
+            enabled: true,
+            interval_secs: 120,
+            services: Vec::new(),
+        }
+    }
+}
+
+impl ConnectivitySettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_secs < MIN_INTERVAL_SECS {
+            return Err(format!(
+                "connectivity.interval_secs must be at least {}",
+                MIN_INTERVAL_SECS
+            ));
+        }
+        if let Some(bad) = self
+            .services
+            .iter()
+            .find(|s| !s.starts_with("https://") && !s.starts_with("http://"))
+        {
+            return Err(format!(
+                "connectivity service '{}' is not an http(s) URL",
+                bad
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServiceReachability {
+    pub url: String,
+    /// True for the update endpoint, false for configured services.
+    pub updater: bool,
+    pub reachable: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectivityStatus {
+    /// `None` until the first check has finished.
+    pub online: Option<bool>,
+    pub checked_at: Option<String>,
+    pub services: Vec<ServiceReachability>,
+}
+
+pub struct Connectivity(Mutex<ConnectivityStatus>);
+
+impl ConnectivityStatus {
+    fn unknown() -> Self {
+        ConnectivityStatus {
+            online: None,
+            checked_at: None,
+            services: Vec::new(),
+        }
+    }
+}
+
+impl Connectivity {
+    pub fn new() -> Self {
+        Connectivity(Mutex::new(ConnectivityStatus::unknown()))
+    }
+}
+
+pub fn updater_endpoints(app_handle: &tauri::AppHandle) -> Vec<String> {
+    app_handle
+        .config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|u| u.get("endpoints"))
+        .and_then(|e| e.as_array())
+        .map(|list| {
+            list.iter()
+                .filter_map(|e| e.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `None` when `url` answered at all.
+fn probe(app_handle: &tauri::AppHandle, url: &str) -> Option<String> {
+    let client = proxy::client_builder(app_handle, url)
+        .connect_timeout(PROBE_CONNECT_TIMEOUT)
+        .timeout(PROBE_TIMEOUT)
+        .build();
+    let req = match client {
+        Ok(client) => client.head(url),
+        Err(e) => return Some(e.to_string()),
+    };
+    backend_http::block_on(PROBE_TIMEOUT, async move {
+        req.send().await.map(|_| ()).map_err(|e| e.to_string())
+    })
+    .err()
+}
+
+/// Probe every target and record the result; announces a change as
+/// `connectivity-changed`. Blocking.
+fn check(app_handle: &tauri::AppHandle) -> ConnectivityStatus {
+    let cfg = settings::current(app_handle).connectivity;
+    let targets = updater_endpoints(app_handle)
+        .into_iter()
+        .map(|url| (url, true))
+        .chain(cfg.services.into_iter().map(|url| (url, false)));
+    let services: Vec<ServiceReachability> = targets
+        .map(|(url, updater)| {
+            let error = probe(app_handle, &url);
+            ServiceReachability {
+                reachable: error.is_none(),
+                url,
+                updater,
+                error,
+            }
+        })
+        .collect();
+    // Nothing to watch means nothing to be offline from.
+    let online = services.is_empty() || services.iter().any(|s| s.reachable);
+
+    let state = app_handle.state::<Connectivity>();
+    let (status, changed) = {
+        let mut current = state.0.lock().unwrap();
+        let reachability = |list: &[ServiceReachability]| {
+            list.iter()
+                .map(|s| (s.url.clone(), s.reachable))
+                .collect::<Vec<_>>()
+        };
+        let changed = current.online != Some(online)
+            || reachability(&current.services) != reachability(&services);
+        *current = ConnectivityStatus {
+            online: Some(online),
+            checked_at: Some(chrono::Local::now().to_rfc3339()),
+            services,
+        };
+        (current.clone(), changed)
+    };
+    if changed {
+        log::info!(
+            "CONNECTIVITY ► {} ({} of {} reachable)",
+            if online { "online" } else { "offline" },
+            status.services.iter().filter(|s| s.reachable).count(),
+            status.services.len()
+        );
+        let _ = app_handle.emit("connectivity-changed", &status);
+    }
+    status
+}
+
+/// Whether the last check found the update endpoint unreachable. Background
+/// update checks skip their run while this holds.
+pub fn updater_offline(app_handle: &tauri::AppHandle) -> bool {
+    let Some(state) = app_handle.try_state::<Connectivity>() else {
+        return false;
+    };
+    let status = state.0.lock().unwrap();
+    let mut updater = status.services.iter().filter(|s| s.updater).peekable();
+    updater.peek().is_some() && updater.all(|s| !s.reachable)
+}
+
+/// Start the monitor on a dedicated thread.
+pub fn spawn(app_handle: tauri::AppHandle) {
+    thread::spawn(move || loop {
+        let cfg = settings::current(&app_handle).connectivity;
+        if !cfg.enabled {
+            // Forget old results so nothing stays suppressed while off.
+            *app_handle.state::<Connectivity>().0.lock().unwrap() = ConnectivityStatus::unknown();
+            thread::sleep(IDLE_TICK);
+            continue;
+        }
+        check(&app_handle);
+        thread::sleep(Duration::from_secs(
+            cfg.interval_secs.max(MIN_INTERVAL_SECS),
+        ));
+    });
+}
+
+#[tauri::command]
+pub async fn get_connectivity(app_handle: tauri::AppHandle) -> Result<ConnectivityStatus, String> {
+    Ok(app_handle.state::<Connectivity>().0.lock().unwrap().clone())
+}
+
+/// Check right away, e.g. from a "Retry" button on an offline banner.
+#[tauri::command]
+pub async fn check_connectivity(
+    app_handle: tauri::AppHandle,
+) -> Result<ConnectivityStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || check(&app_handle))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,153 @@
+// Structured logging with correlation IDs. The shell log is written as one
+// JSON object per line:
+//
+//   {"ts":…,"level":"INFO","target":"app_lib::updates","component":"updates",
+//    "op":"update-3f9a0c12","msg":"UPDATES ► installing 1.4.0"}
+//
+// `component` comes from the record's log target: the shell module that
+// logged, `backend_output` for forwarded backend output, or the crate for
+// dependencies. The `NAME ►` prefixes stay part of the message for people
+// reading the log; nothing parses them. `op` ties the lines of one lifecycle operation together: `begin`
+// opens an operation (start, shutdown, restart, update, crash) on the
+// current thread; operations started inside another one join it, so a
+// restart's shutdown and spawn share the restart's ID. Threads and tasks
+// working for an operation `adopt` its ID. The spawning operation's ID also
+// tags that backend's forwarded output. Lifecycle events go through `emit`,
+// which adds the ID as `correlation_id` to object payloads.
+//
+// Operation IDs are `trace.rs` IDs with the kind in front, and a backend call
+// made inside an operation sends the operation's ID as its `X-Correlation-ID`
+// (see `backend_http::send`), so the shell's `op` and the backend's `cid` are
+// the same string and `find_trace` finds both halves.
+
+use crate::trace;
+use serde::Serialize;
+use serde_json::Value;
+use std::cell::RefCell;
+use tauri::Emitter;
+
+thread_local! {
+    static CURRENT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Guard for the current thread's operation; restores the previous one on drop.
+pub struct Operation {
+    previous: Option<String>,
+    /// Only the guard that opened an operation logs its end.
+    opened: Option<&'static str>,
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        if let Some(kind) = self.opened {
+            log::debug!("OP ► {} finished", kind);
+        }
+        let previous = self.previous.take();
+        CURRENT.with(|c| *c.borrow_mut() = previous);
+    }
+}
+
+pub fn current() -> Option<String> {
+    CURRENT.with(|c| c.borrow().clone())
+}
+
+/// A fresh operation ID, for work that hops threads (async tasks) and
+/// `adopt`s it wherever it logs or emits.
+pub fn new_id(kind: &str) -> String {
+    format!("{}-{}", kind, trace::new_id())
+}
+
+/// The ID a backend call sends as `X-Correlation-ID`: the current
+/// operation's, or a fresh one outside any operation.
+pub fn request_id() -> String {
+    current().unwrap_or_else(trace::new_id)
+}
+
+/// Open an operation of `kind` on this thread, or join the one in progress.
+pub fn begin(kind: &'static str) -> Operation {
+    let previous = current();
+    if previous.is_some() {
+        return Operation {
+            previous,
+            opened: None,
+        };
+    }
+    let id = new_id(kind);
+    CURRENT.with(|c| *c.borrow_mut() = Some(id));
+    log::debug!("OP ► {} started", kind);
+    Operation {
+        previous,
+        opened: Some(kind),
+    }
+}
+
+/// Run the rest of this scope under `id` (captured with `current` on the
+/// thread that started the work).
+pub fn adopt(id: Option<String>) -> Operation {
+    let previous = CURRENT.with(|c| std::mem::replace(&mut *c.borrow_mut(), id));
+    Operation {
+        previous,
+        opened: None,
+    }
+}
+
+/// Emit `event`, adding the current operation's ID to object payloads.
+pub fn emit<S: Serialize + Clone>(app_handle: &tauri::AppHandle, event: &str, payload: S) {
+    let id = current();
+    let result = match (id, serde_json::to_value(&payload)) {
+        (Some(id), Ok(Value::Object(mut map))) => {
+            map.insert("correlation_id".to_string(), Value::String(id));
+            app_handle.emit(event, Value::Object(map))
+        }
+        _ => app_handle.emit(event, payload),
+    };
+    if let Err(e) = result {
+        log::debug!("OP ► could not emit {}: {}", event, e);
+    }
+}
+
+/// Component of a log target: the module below this crate
+/// (`app_lib::updates::scheduler` is `updates`), otherwise the crate.
+fn component(target: &str) -> &str {
+    let inner = target
+        .strip_prefix(env!("CARGO_CRATE_NAME"))
+        .and_then(|rest| rest.strip_prefix("::"))
+        .unwrap_or(target);
+    inner.split("::").next().unwrap_or(inner)
+}
+
+/// One JSON log line for the log plugin's formatter.
+pub fn json_line(message: &std::fmt::Arguments, record: &log::Record) -> String {
+    serde_json::json!({
+        "ts": chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, false),
+        "level": record.level().as_str(),
+        "target": record.target(),
+        "component": component(record.target()),
+        "op": current(),
+        "msg": message.to_string(),
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn component_is_the_module_below_the_crate() {
+        assert_eq!(component("app_lib::updates"), "updates");
+        assert_eq!(component("app_lib::updates::scheduler"), "updates");
+        assert_eq!(component("app_lib::backend_output"), "backend_output");
+        assert_eq!(component("app_lib"), "app_lib");
+    }
+
+    #[test]
+    fn dependency_component_is_its_crate() {
+        assert_eq!(
+            component("tauri_plugin_updater::updater"),
+            "tauri_plugin_updater"
+        );
+        assert_eq!(component("reqwest"), "reqwest");
+        assert_eq!(component("app_libx::module"), "app_libx");
+    }
+}
@@ -0,0 +1,251 @@
+// Crash reports for the shell. A panic hook installed at the very start of
+// `run()` writes `crash_reports/crash-<time>.json` under AppLocalData with
+// the panic message, location, thread, a backtrace, the app version and the
+// tail of the shell log. On Windows a minidump of the process is written
+// next to it. The next launch offers the newest unseen report through
+// `get_last_crash_report` so the frontend can ask the user to send it;
+// `dismiss_crash_report` marks it seen.
+//
+// Paths are only known once the app exists, so `configure` (from setup)
+// records them; a panic before that point is left to the default hook.
+
+use crate::portable;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+pub const REPORT_DIR: &str = "crash_reports";
+/// Reports (and dumps) kept; older ones are pruned on launch.
+const KEEP_REPORTS: usize = 10;
+const LOG_TAIL_LINES: usize = 100;
+/// How far back from the end of the shell log to look for those lines.
+const LOG_TAIL_BYTES: u64 = 64 * 1024;
+
+struct Target {
+    dir: PathBuf,
+    log_dir: Option<PathBuf>,
+    app_version: String,
+}
+
+static TARGET: OnceLock<Target> = OnceLock::new();
+/// Set while a report is being written, so a panic inside the hook can't recurse.
+static WRITING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    /// File name of the report, used to dismiss it.
+    #[serde(default)]
+    pub id: String,
+    pub created_at: String,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub thread: Option<String>,
+    pub message: String,
+    pub location: Option<String>,
+    pub backtrace: String,
+    pub log_tail: String,
+    /// Full path of the minidump written alongside, if any.
+    pub minidump: Option<String>,
+}
+
+/// Install the panic hook. The default hook still runs afterwards.
+pub fn install() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if !WRITING.swap(true, Ordering::SeqCst) {
+            let payload = info.payload();
+            let message = if let Some(s) = payload.downcast_ref::<&str>() {
+                s.to_string()
+            } else if let Some(s) = payload.downcast_ref::<String>() {
+                s.clone()
+            } else {
+                "non-string panic payload".to_string()
+            };
+            let location = info
+                .location()
+                .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+            write_report(message, location);
+            WRITING.store(false, Ordering::SeqCst);
+        }
+        previous(info);
+    }));
+}
+
+/// Record where reports go and prune old ones. Call from setup, after the
+/// log plugin is registered.
+pub fn configure(app_handle: &tauri::AppHandle) {
+    let Ok(dir) = portable::resolve(app_handle, REPORT_DIR) else {
+        log::warn!("CRASH ► no AppLocalData folder; crash reports disabled");
+        return;
+    };
+    prune(&dir);
+    let _ = TARGET.set(Target {
+        dir,
+        log_dir: portable::app_log_dir(app_handle).ok(),
+        app_version: app_handle.package_info().version.to_string(),
+    });
+}
+
+fn write_report(message: String, location: Option<String>) {
+    let Some(target) = TARGET.get() else {
+        return;
+    };
+    let now = chrono::Local::now();
+    let stem = format!("crash-{}", now.format("%Y%m%d-%H%M%S-%3f"));
+    log::error!("CRASH ► panic: {}", message);
+    if fs::create_dir_all(&target.dir).is_err() {
+        return;
+    }
+    let minidump = write_minidump(&target.dir.join(format!("{}.dmp", stem)));
+    let report = CrashReport {
+        id: format!("{}.json", stem),
+        created_at: now.to_rfc3339(),
+        app_version: target.app_version.clone(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        thread: std::thread::current().name().map(str::to_string),
+        message,
+        location,
+        backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+        log_tail: target
+            .log_dir
+            .as_deref()
+            .map(shell_log_tail)
+            .unwrap_or_default(),
+        minidump: minidump.map(|p| p.to_string_lossy().into_owned()),
+    };
+    if let Ok(raw) = serde_json::to_string_pretty(&report) {
+        let _ = fs::write(target.dir.join(&report.id), raw);
+    }
+}
+
+/// Last lines of the newest shell log file.
+fn shell_log_tail(log_dir: &Path) -> String {
+    let newest = fs::read_dir(log_dir).ok().and_then(|entries| {
+        entries
+            .flatten()
+            .filter(|e| e.path().extension().is_some_and(|x| x == "log"))
+            .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+            .max()
+            .map(|(_, path)| path)
+    });
+    let Some(path) = newest else {
+        return String::new();
+    };
+    let mut data = Vec::new();
+    let read = File::open(&path).and_then(|mut file| {
+        let len = file.metadata()?.len();
+        file.seek(SeekFrom::Start(len.saturating_sub(LOG_TAIL_BYTES)))?;
+        file.read_to_end(&mut data)
+    });
+    if read.is_err() {
+        return String::new();
+    }
+    let text = String::from_utf8_lossy(&data);
+    let lines: Vec<&str> = text.lines().collect();
+    lines[lines.len().saturating_sub(LOG_TAIL_LINES)..].join("\n")
+}
+
+#[cfg(windows)]
+fn write_minidump(path: &Path) -> Option<PathBuf> {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::System::Diagnostics::Debug::{MiniDumpNormal, MiniDumpWriteDump};
+    use windows_sys::Win32::System::Threading::{GetCurrentProcess, GetCurrentProcessId};
+
+    let file = File::create(path).ok()?;
+    let ok = unsafe {
+        MiniDumpWriteDump(
+            GetCurrentProcess(),
+            GetCurrentProcessId(),
+            file.as_raw_handle() as isize,
+            MiniDumpNormal,
+            std::ptr::null(),
+            std::ptr::null(),
+            std::ptr::null(),
+        )
+    };
+    drop(file);
+    if ok == 0 {
+        let _ = fs::remove_file(path);
+        return None;
+    }
+    Some(path.to_path_buf())
+}
+
+#[cfg(not(windows))]
+fn write_minidump(_path: &Path) -> Option<PathBuf> {
+    // No in-process dump writer outside Windows; the OS core dump (if
+    // enabled) is the equivalent there.
+    None
+}
+
+/// Report files, oldest first. Names embed the creation time.
+fn report_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|x| x == "json"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+fn prune(dir: &Path) {
+    let files = report_files(dir);
+    let excess = files.len().saturating_sub(KEEP_REPORTS);
+    for report in &files[..excess] {
+        let _ = fs::remove_file(report.with_extension("dmp"));
+        let _ = fs::remove_file(report.with_extension("seen"));
+        let _ = fs::remove_file(report);
+    }
+}
+
+fn report_dir(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    portable::resolve(app_handle, REPORT_DIR).ok()
+}
+
+/// The newest crash report the user hasn't dismissed yet.
+#[tauri::command]
+pub async fn get_last_crash_report(
+    app_handle: tauri::AppHandle,
+) -> Result<Option<CrashReport>, String> {
+    let Some(dir) = report_dir(&app_handle) else {
+        return Ok(None);
+    };
+    let Some(path) = report_files(&dir)
+        .into_iter()
+        .rev()
+        .find(|p| !p.with_extension("seen").exists())
+    else {
+        return Ok(None);
+    };
+    let raw = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let mut report: CrashReport =
+        serde_json::from_str(&raw).map_err(|e| format!("invalid crash report: {}", e))?;
+    report.id = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    Ok(Some(report))
+}
+
+/// Mark a report as seen (sent or declined) so it isn't offered again.
+#[tauri::command]
+pub async fn dismiss_crash_report(app_handle: tauri::AppHandle, id: String) -> Result<(), String> {
+    let dir = report_dir(&app_handle).ok_or("no crash report folder")?;
+    let path = dir.join(&id);
+    if id.contains(['/', '\\']) || !path.is_file() {
+        return Err(format!("unknown crash report: {}", id));
+    }
+    fs::write(path.with_extension("seen"), b"").map_err(|e| e.to_string())?;
+    log::info!("CRASH ► report {} dismissed", id);
+    Ok(())
+}
@@ -0,0 +1,616 @@
+// At-rest encryption of sensitive data folders. With `data_encryption.enabled`
+// each folder in `data_encryption.folders` (top-level folders of the backend
+// data root) is kept on disk only as `<folder>.sealed`, AES-256-GCM under a
+// key the OS wraps for the current user: DPAPI on Windows, the login
+// Keychain on macOS. Other platforms have no such store and refuse the
+// setting.
+//
+// Before the backend is spawned, `unseal` decrypts each folder into the
+// protected runtime location (`unsealed/` under AppLocalData, owner-only)
+// and links it into the data root where the backend expects it (a junction
+// on Windows, a symlink elsewhere). The `shutdown_backend_*` paths call
+// `reseal` once the backend is gone, which encrypts the folder back and
+// removes the plaintext; restarts skip it since the backend comes straight
+// back, and so does the end of an OS session, which leaves no time for it.
+// A session that ended without resealing (OS logoff, a crash) leaves the
+// runtime copy behind; it is the newest data, so the next start seals it
+// first and then keeps using it. A folder is only ever removed once it has
+// been sealed completely: any entry that can't be read fails the seal.
+// Plaintext found in the data root when encryption is turned on is sealed
+// on the next start; turning it off moves the folders back into the data
+// root on the next shutdown.
+//
+// A sealed file is a zip of the folder cut into `FRAME_SIZE` frames, each
+// sealed with its own random nonce and with its index and whether it is the
+// last as associated data, so frames can't be reordered or cut off.
+
+use crate::{backup, paths, portable, settings};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use zip::{ZipArchive, ZipWriter};
+
+const MAGIC: &[u8; 8] = b"PLSEAL1\0";
+const FRAME_SIZE: usize = 1024 * 1024;
+const KEY_LEN: usize = 32;
+const SEALED_EXTENSION: &str = "sealed";
+/// Runtime location for decrypted folders, under AppLocalData.
+const RUNTIME_DIR: &str = "unsealed";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DataEncryptionSettings {
+    pub enabled: bool,
+    /// Top-level folders of the backend data root to keep encrypted.
+    pub folders: Vec<String>,
+}
+
+impl Default for DataEncryptionSettings {
+    fn default() -> Self {
+        DataEncryptionSettings {
+            enabled: false,
+            folders: vec!["dossiers_data".to_string()],
+        }
+    }
+}
+
+impl DataEncryptionSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.enabled && !cfg!(any(windows, target_os = "macos")) {
+            return Err("data_encryption needs DPAPI (Windows) or the Keychain (macOS)".into());
+        }
+        for folder in &self.folders {
+            let plain = !folder.is_empty()
+                && folder != "."
+                && folder != ".."
+                && !folder.contains(['/', '\\', ':']);
+            if !plain {
+                return Err(format!(
+                    "data_encryption.folders: '{}' is not a top-level folder name",
+                    folder
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+const KEY_FILE: &str = "data-key.bin";
+
+/// Run the key through DPAPI, scoped to the current user.
+#[cfg(windows)]
+fn dpapi(data: &[u8], protect: bool) -> Result<Vec<u8>, String> {
+    use windows_sys::Win32::Foundation::LocalFree;
+    use windows_sys::Win32::Security::Cryptography::{
+        CryptProtectData, CryptUnprotectData, CRYPTPROTECT_UI_FORBIDDEN, CRYPT_INTEGER_BLOB,
+    };
+
+    let input = CRYPT_INTEGER_BLOB {
+        cbData: data.len() as u32,
+        pbData: data.as_ptr() as *mut u8,
+    };
+    let mut output = CRYPT_INTEGER_BLOB {
+        cbData: 0,
+        pbData: std::ptr::null_mut(),
+    };
+    let ok = unsafe {
+        if protect {
+            CryptProtectData(
+                &input,
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                CRYPTPROTECT_UI_FORBIDDEN,
+                &mut output,
+            )
+        } else {
+            CryptUnprotectData(
+                &input,
+                std::ptr::null_mut(),
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null(),
+                CRYPTPROTECT_UI_FORBIDDEN,
+                &mut output,
+            )
+        }
+    };
+    if ok == 0 {
+        return Err(format!("DPAPI failed: {}", io::Error::last_os_error()));
+    }
+    let out = unsafe { std::slice::from_raw_parts(output.pbData, output.cbData as usize) }.to_vec();
+    unsafe { LocalFree(output.pbData as _) };
+    Ok(out)
+}
+
+#[cfg(windows)]
+fn load_key(app_handle: &tauri::AppHandle) -> Result<Option<Vec<u8>>, String> {
+    let path = portable::resolve(app_handle, KEY_FILE).map_err(|e| e.to_string())?;
+    match fs::read(&path) {
+        Ok(blob) => dpapi(&blob, false).map(Some),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("cannot read {:?}: {}", path, e)),
+    }
+}
+
+#[cfg(windows)]
+fn store_key(app_handle: &tauri::AppHandle, key: &[u8]) -> Result<(), String> {
+    let path = portable::resolve(app_handle, KEY_FILE).map_err(|e| e.to_string())?;
+    fs::write(&path, dpapi(key, true)?).map_err(|e| format!("cannot write {:?}: {}", path, e))
+}
+
+#[cfg(target_os = "macos")]
+const KEYCHAIN_SERVICE: &str = "com.plattera.app.data-encryption";
+#[cfg(target_os = "macos")]
+const KEYCHAIN_ACCOUNT: &str = "data-key";
+
+#[cfg(target_os = "macos")]
+fn load_key(_app_handle: &tauri::AppHandle) -> Result<Option<Vec<u8>>, String> {
+    /// `errSecItemNotFound`
+    const NOT_FOUND: i32 = -25300;
+    match security_framework::passwords::get_generic_password(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT) {
+        Ok(key) => Ok(Some(key)),
+        Err(e) if e.code() == NOT_FOUND => Ok(None),
+        Err(e) => Err(format!("Keychain: {}", e)),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn store_key(_app_handle: &tauri::AppHandle, key: &[u8]) -> Result<(), String> {
+    security_framework::passwords::set_generic_password(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT, key)
+        .map_err(|e| format!("Keychain: {}", e))
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn load_key(_app_handle: &tauri::AppHandle) -> Result<Option<Vec<u8>>, String> {
+    Err("no OS key store to unwrap the data key on this platform".into())
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn store_key(_app_handle: &tauri::AppHandle, _key: &[u8]) -> Result<(), String> {
+    Err("no OS key store to wrap the data key on this platform".into())
+}
+
+/// The data key; a new one is generated and wrapped when `create` is set
+/// and there is none yet.
+fn key(app_handle: &tauri::AppHandle, create: bool) -> Result<LessSafeKey, String> {
+    let bytes = match load_key(app_handle)? {
+        Some(bytes) => bytes,
+        None if create => {
+            let mut bytes = vec![0u8; KEY_LEN];
+            SystemRandom::new()
+                .fill(&mut bytes)
+                .map_err(|_| "cannot generate a data key".to_string())?;
+            store_key(app_handle, &bytes)?;
+            log::info!("DATA_ENCRYPTION ► generated a new data key");
+            bytes
+        }
+        None => return Err("the data key is missing; sealed folders can't be opened".into()),
+    };
+    let key = UnboundKey::new(&AES_256_GCM, &bytes).map_err(|_| "the data key is invalid")?;
+    Ok(LessSafeKey::new(key))
+}
+
+fn frame_aad(index: u64, last: bool) -> [u8; 9] {
+    let mut aad = [0u8; 9];
+    aad[..8].copy_from_slice(&index.to_le_bytes());
+    aad[8] = last as u8;
+    aad
+}
+
+/// Read until `buf` is full or the input ends; the number of bytes read.
+fn read_full(src: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match src.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+fn encrypt_file(key: &LessSafeKey, src: &Path, dest: &Path) -> Result<(), String> {
+    let total = fs::metadata(src).map_err(|e| e.to_string())?.len();
+    let mut input = BufReader::new(File::open(src).map_err(|e| e.to_string())?);
+    let mut out = BufWriter::new(
+        File::create(paths::extended(dest))
+            .map_err(|e| format!("cannot create {:?}: {}", dest, e))?,
+    );
+    out.write_all(MAGIC).map_err(|e| e.to_string())?;
+    let rng = SystemRandom::new();
+    let mut buf = vec![0u8; FRAME_SIZE];
+    let mut done = 0u64;
+    let mut index = 0u64;
+    loop {
+        let n = read_full(&mut input, &mut buf).map_err(|e| e.to_string())?;
+        done += n as u64;
+        let last = n < FRAME_SIZE || done >= total;
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill(&mut nonce)
+            .map_err(|_| "cannot generate a nonce")?;
+        let mut frame = buf[..n].to_vec();
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(frame_aad(index, last)),
+            &mut frame,
+        )
+        .map_err(|_| "encryption failed")?;
+        out.write_all(&(frame.len() as u32).to_le_bytes())
+            .and_then(|_| out.write_all(&[last as u8]))
+            .and_then(|_| out.write_all(&nonce))
+            .and_then(|_| out.write_all(&frame))
+            .map_err(|e| format!("cannot write {:?}: {}", dest, e))?;
+        if last {
+            break;
+        }
+        index += 1;
+    }
+    out.flush().map_err(|e| e.to_string())
+}
+
+fn decrypt_file(key: &LessSafeKey, src: &Path, dest: &Path) -> Result<(), String> {
+    let corrupt = || format!("{:?} is damaged or was sealed with another key", src);
+    let mut input = BufReader::new(File::open(paths::extended(src)).map_err(|e| e.to_string())?);
+    let mut out = BufWriter::new(File::create(dest).map_err(|e| e.to_string())?);
+    let mut magic = [0u8; 8];
+    input.read_exact(&mut magic).map_err(|_| corrupt())?;
+    if &magic != MAGIC {
+        return Err(corrupt());
+    }
+    let mut index = 0u64;
+    loop {
+        let mut len = [0u8; 4];
+        let mut last = [0u8; 1];
+        let mut nonce = [0u8; NONCE_LEN];
+        input.read_exact(&mut len).map_err(|_| corrupt())?;
+        input.read_exact(&mut last).map_err(|_| corrupt())?;
+        input.read_exact(&mut nonce).map_err(|_| corrupt())?;
+        let last = last[0] == 1;
+        let len = u32::from_le_bytes(len) as usize;
+        if len > FRAME_SIZE + AES_256_GCM.tag_len() {
+            return Err(corrupt());
+        }
+        let mut frame = vec![0u8; len];
+        input.read_exact(&mut frame).map_err(|_| corrupt())?;
+        let plain = key
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(frame_aad(index, last)),
+                &mut frame,
+            )
+            .map_err(|_| corrupt())?;
+        out.write_all(plain).map_err(|e| e.to_string())?;
+        if last {
+            break;
+        }
+        index += 1;
+    }
+    out.flush().map_err(|e| e.to_string())
+}
+
+/// Every entry under `root`, as (zip name, path, is_dir). Unlike
+/// `backup::collect`, anything that can't be listed is an error, so a seal
+/// never leaves data out.
+fn collect_all(
+    root: &Path,
+    prefix: &str,
+    out: &mut Vec<(String, PathBuf, bool)>,
+) -> Result<(), String> {
+    let entries = fs::read_dir(paths::extended(root))
+        .map_err(|e| format!("cannot list {:?}: {}", root, e))?;
+    let mut entries = entries
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("cannot list {:?}: {}", root, e))?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        let meta =
+            fs::symlink_metadata(&path).map_err(|e| format!("cannot read {:?}: {}", path, e))?;
+        if meta.is_dir() {
+            out.push((format!("{}/", name), path.clone(), true));
+            collect_all(&path, &format!("{}/", name), out)?;
+        } else if meta.is_file() {
+            out.push((name, path, false));
+        } else {
+            return Err(format!("{:?} is neither a file nor a folder", path));
+        }
+    }
+    Ok(())
+}
+
+/// Zip `folder` and encrypt it to `sealed`, via a `.partial` file so a
+/// failure leaves the previous sealed copy intact. Fails if any entry can't
+/// be read; the caller must then keep `folder`.
+fn seal_folder(
+    key: &LessSafeKey,
+    folder: &Path,
+    sealed: &Path,
+    scratch: &Path,
+) -> Result<(), String> {
+    let result = (|| -> Result<(), String> {
+        let mut entries = Vec::new();
+        collect_all(folder, "", &mut entries)?;
+        let file = File::create(scratch).map_err(|e| e.to_string())?;
+        let mut zip = ZipWriter::new(BufWriter::new(file));
+        for (name, path, is_dir) in &entries {
+            if *is_dir {
+                zip.add_directory(name.as_str(), backup::options_for(name))
+                    .map_err(|e| format!("cannot add {}: {}", name, e))?;
+                continue;
+            }
+            let mut src = File::open(paths::extended(path))
+                .map_err(|e| format!("cannot read {:?}: {}", path, e))?;
+            zip.start_file(name.as_str(), backup::options_for(name))
+                .map_err(|e| format!("cannot add {}: {}", name, e))?;
+            io::copy(&mut src, &mut zip).map_err(|e| format!("cannot add {}: {}", name, e))?;
+        }
+        zip.finish()
+            .and_then(|mut w| w.flush().map_err(Into::into))
+            .map_err(|e| e.to_string())?;
+        let partial = sealed.with_extension(format!("{}.partial", SEALED_EXTENSION));
+        encrypt_file(key, scratch, &partial)?;
+        fs::rename(paths::extended(&partial), paths::extended(sealed))
+            .map_err(|e| format!("cannot replace {:?}: {}", sealed, e))
+    })();
+    let _ = fs::remove_file(scratch);
+    result
+}
+
+fn open_folder(
+    key: &LessSafeKey,
+    sealed: &Path,
+    dest: &Path,
+    scratch: &Path,
+) -> Result<(), String> {
+    let result = (|| -> Result<(), String> {
+        decrypt_file(key, sealed, scratch)?;
+        let file = File::open(scratch).map_err(|e| e.to_string())?;
+        let mut zip = ZipArchive::new(BufReader::new(file)).map_err(|e| e.to_string())?;
+        fs::create_dir_all(dest).map_err(|e| e.to_string())?;
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i).map_err(|e| e.to_string())?;
+            let Some(name) = entry.enclosed_name() else {
+                return Err(format!(
+                    "sealed folder contains an unsafe path: {}",
+                    entry.name()
+                ));
+            };
+            let target = dest.join(name);
+            if entry.is_dir() {
+                fs::create_dir_all(&target).map_err(|e| e.to_string())?;
+                continue;
+            }
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            let mut out =
+                File::create(&target).map_err(|e| format!("cannot create {:?}: {}", target, e))?;
+            io::copy(&mut entry, &mut out).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    })();
+    let _ = fs::remove_file(scratch);
+    if result.is_err() {
+        let _ = fs::remove_dir_all(dest);
+    }
+    result
+}
+
+#[cfg(unix)]
+fn link(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+/// A junction rather than a symlink: it needs no special privilege.
+#[cfg(windows)]
+fn link(target: &Path, link: &Path) -> io::Result<()> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    let status = std::process::Command::new("cmd")
+        .args(["/C", "mklink", "/J"])
+        .arg(link)
+        .arg(target)
+        .creation_flags(CREATE_NO_WINDOW)
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "mklink /J exited with {}",
+            status
+        )))
+    }
+}
+
+/// Remove the link itself, never what it points to.
+pub fn unlink(link: &Path) -> io::Result<()> {
+    if cfg!(windows) {
+        fs::remove_dir(link)
+    } else {
+        fs::remove_file(link)
+    }
+}
+
+pub fn is_link(path: &Path) -> bool {
+    fs::symlink_metadata(path)
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
+/// Encrypted copy of the data folder `name`.
+pub fn sealed_path(data_root: &Path, name: &str) -> PathBuf {
+    data_root.join(format!("{}.{}", name, SEALED_EXTENSION))
+}
+
+/// Folder holding the decrypted copies while the backend runs.
+pub fn runtime_root(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    portable::resolve(app_handle, RUNTIME_DIR).map_err(|e| e.to_string())
+}
+
+/// Where one folder lives: in the data root, sealed, and while unsealed.
+struct Folder {
+    name: String,
+    data: PathBuf,
+    sealed: PathBuf,
+    runtime: PathBuf,
+    scratch: PathBuf,
+}
+
+fn folders(
+    app_handle: &tauri::AppHandle,
+    cfg: &DataEncryptionSettings,
+) -> Result<Vec<Folder>, String> {
+    let data_root = settings::backend_data_dir(app_handle)?;
+    let runtime_root = runtime_root(app_handle)?;
+    Ok(cfg
+        .folders
+        .iter()
+        .map(|name| Folder {
+            name: name.clone(),
+            data: data_root.join(name),
+            sealed: sealed_path(&data_root, name),
+            runtime: runtime_root.join(name),
+            scratch: runtime_root.join(format!("{}.zip.tmp", name)),
+        })
+        .collect())
+}
+
+/// Create the runtime location, readable by the current user only.
+fn prepare_runtime_root(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let root = runtime_root(app_handle)?;
+    fs::create_dir_all(&root).map_err(|e| format!("cannot create {:?}: {}", root, e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&root, fs::Permissions::from_mode(0o700))
+            .map_err(|e| format!("cannot restrict {:?}: {}", root, e))?;
+    }
+    Ok(())
+}
+
+/// Decrypt the sealed folders and link them into the data root. Called
+/// before the backend is spawned; does nothing when encryption is off and
+/// nothing is sealed.
+pub fn unseal(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let cfg = settings::current(app_handle).data_encryption;
+    let folders = folders(app_handle, &cfg)?;
+    let pending: Vec<&Folder> = folders
+        .iter()
+        .filter(|f| cfg.enabled || f.sealed.exists() || f.runtime.exists())
+        .collect();
+    if pending.is_empty() {
+        return Ok(());
+    }
+    prepare_runtime_root(app_handle)?;
+    let key = key(app_handle, cfg.enabled)?;
+
+    for folder in pending {
+        // Plaintext from before encryption was turned on.
+        if cfg.enabled && folder.data.is_dir() && !is_link(&folder.data) {
+            log::info!("DATA_ENCRYPTION ► sealing existing {}", folder.name);
+            seal_folder(&key, &folder.data, &folder.sealed, &folder.scratch)?;
+            fs::remove_dir_all(paths::extended(&folder.data))
+                .map_err(|e| format!("cannot remove plaintext {:?}: {}", folder.data, e))?;
+        }
+        if folder.runtime.exists() {
+            // Finish the reseal the last session skipped, so the sealed copy
+            // is current, and keep working from the runtime copy.
+            if cfg.enabled {
+                if is_link(&folder.data) {
+                    unlink(&folder.data).map_err(|e| e.to_string())?;
+                }
+                match seal_folder(&key, &folder.runtime, &folder.sealed, &folder.scratch) {
+                    Ok(()) => log::info!(
+                        "DATA_ENCRYPTION ► resealed {} left open last session",
+                        folder.name
+                    ),
+                    Err(e) => log::warn!(
+                        "DATA_ENCRYPTION ► could not reseal {} left open last session: {}",
+                        folder.name,
+                        e
+                    ),
+                }
+            } else {
+                log::info!(
+                    "DATA_ENCRYPTION ► {} was not resealed last session; using it as-is",
+                    folder.name
+                );
+            }
+        } else if folder.sealed.exists() {
+            open_folder(&key, &folder.sealed, &folder.runtime, &folder.scratch)?;
+            log::info!("DATA_ENCRYPTION ► unsealed {}", folder.name);
+        } else {
+            fs::create_dir_all(&folder.runtime).map_err(|e| e.to_string())?;
+        }
+
+        if is_link(&folder.data) {
+            if fs::read_link(&folder.data).ok().as_deref() == Some(folder.runtime.as_path()) {
+                continue;
+            }
+            unlink(&folder.data).map_err(|e| e.to_string())?;
+        } else if folder.data.exists() {
+            return Err(format!(
+                "{:?} exists next to its sealed copy; move it aside to continue",
+                folder.data
+            ));
+        }
+        link(&folder.runtime, &folder.data)
+            .map_err(|e| format!("cannot link {:?}: {}", folder.data, e))?;
+    }
+    Ok(())
+}
+
+/// Encrypt the unsealed folders back and remove the plaintext, or, with
+/// encryption turned off, move them back into the data root. Called once
+/// the backend has exited.
+pub fn reseal(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let cfg = settings::current(app_handle).data_encryption;
+    let folders = folders(app_handle, &cfg)?;
+    let open: Vec<&Folder> = folders.iter().filter(|f| f.runtime.exists()).collect();
+    if open.is_empty() {
+        return Ok(());
+    }
+    let key = if cfg.enabled {
+        Some(key(app_handle, true)?)
+    } else {
+        None
+    };
+
+    for folder in open {
+        if is_link(&folder.data) {
+            unlink(&folder.data).map_err(|e| e.to_string())?;
+        }
+        match &key {
+            Some(key) => {
+                if let Err(e) = seal_folder(key, &folder.runtime, &folder.sealed, &folder.scratch) {
+                    // Keep the data reachable; the next start uses it as-is.
+                    let _ = link(&folder.runtime, &folder.data);
+                    return Err(e);
+                }
+                fs::remove_dir_all(&folder.runtime).map_err(|e| e.to_string())?;
+                log::info!("DATA_ENCRYPTION ► resealed {}", folder.name);
+            }
+            None => {
+                if let Err(e) = fs::rename(&folder.runtime, paths::extended(&folder.data)) {
+                    let _ = link(&folder.runtime, &folder.data);
+                    return Err(format!(
+                        "cannot move {} back into the data folder: {}",
+                        folder.name, e
+                    ));
+                }
+                let _ = fs::remove_file(paths::extended(&folder.sealed));
+                log::info!("DATA_ENCRYPTION ► {} is no longer encrypted", folder.name);
+            }
+        }
+    }
+    Ok(())
+}
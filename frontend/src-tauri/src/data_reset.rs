@@ -0,0 +1,246 @@
+// Granular data reset. `factory_reset_data` wipes everything; `reset_data`
+// clears only the chosen scopes:
+//
+//   caches    preview cache (AppCache); no restart
+//   logs      shell, backend and sidecar output logs, crash reports; no restart
+//   settings  settings and scheduler state; the app restarts, since
+//             transport, data location and monitors are set up from settings
+//             at launch
+//   dossiers  the backend's dossiers_data, with its sealed file and
+//             decrypted runtime copy when it is encrypted; the backend is
+//             stopped for the deletion and started again, the app keeps
+//             running
+//
+// Files that can't be removed (a log the logger still holds) are reported,
+// not fatal: the rest of the scope is still cleared.
+
+use crate::backend_lifecycle::shutdown_backend_for_exit;
+use crate::{
+    backend_log, crash_report, data_encryption, paths, portable, settings, spawn_backend,
+    supervisor, trace,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use tauri::Manager;
+
+/// Shell state files that belong to the `settings` scope.
+const STATE_FILES: &[&str] = &["settings.json", "scheduler_state.json"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResetScope {
+    Caches,
+    Settings,
+    Dossiers,
+    Logs,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResetOutcome {
+    pub cleared: Vec<ResetScope>,
+    pub freed_bytes: u64,
+    /// Entries that could not be removed.
+    pub errors: Vec<String>,
+    /// The app restarts shortly after this reply.
+    pub restarting: bool,
+}
+
+const DOSSIERS_DIR: &str = "dossiers_data";
+
+/// Remove `path` (file or folder), counting what it held. A link is removed
+/// itself; what it points to is listed separately when it should go too.
+fn remove(path: &Path, outcome: &mut ResetOutcome) {
+    let target = paths::extended(path);
+    let Ok(meta) = fs::symlink_metadata(&target) else {
+        return;
+    };
+    if meta.file_type().is_symlink() {
+        if let Err(e) = data_encryption::unlink(&target) {
+            outcome.errors.push(format!("{}: {}", path.display(), e));
+        }
+    } else if meta.is_dir() {
+        // Entry by entry, so one locked file doesn't keep the rest.
+        if let Ok(entries) = fs::read_dir(&target) {
+            for entry in entries.flatten() {
+                remove(&entry.path(), outcome);
+            }
+        }
+        let _ = fs::remove_dir(&target);
+    } else {
+        match fs::remove_file(&target) {
+            Ok(()) => outcome.freed_bytes += meta.len(),
+            Err(e) => outcome.errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+}
+
+fn scope_paths(app_handle: &tauri::AppHandle, scope: ResetScope) -> Result<Vec<PathBuf>, String> {
+    let local = |rel: &str| portable::resolve(app_handle, rel).map_err(|e| e.to_string());
+    Ok(match scope {
+        ResetScope::Caches => vec![portable::app_cache_dir(app_handle).map_err(|e| e.to_string())?],
+        ResetScope::Logs => {
+            let mut dirs = vec![portable::app_log_dir(app_handle).map_err(|e| e.to_string())?];
+            dirs.extend(trace::backend_log_dir(app_handle));
+            dirs.extend(backend_log::log_dir(app_handle));
+            dirs.push(local(crash_report::REPORT_DIR)?);
+            dirs
+        }
+        ResetScope::Settings => STATE_FILES
+            .iter()
+            .map(|f| local(f))
+            .collect::<Result<_, _>>()?,
+        ResetScope::Dossiers => dossier_paths(
+            &settings::backend_data_dir(app_handle)?,
+            &data_encryption::runtime_root(app_handle)?,
+        ),
+    })
+}
+
+/// The dossiers folder in the data root, its sealed file, and the decrypted
+/// copy a session that ended without resealing leaves behind.
+fn dossier_paths(data_root: &Path, runtime_root: &Path) -> Vec<PathBuf> {
+    vec![
+        data_root.join(DOSSIERS_DIR),
+        data_encryption::sealed_path(data_root, DOSSIERS_DIR),
+        runtime_root.join(DOSSIERS_DIR),
+    ]
+}
+
+fn reset(app_handle: &tauri::AppHandle, scopes: &[ResetScope]) -> Result<ResetOutcome, String> {
+    if scopes.is_empty() {
+        return Err("no reset scope selected".into());
+    }
+    let mut outcome = ResetOutcome {
+        cleared: Vec::new(),
+        freed_bytes: 0,
+        errors: Vec::new(),
+        restarting: scopes.contains(&ResetScope::Settings),
+    };
+    let dossiers = scopes.contains(&ResetScope::Dossiers);
+    log::warn!("RESET ► clearing {:?}", scopes);
+
+    if dossiers {
+        // The backend must not hold or rewrite dossier files mid-deletion.
+        shutdown_backend_for_exit(app_handle);
+    }
+    for &scope in scopes {
+        if outcome.cleared.contains(&scope) {
+            continue;
+        }
+        if scope == ResetScope::Logs {
+            backend_log::close(app_handle);
+        }
+        for path in scope_paths(app_handle, scope)? {
+            remove(&path, &mut outcome);
+        }
+        outcome.cleared.push(scope);
+    }
+    if dossiers && !outcome.restarting {
+        supervisor::reset(app_handle);
+        if let Err(e) = spawn_backend(app_handle) {
+            outcome
+                .errors
+                .push(format!("backend did not restart: {}", e));
+        }
+    }
+
+    log::info!(
+        "RESET ► cleared {:?}: {} bytes freed, {} error(s)",
+        outcome.cleared,
+        outcome.freed_bytes,
+        outcome.errors.len()
+    );
+    Ok(outcome)
+}
+
+/// Clear only the selected kinds of data. Restarts the app when settings
+/// were reset; otherwise everything keeps running.
+#[tauri::command]
+pub async fn reset_data(
+    app_handle: tauri::AppHandle,
+    scopes: Vec<ResetScope>,
+) -> Result<ResetOutcome, String> {
+    let handle = app_handle.clone();
+    let outcome = tauri::async_runtime::spawn_blocking(move || reset(&handle, &scopes))
+        .await
+        .map_err(|e| e.to_string())??;
+    if outcome.restarting {
+        // In-memory settings would otherwise be written back on the next change.
+        *app_handle
+            .state::<settings::SettingsState>()
+            .0
+            .lock()
+            .unwrap() = settings::AppSettings::default();
+        // Give the reply a moment to reach the frontend.
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(500));
+            app_handle.restart();
+        });
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome() -> ResetOutcome {
+        ResetOutcome {
+            cleared: Vec::new(),
+            freed_bytes: 0,
+            errors: Vec::new(),
+            restarting: false,
+        }
+    }
+
+    #[test]
+    fn dossier_reset_removes_sealed_and_runtime_copies() {
+        let root = std::env::temp_dir().join(format!("plattera-reset-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let data = root.join("data");
+        let runtime = root.join("unsealed");
+        fs::create_dir_all(data.join(DOSSIERS_DIR)).unwrap();
+        fs::write(data.join(DOSSIERS_DIR).join("dossier.json"), "{}").unwrap();
+        fs::write(data_encryption::sealed_path(&data, DOSSIERS_DIR), "sealed").unwrap();
+        fs::create_dir_all(runtime.join(DOSSIERS_DIR)).unwrap();
+        fs::write(runtime.join(DOSSIERS_DIR).join("open.json"), "{}").unwrap();
+
+        let mut outcome = outcome();
+        for path in dossier_paths(&data, &runtime) {
+            remove(&path, &mut outcome);
+        }
+        assert!(outcome.errors.is_empty(), "{:?}", outcome.errors);
+        assert!(!data.join(DOSSIERS_DIR).exists());
+        assert!(!data_encryption::sealed_path(&data, DOSSIERS_DIR).exists());
+        assert!(!runtime.join(DOSSIERS_DIR).exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn linked_folder_is_unlinked_and_its_target_removed_separately() {
+        let root = std::env::temp_dir().join(format!("plattera-reset-link-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let data = root.join("data");
+        let runtime = root.join("unsealed");
+        fs::create_dir_all(&data).unwrap();
+        fs::create_dir_all(runtime.join(DOSSIERS_DIR)).unwrap();
+        fs::write(runtime.join(DOSSIERS_DIR).join("open.json"), "{}").unwrap();
+        std::os::unix::fs::symlink(runtime.join(DOSSIERS_DIR), data.join(DOSSIERS_DIR)).unwrap();
+
+        let mut outcome = outcome();
+        remove(&data.join(DOSSIERS_DIR), &mut outcome);
+        assert!(fs::symlink_metadata(data.join(DOSSIERS_DIR)).is_err());
+        assert!(runtime.join(DOSSIERS_DIR).join("open.json").exists());
+
+        for path in dossier_paths(&data, &runtime) {
+            remove(&path, &mut outcome);
+        }
+        assert!(outcome.errors.is_empty(), "{:?}", outcome.errors);
+        assert!(!runtime.join(DOSSIERS_DIR).exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,222 @@
+// `plattera://` links. A link like `plattera://dossier/123` starts the app,
+// or focuses the running one, and reaches the frontend as a `deep-link`
+// event carrying the parsed route (`dossier/123`, its segments and query).
+//
+// How the URL arrives depends on the platform: Windows and Linux start the
+// app with the URL as an argument (a second launch is forwarded by the
+// single-instance plugin, see `single_instance.rs`); macOS delivers it as an
+// `Opened` run event. Links that arrive before the frontend is listening are
+// queued until it calls `take_pending_deep_links`. Toast clicks on Windows
+// arrive as `plattera://notification/<id>` and go to `notifications.rs`
+// instead.
+//
+// The scheme is registered for the current user at startup: a registry key
+// on Windows, a `.desktop` handler on Linux, Info.plist on macOS. Debug
+// builds and portable mode don't register, so neither a dev binary nor a
+// copy on a USB stick takes the links over from the installed app.
+
+use crate::{notifications, portable};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager, Url};
+
+pub const SCHEME: &str = "plattera";
+/// Links queued before the frontend asked for them; more are dropped.
+const MAX_PENDING: usize = 16;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeepLink {
+    pub url: String,
+    /// Host and path without slashes at either end, e.g. `dossier/123`.
+    pub route: String,
+    pub segments: Vec<String>,
+    pub query: BTreeMap<String, String>,
+}
+
+#[derive(Default)]
+struct Inner {
+    pending: Vec<DeepLink>,
+    /// Set once the frontend took the queue; later links are only emitted.
+    frontend_ready: bool,
+}
+
+pub struct DeepLinks(Mutex<Inner>);
+
+impl DeepLinks {
+    pub fn new() -> Self {
+        DeepLinks(Mutex::new(Inner::default()))
+    }
+}
+
+pub fn parse(raw: &str) -> Option<DeepLink> {
+    let url = Url::parse(raw.trim()).ok()?;
+    if url.scheme() != SCHEME {
+        return None;
+    }
+    let mut segments: Vec<String> = url.host_str().map(str::to_string).into_iter().collect();
+    segments.extend(
+        url.path_segments()
+            .into_iter()
+            .flatten()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string()),
+    );
+    Some(DeepLink {
+        url: url.to_string(),
+        route: segments.join("/"),
+        segments,
+        query: url.query_pairs().into_owned().collect(),
+    })
+}
+
+/// Deep links among command-line arguments.
+pub fn from_args<S: AsRef<str>>(args: &[S]) -> Vec<DeepLink> {
+    args.iter().filter_map(|a| parse(a.as_ref())).collect()
+}
+
+/// Focus the main window and deliver `links` to the frontend.
+pub fn handle(app_handle: &tauri::AppHandle, links: Vec<DeepLink>) {
+    let (clicks, links): (Vec<DeepLink>, Vec<DeepLink>) = links
+        .into_iter()
+        .partition(|l| l.segments.len() == 2 && l.segments[0] == "notification");
+    for click in clicks {
+        notifications::clicked(app_handle, &click.segments[1]);
+    }
+    if links.is_empty() {
+        return;
+    }
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    let state = app_handle.state::<DeepLinks>();
+    let mut inner = state.0.lock().unwrap();
+    for link in links {
+        log::info!("DEEP_LINK ► {}", link.url);
+        if !inner.frontend_ready && inner.pending.len() < MAX_PENDING {
+            inner.pending.push(link.clone());
+        }
+        let _ = app_handle.emit("deep-link", &link);
+    }
+}
+
+/// Links the app was launched with. Called from setup.
+pub fn handle_launch_args(app_handle: &tauri::AppHandle) {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    handle(app_handle, from_args(&args));
+}
+
+/// Links that arrived before the frontend was listening. From then on links
+/// are delivered only as `deep-link` events.
+#[tauri::command]
+pub async fn take_pending_deep_links(
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<DeepLink>, String> {
+    let state = app_handle.state::<DeepLinks>();
+    let mut inner = state.0.lock().unwrap();
+    inner.frontend_ready = true;
+    Ok(std::mem::take(&mut inner.pending))
+}
+
+#[cfg(windows)]
+fn register_scheme(_app_handle: &tauri::AppHandle, exe: &std::path::Path) -> Result<(), String> {
+    use std::os::windows::process::CommandExt;
+    use std::process::Command;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let key = format!(r"HKCU\Software\Classes\{}", SCHEME);
+    let command_key = format!(r"{}\shell\open\command", key);
+    let command = format!("\"{}\" \"%1\"", exe.display());
+    let current = Command::new("reg")
+        .args(["query", &command_key, "/ve"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+        .unwrap_or_default();
+    if current.contains(&command) {
+        return Ok(());
+    }
+    let entries: [&[&str]; 3] = [
+        &["add", &key, "/ve", "/d", "URL:Plattera", "/f"],
+        &["add", &key, "/v", "URL Protocol", "/d", "", "/f"],
+        &["add", &command_key, "/ve", "/d", &command, "/f"],
+    ];
+    for args in entries {
+        let status = Command::new("reg")
+            .args(args)
+            .creation_flags(CREATE_NO_WINDOW)
+            .status()
+            .map_err(|e| format!("reg: {}", e))?;
+        if !status.success() {
+            return Err(format!("reg {:?} exited with {}", args, status));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn register_scheme(app_handle: &tauri::AppHandle, exe: &std::path::Path) -> Result<(), String> {
+    const DESKTOP_FILE: &str = "plattera-url-handler.desktop";
+
+    // An AppImage runs from a temporary mount; the image itself is stable.
+    let exe = std::env::var_os("APPIMAGE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| exe.to_path_buf());
+    let dir = app_handle
+        .path()
+        .data_dir()
+        .map_err(|e| e.to_string())?
+        .join("applications");
+    let path = dir.join(DESKTOP_FILE);
+    let entry = format!(
+        "[Desktop Entry]\nType=Application\nName=Plattera\nExec=\"{}\" %u\n\
+         NoDisplay=true\nTerminal=false\nMimeType=x-scheme-handler/{};\n",
+        exe.display(),
+        SCHEME
+    );
+    if std::fs::read_to_string(&path).ok().as_deref() == Some(entry.as_str()) {
+        return Ok(());
+    }
+    std::fs::create_dir_all(&dir).map_err(|e| format!("create {:?}: {}", dir, e))?;
+    std::fs::write(&path, entry).map_err(|e| format!("write {:?}: {}", path, e))?;
+    let _ = std::process::Command::new("update-desktop-database")
+        .arg(&dir)
+        .status();
+    std::process::Command::new("xdg-mime")
+        .args([
+            "default",
+            DESKTOP_FILE,
+            &format!("x-scheme-handler/{}", SCHEME),
+        ])
+        .status()
+        .map_err(|e| format!("xdg-mime: {}", e))
+        .map(|_| ())
+}
+
+/// macOS reads the scheme from Info.plist; nothing to do at runtime.
+#[cfg(target_os = "macos")]
+fn register_scheme(_app_handle: &tauri::AppHandle, _exe: &std::path::Path) -> Result<(), String> {
+    Ok(())
+}
+
+/// Register `plattera://` for the current user, on a background thread.
+pub fn register(app_handle: &tauri::AppHandle) {
+    if cfg!(debug_assertions) || portable::is_portable() {
+        return;
+    }
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        let result = std::env::current_exe()
+            .map_err(|e| e.to_string())
+            .and_then(|exe| register_scheme(&app_handle, &exe));
+        if let Err(e) = result {
+            log::warn!(
+                "DEEP_LINK ► could not register {}:// handler: {}",
+                SCHEME,
+                e
+            );
+        }
+    });
+}
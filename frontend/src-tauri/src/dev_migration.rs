@@ -0,0 +1,317 @@
+// Migration assistant for users who started on the dev setup. The dev
+// backend keeps its data next to the sources (`backend/dossiers_data`,
+// `plss/`), the installed backend under %LOCALAPPDATA%\Plattera\Data; after
+// switching to the installer those dossiers are simply not found. This finds
+// the dev data, copies it over with the backend stopped, restarts the
+// backend and checks that it lists the imported dossiers.
+
+use crate::backend_lifecycle::shutdown_backend_for_exit;
+use crate::{backend_http, health, paths, portable, settings, spawn_backend, supervisor};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::Emitter;
+
+const MARKER_FILE: &str = "dev_migration.json";
+const PROGRESS_EVERY: usize = 50;
+const READY_TIMEOUT: Duration = Duration::from_secs(90);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DevDataCandidate {
+    /// Repository root of the dev checkout.
+    pub root: String,
+    pub dossiers_path: String,
+    pub plss_path: Option<String>,
+    /// Dossier records found under `management/`.
+    pub dossier_count: usize,
+    pub file_count: usize,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub source: String,
+    pub target: String,
+    pub files_copied: usize,
+    pub files_skipped: usize,
+    pub errors: Vec<String>,
+    pub expected_dossiers: usize,
+    /// Dossiers the restarted backend lists; `None` if it didn't come up.
+    pub backend_dossiers: Option<usize>,
+    pub verified: bool,
+}
+
+#[derive(Clone, Serialize)]
+struct MigrationProgress {
+    phase: &'static str,
+    copied: usize,
+    total: usize,
+    current: Option<String>,
+}
+
+/// Accept either the repository root or its `backend` folder.
+fn resolve_root(path: &Path) -> Option<PathBuf> {
+    if path.join("backend").join("dossiers_data").is_dir() {
+        Some(path.to_path_buf())
+    } else if path.join("dossiers_data").is_dir() {
+        path.parent().map(Path::to_path_buf)
+    } else {
+        None
+    }
+}
+
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(paths::extended(dir)) else {
+        return;
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.is_dir() {
+            collect_files(&path, out);
+        } else if path.is_file() {
+            out.push(path);
+        }
+    }
+}
+
+fn count_dossiers(dossiers: &Path) -> usize {
+    fs::read_dir(dossiers.join("management"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().extension().map(|x| x == "json").unwrap_or(false))
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+fn describe(root: &Path) -> DevDataCandidate {
+    let dossiers = root.join("backend").join("dossiers_data");
+    let plss = root.join("plss");
+    let mut files = Vec::new();
+    collect_files(&dossiers, &mut files);
+    DevDataCandidate {
+        root: root.to_string_lossy().into_owned(),
+        dossiers_path: dossiers.to_string_lossy().into_owned(),
+        plss_path: plss.is_dir().then(|| plss.to_string_lossy().into_owned()),
+        dossier_count: count_dossiers(&dossiers),
+        file_count: files.len(),
+        size_bytes: files
+            .iter()
+            .filter_map(|f| fs::metadata(f).ok())
+            .map(|m| m.len())
+            .sum(),
+    }
+}
+
+/// Look for dev data at `hint`, or in the usual places when no hint is given
+/// (the checkout the shell is running from during `tauri dev`).
+pub fn detect(hint: Option<&Path>) -> Vec<DevDataCandidate> {
+    let mut roots: Vec<PathBuf> = Vec::new();
+    match hint {
+        Some(hint) => roots.extend(resolve_root(hint)),
+        None => {
+            for guess in ["../..", ".."] {
+                if let Some(root) = resolve_root(Path::new(guess)) {
+                    roots.push(root.canonicalize().unwrap_or(root));
+                }
+            }
+        }
+    }
+    roots.dedup();
+    roots.iter().map(|r| describe(r)).collect()
+}
+
+fn copy_tree(
+    app_handle: &tauri::AppHandle,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+    report: &mut MigrationReport,
+) {
+    let mut files = Vec::new();
+    collect_files(source, &mut files);
+    let total = files.len();
+
+    for (i, file) in files.iter().enumerate() {
+        // Deep files come back in `\\?\` form; compare against the plain root.
+        let file = paths::plain(file);
+        let Ok(rel) = file.strip_prefix(source) else {
+            continue;
+        };
+        let dest = target.join(rel);
+        if paths::extended(&dest).exists() && !overwrite {
+            report.files_skipped += 1;
+        } else {
+            let result = dest
+                .parent()
+                .map(|parent| fs::create_dir_all(paths::extended(parent)))
+                .transpose()
+                .and_then(|_| fs::copy(paths::extended(&file), paths::extended(&dest)));
+            match result {
+                Ok(_) => report.files_copied += 1,
+                Err(e) => report.errors.push(format!("{}: {}", rel.display(), e)),
+            }
+        }
+
+        if i % PROGRESS_EVERY == 0 || i + 1 == total {
+            let _ = app_handle.emit(
+                "dev-migration-progress",
+                MigrationProgress {
+                    phase: "copying",
+                    copied: i + 1,
+                    total,
+                    current: Some(rel.to_string_lossy().into_owned()),
+                },
+            );
+        }
+    }
+}
+
+fn backend_dossier_count(app_handle: &tauri::AppHandle) -> Option<usize> {
+    let reply = backend_http::call_blocking(
+        app_handle,
+        reqwest::Method::GET,
+        "/api/dossier-management/list?limit=100000&offset=0",
+        None,
+        Duration::from_secs(30),
+        Some("dev_migration"),
+    )
+    .ok()
+    .filter(|r| r.status.is_success())?;
+    reply.body.get("total_count")?.as_u64().map(|n| n as usize)
+}
+
+/// Copy dev data into the installed layout and verify it. Blocking.
+pub fn import(
+    app_handle: &tauri::AppHandle,
+    dev_root: &Path,
+    include_plss: bool,
+    overwrite: bool,
+) -> Result<MigrationReport, String> {
+    let root = resolve_root(dev_root)
+        .ok_or_else(|| format!("no dev data found at {}", dev_root.display()))?;
+    let candidate = describe(&root);
+    let target = settings::backend_data_dir(app_handle)?;
+    log::info!(
+        "DEV_MIGRATION ► importing {} ({} files) into {:?}",
+        candidate.dossiers_path,
+        candidate.file_count,
+        target
+    );
+
+    let mut report = MigrationReport {
+        source: candidate.root.clone(),
+        target: target.to_string_lossy().into_owned(),
+        files_copied: 0,
+        files_skipped: 0,
+        errors: Vec::new(),
+        expected_dossiers: candidate.dossier_count,
+        backend_dossiers: None,
+        verified: false,
+    };
+
+    // The backend must not write into the target while files land there.
+    shutdown_backend_for_exit(app_handle);
+
+    copy_tree(
+        app_handle,
+        Path::new(&candidate.dossiers_path),
+        &target.join("dossiers_data"),
+        overwrite,
+        &mut report,
+    );
+    if include_plss {
+        if let Some(plss) = &candidate.plss_path {
+            copy_tree(
+                app_handle,
+                Path::new(plss),
+                &target.join("plss"),
+                overwrite,
+                &mut report,
+            );
+        }
+    }
+
+    if let Ok(marker) = portable::resolve(app_handle, MARKER_FILE) {
+        let stamp = serde_json::json!({
+            "source": report.source,
+            "migrated_at": chrono::Local::now().to_rfc3339(),
+            "files_copied": report.files_copied,
+            "files_skipped": report.files_skipped,
+        });
+        if let Some(parent) = marker.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = fs::write(&marker, stamp.to_string());
+    }
+
+    let _ = app_handle.emit(
+        "dev-migration-progress",
+        MigrationProgress {
+            phase: "verifying",
+            copied: report.files_copied,
+            total: candidate.file_count,
+            current: None,
+        },
+    );
+    supervisor::reset(app_handle);
+    spawn_backend(app_handle)?;
+    if health::wait_until_healthy(app_handle, READY_TIMEOUT) {
+        report.backend_dossiers = backend_dossier_count(app_handle);
+    }
+    report.verified = report.errors.is_empty()
+        && report
+            .backend_dossiers
+            .map(|n| n >= report.expected_dossiers)
+            .unwrap_or(false);
+
+    log::info!(
+        "DEV_MIGRATION ► copied={} skipped={} errors={} dossiers expected={} listed={:?}",
+        report.files_copied,
+        report.files_skipped,
+        report.errors.len(),
+        report.expected_dossiers,
+        report.backend_dossiers
+    );
+    let _ = app_handle.emit(
+        "dev-migration-progress",
+        MigrationProgress {
+            phase: if report.verified {
+                "done"
+            } else {
+                "unverified"
+            },
+            copied: report.files_copied,
+            total: candidate.file_count,
+            current: None,
+        },
+    );
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn detect_dev_data(path: Option<String>) -> Result<Vec<DevDataCandidate>, String> {
+    tauri::async_runtime::spawn_blocking(move || detect(path.as_deref().map(Path::new)))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn import_dev_data(
+    app_handle: tauri::AppHandle,
+    path: String,
+    include_plss: Option<bool>,
+    overwrite: Option<bool>,
+) -> Result<MigrationReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        import(
+            &app_handle,
+            Path::new(&path),
+            include_plss.unwrap_or(true),
+            overwrite.unwrap_or(false),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,145 @@
+// Hot reload of the dev Python backend. In `dev-python` mode (see
+// `backend_mode.rs`) the backend source tree is watched and the backend is
+// restarted once edits to `.py` files have settled for `DEBOUNCE`, so backend
+// work doesn't need an app restart. Each reload is emitted as
+// `backend-reloaded` (with whether the new backend came up healthy) so the
+// frontend can re-fetch state. A reload storm, e.g. a formatter rewriting
+// the tree or an edit that keeps crashing the backend, is capped at
+// `MAX_RELOADS` per `RELOAD_WINDOW`; further changes are ignored until the
+// window has passed. In any other mode changes are ignored.
+
+use crate::backend_mode::{self, BackendMode};
+use crate::{backend_lifecycle, backend_state, correlation, startup};
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::{BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Source tree of the dev backend, relative to the shell's working
+/// directory; the Python fallback runs from here.
+pub const DEV_BACKEND_DIR: &str = "../../backend";
+const DEBOUNCE: Duration = Duration::from_millis(750);
+const MAX_RELOADS: usize = 5;
+const RELOAD_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize)]
+struct BackendReloaded {
+    /// Changed files, relative to the backend folder.
+    changed: Vec<String>,
+    ok: bool,
+    error: Option<String>,
+}
+
+fn is_source(path: &Path) -> bool {
+    path.extension().map(|e| e == "py").unwrap_or(false)
+        && !path.components().any(|c| c.as_os_str() == "__pycache__")
+}
+
+fn collect(event: notify::Result<notify::Event>, changed: &mut BTreeSet<PathBuf>) {
+    let Ok(event) = event else {
+        return;
+    };
+    if matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+        changed.extend(event.paths.into_iter().filter(|p| is_source(p)));
+    }
+}
+
+fn reload(app_handle: &tauri::AppHandle, root: &Path, changed: BTreeSet<PathBuf>) {
+    let _op = correlation::begin("reload");
+    let changed: Vec<String> = changed
+        .iter()
+        .map(|p| {
+            p.strip_prefix(root)
+                .unwrap_or(p)
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect();
+    log::info!(
+        "DEV_RELOAD ► {} file(s) changed, restarting backend: {:?}",
+        changed.len(),
+        changed
+    );
+    backend_state::set_starting(app_handle);
+    let result = backend_lifecycle::restart_backend(app_handle)
+        .and_then(|_| tauri::async_runtime::block_on(startup::wait_until_healthy(app_handle)));
+    match &result {
+        Ok(()) => backend_state::set_ready(app_handle),
+        Err(e) => {
+            log::warn!("DEV_RELOAD ► reloaded backend is not healthy: {}", e);
+            backend_state::set_failed(app_handle, e);
+        }
+    }
+    correlation::emit(
+        app_handle,
+        "backend-reloaded",
+        BackendReloaded {
+            changed,
+            ok: result.is_ok(),
+            error: result.err(),
+        },
+    );
+}
+
+/// Watch the dev backend tree on a dedicated thread. Does nothing when the
+/// tree isn't there (installed builds).
+pub fn spawn(app_handle: tauri::AppHandle) {
+    let Ok(root) = Path::new(DEV_BACKEND_DIR).canonicalize() else {
+        return;
+    };
+    thread::spawn(move || {
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(w) => w,
+            Err(e) => {
+                log::warn!("DEV_RELOAD ► watcher unavailable: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = watcher.watch(&root, RecursiveMode::Recursive) {
+            log::warn!("DEV_RELOAD ► cannot watch {:?}: {}", root, e);
+            return;
+        }
+        log::info!("DEV_RELOAD ► watching {:?}", root);
+
+        let mut recent: VecDeque<Instant> = VecDeque::new();
+        let mut changed = BTreeSet::new();
+        while let Ok(event) = rx.recv() {
+            collect(event, &mut changed);
+            // Wait for the edits to settle.
+            while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+                collect(event, &mut changed);
+            }
+            if changed.is_empty() {
+                continue;
+            }
+            let files = std::mem::take(&mut changed);
+            if backend_mode::current(&app_handle) != BackendMode::DevPython {
+                continue;
+            }
+            while recent
+                .front()
+                .map(|t| t.elapsed() > RELOAD_WINDOW)
+                .unwrap_or(false)
+            {
+                recent.pop_front();
+            }
+            if recent.len() >= MAX_RELOADS {
+                log::warn!(
+                    "DEV_RELOAD ► {} reloads in the last {}s; ignoring changes for now",
+                    recent.len(),
+                    RELOAD_WINDOW.as_secs()
+                );
+                continue;
+            }
+            recent.push_back(Instant::now());
+            reload(&app_handle, &root, files);
+        }
+    });
+}
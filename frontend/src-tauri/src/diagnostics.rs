@@ -0,0 +1,221 @@
+// Diagnostics bundle for support tickets. `export_diagnostics` writes one
+// zip with everything a "Plattera doesn't work" report usually needs:
+//
+//   summary.json          app/backend versions, OS, CPU, RAM, updater state
+//   sidecar-tail.txt      last lines of backend stdout/stderr
+//   logs/shell/           shell logs (tauri-plugin-log)
+//   logs/backend/         the backend's own log files
+//   logs/backend_output/  captured sidecar output
+//
+// Large log files are cut to their tail so the bundle stays attachable.
+
+use crate::{backend_log, backend_update, paths, portable, rollback, runtime_info, trace, updates};
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+const SIDECAR_TAIL_LINES: usize = 200;
+/// Per-file cap; longer logs keep their most recent part.
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+struct SystemSummary {
+    os: Option<String>,
+    os_version: Option<String>,
+    kernel_version: Option<String>,
+    arch: &'static str,
+    cpu_brand: Option<String>,
+    cpu_count: usize,
+    total_memory_mb: u64,
+    available_memory_mb: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Summary {
+    created_at: String,
+    app_version: String,
+    backend_version: Option<String>,
+    backend_patch: Option<backend_update::InstalledPatch>,
+    system: SystemSummary,
+    update: updates::UpdateStatus,
+    rollback: Option<rollback::RollbackOffer>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticsExport {
+    pub path: String,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+fn system_summary() -> SystemSummary {
+    let mut sys = sysinfo::System::new();
+    sys.refresh_memory();
+    sys.refresh_cpu();
+    SystemSummary {
+        os: sysinfo::System::name(),
+        os_version: sysinfo::System::long_os_version(),
+        kernel_version: sysinfo::System::kernel_version(),
+        arch: std::env::consts::ARCH,
+        cpu_brand: sys.cpus().first().map(|c| c.brand().trim().to_string()),
+        cpu_count: sys.cpus().len(),
+        total_memory_mb: sys.total_memory() / 1024 / 1024,
+        available_memory_mb: sys.available_memory() / 1024 / 1024,
+    }
+}
+
+/// The last `n` lines across the newest sidecar output files.
+fn sidecar_tail(app_handle: &tauri::AppHandle, n: usize) -> String {
+    let Some(dir) = backend_log::log_dir(app_handle) else {
+        return String::new();
+    };
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_file())
+                .collect()
+        })
+        .unwrap_or_default();
+    // Names embed the creation time; newest last.
+    files.sort();
+    let mut lines: Vec<String> = Vec::new();
+    for file in files.iter().rev() {
+        let Ok(text) = fs::read_to_string(file) else {
+            continue;
+        };
+        let mut chunk: Vec<String> = text.lines().map(str::to_string).collect();
+        chunk.append(&mut lines);
+        lines = chunk;
+        if lines.len() >= n {
+            break;
+        }
+    }
+    let skip = lines.len().saturating_sub(n);
+    lines[skip..].join("\n")
+}
+
+/// Contents of `path`, cut to its last `MAX_LOG_BYTES`.
+fn read_tail(path: &Path) -> std::io::Result<Vec<u8>> {
+    let mut file = File::open(paths::extended(path))?;
+    let len = file.metadata()?.len();
+    let mut data = Vec::new();
+    if len > MAX_LOG_BYTES {
+        file.seek(SeekFrom::Start(len - MAX_LOG_BYTES))?;
+        data.extend_from_slice(b"[... truncated ...]\n");
+    }
+    file.read_to_end(&mut data)?;
+    Ok(data)
+}
+
+struct Bundle {
+    zip: ZipWriter<File>,
+    files: usize,
+}
+
+impl Bundle {
+    fn add(&mut self, name: &str, data: &[u8]) -> Result<(), String> {
+        self.zip
+            .start_file(name, SimpleFileOptions::default())
+            .and_then(|_| self.zip.write_all(data).map_err(Into::into))
+            .map_err(|e| format!("cannot add {}: {}", name, e))?;
+        self.files += 1;
+        Ok(())
+    }
+
+    fn add_dir(&mut self, dir: Option<PathBuf>, prefix: &str) {
+        let Some(dir) = dir else {
+            return;
+        };
+        let Ok(entries) = fs::read_dir(paths::extended(&dir)) else {
+            return;
+        };
+        for entry in entries.flatten().filter(|e| e.path().is_file()) {
+            let name = format!("{}/{}", prefix, entry.file_name().to_string_lossy());
+            match read_tail(&entry.path()) {
+                Ok(data) => {
+                    if let Err(e) = self.add(&name, &data) {
+                        log::warn!("DIAGNOSTICS ► {}", e);
+                    }
+                }
+                Err(e) => log::debug!("DIAGNOSTICS ► skipped {}: {}", name, e),
+            }
+        }
+    }
+}
+
+/// `path` itself, or a timestamped file inside it when it is a folder.
+fn target_path(path: &str) -> Result<PathBuf, String> {
+    let path = paths::plain(Path::new(path.trim()));
+    if !path.is_absolute() {
+        return Err(format!("{} is not an absolute path", path.display()));
+    }
+    if path.is_dir() {
+        return Ok(path.join(format!(
+            "plattera-diagnostics-{}.zip",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        )));
+    }
+    Ok(path)
+}
+
+fn export(app_handle: &tauri::AppHandle, path: &str) -> Result<DiagnosticsExport, String> {
+    let target = target_path(path)?;
+    let summary = Summary {
+        created_at: chrono::Local::now().to_rfc3339(),
+        app_version: app_handle.package_info().version.to_string(),
+        backend_version: runtime_info::probe_backend_version(app_handle),
+        backend_patch: backend_update::installed_patch(app_handle),
+        system: system_summary(),
+        update: updates::status(app_handle),
+        rollback: rollback::offer(app_handle),
+    };
+
+    let file = File::create(paths::extended(&target))
+        .map_err(|e| format!("cannot create {}: {}", target.display(), e))?;
+    let mut bundle = Bundle {
+        zip: ZipWriter::new(file),
+        files: 0,
+    };
+    let json = serde_json::to_vec_pretty(&summary).map_err(|e| e.to_string())?;
+    bundle.add("summary.json", &json)?;
+    bundle.add(
+        "sidecar-tail.txt",
+        sidecar_tail(app_handle, SIDECAR_TAIL_LINES).as_bytes(),
+    )?;
+    bundle.add_dir(portable::app_log_dir(app_handle).ok(), "logs/shell");
+    bundle.add_dir(trace::backend_log_dir(app_handle), "logs/backend");
+    bundle.add_dir(backend_log::log_dir(app_handle), "logs/backend_output");
+    let files = bundle.files;
+    bundle.zip.finish().map_err(|e| e.to_string())?;
+
+    let bytes = fs::metadata(paths::extended(&target))
+        .map(|m| m.len())
+        .unwrap_or(0);
+    log::info!(
+        "DIAGNOSTICS ► wrote {} ({} files, {} bytes)",
+        target.display(),
+        files,
+        bytes
+    );
+    Ok(DiagnosticsExport {
+        path: target.to_string_lossy().into_owned(),
+        files,
+        bytes,
+    })
+}
+
+/// Write a diagnostics zip to `path` (a file, or a folder to put it in).
+#[tauri::command]
+pub async fn export_diagnostics(
+    app_handle: tauri::AppHandle,
+    path: String,
+) -> Result<DiagnosticsExport, String> {
+    tauri::async_runtime::spawn_blocking(move || export(&app_handle, &path))
+        .await
+        .map_err(|e| e.to_string())?
+}
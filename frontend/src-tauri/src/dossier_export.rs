@@ -0,0 +1,219 @@
+// Dossier catalog export for spreadsheets and other tools. Pulls the
+// dossier list from the backend and writes one row per dossier as CSV or
+// JSON. Runs on demand via `export_dossier_index`, or on a schedule kept in
+// settings and driven by the background scheduler (`scheduler.rs`).
+
+use crate::scheduler::Schedule;
+use crate::{backend_http, paths, settings};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const LIST_PATH: &str = "/api/dossier-management/list?limit=100000&offset=0";
+const CSV_COLUMNS: &[&str] = &[
+    "id",
+    "title",
+    "description",
+    "created_at",
+    "updated_at",
+    "segment_count",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// Export run by the scheduler.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledDossierExport {
+    pub format: ExportFormat,
+    /// Target file; overwritten on every run.
+    pub path: String,
+    pub schedule: Schedule,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportSummary {
+    pub path: String,
+    pub format: ExportFormat,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DossierRow {
+    id: String,
+    title: String,
+    description: String,
+    created_at: String,
+    updated_at: String,
+    segment_count: usize,
+}
+
+impl DossierRow {
+    fn from_value(v: &Value) -> Option<Self> {
+        let text = |key: &str| v.get(key).and_then(Value::as_str).unwrap_or("").to_string();
+        Some(DossierRow {
+            id: v.get("id")?.as_str()?.to_string(),
+            title: text("title"),
+            description: text("description"),
+            created_at: text("created_at"),
+            updated_at: text("updated_at"),
+            segment_count: v
+                .get("segments")
+                .and_then(Value::as_array)
+                .map_or(0, Vec::len),
+        })
+    }
+
+    fn fields(&self) -> [String; 6] {
+        [
+            self.id.clone(),
+            self.title.clone(),
+            self.description.clone(),
+            self.created_at.clone(),
+            self.updated_at.clone(),
+            self.segment_count.to_string(),
+        ]
+    }
+}
+
+fn fetch_rows(app_handle: &tauri::AppHandle) -> Result<Vec<DossierRow>, String> {
+    let reply = backend_http::call_blocking(
+        app_handle,
+        reqwest::Method::GET,
+        LIST_PATH,
+        None,
+        Duration::from_secs(60),
+        Some("dossier_export"),
+    )?;
+    if !reply.status.is_success() {
+        return Err(format!("dossier list returned {}", reply.status));
+    }
+    let dossiers = reply
+        .body
+        .get("dossiers")
+        .and_then(Value::as_array)
+        .ok_or("dossier list response has no `dossiers`")?;
+    Ok(dossiers.iter().filter_map(DossierRow::from_value).collect())
+}
+
+/// Quote a CSV field when needed. Values a spreadsheet would evaluate as a
+/// formula get a leading apostrophe.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn render(rows: &[DossierRow], format: ExportFormat) -> Result<String, String> {
+    match format {
+        ExportFormat::Csv => {
+            // BOM so Excel reads non-ASCII titles as UTF-8.
+            let mut out = String::from("\u{feff}");
+            out.push_str(&CSV_COLUMNS.join(","));
+            out.push_str("\r\n");
+            for row in rows {
+                let line: Vec<String> = row.fields().iter().map(|f| csv_field(f)).collect();
+                out.push_str(&line.join(","));
+                out.push_str("\r\n");
+            }
+            Ok(out)
+        }
+        ExportFormat::Json => serde_json::to_string_pretty(&serde_json::json!({
+            "exported_at": chrono::Local::now().to_rfc3339(),
+            "count": rows.len(),
+            "dossiers": rows,
+        }))
+        .map_err(|e| e.to_string()),
+    }
+}
+
+/// Write via a temp file so a reader never sees a half-written export.
+fn write_atomic(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(paths::extended(parent))
+            .map_err(|e| format!("cannot create {}: {}", parent.display(), e))?;
+    }
+    let tmp = path.with_extension("export-tmp");
+    fs::write(paths::extended(&tmp), contents)
+        .map_err(|e| format!("cannot write {}: {}", tmp.display(), e))?;
+    fs::rename(paths::extended(&tmp), paths::extended(path))
+        .map_err(|e| format!("cannot replace {}: {}", path.display(), e))
+}
+
+/// Export the dossier list to `path`. Blocking.
+pub fn export(
+    app_handle: &tauri::AppHandle,
+    format: ExportFormat,
+    path: &str,
+) -> Result<ExportSummary, String> {
+    let path = paths::plain(Path::new(path.trim()));
+    if !path.is_absolute() {
+        return Err(format!("{} is not an absolute path", path.display()));
+    }
+    let rows = fetch_rows(app_handle)?;
+    write_atomic(&path, &render(&rows, format)?)?;
+    log::info!(
+        "DOSSIER_EXPORT ► wrote {} dossier(s) as {:?} to {}",
+        rows.len(),
+        format,
+        path.display()
+    );
+    Ok(ExportSummary {
+        path: path.to_string_lossy().into_owned(),
+        format,
+        count: rows.len(),
+    })
+}
+
+#[tauri::command]
+pub async fn export_dossier_index(
+    app_handle: tauri::AppHandle,
+    format: ExportFormat,
+    path: String,
+) -> Result<ExportSummary, String> {
+    tauri::async_runtime::spawn_blocking(move || export(&app_handle, format, &path))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Set or clear (`None`) the scheduled export.
+#[tauri::command]
+pub async fn schedule_dossier_export(
+    app_handle: tauri::AppHandle,
+    export: Option<ScheduledDossierExport>,
+) -> Result<(), String> {
+    if let Some(export) = &export {
+        if !PathBuf::from(export.path.trim()).is_absolute() {
+            return Err(format!("{} is not an absolute path", export.path));
+        }
+    }
+    match &export {
+        Some(e) => log::info!(
+            "DOSSIER_EXPORT ► scheduled {:?} export to {}",
+            e.format,
+            e.path
+        ),
+        None => log::info!("DOSSIER_EXPORT ► scheduled export cleared"),
+    }
+    settings::update(&app_handle, |s| s.dossier_export = export)?;
+    Ok(())
+}
@@ -0,0 +1,81 @@
+// Dossier windows: a dossier opened in a window of its own, beside the main
+// window. Each gets the label `dossier-<id>` (opening the same dossier again
+// focuses the existing window) and loads the process page with the dossier
+// preselected. The windows take part in the close guard like the main
+// window: closing one asks only that window about unsaved changes, closing
+// the main window asks all of them before the app exits.
+
+use crate::{i18n, zoom};
+use serde::Serialize;
+use tauri::{Manager, WebviewUrl, WebviewWindowBuilder};
+
+pub const LABEL_PREFIX: &str = "dossier-";
+const MAX_ID_LEN: usize = 128;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DossierWindow {
+    pub label: String,
+    pub dossier_id: String,
+    /// False when an existing window was focused instead.
+    pub created: bool,
+}
+
+pub fn is_dossier_window(label: &str) -> bool {
+    label.starts_with(LABEL_PREFIX)
+}
+
+/// Dossier ids end up in a window label and a URL; only accept the
+/// characters both allow without escaping.
+fn validate_id(app_handle: &tauri::AppHandle, id: &str) -> Result<(), String> {
+    let ok = !id.is_empty()
+        && id.len() <= MAX_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if ok {
+        Ok(())
+    } else {
+        Err(i18n::tf(
+            app_handle,
+            "error.invalid_dossier_id",
+            &[("id", id)],
+        ))
+    }
+}
+
+/// Open `dossier_id` in its own window, or focus the window already
+/// showing it.
+#[tauri::command]
+pub async fn open_dossier_window(
+    app_handle: tauri::AppHandle,
+    dossier_id: String,
+) -> Result<DossierWindow, String> {
+    let dossier_id = dossier_id.trim().to_string();
+    validate_id(&app_handle, &dossier_id)?;
+    let label = format!("{}{}", LABEL_PREFIX, dossier_id);
+    if let Some(window) = app_handle.get_webview_window(&label) {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(DossierWindow {
+            label,
+            dossier_id,
+            created: false,
+        });
+    }
+    let url = format!("process?dossierId={}", dossier_id);
+    let window = WebviewWindowBuilder::new(&app_handle, &label, WebviewUrl::App(url.into()))
+        .title(format!("Plattera - Dossier {}", dossier_id))
+        .inner_size(1200.0, 800.0)
+        .min_inner_size(800.0, 600.0)
+        .resizable(true)
+        .build()
+        .map_err(|e| format!("could not open dossier window: {}", e))?;
+    zoom::restore(&window);
+    log::info!("WINDOWS ► opened '{}'", label);
+    Ok(DossierWindow {
+        label,
+        dossier_id,
+        created: true,
+    })
+}
@@ -0,0 +1,329 @@
+// Streaming downloads of files the backend generates (exports, reports).
+// `start_download` asks for a save location with the native save dialog and
+// streams the backend's response straight to disk, so a large export never
+// passes through webview memory. The file is written next to its target as
+// `<name>.part` and only renamed into place once it is complete and, when
+// the backend sends an `X-Content-SHA256` header, once its SHA-256 matches.
+// Progress goes out as `download-progress` (at most every
+// `PROGRESS_INTERVAL`, plus every phase change, including the final
+// completed / cancelled / failed). A paused download keeps its connection
+// open and simply stops reading; a cancelled or failed one removes its
+// partial file.
+
+use crate::{backend_http, correlation};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::Manager;
+use tauri_plugin_dialog::DialogExt;
+use tokio::io::AsyncWriteExt;
+
+/// Minimum spacing between progress events.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+const PAUSE_POLL: Duration = Duration::from_millis(200);
+/// Hex SHA-256 of the body, when the backend provides one.
+const CHECKSUM_HEADER: &str = "x-content-sha256";
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadPhase {
+    Downloading,
+    Paused,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadStatus {
+    pub id: String,
+    /// Where the file ends up.
+    pub path: String,
+    pub phase: DownloadPhase,
+    pub received: u64,
+    pub total: Option<u64>,
+    pub percent: Option<u8>,
+    /// Whether the file matched the backend's checksum; `None` until it is
+    /// complete, or when the backend sent none.
+    pub verified: Option<bool>,
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+struct Control {
+    paused: AtomicBool,
+    cancelled: AtomicBool,
+}
+
+impl Control {
+    /// Paused and not cancelled since.
+    fn held(&self) -> bool {
+        self.paused.load(Ordering::SeqCst) && !self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Controls of the downloads in flight, by ID.
+pub struct Downloads(Mutex<HashMap<String, Arc<Control>>>);
+
+impl Downloads {
+    pub fn new() -> Self {
+        Downloads(Mutex::new(HashMap::new()))
+    }
+}
+
+struct Transfer {
+    app_handle: tauri::AppHandle,
+    id: String,
+    dest: PathBuf,
+    total: Option<u64>,
+    received: u64,
+    op: Option<String>,
+}
+
+impl Transfer {
+    fn emit(&self, phase: DownloadPhase, verified: Option<bool>, error: Option<String>) {
+        let _op = correlation::adopt(self.op.clone());
+        correlation::emit(
+            &self.app_handle,
+            "download-progress",
+            DownloadStatus {
+                id: self.id.clone(),
+                path: self.dest.to_string_lossy().into_owned(),
+                phase,
+                received: self.received,
+                total: self.total,
+                percent: self
+                    .total
+                    .filter(|t| *t > 0)
+                    .map(|t| ((self.received * 100 / t).min(100)) as u8),
+                verified,
+                error,
+            },
+        );
+    }
+}
+
+fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+/// File name offered in the save dialog: the last segment of the path.
+fn default_name(path: &str) -> String {
+    let path = path.split('?').next().unwrap_or(path).trim_end_matches('/');
+    match path.rsplit('/').next() {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => "download".to_string(),
+    }
+}
+
+/// Stream the body into the part file and move it into place. Returns
+/// whether a checksum was verified.
+async fn stream(
+    transfer: &mut Transfer,
+    mut res: reqwest::Response,
+    control: &Control,
+) -> Result<Option<bool>, String> {
+    let expected = res
+        .headers()
+        .get(CHECKSUM_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_ascii_lowercase());
+    let part = part_path(&transfer.dest);
+    let mut file = tokio::fs::File::create(&part)
+        .await
+        .map_err(|e| format!("cannot create {:?}: {}", part, e))?;
+    let mut hasher = Sha256::new();
+    let mut last_emit = Instant::now();
+
+    loop {
+        if control.held() {
+            transfer.emit(DownloadPhase::Paused, None, None);
+            while control.held() {
+                tokio::time::sleep(PAUSE_POLL).await;
+            }
+            transfer.emit(DownloadPhase::Downloading, None, None);
+        }
+        if control.cancelled.load(Ordering::SeqCst) {
+            return Err("cancelled".to_string());
+        }
+        let Some(chunk) = res.chunk().await.map_err(|e| e.to_string())? else {
+            break;
+        };
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("cannot write {:?}: {}", part, e))?;
+        hasher.update(&chunk);
+        transfer.received += chunk.len() as u64;
+        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+            last_emit = Instant::now();
+            transfer.emit(DownloadPhase::Downloading, None, None);
+        }
+    }
+    file.flush().await.map_err(|e| e.to_string())?;
+    drop(file);
+
+    let verified = match expected {
+        Some(expected) => {
+            let actual: String = hasher
+                .finalize()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            if actual != expected {
+                return Err(format!(
+                    "checksum mismatch: expected {}, got {}",
+                    expected, actual
+                ));
+            }
+            Some(true)
+        }
+        None => None,
+    };
+    tokio::fs::rename(&part, &transfer.dest)
+        .await
+        .map_err(|e| format!("cannot move download into place: {}", e))?;
+    Ok(verified)
+}
+
+async fn run(mut transfer: Transfer, res: reqwest::Response, control: Arc<Control>) {
+    let started = Instant::now();
+    let result = stream(&mut transfer, res, &control).await;
+    transfer
+        .app_handle
+        .state::<Downloads>()
+        .0
+        .lock()
+        .unwrap()
+        .remove(&transfer.id);
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(part_path(&transfer.dest)).await;
+    }
+
+    let _op = correlation::adopt(transfer.op.clone());
+    match result {
+        Ok(verified) => {
+            log::info!(
+                "DOWNLOADS ► {} saved to {:?} ({} bytes in {:?})",
+                transfer.id,
+                transfer.dest,
+                transfer.received,
+                started.elapsed()
+            );
+            transfer.emit(DownloadPhase::Completed, verified, None);
+        }
+        Err(e) => {
+            if control.cancelled.load(Ordering::SeqCst) {
+                log::info!("DOWNLOADS ► {} cancelled", transfer.id);
+                transfer.emit(DownloadPhase::Cancelled, None, None);
+            } else {
+                log::warn!("DOWNLOADS ► {} failed: {}", transfer.id, e);
+                transfer.emit(DownloadPhase::Failed, None, Some(e));
+            }
+        }
+    }
+}
+
+fn control(app_handle: &tauri::AppHandle, id: &str) -> Result<Arc<Control>, String> {
+    app_handle
+        .state::<Downloads>()
+        .0
+        .lock()
+        .unwrap()
+        .get(id)
+        .cloned()
+        .ok_or_else(|| format!("no download {}", id))
+}
+
+/// Download the file at backend `path` to a location the user picks.
+/// Returns `None` when the save dialog is dismissed; otherwise the download
+/// continues in the background and reports through `download-progress`.
+#[tauri::command]
+pub async fn start_download(
+    app_handle: tauri::AppHandle,
+    path: String,
+    file_name: Option<String>,
+) -> Result<Option<DownloadStatus>, String> {
+    if !path.starts_with('/') {
+        return Err(format!("'{}' is not a backend path", path));
+    }
+    let name = file_name.unwrap_or_else(|| default_name(&path));
+    let dialog_handle = app_handle.clone();
+    let picked = tauri::async_runtime::spawn_blocking(move || {
+        dialog_handle
+            .dialog()
+            .file()
+            .set_file_name(name)
+            .blocking_save_file()
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some(picked) = picked else {
+        return Ok(None);
+    };
+    let dest = picked.into_path().map_err(|e| e.to_string())?;
+
+    let res = backend_http::open_download(&app_handle, &path).await?;
+    let id = format!("download-{}", NEXT_ID.fetch_add(1, Ordering::SeqCst));
+    log::info!("DOWNLOADS ► {} started: {} -> {:?}", id, path, dest);
+    let control = Arc::new(Control::default());
+    app_handle
+        .state::<Downloads>()
+        .0
+        .lock()
+        .unwrap()
+        .insert(id.clone(), control.clone());
+
+    let transfer = Transfer {
+        app_handle: app_handle.clone(),
+        id: id.clone(),
+        dest: dest.clone(),
+        total: res.content_length(),
+        received: 0,
+        op: Some(correlation::new_id("download")),
+    };
+    let status = DownloadStatus {
+        id,
+        path: dest.to_string_lossy().into_owned(),
+        phase: DownloadPhase::Downloading,
+        received: 0,
+        total: transfer.total,
+        percent: None,
+        verified: None,
+        error: None,
+    };
+    tauri::async_runtime::spawn(run(transfer, res, control));
+    Ok(Some(status))
+}
+
+#[tauri::command]
+pub async fn pause_download(app_handle: tauri::AppHandle, id: String) -> Result<(), String> {
+    control(&app_handle, &id)?
+        .paused
+        .store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn resume_download(app_handle: tauri::AppHandle, id: String) -> Result<(), String> {
+    control(&app_handle, &id)?
+        .paused
+        .store(false, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Stop a download and remove its partial file.
+#[tauri::command]
+pub async fn cancel_download(app_handle: tauri::AppHandle, id: String) -> Result<(), String> {
+    control(&app_handle, &id)?
+        .cancelled
+        .store(true, Ordering::SeqCst);
+    Ok(())
+}
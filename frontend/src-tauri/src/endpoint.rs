@@ -0,0 +1,256 @@
+// Where the backend is listening. The port is allocated at spawn time and
+// held in managed state rather than hardcoded, so another app owning 8000
+// doesn't break startup and a warm-standby restart can move the backend;
+// every shell-side HTTP call builds its URL through here. With the
+// `local_socket` transport the backend listens on a Unix domain socket or
+// Windows named pipe instead of a port, and the shell's client follows it
+// (see `backend_http::client`). In `external` backend mode (see
+// `backend_mode.rs`) the host is whatever the user configured instead of
+// loopback. Ports this run spawned a backend on are remembered, so the auth
+// token only ever goes to our own child and never to a listener we merely
+// found or attached to.
+
+use crate::{backend_http, correlation, settings};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicU16, AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::Manager;
+
+/// Host of a backend the shell spawned itself.
+pub const LOOPBACK_HOST: &str = "127.0.0.1";
+/// Default preferred port. The webview asks for the actual address
+/// (`get_backend_url`) and follows `backend-endpoint-changed`.
+pub const DEFAULT_PORT: u16 = 8000;
+/// Environment variable the backend reads to choose its listen port.
+pub const PORT_ENV: &str = "PLATTERA_BACKEND_PORT";
+/// Environment variable the backend reads to choose its listen address.
+pub const HOST_ENV: &str = "PLATTERA_BACKEND_HOST";
+/// Environment variable that makes the backend listen on a Unix socket /
+/// named pipe instead of a TCP port.
+pub const SOCKET_ENV: &str = "PLATTERA_BACKEND_SOCKET";
+
+/// How the shell reaches the backend it spawns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendTransport {
+    #[default]
+    Tcp,
+    /// Unix domain socket (Linux/macOS) or named pipe (Windows). The
+    /// webview's own direct `fetch` calls still expect TCP, so this only
+    /// suits builds whose frontend goes through `backend_request`.
+    LocalSocket,
+}
+
+static SOCKET_SEQ: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Clone, Serialize)]
+struct EndpointChanged {
+    port: u16,
+    base_url: String,
+}
+
+pub struct BackendEndpoint {
+    host: Mutex<String>,
+    port: AtomicU16,
+    socket: Mutex<Option<String>>,
+    /// TCP ports a backend child of this run listens on.
+    spawned: Mutex<HashSet<u16>>,
+}
+
+impl BackendEndpoint {
+    pub fn new(port: u16) -> Self {
+        BackendEndpoint {
+            host: Mutex::new(LOOPBACK_HOST.to_string()),
+            port: AtomicU16::new(port),
+            socket: Mutex::new(None),
+            spawned: Mutex::new(HashSet::new()),
+        }
+    }
+}
+
+pub fn host(app_handle: &tauri::AppHandle) -> String {
+    app_handle
+        .state::<BackendEndpoint>()
+        .host
+        .lock()
+        .unwrap()
+        .clone()
+}
+
+pub fn port(app_handle: &tauri::AppHandle) -> u16 {
+    app_handle
+        .state::<BackendEndpoint>()
+        .port
+        .load(Ordering::SeqCst)
+}
+
+/// Point backend calls at `host:port`. A change is announced to the webview
+/// as `backend-endpoint-changed`, which moves its requests along.
+pub fn set_address(app_handle: &tauri::AppHandle, host: &str, port: u16) {
+    let state = app_handle.state::<BackendEndpoint>();
+    let old_host = std::mem::replace(&mut *state.host.lock().unwrap(), host.to_string());
+    let old_port = state.port.swap(port, Ordering::SeqCst);
+    if old_host == host && old_port == port {
+        return;
+    }
+    let base_url = url(app_handle, "");
+    log::info!("ENDPOINT ► backend address is now {}", base_url);
+    correlation::emit(
+        app_handle,
+        "backend-endpoint-changed",
+        EndpointChanged { port, base_url },
+    );
+}
+
+/// Local socket / pipe the backend listens on, when not using TCP.
+pub fn socket(app_handle: &tauri::AppHandle) -> Option<String> {
+    app_handle
+        .state::<BackendEndpoint>()
+        .socket
+        .lock()
+        .unwrap()
+        .clone()
+}
+
+pub fn set_socket(app_handle: &tauri::AppHandle, socket: Option<String>) {
+    *app_handle.state::<BackendEndpoint>().socket.lock().unwrap() = socket;
+}
+
+/// Record that a backend child of this run listens on `port`.
+pub fn mark_spawned(app_handle: &tauri::AppHandle, port: u16) {
+    app_handle
+        .state::<BackendEndpoint>()
+        .spawned
+        .lock()
+        .unwrap()
+        .insert(port);
+}
+
+/// The child on `port` is gone; whatever listens there next is unknown.
+pub fn forget_spawned(app_handle: &tauri::AppHandle, port: u16) {
+    app_handle
+        .state::<BackendEndpoint>()
+        .spawned
+        .lock()
+        .unwrap()
+        .remove(&port);
+}
+
+/// Whether the listener on loopback `port` is a child this run spawned.
+pub fn is_spawned(app_handle: &tauri::AppHandle, port: u16) -> bool {
+    app_handle
+        .state::<BackendEndpoint>()
+        .spawned
+        .lock()
+        .unwrap()
+        .contains(&port)
+}
+
+/// Whether the active backend is a child this run spawned, as opposed to an
+/// `external` one or one found already running and attached to. A local
+/// socket is always ours: its name is made fresh for each spawn.
+pub fn active_is_spawned(app_handle: &tauri::AppHandle) -> bool {
+    if socket(app_handle).is_some() {
+        return true;
+    }
+    host(app_handle) == LOOPBACK_HOST && is_spawned(app_handle, port(app_handle))
+}
+
+/// A fresh socket path (Unix) or pipe name (Windows) for the next spawn.
+pub fn new_socket_name() -> String {
+    let seq = SOCKET_SEQ.fetch_add(1, Ordering::SeqCst);
+    let name = format!("plattera-backend-{}-{}", std::process::id(), seq);
+    if cfg!(windows) {
+        format!(r"\\.\pipe\{}", name)
+    } else {
+        std::env::temp_dir()
+            .join(format!("{}.sock", name))
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+pub fn base_url_for(port: u16) -> String {
+    format!("http://{}:{}", LOOPBACK_HOST, port)
+}
+
+/// Absolute URL for a backend path such as `/api/health`.
+pub fn url(app_handle: &tauri::AppHandle, path: &str) -> String {
+    let host = host(app_handle);
+    // Bare IPv6 literals need brackets in a URL.
+    let host = if host.contains(':') && !host.starts_with('[') {
+        format!("[{}]", host)
+    } else {
+        host
+    };
+    format!("http://{}:{}{}", host, port(app_handle), path)
+}
+
+/// Ask the OS for a free loopback port.
+pub fn free_loopback_port() -> Result<u16, String> {
+    let listener =
+        TcpListener::bind(("127.0.0.1", 0)).map_err(|e| format!("no free port: {}", e))?;
+    listener
+        .local_addr()
+        .map(|a| a.port())
+        .map_err(|e| e.to_string())
+}
+
+/// Port tried first for a new backend (`lifecycle.preferred_port`).
+pub fn preferred_port(app_handle: &tauri::AppHandle) -> u16 {
+    settings::current(app_handle).lifecycle.preferred_port
+}
+
+/// Pick the port for a new backend: `preferred` when it is free, otherwise
+/// any free loopback port.
+pub fn allocate_port(preferred: u16) -> Result<u16, String> {
+    if TcpListener::bind(("127.0.0.1", preferred)).is_ok() {
+        return Ok(preferred);
+    }
+    let port = free_loopback_port()?;
+    log::info!(
+        "ENDPOINT ► port {} is taken; allocated {} for the backend",
+        preferred,
+        port
+    );
+    Ok(port)
+}
+
+/// Whether whatever listens on `port` is a Plattera backend this shell can
+/// use (e.g. one started by hand in a dev shell) rather than an unrelated app.
+/// A backend that requires a token was spawned by another shell run and would
+/// reject this run's token, so it doesn't count.
+pub fn is_plattera_backend(app_handle: &tauri::AppHandle, port: u16) -> bool {
+    backend_http::probe_port_blocking(app_handle, port, "/", Duration::from_millis(1_500))
+        .ok()
+        .filter(|reply| reply.status.is_success())
+        .map(|reply| {
+            let v = reply.body;
+            let is_plattera = v
+                .get("message")
+                .and_then(|m| m.as_str())
+                .map(|m| m.starts_with("Plattera API"))
+                .unwrap_or(false);
+            let auth_required = v
+                .get("auth_required")
+                .and_then(|a| a.as_bool())
+                .unwrap_or(false);
+            is_plattera && !auth_required
+        })
+        .unwrap_or(false)
+}
+
+/// Port the backend is (or will be) listening on.
+#[tauri::command]
+pub async fn get_backend_port(app_handle: tauri::AppHandle) -> Result<u16, String> {
+    Ok(port(&app_handle))
+}
+
+/// Base URL the webview addresses the backend at, e.g. `http://127.0.0.1:8000`.
+#[tauri::command]
+pub async fn get_backend_url(app_handle: tauri::AppHandle) -> Result<String, String> {
+    Ok(url(&app_handle, ""))
+}
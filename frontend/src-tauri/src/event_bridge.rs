@@ -0,0 +1,130 @@
+// Backend → webview event bridge. The shell holds one connection to the
+// backend's server-sent event stream and re-emits every message as a
+// `backend-event` Tauri event, so dossier updates and job progress keep
+// reaching the frontend across page reloads, when no connection held by
+// JS survives. The bridge connects once the backend is ready, reconnects
+// with exponential backoff when the stream drops (a restart, a warm-standby
+// swap), and reports whether it is connected as `backend-event-bridge`.
+
+use crate::backend_state::{self, BackendState};
+use crate::{backend_http, correlation};
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Duration;
+
+const STREAM_PATH: &str = "/api/dossier/events";
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const READY_POLL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Serialize)]
+struct BridgedEvent {
+    /// SSE event name; `message` when the stream didn't name it.
+    event: String,
+    id: Option<String>,
+    /// Parsed JSON, or the raw text when the data isn't JSON.
+    data: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct BridgeStatus {
+    connected: bool,
+    error: Option<String>,
+}
+
+/// Parse one SSE block (the lines between blank lines). Comments and
+/// blocks without data are skipped.
+fn parse_event(block: &str) -> Option<BridgedEvent> {
+    let mut event = None;
+    let mut id = None;
+    let mut data: Vec<&str> = Vec::new();
+    for line in block.lines() {
+        let (field, value) = match line.split_once(':') {
+            Some((f, v)) => (f, v.strip_prefix(' ').unwrap_or(v)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => event = Some(value.to_string()),
+            "id" => id = Some(value.to_string()),
+            "data" => data.push(value),
+            _ => {}
+        }
+    }
+    if data.is_empty() {
+        return None;
+    }
+    let data = data.join("\n");
+    Some(BridgedEvent {
+        event: event.unwrap_or_else(|| "message".to_string()),
+        id,
+        data: serde_json::from_str(&data).unwrap_or(Value::String(data)),
+    })
+}
+
+/// Relay one connection until the stream ends. A connection that was
+/// established resets `backoff`.
+async fn relay(app_handle: &tauri::AppHandle, backoff: &mut Duration) -> Result<(), String> {
+    let mut res = backend_http::open_stream(app_handle, STREAM_PATH).await?;
+    *backoff = INITIAL_BACKOFF;
+    log::info!("EVENT_BRIDGE ► connected to {}", STREAM_PATH);
+    correlation::emit(
+        app_handle,
+        "backend-event-bridge",
+        BridgeStatus {
+            connected: true,
+            error: None,
+        },
+    );
+    // Bytes until a complete block is in, so a character split across
+    // chunks isn't mangled.
+    let mut buf: Vec<u8> = Vec::new();
+    while let Some(chunk) = res.chunk().await.map_err(|e| e.to_string())? {
+        buf.extend(chunk.iter().filter(|b| **b != b'\r'));
+        while let Some(end) = buf.windows(2).position(|w| w == b"\n\n") {
+            let block: Vec<u8> = buf.drain(..end + 2).collect();
+            if let Some(event) = parse_event(&String::from_utf8_lossy(&block)) {
+                correlation::emit(app_handle, "backend-event", event);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn backend_up(app_handle: &tauri::AppHandle) -> bool {
+    matches!(
+        backend_state::current(app_handle),
+        BackendState::Ready { .. } | BackendState::Degraded { .. }
+    )
+}
+
+/// Run the bridge for the rest of the session.
+pub fn spawn(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut backoff = INITIAL_BACKOFF;
+        loop {
+            if !backend_up(&app_handle) {
+                tokio::time::sleep(READY_POLL).await;
+                continue;
+            }
+            let error = match relay(&app_handle, &mut backoff).await {
+                Ok(()) => "stream ended".to_string(),
+                Err(e) => e,
+            };
+            log::warn!(
+                "EVENT_BRIDGE ► disconnected ({}); retrying in {}s",
+                error,
+                backoff.as_secs()
+            );
+            correlation::emit(
+                &app_handle,
+                "backend-event-bridge",
+                BridgeStatus {
+                    connected: false,
+                    error: Some(error),
+                },
+            );
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    });
+}
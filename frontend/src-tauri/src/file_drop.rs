@@ -0,0 +1,236 @@
+// Files dropped on a window from the OS. The drop is handled here rather
+// than in the webview so large scans never travel through it as base64:
+// each file is checked (extension, size), copied into a quarantine folder
+// under the cache, and the window that received the drop gets a
+// `files-dropped` event listing the quarantined copies and what was
+// rejected. `import_dropped_files` then hands copies to the backend's import
+// endpoint; it only accepts paths inside the quarantine folder, so the
+// frontend can't use it to feed arbitrary files to the backend.
+//
+// Each drop is copied into its own batch folder. Imported copies are deleted;
+// batches left behind are removed once they are a day old. Images pasted
+// from the clipboard (`clipboard.rs`) land in a batch of their own and are
+// imported the same way.
+
+use crate::{file_import, portable, trace};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::{Emitter, Manager, Window};
+
+const QUARANTINE_DIR: &str = "dropped";
+const ALLOWED_EXTENSIONS: &[&str] = &[
+    file_import::EXTENSION,
+    "png",
+    "jpg",
+    "jpeg",
+    "tif",
+    "tiff",
+    "pdf",
+];
+const MAX_FILE_BYTES: u64 = 1024 * 1024 * 1024;
+const MAX_FILES: usize = 50;
+const STALE_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DroppedFile {
+    pub name: String,
+    /// The quarantined copy.
+    pub path: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RejectedFile {
+    pub path: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FilesDropped {
+    pub batch: String,
+    pub files: Vec<DroppedFile>,
+    pub rejected: Vec<RejectedFile>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DroppedImport {
+    pub path: String,
+    pub ok: bool,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+}
+
+fn quarantine_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    portable::app_cache_dir(app_handle)
+        .map(|d| d.join(QUARANTINE_DIR))
+        .map_err(|e| e.to_string())
+}
+
+/// Remove batch folders older than `STALE_AFTER`.
+fn prune(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let age = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| SystemTime::now().duration_since(t).ok());
+        if age.is_some_and(|a| a > STALE_AFTER) {
+            let _ = fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+/// Why `path` may not be imported, if it may not.
+fn check(path: &Path) -> Option<String> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    if !ALLOWED_EXTENSIONS.contains(&ext.as_str()) {
+        return Some(format!("unsupported file type '.{}'", ext));
+    }
+    match fs::metadata(path) {
+        Ok(meta) if !meta.is_file() => Some("not a file".to_string()),
+        Ok(meta) if meta.len() > MAX_FILE_BYTES => {
+            Some(format!("larger than {} MB", MAX_FILE_BYTES / (1024 * 1024)))
+        }
+        Ok(_) => None,
+        Err(e) => Some(e.to_string()),
+    }
+}
+
+/// Create a fresh batch folder, pruning stale ones first. Returns the batch
+/// id and its folder.
+pub fn new_batch(app_handle: &tauri::AppHandle) -> Result<(String, PathBuf), String> {
+    let root = quarantine_dir(app_handle)?;
+    prune(&root);
+    let batch = trace::new_id();
+    let dir = root.join(&batch);
+    fs::create_dir_all(&dir).map_err(|e| format!("create {:?}: {}", dir, e))?;
+    Ok((batch, dir))
+}
+
+fn quarantine(app_handle: &tauri::AppHandle, paths: Vec<PathBuf>) -> Result<FilesDropped, String> {
+    let (batch, dir) = new_batch(app_handle)?;
+
+    let mut files = Vec::new();
+    let mut rejected = Vec::new();
+    for (i, path) in paths.into_iter().enumerate() {
+        let reject = |reason: String| RejectedFile {
+            path: path.to_string_lossy().into_owned(),
+            reason,
+        };
+        if i >= MAX_FILES {
+            rejected.push(reject(format!("more than {} files dropped", MAX_FILES)));
+            continue;
+        }
+        if let Some(reason) = check(&path) {
+            rejected.push(reject(reason));
+            continue;
+        }
+        let Some(name) = path.file_name() else {
+            rejected.push(reject("no file name".to_string()));
+            continue;
+        };
+        // Two dropped files may share a name; keep both.
+        let mut target = dir.join(name);
+        if target.exists() {
+            target = dir.join(format!("{}-{}", i, name.to_string_lossy()));
+        }
+        match fs::copy(&path, &target) {
+            Ok(size) => files.push(DroppedFile {
+                name: name.to_string_lossy().into_owned(),
+                path: target.to_string_lossy().into_owned(),
+                size,
+            }),
+            Err(e) => rejected.push(reject(format!("copy failed: {}", e))),
+        }
+    }
+    Ok(FilesDropped {
+        batch,
+        files,
+        rejected,
+    })
+}
+
+/// Quarantine the files dropped on `window` and tell it what arrived.
+/// Called from the window event handler; copying runs on its own thread.
+pub fn on_drop(window: &Window, paths: Vec<PathBuf>) {
+    if paths.is_empty() {
+        return;
+    }
+    let app_handle = window.app_handle().clone();
+    let label = window.label().to_string();
+    std::thread::spawn(move || match quarantine(&app_handle, paths) {
+        Ok(dropped) => {
+            log::info!(
+                "FILE_DROP ► '{}': {} quarantined, {} rejected",
+                label,
+                dropped.files.len(),
+                dropped.rejected.len()
+            );
+            let _ = app_handle.emit_to(label.as_str(), "files-dropped", &dropped);
+        }
+        Err(e) => log::warn!("FILE_DROP ► could not quarantine drop: {}", e),
+    });
+}
+
+/// `path` if it is a file inside the quarantine folder.
+fn quarantined(app_handle: &tauri::AppHandle, path: &str) -> Result<PathBuf, String> {
+    let root = quarantine_dir(app_handle)?
+        .canonicalize()
+        .map_err(|e| e.to_string())?;
+    let path = PathBuf::from(path)
+        .canonicalize()
+        .map_err(|e| format!("cannot open {}: {}", path, e))?;
+    if !path.starts_with(&root) || !path.is_file() {
+        return Err(format!("not a dropped file: {}", path.display()));
+    }
+    Ok(path)
+}
+
+/// Import quarantined copies from `files-dropped` through the backend.
+/// Copies are deleted once imported.
+#[tauri::command]
+pub async fn import_dropped_files(
+    app_handle: tauri::AppHandle,
+    paths: Vec<String>,
+) -> Result<Vec<DroppedImport>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        paths
+            .into_iter()
+            .map(|raw| {
+                let result = quarantined(&app_handle, &raw).and_then(|path| {
+                    let imported = file_import::forward(&app_handle, &path)?;
+                    let _ = fs::remove_file(&path);
+                    Ok(imported)
+                });
+                match result {
+                    Ok(imported) => DroppedImport {
+                        path: raw,
+                        ok: true,
+                        result: Some(imported.result),
+                        error: None,
+                    },
+                    Err(e) => {
+                        log::warn!("FILE_DROP ► {}", e);
+                        DroppedImport {
+                            path: raw,
+                            ok: false,
+                            result: None,
+                            error: Some(e),
+                        }
+                    }
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,157 @@
+// `.plattera` export files. The installers register the extension (see
+// `fileAssociations` in tauri.conf.json), so double-clicking an export starts
+// the app with the file as an argument, or hands it to the running instance
+// through the single-instance plugin; macOS delivers it as an `Opened` run
+// event instead. Opened files are imported right away.
+//
+// `import_file` forwards a file to the backend's import endpoint. The backend
+// may still be booting when the app was launched by a double-click, so the
+// import waits for the health definition to pass first. Results are also
+// emitted as `file-imported` / `file-import-failed` for imports the frontend
+// did not start itself.
+
+use crate::{backend_http, health, i18n};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+pub const EXTENSION: &str = "plattera";
+const IMPORT_PATH: &str = "/api/dossier-management/import";
+/// How long an import waits for a booting backend.
+const READY_TIMEOUT: Duration = Duration::from_secs(90);
+const IMPORT_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportResult {
+    pub path: String,
+    /// The backend's reply.
+    pub result: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ImportFailed {
+    path: String,
+    error: String,
+}
+
+fn has_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(EXTENSION))
+}
+
+/// `.plattera` files among command-line arguments. Relative paths are taken
+/// from `cwd`, which for a forwarded launch is the second instance's.
+pub fn from_args<S: AsRef<str>>(args: &[S], cwd: &Path) -> Vec<PathBuf> {
+    args.iter()
+        .map(|a| cwd.join(a.as_ref()))
+        .filter(|p| has_extension(p) && p.is_file())
+        .collect()
+}
+
+fn validate(app_handle: &tauri::AppHandle, path: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(path.trim());
+    if !has_extension(&path) {
+        let display = path.display().to_string();
+        return Err(i18n::tf(
+            app_handle,
+            "error.not_plattera_file",
+            &[("path", &display)],
+        ));
+    }
+    let path = path
+        .canonicalize()
+        .map_err(|e| format!("cannot open {}: {}", path.display(), e))?;
+    if !path.is_file() {
+        return Err(format!("not a file: {}", path.display()));
+    }
+    Ok(path)
+}
+
+fn import(app_handle: &tauri::AppHandle, path: &str) -> Result<ImportResult, String> {
+    forward(app_handle, &validate(app_handle, path)?)
+}
+
+/// Hand `path` to the backend's import endpoint once the backend is healthy.
+/// The caller has vetted the file.
+pub fn forward(app_handle: &tauri::AppHandle, path: &Path) -> Result<ImportResult, String> {
+    if !health::wait_until_healthy(app_handle, READY_TIMEOUT) {
+        return Err(i18n::t(app_handle, "error.backend_not_ready_for_import"));
+    }
+    let display = path.to_string_lossy().into_owned();
+    log::info!("FILE_IMPORT ► importing {}", display);
+    let reply = backend_http::call_blocking(
+        app_handle,
+        reqwest::Method::POST,
+        IMPORT_PATH,
+        Some(&json!({ "path": display })),
+        IMPORT_TIMEOUT,
+        Some("file_import"),
+    )?;
+    if !reply.status.is_success() {
+        let detail = reply
+            .body
+            .get("detail")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| reply.status.to_string());
+        return Err(format!("import of {} failed: {}", display, detail));
+    }
+    Ok(ImportResult {
+        path: display,
+        result: reply.body,
+    })
+}
+
+/// Import files the OS opened with the app, in the background.
+pub fn handle(app_handle: &tauri::AppHandle, paths: Vec<PathBuf>) {
+    if paths.is_empty() {
+        return;
+    }
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        for path in paths {
+            let path = path.to_string_lossy().into_owned();
+            match import(&app_handle, &path) {
+                Ok(result) => {
+                    let _ = app_handle.emit("file-imported", &result);
+                }
+                Err(error) => {
+                    log::warn!("FILE_IMPORT ► {}", error);
+                    let _ = app_handle.emit("file-import-failed", ImportFailed { path, error });
+                }
+            }
+        }
+    });
+}
+
+/// Files the app was launched with. Called from setup.
+pub fn handle_launch_args(app_handle: &tauri::AppHandle) {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let cwd = std::env::current_dir().unwrap_or_default();
+    handle(app_handle, from_args(&args, &cwd));
+}
+
+/// Import a `.plattera` export through the backend, once it is healthy.
+#[tauri::command]
+pub async fn import_file(
+    app_handle: tauri::AppHandle,
+    path: String,
+) -> Result<ImportResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = import(&app_handle, &path);
+        if let Err(e) = &result {
+            log::warn!("FILE_IMPORT ► {}", e);
+        }
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
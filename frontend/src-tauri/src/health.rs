@@ -0,0 +1,138 @@
+// Backend health probe for the frontend. Distinguishes a backend that was
+// never started from one that is still booting, one that answers, and one
+// that should answer but doesn't. A backend that answers but fails the
+// configured health definition (`health_checks.rs`) is reported as degraded.
+
+use crate::health_checks::{self, CheckResult};
+use crate::{backend_http, endpoint, port_in_use, runtime_info, sidecar_fallback, BackendProcess};
+use serde::Serialize;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendHealthState {
+    NotStarted,
+    Starting,
+    Healthy,
+    /// Answers, but the health definition doesn't pass.
+    Degraded,
+    Unreachable,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendHealth {
+    pub state: BackendHealthState,
+    /// `overall_status` reported by `/api/health` when reachable.
+    pub status: Option<String>,
+    pub latency_ms: Option<u64>,
+    pub backend_version: Option<String>,
+    /// PID of the backend child this shell spawned, if any.
+    pub pid: Option<u32>,
+    pub port: u16,
+    pub detail: Option<String>,
+    /// Results of the configured health checks (empty when unreachable).
+    pub checks: Vec<CheckResult>,
+}
+
+/// Probe `/api/health` and classify the result. Blocking.
+pub fn probe(app_handle: &tauri::AppHandle) -> BackendHealth {
+    let port = endpoint::port(app_handle);
+    let pid = app_handle
+        .state::<BackendProcess>()
+        .0
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|c| c.pid());
+
+    let start = Instant::now();
+    let result = backend_http::call_blocking(
+        app_handle,
+        reqwest::Method::GET,
+        "/api/health",
+        None,
+        Duration::from_millis(3_000),
+        None,
+    )
+    .and_then(|reply| {
+        if reply.status.is_success() {
+            Ok(reply.body)
+        } else {
+            Err(format!("health check returned {}", reply.status))
+        }
+    });
+    let latency_ms = start.elapsed().as_millis() as u64;
+
+    match result {
+        Ok(body) => {
+            let status = body
+                .get("overall_status")
+                .or_else(|| body.get("status"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
+            let evaluation = health_checks::evaluate_current(app_handle);
+            let failed: Vec<String> = evaluation
+                .results
+                .iter()
+                .filter(|r| !r.ok)
+                .map(|r| format!("{}: {}", r.name, r.detail.as_deref().unwrap_or("failed")))
+                .collect();
+            BackendHealth {
+                state: if evaluation.healthy {
+                    BackendHealthState::Healthy
+                } else {
+                    BackendHealthState::Degraded
+                },
+                status,
+                latency_ms: Some(latency_ms),
+                backend_version: runtime_info::probe_backend_version(app_handle),
+                pid,
+                port,
+                detail: (!failed.is_empty()).then(|| failed.join("; ")),
+                checks: evaluation.results,
+            }
+        }
+        Err(e) => {
+            let listening = endpoint::socket(app_handle).is_some() || port_in_use(port);
+            let state = if pid.is_none() && !listening {
+                BackendHealthState::NotStarted
+            } else if pid.is_some() && sidecar_fallback::boot_in_progress(app_handle) {
+                BackendHealthState::Starting
+            } else {
+                BackendHealthState::Unreachable
+            };
+            BackendHealth {
+                state,
+                status: None,
+                latency_ms: None,
+                backend_version: None,
+                pid,
+                port,
+                detail: Some(e),
+                checks: Vec::new(),
+            }
+        }
+    }
+}
+
+/// Poll the configured health definition on the active transport until it
+/// passes or `timeout` passes. Blocking.
+pub fn wait_until_healthy(app_handle: &tauri::AppHandle, timeout: Duration) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        if health_checks::evaluate_current(app_handle).healthy {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(500));
+    }
+    false
+}
+
+#[tauri::command]
+pub async fn check_backend_health(app_handle: tauri::AppHandle) -> Result<BackendHealth, String> {
+    tauri::async_runtime::spawn_blocking(move || probe(&app_handle))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,279 @@
+// Configurable health definition. By default "healthy" means `/api/health`
+// answers, which only proves the server accepts requests. Settings can
+// replace that with a list of checks combined with all/any logic:
+//
+// - `http`: a backend endpoint answers with a 2xx, optionally within a
+//   latency budget;
+// - `file_fresh`: a file (relative paths resolve against the backend data
+//   folder) was modified within `max_age_secs`, e.g. a database that the
+//   backend writes as it works;
+// - `heartbeat`: a JSON field on a backend endpoint holds a timestamp (epoch
+//   seconds or RFC 3339) no older than `max_age_secs`, e.g. a worker loop
+//   reporting in.
+//
+// `health.rs` evaluates the definition for `check_backend_health` and for
+// every "wait until healthy" gate (updates, rollback, data migration).
+
+use crate::{backend_http, settings};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+fn default_timeout_ms() -> u64 {
+    3_000
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Combine {
+    /// Every check must pass.
+    #[default]
+    All,
+    /// One passing check is enough.
+    Any,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HealthCheck {
+    Http {
+        path: String,
+        #[serde(default = "default_timeout_ms")]
+        timeout_ms: u64,
+        /// Slower answers count as failures.
+        #[serde(default)]
+        max_latency_ms: Option<u64>,
+    },
+    FileFresh {
+        path: String,
+        max_age_secs: u64,
+    },
+    Heartbeat {
+        path: String,
+        /// Dotted path to the timestamp in the response, e.g. `worker.last_beat`.
+        field: String,
+        max_age_secs: u64,
+        #[serde(default = "default_timeout_ms")]
+        timeout_ms: u64,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthDefinition {
+    pub combine: Combine,
+    pub checks: Vec<HealthCheck>,
+}
+
+impl Default for HealthDefinition {
+    fn default() -> Self {
+        HealthDefinition {
+            combine: Combine::All,
+            checks: vec![HealthCheck::Http {
+                path: "/api/health".to_string(),
+                timeout_ms: default_timeout_ms(),
+                max_latency_ms: None,
+            }],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    /// `kind:path`, e.g. `http:/api/health`.
+    pub name: String,
+    pub ok: bool,
+    pub duration_ms: u64,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Evaluation {
+    pub healthy: bool,
+    pub combine: Combine,
+    pub results: Vec<CheckResult>,
+}
+
+fn get_json(app_handle: &tauri::AppHandle, path: &str, timeout_ms: u64) -> Result<Value, String> {
+    let reply = backend_http::call_blocking(
+        app_handle,
+        reqwest::Method::GET,
+        path,
+        None,
+        Duration::from_millis(timeout_ms),
+        None,
+    )?;
+    if reply.status.is_success() {
+        Ok(reply.body)
+    } else {
+        Err(format!("returned {}", reply.status))
+    }
+}
+
+fn age_secs(time: SystemTime) -> u64 {
+    SystemTime::now()
+        .duration_since(time)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Timestamp in a heartbeat field: epoch seconds (or milliseconds) or an
+/// RFC 3339 string.
+fn parse_timestamp(value: &Value) -> Option<SystemTime> {
+    if let Some(n) = value.as_f64() {
+        // Anything past the year 33658 in seconds is really milliseconds.
+        let secs = if n > 1e12 { n / 1000.0 } else { n };
+        return Some(UNIX_EPOCH + Duration::from_secs_f64(secs.max(0.0)));
+    }
+    let parsed = chrono::DateTime::parse_from_rfc3339(value.as_str()?).ok()?;
+    let secs = u64::try_from(parsed.timestamp()).ok()?;
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+fn resolve_file(app_handle: &tauri::AppHandle, path: &str) -> Result<PathBuf, String> {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        return Ok(path.to_path_buf());
+    }
+    Ok(settings::backend_data_dir(app_handle)?.join(path))
+}
+
+fn run_check(app_handle: &tauri::AppHandle, check: &HealthCheck) -> Result<String, String> {
+    match check {
+        HealthCheck::Http {
+            path,
+            timeout_ms,
+            max_latency_ms,
+        } => {
+            let start = Instant::now();
+            get_json(app_handle, path, *timeout_ms)?;
+            let latency = start.elapsed().as_millis() as u64;
+            match max_latency_ms {
+                Some(max) if latency > *max => {
+                    Err(format!("answered in {}ms (limit {}ms)", latency, max))
+                }
+                _ => Ok(format!("answered in {}ms", latency)),
+            }
+        }
+        HealthCheck::FileFresh { path, max_age_secs } => {
+            let file = resolve_file(app_handle, path)?;
+            let modified = fs::metadata(&file)
+                .and_then(|m| m.modified())
+                .map_err(|e| format!("{}: {}", file.display(), e))?;
+            let age = age_secs(modified);
+            if age > *max_age_secs {
+                Err(format!(
+                    "last modified {}s ago (limit {}s)",
+                    age, max_age_secs
+                ))
+            } else {
+                Ok(format!("modified {}s ago", age))
+            }
+        }
+        HealthCheck::Heartbeat {
+            path,
+            field,
+            max_age_secs,
+            timeout_ms,
+        } => {
+            let body = get_json(app_handle, path, *timeout_ms)?;
+            let value = field
+                .split('.')
+                .try_fold(&body, |v, key| v.get(key))
+                .ok_or_else(|| format!("no `{}` in response", field))?;
+            let beat = parse_timestamp(value)
+                .ok_or_else(|| format!("`{}` is not a timestamp: {}", field, value))?;
+            let age = age_secs(beat);
+            if age > *max_age_secs {
+                Err(format!(
+                    "last heartbeat {}s ago (limit {}s)",
+                    age, max_age_secs
+                ))
+            } else {
+                Ok(format!("heartbeat {}s ago", age))
+            }
+        }
+    }
+}
+
+fn check_name(check: &HealthCheck) -> String {
+    match check {
+        HealthCheck::Http { path, .. } => format!("http:{}", path),
+        HealthCheck::FileFresh { path, .. } => format!("file_fresh:{}", path),
+        HealthCheck::Heartbeat { path, field, .. } => format!("heartbeat:{}#{}", path, field),
+    }
+}
+
+/// Run every check of `definition`. Blocking.
+pub fn evaluate(app_handle: &tauri::AppHandle, definition: &HealthDefinition) -> Evaluation {
+    let results: Vec<CheckResult> = definition
+        .checks
+        .iter()
+        .map(|check| {
+            let start = Instant::now();
+            let outcome = run_check(app_handle, check);
+            CheckResult {
+                name: check_name(check),
+                ok: outcome.is_ok(),
+                duration_ms: start.elapsed().as_millis() as u64,
+                detail: Some(outcome.unwrap_or_else(|e| e)),
+            }
+        })
+        .collect();
+    let healthy = match definition.combine {
+        Combine::All => results.iter().all(|r| r.ok),
+        Combine::Any => results.iter().any(|r| r.ok),
+    };
+    Evaluation {
+        healthy: healthy && !results.is_empty(),
+        combine: definition.combine,
+        results,
+    }
+}
+
+/// Evaluate the configured definition. Blocking.
+pub fn evaluate_current(app_handle: &tauri::AppHandle) -> Evaluation {
+    evaluate(app_handle, &settings::current(app_handle).health)
+}
+
+fn validate(definition: &HealthDefinition) -> Result<(), String> {
+    if definition.checks.is_empty() {
+        return Err("a health definition needs at least one check".into());
+    }
+    for check in &definition.checks {
+        match check {
+            HealthCheck::Http { path, .. } | HealthCheck::Heartbeat { path, .. }
+                if !path.starts_with('/') =>
+            {
+                return Err(format!("endpoint `{}` must start with /", path));
+            }
+            HealthCheck::FileFresh { path, .. } if path.trim().is_empty() => {
+                return Err("file check has no path".into());
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Replace the health definition (`None` restores the default) and return
+/// an evaluation of the new one.
+#[tauri::command]
+pub async fn set_health_definition(
+    app_handle: tauri::AppHandle,
+    definition: Option<HealthDefinition>,
+) -> Result<Evaluation, String> {
+    let definition = definition.unwrap_or_default();
+    validate(&definition)?;
+    log::info!(
+        "HEALTH ► definition set: {:?} of {} check(s)",
+        definition.combine,
+        definition.checks.len()
+    );
+    settings::update(&app_handle, |s| s.health = definition.clone())?;
+    tauri::async_runtime::spawn_blocking(move || evaluate(&app_handle, &definition))
+        .await
+        .map_err(|e| e.to_string())
+}
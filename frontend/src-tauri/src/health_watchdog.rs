@@ -0,0 +1,150 @@
+// Health watchdog. Once startup has reported the backend ready, keep
+// pinging `/api/health` every `interval_ms`. A slow answer (over
+// `degraded_latency_ms`) or a failed ping marks the backend `degraded`;
+// `failures_before_unreachable` failures in a row mark it `unreachable`,
+// and the next good, fast answer makes it `ready` again. Transitions go
+// through `backend_state.rs`, which emits them. With
+// `restart_when_unreachable` an unreachable backend gets an automatic
+// restart (`auto_restart.rs`, so the user still sees the countdown), once
+// per episode. Nothing is probed while the backend is starting, has failed
+// to start, or maintenance mode is on.
+
+use crate::backend_state::{self, BackendState};
+use crate::{auto_restart, backend_http, maintenance, settings};
+use serde::{Deserialize, Serialize};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthWatchdogSettings {
+    pub enabled: bool,
+    pub interval_ms: u64,
+    /// Per-ping timeout; a ping that runs out counts as failed.
+    pub timeout_ms: u64,
+    /// Answers slower than this mark the backend degraded.
+    pub degraded_latency_ms: u64,
+    /// Consecutive failed pings before the backend counts as unreachable.
+    pub failures_before_unreachable: u32,
+    /// Schedule an automatic restart when the backend becomes unreachable.
+    pub restart_when_unreachable: bool,
+}
+
+impl Default for HealthWatchdogSettings {
+    fn default() -> Self {
+        HealthWatchdogSettings {
+            enabled: true,
+            interval_ms: 15_000,
+            timeout_ms: 5_000,
+            degraded_latency_ms: 2_000,
+            failures_before_unreachable: 3,
+            restart_when_unreachable: false,
+        }
+    }
+}
+
+impl HealthWatchdogSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_ms < 1_000 {
+            return Err("health_watchdog.interval_ms must be at least 1000".into());
+        }
+        if self.timeout_ms == 0 {
+            return Err("health_watchdog.timeout_ms must be greater than zero".into());
+        }
+        if self.failures_before_unreachable == 0 {
+            return Err("health_watchdog.failures_before_unreachable must be at least 1".into());
+        }
+        Ok(())
+    }
+}
+
+/// Ping once; the latency of a successful answer, or why it failed.
+fn ping(app_handle: &tauri::AppHandle, timeout: Duration) -> Result<u64, String> {
+    let started = Instant::now();
+    backend_http::call_blocking(
+        app_handle,
+        reqwest::Method::GET,
+        "/api/health",
+        None,
+        timeout,
+        None,
+    )
+    .and_then(|reply| {
+        if reply.status.is_success() {
+            Ok(started.elapsed().as_millis() as u64)
+        } else {
+            Err(format!("health check returned {}", reply.status))
+        }
+    })
+}
+
+/// Run the watchdog on a dedicated thread.
+pub fn spawn(app_handle: tauri::AppHandle) {
+    thread::spawn(move || {
+        let mut failures = 0u32;
+        let mut restart_requested = false;
+        loop {
+            let cfg = settings::current(&app_handle).health_watchdog;
+            thread::sleep(Duration::from_millis(cfg.interval_ms.max(1_000)));
+            if !cfg.enabled || maintenance::active(&app_handle) {
+                continue;
+            }
+            let state = backend_state::current(&app_handle);
+            if !matches!(
+                state,
+                BackendState::Ready { .. }
+                    | BackendState::Degraded { .. }
+                    | BackendState::Unreachable { .. }
+            ) {
+                failures = 0;
+                restart_requested = false;
+                continue;
+            }
+
+            match ping(&app_handle, Duration::from_millis(cfg.timeout_ms)) {
+                Ok(latency_ms) => {
+                    failures = 0;
+                    restart_requested = false;
+                    if latency_ms > cfg.degraded_latency_ms {
+                        if !matches!(state, BackendState::Degraded { .. }) {
+                            backend_state::set_degraded(
+                                &app_handle,
+                                &format!(
+                                    "health check took {}ms (threshold {}ms)",
+                                    latency_ms, cfg.degraded_latency_ms
+                                ),
+                            );
+                        }
+                    } else if !matches!(state, BackendState::Ready { .. }) {
+                        log::info!("HEALTH_WATCHDOG ► backend recovered ({}ms)", latency_ms);
+                        backend_state::set_ready(&app_handle);
+                    }
+                }
+                Err(e) => {
+                    failures += 1;
+                    log::warn!(
+                        "HEALTH_WATCHDOG ► health check failed ({} in a row): {}",
+                        failures,
+                        e
+                    );
+                    if failures >= cfg.failures_before_unreachable {
+                        if !matches!(state, BackendState::Unreachable { .. }) {
+                            backend_state::set_unreachable(
+                                &app_handle,
+                                &format!("{} health checks failed in a row: {}", failures, e),
+                            );
+                        }
+                        if cfg.restart_when_unreachable && !restart_requested {
+                            restart_requested = auto_restart::schedule(
+                                &app_handle,
+                                format!("backend unreachable: {}", e),
+                            );
+                        }
+                    } else if matches!(state, BackendState::Ready { .. }) {
+                        backend_state::set_degraded(&app_handle, &e);
+                    }
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,349 @@
+// Localization of strings produced on the Rust side: menu and tray labels,
+// and the error messages commands return to the frontend for display. The
+// locale comes from the `locale` setting, or the OS language when unset;
+// anything without a catalog falls back to English. `set_locale` changes the
+// setting, and any change to it rebuilds the menu and tray at runtime and is
+// announced as `locale-changed`.
+//
+// Catalogs are plain key → text tables compiled in. Placeholders are written
+// `{name}` and filled by `tf`. A key missing from a catalog falls back to the
+// English text, and an unknown key to the key itself, so a gap shows up as
+// an untranslated string rather than an error.
+
+use crate::{menu, settings, tray};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+
+pub const DEFAULT_LOCALE: &str = "en";
+pub const SUPPORTED: &[&str] = &["en", "es", "fr"];
+
+const EN: &[(&str, &str)] = &[
+    ("menu.file", "File"),
+    ("menu.new_dossier", "New Dossier"),
+    ("menu.open_recent", "Open Recent"),
+    ("menu.clear_recent", "Clear Recent"),
+    ("menu.export", "Export…"),
+    ("menu.quit", "Quit"),
+    ("menu.view", "View"),
+    ("menu.zoom_in", "Zoom In"),
+    ("menu.zoom_out", "Zoom Out"),
+    ("menu.zoom_reset", "Actual Size"),
+    ("menu.reload", "Reload"),
+    ("menu.tools", "Tools"),
+    ("menu.open_devtools", "Open DevTools"),
+    ("menu.help", "Help"),
+    ("menu.logs", "Logs"),
+    ("menu.diagnostics", "Diagnostics"),
+    ("menu.check_updates", "Check for Updates"),
+    ("tray.toggle_window", "Show/Hide Window"),
+    ("tray.restart_backend", "Restart Backend"),
+    ("tray.check_updates", "Check for Updates"),
+    ("tray.run_pipeline", "Run Pipeline"),
+    ("tray.quit", "Quit"),
+    ("error.unsupported_locale", "unsupported locale '{locale}'"),
+    ("error.invalid_dossier_id", "invalid dossier id '{id}'"),
+    ("error.empty_dossier_id", "dossier id is empty"),
+    (
+        "error.profile_name_length",
+        "profile name must be 1-{max} characters",
+    ),
+    (
+        "error.profile_exists",
+        "a profile named '{name}' already exists",
+    ),
+    ("error.unknown_profile", "unknown profile '{id}'"),
+    (
+        "error.empty_notification_title",
+        "notification title is empty",
+    ),
+    ("error.not_plattera_file", "not a .plattera file: {path}"),
+    (
+        "error.backend_not_ready_for_import",
+        "backend did not become ready for the import",
+    ),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("menu.file", "Archivo"),
+    ("menu.new_dossier", "Nuevo expediente"),
+    ("menu.open_recent", "Abrir reciente"),
+    ("menu.clear_recent", "Borrar recientes"),
+    ("menu.export", "Exportar…"),
+    ("menu.quit", "Salir"),
+    ("menu.view", "Ver"),
+    ("menu.zoom_in", "Acercar"),
+    ("menu.zoom_out", "Alejar"),
+    ("menu.zoom_reset", "Tamaño real"),
+    ("menu.reload", "Recargar"),
+    ("menu.tools", "Herramientas"),
+    ("menu.open_devtools", "Abrir DevTools"),
+    ("menu.help", "Ayuda"),
+    ("menu.logs", "Registros"),
+    ("menu.diagnostics", "Diagnóstico"),
+    ("menu.check_updates", "Buscar actualizaciones"),
+    ("tray.toggle_window", "Mostrar/ocultar ventana"),
+    ("tray.restart_backend", "Reiniciar backend"),
+    ("tray.check_updates", "Buscar actualizaciones"),
+    ("tray.run_pipeline", "Ejecutar canalización"),
+    ("tray.quit", "Salir"),
+    ("error.unsupported_locale", "idioma no admitido: '{locale}'"),
+    (
+        "error.invalid_dossier_id",
+        "identificador de expediente no válido: '{id}'",
+    ),
+    (
+        "error.empty_dossier_id",
+        "el identificador del expediente está vacío",
+    ),
+    (
+        "error.profile_name_length",
+        "el nombre del perfil debe tener entre 1 y {max} caracteres",
+    ),
+    (
+        "error.profile_exists",
+        "ya existe un perfil llamado '{name}'",
+    ),
+    ("error.unknown_profile", "perfil desconocido: '{id}'"),
+    (
+        "error.empty_notification_title",
+        "el título de la notificación está vacío",
+    ),
+    (
+        "error.not_plattera_file",
+        "no es un archivo .plattera: {path}",
+    ),
+    (
+        "error.backend_not_ready_for_import",
+        "el backend no estuvo listo para la importación",
+    ),
+];
+
+const FR: &[(&str, &str)] = &[
+    ("menu.file", "Fichier"),
+    ("menu.new_dossier", "Nouveau dossier"),
+    ("menu.open_recent", "Ouvrir un élément récent"),
+    ("menu.clear_recent", "Effacer les éléments récents"),
+    ("menu.export", "Exporter…"),
+    ("menu.quit", "Quitter"),
+    ("menu.view", "Affichage"),
+    ("menu.zoom_in", "Zoom avant"),
+    ("menu.zoom_out", "Zoom arrière"),
+    ("menu.zoom_reset", "Taille réelle"),
+    ("menu.reload", "Recharger"),
+    ("menu.tools", "Outils"),
+    ("menu.open_devtools", "Ouvrir les DevTools"),
+    ("menu.help", "Aide"),
+    ("menu.logs", "Journaux"),
+    ("menu.diagnostics", "Diagnostics"),
+    ("menu.check_updates", "Rechercher des mises à jour"),
+    ("tray.toggle_window", "Afficher/masquer la fenêtre"),
+    ("tray.restart_backend", "Redémarrer le backend"),
+    ("tray.check_updates", "Rechercher des mises à jour"),
+    ("tray.run_pipeline", "Exécuter un pipeline"),
+    ("tray.quit", "Quitter"),
+    (
+        "error.unsupported_locale",
+        "langue non prise en charge : '{locale}'",
+    ),
+    (
+        "error.invalid_dossier_id",
+        "identifiant de dossier invalide : '{id}'",
+    ),
+    (
+        "error.empty_dossier_id",
+        "l'identifiant du dossier est vide",
+    ),
+    (
+        "error.profile_name_length",
+        "le nom du profil doit comporter de 1 à {max} caractères",
+    ),
+    (
+        "error.profile_exists",
+        "un profil nommé '{name}' existe déjà",
+    ),
+    ("error.unknown_profile", "profil inconnu : '{id}'"),
+    (
+        "error.empty_notification_title",
+        "le titre de la notification est vide",
+    ),
+    (
+        "error.not_plattera_file",
+        "ce n'est pas un fichier .plattera : {path}",
+    ),
+    (
+        "error.backend_not_ready_for_import",
+        "le backend n'était pas prêt pour l'importation",
+    ),
+];
+
+fn catalog(locale: &str) -> &'static [(&'static str, &'static str)] {
+    match locale {
+        "es" => ES,
+        "fr" => FR,
+        _ => EN,
+    }
+}
+
+fn lookup(table: &[(&str, &'static str)], key: &str) -> Option<&'static str> {
+    table.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+/// The resolved locale, one of `SUPPORTED`.
+pub struct Locale(Mutex<&'static str>);
+
+impl Locale {
+    pub fn new() -> Self {
+        Locale(Mutex::new(DEFAULT_LOCALE))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocaleInfo {
+    /// Locale in use.
+    pub locale: &'static str,
+    /// The `locale` setting; `None` follows the OS.
+    pub setting: Option<String>,
+    pub system: Option<String>,
+    pub supported: &'static [&'static str],
+}
+
+/// `SUPPORTED` entry for a tag like `es`, `es-MX` or `es_ES.UTF-8`.
+fn supported(tag: &str) -> Option<&'static str> {
+    let lang = tag
+        .split(['-', '_', '.'])
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    SUPPORTED.iter().copied().find(|s| *s == lang)
+}
+
+/// The OS language tag, if it can be read.
+#[cfg(windows)]
+fn system_locale() -> Option<String> {
+    use windows_sys::Win32::Globalization::GetUserDefaultLocaleName;
+    let mut buf = [0u16; 85];
+    let len = unsafe { GetUserDefaultLocaleName(buf.as_mut_ptr(), buf.len() as i32) };
+    (len > 1).then(|| String::from_utf16_lossy(&buf[..len as usize - 1]))
+}
+
+#[cfg(target_os = "macos")]
+fn system_locale() -> Option<String> {
+    let out = std::process::Command::new("defaults")
+        .args(["read", "-g", "AppleLocale"])
+        .output()
+        .ok()?;
+    let tag = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    (!tag.is_empty()).then_some(tag)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn system_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|v| !v.is_empty() && v != "C" && v != "POSIX")
+}
+
+fn resolve(setting: Option<&str>) -> &'static str {
+    setting
+        .and_then(supported)
+        .or_else(|| system_locale().as_deref().and_then(supported))
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// The locale in use.
+pub fn current(app_handle: &tauri::AppHandle) -> &'static str {
+    app_handle
+        .try_state::<Locale>()
+        .map(|l| *l.0.lock().unwrap())
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// Text for `key` in the current locale.
+pub fn t(app_handle: &tauri::AppHandle, key: &str) -> String {
+    lookup(catalog(current(app_handle)), key)
+        .or_else(|| lookup(EN, key))
+        .unwrap_or(key)
+        .to_string()
+}
+
+/// `t` with `{name}` placeholders filled from `args`.
+pub fn tf(app_handle: &tauri::AppHandle, key: &str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(t(app_handle, key), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+/// Reject a `locale` setting without a catalog. Called from settings
+/// validation.
+pub fn validate(setting: Option<&str>) -> Result<(), String> {
+    match setting {
+        Some(tag) if supported(tag).is_none() => Err(format!("unsupported locale '{}'", tag)),
+        _ => Ok(()),
+    }
+}
+
+fn info(app_handle: &tauri::AppHandle) -> LocaleInfo {
+    LocaleInfo {
+        locale: current(app_handle),
+        setting: settings::current(app_handle).locale,
+        system: system_locale(),
+        supported: SUPPORTED,
+    }
+}
+
+/// Resolve the locale from settings and return the previous one. Called from
+/// setup, before the menu and tray are built.
+pub fn init(app_handle: &tauri::AppHandle) -> &'static str {
+    let setting = settings::current(app_handle).locale;
+    let next = resolve(setting.as_deref());
+    std::mem::replace(&mut *app_handle.state::<Locale>().0.lock().unwrap(), next)
+}
+
+/// Re-resolve the locale after the `locale` setting changed and rebuild the
+/// menu and tray in the new language.
+pub fn apply(app_handle: &tauri::AppHandle) {
+    let previous = init(app_handle);
+    let next = current(app_handle);
+    if previous == next {
+        return;
+    }
+    log::info!("I18N ► locale {} → {}", previous, next);
+    if let Err(e) = menu::refresh(app_handle) {
+        log::warn!("I18N ► could not rebuild menu: {}", e);
+    }
+    if let Err(e) = tray::refresh(app_handle) {
+        log::warn!("I18N ► could not rebuild tray menu: {}", e);
+    }
+    let _ = app_handle.emit("locale-changed", info(app_handle));
+}
+
+#[tauri::command]
+pub async fn get_locale(app_handle: tauri::AppHandle) -> Result<LocaleInfo, String> {
+    Ok(info(&app_handle))
+}
+
+/// Set the locale (`None` follows the OS). The menu and tray are rebuilt in
+/// the new language.
+#[tauri::command]
+pub async fn set_locale(
+    app_handle: tauri::AppHandle,
+    locale: Option<String>,
+) -> Result<LocaleInfo, String> {
+    let locale = locale
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty());
+    if let Some(tag) = &locale {
+        if supported(tag).is_none() {
+            return Err(tf(
+                &app_handle,
+                "error.unsupported_locale",
+                &[("locale", tag)],
+            ));
+        }
+    }
+    // Saving triggers `apply` through the settings change hook.
+    settings::update(&app_handle, |s| s.locale = locale)?;
+    Ok(info(&app_handle))
+}
@@ -1,21 +1,115 @@
 use tauri::Manager;
 use tauri_plugin_shell::{process::{CommandChild, CommandEvent}, ShellExt};
 use std::sync::Mutex;
-use std::thread;
 use std::time::Duration;
 use std::net::TcpStream;
 
-mod windows_job;
+mod auth;
+mod auto_restart;
+mod backend_config;
+mod backend_control;
+mod backend_http;
 mod backend_lifecycle;
+mod backend_log;
+mod backend_mode;
+mod backend_pid;
+mod backend_state;
+mod backend_stats;
+mod backend_update;
+mod backup;
+mod capabilities;
+mod clipboard;
+mod close_guard;
+mod compat;
+mod connectivity;
+mod correlation;
+mod crash_report;
+mod data_encryption;
+mod data_reset;
+mod deep_link;
+mod dev_migration;
+mod dev_reload;
+mod diagnostics;
+mod dossier_export;
+mod dossier_windows;
+mod downloads;
+mod endpoint;
+mod event_bridge;
+mod file_drop;
+mod file_import;
+mod health;
+mod health_checks;
+mod health_watchdog;
+mod i18n;
+mod log_tail;
+mod loopback_bind;
+mod maintenance;
+mod menu;
+mod migrations;
+mod notifications;
+mod onboarding;
+mod orphan_sweep;
+mod paths;
+mod pdf_export;
+mod pipelines;
+mod port_conflict;
+mod portable;
+mod power;
+mod preflight;
+mod preview;
+mod prewarm;
+mod process_env;
+mod profiles;
+mod proxy;
+mod readiness;
+mod recent;
+mod recovery;
+mod repair;
+mod rollback;
+mod runtime_info;
+mod scheduler;
+mod session;
+mod session_end;
+mod settings;
+mod shortcuts;
+mod sidecar_fallback;
+mod sidecar_integrity;
+mod single_instance;
+mod splash;
+mod standby;
+mod startup;
+mod state_snapshot;
+mod storage;
+mod supervisor;
+mod tasks;
+mod telemetry;
+mod theme;
+mod tls_trust;
+mod trace;
+mod tray;
+mod unix_pgroup;
+mod updates;
+mod uploads;
+mod version_pin;
+mod warmup;
+mod whats_new;
+mod window_state;
+mod windows_job;
+mod zoom;
 
-use backend_lifecycle::{shutdown_backend_for_update, shutdown_backend_for_exit};
+use backend_lifecycle::shutdown_backend_for_update;
 
-// Blocking HTTP for quick cleanup ping
-fn cleanup_via_http(timeout_ms: u64) {
-    let agent = ureq::AgentBuilder::new()
-        .timeout_connect(Duration::from_millis(timeout_ms))
-        .build();
-    let _ = agent.post("http://127.0.0.1:8000/api/cleanup").call();
+// Quick cleanup ping over the shared client; true if the backend answered.
+// Bounded overall so a wedged backend can't stall a time-limited shutdown.
+fn cleanup_via_http(app_handle: &tauri::AppHandle, port: u16, timeout_ms: u64) -> bool {
+    backend_http::call_port_blocking(
+        app_handle,
+        port,
+        reqwest::Method::POST,
+        "/api/cleanup",
+        Duration::from_millis(timeout_ms * 2),
+    )
+    .is_ok()
 }
 
 fn port_in_use(port: u16) -> bool {
@@ -32,27 +126,30 @@ struct BackendJob(Mutex<Option<windows_job::JobHandle>>);
 /// fetches an arbitrary URL (typically the configured latest.json endpoint),
 /// logs what it sees, and returns a terse status to the frontend.
 #[tauri::command]
-async fn debug_updater_endpoint(url: String) -> Result<String, String> {
-    use ureq::AgentBuilder;
-
-    let agent = AgentBuilder::new()
-        .timeout_connect(Duration::from_millis(2_000))
+async fn debug_updater_endpoint(app_handle: tauri::AppHandle, url: String) -> Result<String, String> {
+    let client = proxy::client_builder(&app_handle, &url)
+        .connect_timeout(Duration::from_millis(2_000))
         .timeout(Duration::from_millis(5_000))
-        .build();
+        .build()
+        .map_err(|e| format!("request error: {e}"))?;
 
-    let res = agent
+    let res = client
         .get(&url)
-        .call()
+        .send()
+        .await
         .map_err(|e| format!("request error: {e}"))?;
 
     let status = res.status();
     let content_type = res
-        .header("content-type")
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
         .map(|s| s.to_string())
         .unwrap_or_else(|| "<none>".to_string());
 
     let body = res
-        .into_string()
+        .text()
+        .await
         .unwrap_or_else(|_| "<body read error>".to_string());
 
     log::info!(
@@ -75,104 +172,334 @@ async fn debug_updater_endpoint(url: String) -> Result<String, String> {
     }
 }
 
-#[tauri::command]
-async fn start_backend(app_handle: tauri::AppHandle) -> Result<String, String> {
-    let backend_process = app_handle.state::<BackendProcess>();
-    let mut process_guard = backend_process.0.lock().unwrap();
-    
-    if process_guard.is_none() {
-        // If port 8000 is already in use (external server), don't spawn another
-        if port_in_use(8000) {
-            return Ok("Backend already running (detected on port 8000)".to_string());
+#[derive(Clone, serde::Serialize)]
+struct BackendLogLine {
+    level: &'static str,
+    /// RFC 3339 time the shell received the line.
+    timestamp: String,
+    stream: &'static str,
+    pid: u32,
+    line: String,
+}
+
+/// Best-effort level for a backend output line. Python logging puts the
+/// level name in the line; anything else takes the stream's default.
+fn backend_line_level(line: &str, default: &'static str) -> &'static str {
+    if line.contains("CRITICAL") || line.contains("ERROR") || line.contains("Traceback") {
+        "error"
+    } else if line.contains("WARNING") {
+        "warn"
+    } else if line.contains("DEBUG") {
+        "debug"
+    } else if line.contains("INFO") {
+        "info"
+    } else {
+        default
+    }
+}
+
+/// Log target of forwarded backend output.
+const BACKEND_OUTPUT_TARGET: &str = concat!(module_path!(), "::backend_output");
+
+/// Pump a spawned backend's output into the app log, the rotating backend
+/// output files and `backend-log` events (the frontend's live console), and
+/// notice when it exits.
+fn forward_backend_output(
+    app_handle: tauri::AppHandle,
+    mut rx: tauri::async_runtime::Receiver<CommandEvent>,
+    pid: u32,
+    is_sidecar: bool,
+) {
+    // Output is tagged with the operation that spawned this backend.
+    let op = correlation::current();
+    let label = if is_sidecar { "SIDECAR" } else { "BACKEND" };
+    let emit_line = move |app_handle: &tauri::AppHandle, stream, default_level, raw: &[u8]| {
+        let line = String::from_utf8_lossy(raw).trim_end().to_string();
+        backend_log::append(app_handle, stream, pid, &line);
+        correlation::emit(
+            app_handle,
+            "backend-log",
+            BackendLogLine {
+                level: backend_line_level(&line, default_level),
+                timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+                stream,
+                pid,
+                line,
+            },
+        );
+    };
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Stdout(line) => {
+                    let _op = correlation::adopt(op.clone());
+                    log::info!(
+                        target: BACKEND_OUTPUT_TARGET,
+                        "[{} stdout] {}",
+                        label,
+                        String::from_utf8_lossy(&line)
+                    );
+                    emit_line(&app_handle, "stdout", "info", &line);
+                }
+                CommandEvent::Stderr(line) => {
+                    let _op = correlation::adopt(op.clone());
+                    log::error!(
+                        target: BACKEND_OUTPUT_TARGET,
+                        "[{} stderr] {}",
+                        label,
+                        String::from_utf8_lossy(&line)
+                    );
+                    emit_line(&app_handle, "stderr", "error", &line);
+                }
+                CommandEvent::Terminated(payload) => {
+                    {
+                        let _op = correlation::adopt(op.clone());
+                        log::warn!(
+                            target: BACKEND_OUTPUT_TARGET,
+                            "[{}] backend pid {} exited (code={:?}, signal={:?})",
+                            label,
+                            pid,
+                            payload.code,
+                            payload.signal
+                        );
+                    }
+                    // Handling the exit (fallback, supervisor restart) is an
+                    // operation of its own.
+                    let _op = correlation::begin("crash");
+                    // Only react if this is still the tracked child; shutdown
+                    // paths take the child out of state before killing it.
+                    if untrack_backend_child(&app_handle, pid) {
+                        endpoint::forget_spawned(&app_handle, endpoint::port(&app_handle));
+                        let handled_by_fallback = is_sidecar
+                            && sidecar_fallback::on_unexpected_exit(&app_handle, payload.code);
+                        supervisor::on_backend_exit(
+                            &app_handle,
+                            pid,
+                            payload.code,
+                            payload.signal,
+                            handled_by_fallback,
+                        );
+                    }
+                }
+                _ => {}
+            }
         }
+    });
+}
+
+/// Clear `BackendProcess` if it still holds the child with `pid`.
+fn untrack_backend_child(app_handle: &tauri::AppHandle, pid: u32) -> bool {
+    let backend_process = app_handle.state::<BackendProcess>();
+    let mut guard = backend_process.0.lock().unwrap();
+    if guard.as_ref().map(|c| c.pid()) == Some(pid) {
+        *guard = None;
+        backend_pid::untrack(app_handle, pid);
+        true
+    } else {
+        false
+    }
+}
+
+/// Launch a backend process listening on `port`, or on the endpoint's local
+/// socket when one is set (sidecar first, Python venv as the dev fallback),
+/// and start forwarding its output. Returns the child and whether it is the
+/// sidecar. Does not touch `BackendProcess`.
+fn spawn_backend_child(
+    app_handle: &tauri::AppHandle,
+    port: u16,
+) -> Result<(CommandChild, bool), String> {
+    let socket = endpoint::socket(app_handle);
 
-        // Try sidecar first; if that fails, fall back to Python (dev)
-        let try_sidecar = (|| -> Result<CommandChild, String> {
-            let sidecar = app_handle
+    let dev_python = backend_mode::current(app_handle) == backend_mode::BackendMode::DevPython;
+
+    // A bundled sidecar that fails its integrity check is not started, and
+    // not papered over with the dev fallback either.
+    if !dev_python && sidecar_fallback::active_override(app_handle).is_none() {
+        sidecar_integrity::check_before_spawn(app_handle)?;
+    }
+
+    // Try sidecar first; if that fails, fall back to Python (dev)
+    let try_sidecar = (|| -> Result<CommandChild, String> {
+        if dev_python {
+            return Err("backend_mode is dev-python".to_string());
+        }
+        // After repeated boot failures, run the retained last-known-good
+        // binary instead of the bundled one.
+        let sidecar = match sidecar_fallback::active_override(app_handle) {
+            Some(path) => app_handle.shell().command(path),
+            None => app_handle
                 .shell()
                 .sidecar("plattera-backend")
-                .map_err(|e| format!("sidecar error: {}", e))?;
-            let sidecar = sidecar
+                .map_err(|e| format!("sidecar error: {}", e))?,
+        };
+        let mut sidecar = sidecar
+            .env("PYTHONIOENCODING", "utf-8")
+            .env("PYTHONUTF8", "1")
+            .envs(backend_config::spawn_env(app_handle, port))
+            .args(backend_config::args(app_handle));
+        // The frozen backend's default log dir lives in its unpack folder;
+        // keep logs somewhere persistent (and where `find_trace` looks).
+        if let Some(dir) = trace::backend_log_dir(app_handle) {
+            sidecar = sidecar.env(trace::BACKEND_LOG_DIR_ENV, dir);
+        }
+        if let Some(dir) = settings::data_dir_override(app_handle) {
+            sidecar = sidecar.env(settings::DATA_DIR_ENV, dir);
+        }
+        if let Some(socket) = &socket {
+            sidecar = sidecar.env(endpoint::SOCKET_ENV, socket);
+        }
+        // Start in a known per-user folder instead of wherever the app was
+        // launched from (often a non-ASCII or unwritable directory).
+        if let Ok(dir) = portable::app_local_data_dir(app_handle) {
+            if std::fs::create_dir_all(&dir).is_ok() {
+                sidecar = sidecar.current_dir(paths::plain(&dir));
+            }
+        }
+        let (rx, child) = sidecar.spawn().map_err(|e| format!("spawn error: {}", e))?;
+        forward_backend_output(app_handle.clone(), rx, child.pid(), true);
+        Ok(child)
+    })();
+
+    let spawned = match try_sidecar {
+        Ok(child) => {
+            assign_to_job(app_handle, child.pid(), "sidecar");
+            Ok((child, true))
+        }
+        Err(_e) => {
+            // DEV FALLBACK: run Python backend directly from venv
+            let mut python = app_handle
+                .shell()
+                .command("../../.venv/Scripts/python.exe")
+                .args(["-X", "utf8", "main.py"])
+                .current_dir(dev_reload::DEV_BACKEND_DIR)
                 .env("PYTHONIOENCODING", "utf-8")
-                .env("PYTHONUTF8", "1");
-            let (mut rx, child) = sidecar.spawn().map_err(|e| format!("spawn error: {}", e))?;
-            tauri::async_runtime::spawn(async move {
-                while let Some(event) = rx.recv().await {
-                    match event {
-                        CommandEvent::Stdout(line) => {
-                            log::info!("[SIDECAR stdout] {}", String::from_utf8_lossy(&line))
-                        }
-                        CommandEvent::Stderr(line) => {
-                            log::error!("[SIDECAR stderr] {}", String::from_utf8_lossy(&line))
-                        }
-                        _ => {}
-                    }
-                }
-            });
-            Ok(child)
-        })();
-
-        match try_sidecar {
-            Ok(child) => {
-                // Assign the sidecar process to the Windows Job Object when available.
-                if let Some(job_state) = app_handle.try_state::<BackendJob>() {
-                    if let Ok(guard) = job_state.0.lock() {
-                        if let Some(ref job) = *guard {
-                            let pid = child.pid();
-                            if windows_job::assign_pid_to_job(job, pid) {
-                                log::info!(
-                                    "JOB_OBJECT ► assigned backend sidecar pid {} to job",
-                                    pid
-                                );
-                            } else {
-                                log::debug!(
-                                    "JOB_OBJECT ► failed to assign backend sidecar pid {} to job",
-                                    pid
-                                );
-                            }
-                        }
-                    }
-                }
-                *process_guard = Some(child);
-                Ok("Backend sidecar started".to_string())
+                .env("PYTHONUTF8", "1")
+                .envs(backend_config::spawn_env(app_handle, port))
+                .args(backend_config::args(app_handle));
+            if let Some(socket) = &socket {
+                python = python.env(endpoint::SOCKET_ENV, socket);
             }
-            Err(_e) => {
-                // DEV FALLBACK: run Python backend directly from venv
-                let (mut rx, child) = app_handle
-                    .shell()
-                    .command("../../.venv/Scripts/python.exe")
-                    .args(["-X", "utf8", "main.py"])
-                    .current_dir("../../backend")
-                    .env("PYTHONIOENCODING", "utf-8")
-                    .env("PYTHONUTF8", "1")
-                    .spawn()
-                    .map_err(|err| format!("fallback python spawn error: {}", err))?;
-                tauri::async_runtime::spawn(async move {
-                    while let Some(event) = rx.recv().await {
-                        match event {
-                            CommandEvent::Stdout(line) => {
-                                log::info!("[BACKEND stdout] {}", String::from_utf8_lossy(&line))
-                            }
-                            CommandEvent::Stderr(line) => {
-                                log::error!("[BACKEND stderr] {}", String::from_utf8_lossy(&line))
-                            }
-                            _ => {}
-                        }
-                    }
-                });
-                *process_guard = Some(child);
-                Ok("Backend started via Python fallback".to_string())
+            // The dev backend keeps its data beside the sources unless a
+            // profile or portable mode asks for a folder of its own.
+            if portable::is_portable() || profiles::active(app_handle).is_some() {
+                if let Some(dir) = settings::data_dir_override(app_handle) {
+                    python = python.env(settings::DATA_DIR_ENV, dir);
+                }
             }
+            let (rx, child) = python
+                .spawn()
+                .map_err(|err| format!("fallback python spawn error: {}", err))?;
+            forward_backend_output(app_handle.clone(), rx, child.pid(), false);
+            assign_to_job(app_handle, child.pid(), "python fallback");
+            Ok((child, false))
+        }
+    };
+    if spawned.is_ok() && socket.is_none() {
+        endpoint::mark_spawned(app_handle, port);
+    }
+    spawned
+}
+
+#[derive(Clone, serde::Serialize)]
+struct JobAssignFailedPayload {
+    pid: u32,
+    kind: &'static str,
+    reason: String,
+}
+
+/// Put a freshly spawned backend into the kill-on-close Job Object so it
+/// dies with the app. A failure leaves only the explicit shutdown paths, so
+/// it is logged and reported as `backend-job-assign-failed`.
+fn assign_to_job(app_handle: &tauri::AppHandle, pid: u32, kind: &'static str) {
+    let Some(job_state) = app_handle.try_state::<BackendJob>() else {
+        return;
+    };
+    let guard = job_state.0.lock().unwrap();
+    let reason = match *guard {
+        Some(ref job) if windows_job::assign_pid_to_job(job, pid) => {
+            log::info!("JOB_OBJECT ► assigned backend {} pid {} to job", kind, pid);
+            return;
+        }
+        Some(_) => "AssignProcessToJobObject failed".to_string(),
+        // Job Objects only exist on Windows; elsewhere see unix_pgroup.
+        None if !cfg!(windows) => return,
+        None => "job object was not created at startup".to_string(),
+    };
+    log::warn!(
+        "JOB_OBJECT ► could not assign backend {} pid {} to job: {}",
+        kind,
+        pid,
+        reason
+    );
+    correlation::emit(
+        app_handle,
+        "backend-job-assign-failed",
+        JobAssignFailedPayload { pid, kind, reason },
+    );
+}
+
+/// Spawn the backend unless one is already running. Kept synchronous so
+/// exit handlers (boot retries, fallback) can call it directly.
+fn spawn_backend(app_handle: &tauri::AppHandle) -> Result<String, String> {
+    let _op = correlation::begin("start");
+    let backend_process = app_handle.state::<BackendProcess>();
+    let mut process_guard = backend_process.0.lock().unwrap();
+
+    if process_guard.is_some() {
+        return Ok("Backend already running".to_string());
+    }
+
+    // `external` mode: someone else runs the backend; just point at it.
+    if let Some(external) = backend_mode::external(app_handle) {
+        endpoint::set_address(app_handle, &external.host, external.port);
+        endpoint::set_socket(app_handle, None);
+        return Ok(format!(
+            "Attached to external backend at {}:{}",
+            external.host, external.port
+        ));
+    }
+
+    // A Plattera backend already on the preferred port (e.g. started by
+    // hand in a dev shell) is attached to instead of spawning another.
+    // Anything else holding it is named in the log before we move aside.
+    let preferred = endpoint::preferred_port(app_handle);
+    if port_in_use(preferred) {
+        if endpoint::is_plattera_backend(app_handle, preferred) {
+            endpoint::set_address(app_handle, endpoint::LOOPBACK_HOST, preferred);
+            endpoint::set_socket(app_handle, None);
+            return Ok(format!("Backend already running (detected on port {})", preferred));
         }
+        port_conflict::report(app_handle, preferred);
+    }
+
+    preflight::check_before_spawn(app_handle)?;
+    data_encryption::unseal(app_handle)?;
+
+    let port = endpoint::allocate_port(preferred)?;
+    endpoint::set_address(app_handle, endpoint::LOOPBACK_HOST, port);
+    let socket = (profiles::transport(app_handle) == endpoint::BackendTransport::LocalSocket)
+        .then(endpoint::new_socket_name);
+    endpoint::set_socket(app_handle, socket);
+    let (child, is_sidecar) = spawn_backend_child(app_handle, port)?;
+    let pid = child.pid();
+    *process_guard = Some(child);
+    backend_pid::track(app_handle, pid);
+    sidecar_fallback::note_spawn(app_handle);
+    if is_sidecar {
+        Ok("Backend sidecar started".to_string())
     } else {
-        Ok("Backend already running".to_string())
+        Ok("Backend started via Python fallback".to_string())
     }
 }
 
 #[tauri::command]
-async fn check_backend_health() -> Result<String, String> {
-    // Simple health check - in a real app you'd ping the backend
-    Ok("Backend is healthy".to_string())
+async fn start_backend(app_handle: tauri::AppHandle) -> Result<String, String> {
+    // A reloaded webview asks again; a deliberate stop stays in force.
+    if backend_control::stopped_by_user(&app_handle) {
+        return Ok("Backend stopped by the user".to_string());
+    }
+    supervisor::reset(&app_handle);
+    spawn_backend(&app_handle)
 }
 
 /// Delete all user-local data under %LOCALAPPDATA%\Plattera and restart the app.
@@ -181,12 +508,15 @@ async fn check_backend_health() -> Result<String, String> {
 /// on the uninstaller's optional data deletion checkbox.
 #[tauri::command]
 async fn factory_reset_data(app_handle: tauri::AppHandle) -> Result<(), String> {
-    use tauri::path::BaseDirectory;
+    factory_reset(&app_handle)
+}
+
+/// Shared by `factory_reset_data` and the recovery console.
+fn factory_reset(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = portable::resolve(app_handle, "").map_err(|e| e.to_string())?;
 
-    let app_data_dir = app_handle
-        .path()
-        .resolve("", BaseDirectory::AppLocalData)
-        .map_err(|e| e.to_string())?;
+    // A reset that stops halfway leaves neither the old data nor clean folders.
+    storage::check(app_handle, "factory_reset", &app_data_dir, storage::RESET_MIN_FREE)?;
 
     log::warn!("☢️ FACTORY RESET REQUESTED. Deleting: {:?}", app_data_dir);
 
@@ -200,114 +530,407 @@ async fn factory_reset_data(app_handle: tauri::AppHandle) -> Result<(), String>
     Ok(())
 }
 
-/// Open devtools for the main window. Used by both the global menu
-/// accelerator (CmdOrCtrl+Shift+I) and any frontend "open devtools"
-/// actions (for example, right‑click context menus).
+/// Open devtools for a window. Used by frontend "open devtools" actions
+/// (for example, right‑click context menus) in any window; the Tools menu
+/// accelerator (CmdOrCtrl+Shift+I) is routed in `menu.rs`. Defaults to the
+/// calling window when no label is given.
 #[tauri::command]
-async fn open_devtools(app_handle: tauri::AppHandle) -> Result<(), String> {
-    if let Some(window) = app_handle.get_webview_window("main") {
-        window.open_devtools();
-        Ok(())
-    } else {
-        Err("main window not found".into())
-    }
+async fn open_devtools(
+    app_handle: tauri::AppHandle,
+    webview_window: tauri::WebviewWindow,
+    label: Option<String>,
+) -> Result<(), String> {
+    let target = match label {
+        Some(label) => app_handle
+            .get_webview_window(&label)
+            .ok_or_else(|| format!("window '{}' not found", label))?,
+        None => webview_window,
+    };
+    target.open_devtools();
+    Ok(())
+}
+
+// Every command the webview may invoke. Expands to the invoke handler and to
+// `SHELL_COMMANDS`, the names `get_capabilities` advertises, so the two can't
+// drift apart.
+macro_rules! shell_commands {
+    ($($($seg:ident)::+),* $(,)?) => {
+        /// Registered command paths, stringified (`"zoom :: get_zoom"`).
+        const SHELL_COMMANDS: &[&str] = &[$(stringify!($($seg)::+)),*];
+
+        fn invoke_handler() -> impl Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static {
+            tauri::generate_handler![$($($seg)::+),*]
+        }
+    };
+}
+
+shell_commands! {
+    start_backend,
+    health::check_backend_health,
+    health_checks::set_health_definition,
+    debug_updater_endpoint,
+    factory_reset_data,
+    open_devtools,
+    pipelines::list_pipelines,
+    pipelines::save_pipeline,
+    pipelines::delete_pipeline,
+    pipelines::run_pipeline,
+    runtime_info::get_runtime_info,
+    version_pin::set_backend_pin,
+    standby::warm_restart_backend,
+    session::report_session_state,
+    session::restore_last_session,
+    close_guard::respond_to_close_request,
+    endpoint::get_backend_port,
+    endpoint::get_backend_url,
+    power::get_power_status,
+    trace::find_trace,
+    backend_lifecycle::get_last_shutdown_report,
+    repair::repair_installation,
+    backend_log::list_backend_logs,
+    backend_log::open_backend_log,
+    dev_migration::detect_dev_data,
+    dev_migration::import_dev_data,
+    auto_restart::cancel_pending_restart,
+    preflight::run_preflight,
+    readiness::get_readiness_report,
+    onboarding::get_onboarding_state,
+    onboarding::set_data_location,
+    onboarding::set_backups_enabled,
+    onboarding::set_update_channel,
+    onboarding::complete_onboarding,
+    paths::validate_data_path,
+    whats_new::get_whats_new,
+    rollback::prepare_update,
+    rollback::get_rollback_state,
+    rollback::rollback_update,
+    updates::start_update_download,
+    updates::get_update_status,
+    updates::install_update,
+    updates::set_install_on_exit,
+    dossier_export::export_dossier_index,
+    dossier_export::schedule_dossier_export,
+    preview::generate_preview,
+    crash_report::get_last_crash_report,
+    crash_report::dismiss_crash_report,
+    state_snapshot::get_state_snapshot,
+    telemetry::set_telemetry_enabled,
+    telemetry::get_telemetry_status,
+    log_tail::tail_logs,
+    backend_stats::get_backend_stats,
+    storage::get_storage_report,
+    data_reset::reset_data,
+    backup::create_backup,
+    backup::restore_backup,
+    profiles::list_profiles,
+    profiles::create_profile,
+    profiles::switch_profile,
+    settings::get_settings,
+    settings::update_settings,
+    dossier_windows::open_dossier_window,
+    deep_link::take_pending_deep_links,
+    file_import::import_file,
+    file_drop::import_dropped_files,
+    notifications::send_notification,
+    shortcuts::list_shortcuts,
+    shortcuts::set_shortcut,
+    recent::list_recent_dossiers,
+    recent::add_recent_dossier,
+    recent::clear_recent_dossiers,
+    i18n::get_locale,
+    i18n::set_locale,
+    theme::get_system_theme,
+    theme::set_theme_preference,
+    zoom::get_zoom,
+    zoom::zoom_in,
+    zoom::zoom_out,
+    zoom::reset_zoom,
+    zoom::set_zoom,
+    pdf_export::print_to_pdf,
+    clipboard::read_clipboard_text,
+    clipboard::write_clipboard_text,
+    clipboard::paste_clipboard_image,
+    clipboard::write_clipboard_image,
+    connectivity::get_connectivity,
+    connectivity::check_connectivity,
+    proxy::get_proxy_info,
+    tls_trust::validate_ca_bundle,
+    process_env::get_process_environment,
+    backend_update::check_backend_update,
+    backend_update::install_backend_update,
+    backend_update::rollback_backend_update,
+    diagnostics::export_diagnostics,
+    maintenance::enter_maintenance_mode,
+    maintenance::exit_maintenance_mode,
+    maintenance::get_maintenance_state,
+    backend_http::backend_request,
+    recovery::report_ui_ready,
+    recovery::is_safe_mode,
+    tasks::list_active_tasks,
+    tasks::start_backend_task,
+    tasks::get_task_progress,
+    tasks::cancel_task,
+    downloads::start_download,
+    downloads::pause_download,
+    downloads::resume_download,
+    downloads::cancel_download,
+    uploads::upload_file_to_backend,
+    uploads::resume_upload,
+    uploads::cancel_upload,
+    auth::get_backend_token,
+    backend_state::get_backend_state,
+    prewarm::run_prewarm,
+    port_conflict::diagnose_port_conflict,
+    backend_config::configure_backend,
+    backend_mode::set_backend_mode,
+    backend_control::restart_backend,
+    backend_control::stop_backend,
+    backend_control::resume_backend,
+    compat::check_version_compatibility,
+    capabilities::get_capabilities,
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    crash_report::install();
+    // `portable.flag` / `--portable`: keep all state beside the exe
+    portable::detect();
+    // Restore helper started by `rollback_update`: do the restore, never the app.
+    if rollback::apply_from_args() {
+        return;
+    }
     tauri::Builder::default()
+        // Must be the first plugin so a second launch exits before it does
+        // anything else (in particular before spawning a backend).
+        .plugin(tauri_plugin_single_instance::init(single_instance::on_second_instance))
         .manage(BackendProcess(Mutex::new(None)))
-        .manage(BackendJob(Mutex::new(windows_job::create_kill_on_close_job())))
+        .manage(endpoint::BackendEndpoint::new(endpoint::DEFAULT_PORT))
+        .manage(pipelines::PipelineRuns::new())
+        .manage(sidecar_fallback::BootTracker::new())
+        .manage(session::SessionState::new())
+        .manage(window_state::WindowStates::new())
+        .manage(deep_link::DeepLinks::new())
+        .manage(close_guard::CloseGuard::new())
+        .manage(supervisor::CrashSupervisor::new())
+        .manage(backend_log::BackendLogWriter::new())
+        .manage(auto_restart::PendingRestart::new())
+        .manage(readiness::ReadinessLog::new())
+        .manage(onboarding::OnboardingState::new())
+        .manage(backend_http::BackendClient::new())
+        .manage(tasks::TaskRegistry::new())
+        .manage(notifications::Notifications::new())
+        .manage(shortcuts::ShortcutState::new())
+        .manage(i18n::Locale::new())
+        .manage(theme::SystemTheme::new())
+        .manage(clipboard::ClipboardBridge::new())
+        .manage(connectivity::Connectivity::new())
+        .manage(whats_new::WhatsNewState::new())
+        .manage(rollback::RollbackState::new())
+        .manage(updates::UpdateManager::new())
+        .manage(preview::PreviewState::new())
+        .manage(maintenance::MaintenanceState::new())
+        .manage(telemetry::TelemetryQueue::new())
+        .manage(backend_stats::BackendStats::new())
+        .manage(splash::SplashState::new())
+        .manage(backend_state::BackendStateCell::new())
+        .manage(backend_pid::BackendIdentity::new())
+        .manage(downloads::Downloads::new())
+        .manage(uploads::Uploads::new())
+        .on_page_load(|webview, payload| {
+            session::on_page_load(webview, payload);
+            onboarding::on_page_load(webview, payload);
+            whats_new::on_page_load(webview, payload);
+        })
+        .on_menu_event(menu::on_menu_event)
+        .register_asynchronous_uri_scheme_protocol(recovery::SCHEME, recovery::handle_protocol)
+        .register_asynchronous_uri_scheme_protocol(splash::SCHEME, splash::handle_protocol)
         .setup(|app| {
             // Always register log plugin (dev + release)
+            let log_target = match portable::root() {
+                Some(_) => tauri_plugin_log::TargetKind::Folder {
+                    path: portable::app_log_dir(app.handle())?,
+                    file_name: None,
+                },
+                None => tauri_plugin_log::TargetKind::LogDir { file_name: None },
+            };
             app.handle().plugin(
                 tauri_plugin_log::Builder::default()
+                    .targets([
+                        tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Stdout),
+                        tauri_plugin_log::Target::new(log_target),
+                    ])
                     .level(log::LevelFilter::Info)
                     // Make updater and sidecar chatter as verbose as needed in logs.
                     .level_for("tauri_plugin_updater", log::LevelFilter::Trace)
                     .level_for("app_lib", log::LevelFilter::Debug)
+                    // JSON lines with the lifecycle operation's correlation ID
+                    .format(|out, message, record| {
+                        out.finish(format_args!("{}", correlation::json_line(message, record)))
+                    })
                     .build(),
             )?;
+            if let Some(root) = portable::root() {
+                log::info!("PORTABLE ► keeping app data in {:?}", root);
+            }
+            // App data layout migrations, before anything reads app data
+            migrations::run(app.handle());
+            // Persisted settings must be loaded before anything reads them
+            // (the updater's version comparator consults the backend pin).
+            let loaded = settings::load(app.handle());
+            app.manage(settings::SettingsState(Mutex::new(loaded)));
+            // Language for menu, tray and command errors
+            i18n::init(app.handle());
+            // Crash reports land next to the other app-local state
+            crash_report::configure(app.handle());
+            // Flag a brand-new install for the onboarding flow
+            onboarding::detect_first_run(app.handle());
+            // Record upgrades for the what's-new dialog
+            whats_new::detect_upgrade(app.handle());
+            // Verify a just-installed update; offer a rollback if it fails
+            rollback::check_after_update(app.handle());
+            // Forget backend patches superseded by this app version's sidecar
+            backend_update::reconcile(app.handle());
+            // Safe mode (requested from the recovery console) and the
+            // watchdog that opens that console if the UI never loads
+            let safe_mode = recovery::take_safe_mode_flag(app.handle());
+            app.manage(recovery::RecoveryState::new(safe_mode));
+            recovery::spawn_ui_watchdog(app.handle().clone());
+            // Saved size/position/maximized state; the main window stays
+            // hidden behind the splash until the backend is healthy
+            window_state::restore(app.handle());
+            splash::open(app.handle());
+            // OS theme, or the pinned light/dark preference
+            theme::apply(app.handle());
+            // Saved zoom factor for the main window
+            if let Some(window) = app.get_webview_window("main") {
+                zoom::restore(&window);
+            }
+            // plattera:// links: register the scheme, queue a launch link
+            deep_link::register(app.handle());
+            deep_link::handle_launch_args(app.handle());
+            file_import::handle_launch_args(app.handle());
 
             // Native devtools integration (including context-menu inspector)
             app.handle().plugin(tauri_plugin_devtools_app::init())?;
+            // Kill-on-close Job Object for backend children (Windows only)
+            let job = windows_job::create_kill_on_close_job();
+            if job.is_none() && cfg!(windows) {
+                log::warn!("JOB_OBJECT ► could not create kill-on-close job; backend may outlive the app");
+            }
+            app.manage(BackendJob(Mutex::new(job)));
+            // App menu (File/View/Tools/Help, routed to the focused window)
+            menu::install(app)?;
+            // Tray icon (show/hide, restart backend, updates, quit)
+            tray::install(app)?;
+            // Global shortcuts from settings (show/hide, quick capture)
+            shortcuts::apply(app.handle());
             // Register shell plugin for sidecar
             app.handle().plugin(tauri_plugin_shell::init())?;
-            // Updater plugin (GitHub Releases).
-            app.handle()
-                .plugin(tauri_plugin_updater::Builder::new().build())?;
+            // Updater plugin (GitHub Releases). The comparator keeps the
+            // default "remote is newer" rule but honours the update channel
+            // and a backend pin.
+            let pin_handle = app.handle().clone();
+            app.handle().plugin(
+                tauri_plugin_updater::Builder::new()
+                    .default_version_comparator(move |current, release| {
+                        let newer = release.version > current;
+                        newer
+                            && updates::channel_allows(&pin_handle, &release.version)
+                            && version_pin::allow_app_update(
+                                &pin_handle,
+                                &current.to_string(),
+                                &release.version.to_string(),
+                                newer,
+                            )
+                    })
+                    .build(),
+            )?;
             // Process plugin (relaunch after update)
             app.handle().plugin(tauri_plugin_process::init())?;
+            // Dialog plugin (save location for streamed downloads)
+            app.handle().plugin(tauri_plugin_dialog::init())?;
+
+            // Power-source monitor (battery deferral of background work)
+            power::spawn_monitor(app.handle().clone());
+            // Backend memory limit (countdown-and-cancel auto restart)
+            auto_restart::spawn_memory_monitor(app.handle().clone());
+            // Backend CPU / memory / handle sampling for the performance HUD
+            backend_stats::spawn_sampler(app.handle().clone());
+            // Scheduled automation pipelines
+            scheduler::spawn(app.handle().clone());
+            // Status polling for tracked backend jobs
+            tasks::spawn_poller(app.handle().clone());
+            // Opt-in telemetry upload (no-op until enabled)
+            telemetry::spawn_flusher(app.handle().clone());
+            // Reachability of the update server and cloud services
+            connectivity::spawn(app.handle().clone());
+            // Relay the backend's event stream to the webview
+            event_bridge::spawn(app.handle().clone());
+            // Periodic health pings once the backend is ready
+            health_watchdog::spawn(app.handle().clone());
+            // Restart the dev Python backend when its sources change
+            dev_reload::spawn(app.handle().clone());
             
-            // Auto-start backend when app launches
-                let app_handle = app.handle().clone();
-            thread::spawn(move || {
-                // Give a moment for the app to fully initialize
-                thread::sleep(Duration::from_millis(2000));
-                
-                // Start the backend
-                let runtime = tokio::runtime::Runtime::new().unwrap();
-                runtime.block_on(async {
-                    match start_backend(app_handle).await {
-                        Ok(msg) => log::info!("✅ {}", msg),
-                        Err(e) => log::error!("❌ Failed to start backend: {}", e),
-                    }
-                });
-                // Backend prewarm (after launch): wait for readiness, then warm dossier list
-                thread::spawn(|| {
-                    // Poll health with backoff
-                    let agent = ureq::AgentBuilder::new()
-                        .timeout_connect(Duration::from_millis(1000))
-                        .timeout(Duration::from_millis(8000))
-                        .build();
-                    let delays = [500u64, 1000, 1500, 2500];
-                    let mut ready = false;
-                    for d in delays {
-                        let res = agent.get("http://127.0.0.1:8000/api/health").call();
-                        if res.is_ok() {
-                            ready = true;
-                            break;
-                        }
-                        thread::sleep(Duration::from_millis(d));
-                    }
-                    if !ready {
-                        return; // abort silently
-                    }
-                    // Allow other startup tasks to settle
-                    thread::sleep(Duration::from_millis(1000));
-                    // Warm dossier list (ignore errors)
-                    let _ = agent
-                        .get("http://127.0.0.1:8000/api/dossier-management/list?limit=50&offset=0")
-                        .call();
-                });
-            });
-            
-            // Ctrl+C handler for dev shells to ensure same cleanup path
+            // Start the backend, then hand over from the splash
+            startup::spawn(app.handle().clone());
+
+            // Ctrl+C / SIGTERM / SIGHUP: same cleanup path, time-bounded so
+            // an OS-initiated termination (logout, shutdown) finishes in time
             {
                 let app_handle = app.handle().clone();
                 let _ = ctrlc::set_handler(move || {
-                    log::info!("Received Ctrl+C - cleaning up backend process...");
-                    shutdown_backend_for_exit(&app_handle);
+                    log::info!("Received termination signal - cleaning up backend process...");
+                    // A ready update flagged for exit installs now, in place
+                    // of the time-bounded session-end shutdown.
+                    if updates::install_pending_on_exit(&app_handle) {
+                        session::persist(&app_handle);
+                        if updates::install_on_exit(&app_handle) {
+                            std::process::exit(0);
+                        }
+                    }
+                    session_end::handle(&app_handle, "termination signal received");
                     std::process::exit(0);
                 });
             }
+            // Windows logoff/shutdown (WM_QUERYENDSESSION / WM_ENDSESSION)
+            session_end::install(app.handle().clone());
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
-            start_backend,
-            check_backend_health,
-            debug_updater_endpoint,
-            factory_reset_data,
-            open_devtools
-        ])
+        .invoke_handler(invoke_handler())
         .on_window_event(|window, event| match event {
-            tauri::WindowEvent::CloseRequested { .. } => {
-                log::info!("Window close requested - running backend shutdown routine");
-                shutdown_backend_for_exit(&window.app_handle());
+            tauri::WindowEvent::CloseRequested { api, .. } => {
+                // Shutdown runs once the frontend confirms nothing is unsaved.
+                close_guard::on_close_requested(window, api);
+            }
+            tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) => {
+                window_state::track(window);
+            }
+            tauri::WindowEvent::ThemeChanged(theme) => {
+                theme::on_theme_changed(window.app_handle(), *theme);
+            }
+            tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
+                file_drop::on_drop(window, paths.clone());
+            }
+            tauri::WindowEvent::Destroyed => {
+                session::forget_window(window.app_handle(), window.label());
             }
             _ => {}
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app_handle, _event| {
+            // macOS hands plattera:// links to the running app as an event.
+            #[cfg(target_os = "macos")]
+            if let tauri::RunEvent::Opened { urls } = _event {
+                let links = urls.iter().filter_map(|u| deep_link::parse(u.as_str())).collect();
+                deep_link::handle(_app_handle, links);
+                let files = urls
+                    .iter()
+                    .filter_map(|u| u.to_file_path().ok())
+                    .filter(|p| p.is_file())
+                    .collect();
+                file_import::handle(_app_handle, files);
+            }
+        });
 }
@@ -0,0 +1,86 @@
+// Log tail for the Help → View Logs panel, so the frontend can show recent
+// log lines without filesystem access from JS. The app log is read from the
+// newest shell log file; the backend log comes from the in-memory ring
+// buffer of this launch's sidecar output (`backend_log.rs`).
+
+use crate::{backend_log, portable};
+use serde::Deserialize;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+const MAX_LINES: usize = 5_000;
+/// Read backwards in chunks of this size until enough lines are found.
+const CHUNK_BYTES: u64 = 64 * 1024;
+/// Never read more than this from the end of a file.
+const MAX_READ_BYTES: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogSource {
+    /// The shell's own log (tauri-plugin-log, JSON lines).
+    App,
+    /// Backend stdout/stderr captured by the shell.
+    Backend,
+}
+
+fn newest_log(dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|x| x == "log"))
+        .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+        .max()
+        .map(|(_, path)| path)
+}
+
+/// The last `n` lines of `path`.
+fn tail_file(path: &Path, n: usize) -> Result<Vec<String>, String> {
+    let mut file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let len = file.metadata().map_err(|e| e.to_string())?.len();
+    let mut start = len;
+    let mut data: Vec<u8> = Vec::new();
+    // One extra newline: the first line of a chunk is usually cut.
+    while start > 0
+        && len - start < MAX_READ_BYTES
+        && data.iter().filter(|b| **b == b'\n').count() <= n
+    {
+        let from = start.saturating_sub(CHUNK_BYTES);
+        let mut chunk = vec![0u8; (start - from) as usize];
+        file.seek(SeekFrom::Start(from))
+            .and_then(|_| file.read_exact(&mut chunk))
+            .map_err(|e| e.to_string())?;
+        chunk.extend_from_slice(&data);
+        data = chunk;
+        start = from;
+    }
+    let text = String::from_utf8_lossy(&data);
+    let mut lines: Vec<&str> = text.lines().collect();
+    if start > 0 && !lines.is_empty() {
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(n);
+    Ok(lines[skip..].iter().map(|l| l.to_string()).collect())
+}
+
+/// The last `lines` lines of the app or backend log, oldest first.
+#[tauri::command]
+pub async fn tail_logs(
+    app_handle: tauri::AppHandle,
+    source: LogSource,
+    lines: usize,
+) -> Result<Vec<String>, String> {
+    let n = lines.clamp(1, MAX_LINES);
+    match source {
+        LogSource::Backend => Ok(backend_log::recent(&app_handle, n)),
+        LogSource::App => {
+            let dir = portable::app_log_dir(&app_handle).map_err(|e| e.to_string())?;
+            tauri::async_runtime::spawn_blocking(move || match newest_log(&dir) {
+                Some(path) => tail_file(&path, n),
+                None => Ok(Vec::new()),
+            })
+            .await
+            .map_err(|e| e.to_string())?
+        }
+    }
+}
@@ -0,0 +1,166 @@
+// Loopback binding check. The backend holds the user's data and must only
+// listen on the loopback interface, but launch settings (`backend_config.rs`)
+// or a modified backend can move it elsewhere. Once startup has the backend
+// healthy, `check_after_startup` looks up the addresses listening on its port
+// in the OS socket table (`port_conflict.rs`). A listener on anything beyond
+// loopback (0.0.0.0, ::, a LAN address) is logged and emitted as
+// `backend-exposed`. With `lifecycle.enforce_loopback_bind` the backend is
+// then restarted without the `--host` launch flags for the rest of the
+// session, so it listens on the 127.0.0.1 the shell passes it, and checked
+// again. Local-socket transport and `external` mode have no
+// listener of ours to check.
+
+use crate::backend_mode::{self, BackendMode};
+use crate::{backend_lifecycle, correlation, endpoint, port_conflict, settings, startup};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set once an exposed backend has been restarted; later spawns keep the
+/// forced address.
+static FORCED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BindCheck {
+    pub port: u16,
+    /// Addresses listening on the port.
+    pub addresses: Vec<String>,
+    pub exposed: bool,
+    /// The backend was restarted with loopback forced because of it.
+    pub enforced: bool,
+}
+
+pub fn forced() -> bool {
+    FORCED.load(Ordering::SeqCst)
+}
+
+/// Drop `--host <addr>` / `--host=<addr>` from launch flags.
+pub fn strip_host_args(args: Vec<String>) -> Vec<String> {
+    let mut out = Vec::with_capacity(args.len());
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--host" {
+            args.next();
+        } else if !arg.starts_with("--host=") {
+            out.push(arg);
+        }
+    }
+    out
+}
+
+/// Addresses the backend listens on. `None` when there is no TCP listener
+/// of ours to check, or the socket table could not be read. Blocking.
+fn inspect(app_handle: &tauri::AppHandle) -> Option<BindCheck> {
+    if backend_mode::current(app_handle) == BackendMode::External
+        || endpoint::socket(app_handle).is_some()
+    {
+        return None;
+    }
+    let port = endpoint::port(app_handle);
+    let addresses = port_conflict::listening_addresses(port);
+    if addresses.is_empty() {
+        log::info!(
+            "LOOPBACK_BIND ► no listener found on port {}; binding not checked",
+            port
+        );
+        return None;
+    }
+    Some(BindCheck {
+        port,
+        exposed: addresses.iter().any(|a| !a.is_loopback()),
+        addresses: addresses.iter().map(|a| a.to_string()).collect(),
+        enforced: false,
+    })
+}
+
+async fn inspect_async(app_handle: &tauri::AppHandle) -> Option<BindCheck> {
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || inspect(&handle))
+        .await
+        .ok()
+        .flatten()
+}
+
+fn report(app_handle: &tauri::AppHandle, check: &BindCheck) {
+    log::error!(
+        "LOOPBACK_BIND ► backend on port {} is reachable beyond this machine: listening on {}",
+        check.port,
+        check.addresses.join(", ")
+    );
+    correlation::emit(app_handle, "backend-exposed", check.clone());
+}
+
+/// Verify the freshly started backend listens on loopback only, and
+/// restart it bound to loopback when that is enforced. An error means the
+/// restarted backend did not come back.
+pub async fn check_after_startup(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let Some(mut check) = inspect_async(app_handle).await else {
+        return Ok(());
+    };
+    if !check.exposed {
+        log::info!(
+            "LOOPBACK_BIND ► backend listens on {} only",
+            check.addresses.join(", ")
+        );
+        return Ok(());
+    }
+    let enforce = settings::current(app_handle)
+        .lifecycle
+        .enforce_loopback_bind
+        && !FORCED.swap(true, Ordering::SeqCst);
+    check.enforced = enforce;
+    report(app_handle, &check);
+    if !enforce {
+        return Ok(());
+    }
+
+    log::warn!(
+        "LOOPBACK_BIND ► restarting the backend bound to {}",
+        endpoint::LOOPBACK_HOST
+    );
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || backend_lifecycle::restart_backend(&handle))
+        .await
+        .map_err(|e| e.to_string())??;
+    startup::wait_until_healthy(app_handle).await?;
+    if let Some(check) = inspect_async(app_handle).await {
+        if check.exposed {
+            // Something other than the launch settings binds it; say so again.
+            report(app_handle, &check);
+        } else {
+            log::info!("LOOPBACK_BIND ► backend now listens on loopback only");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn host_flags_are_dropped_with_their_value() {
+        let stripped = strip_host_args(args(&[
+            "--log-level",
+            "debug",
+            "--host",
+            "0.0.0.0",
+            "--host=::",
+            "--workers",
+            "2",
+        ]));
+        assert_eq!(stripped, args(&["--log-level", "debug", "--workers", "2"]));
+    }
+
+    #[test]
+    fn trailing_host_flag_and_lookalikes() {
+        assert!(strip_host_args(args(&["--host"])).is_empty());
+        assert_eq!(
+            strip_host_args(args(&["--hostname", "x", "--port", "9000"])),
+            args(&["--hostname", "x", "--port", "9000"])
+        );
+    }
+}
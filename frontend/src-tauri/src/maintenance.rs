@@ -0,0 +1,155 @@
+// Time-boxed maintenance mode. While a user does manual work on the data
+// folder (restoring a backup, fixing a dossier file by hand) the shell's
+// automation would otherwise fight them: scheduled pipelines and exports
+// start, the crash supervisor brings a deliberately stopped backend back,
+// the memory monitor schedules restarts. Maintenance mode pauses all of that
+// for a fixed window and announces itself via `maintenance-mode` so the
+// frontend can show a banner. It ends on its own when the window elapses,
+// or earlier through `exit_maintenance_mode`.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+
+/// Longest window a single request may ask for.
+const MAX_DURATION: Duration = Duration::from_secs(4 * 3600);
+
+struct Window {
+    /// Distinguishes windows so an expired timer can't end a newer one.
+    id: u64,
+    until: Instant,
+    ends_at: chrono::DateTime<chrono::Local>,
+    reason: Option<String>,
+}
+
+#[derive(Default)]
+struct Inner {
+    window: Option<Window>,
+    next_id: u64,
+}
+
+pub struct MaintenanceState(Mutex<Inner>);
+
+impl MaintenanceState {
+    pub fn new() -> Self {
+        MaintenanceState(Mutex::new(Inner::default()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MaintenanceStatus {
+    pub active: bool,
+    /// RFC 3339 end of the window.
+    pub ends_at: Option<String>,
+    pub remaining_secs: u64,
+    pub reason: Option<String>,
+}
+
+fn status_of(inner: &Inner) -> MaintenanceStatus {
+    match inner.window.as_ref().filter(|w| w.until > Instant::now()) {
+        Some(w) => MaintenanceStatus {
+            active: true,
+            ends_at: Some(w.ends_at.to_rfc3339()),
+            remaining_secs: w.until.saturating_duration_since(Instant::now()).as_secs(),
+            reason: w.reason.clone(),
+        },
+        None => MaintenanceStatus {
+            active: false,
+            ends_at: None,
+            remaining_secs: 0,
+            reason: None,
+        },
+    }
+}
+
+pub fn status(app_handle: &tauri::AppHandle) -> MaintenanceStatus {
+    status_of(&app_handle.state::<MaintenanceState>().0.lock().unwrap())
+}
+
+/// Whether automation should stand down right now.
+pub fn active(app_handle: &tauri::AppHandle) -> bool {
+    app_handle
+        .try_state::<MaintenanceState>()
+        .map(|state| status_of(&state.0.lock().unwrap()).active)
+        .unwrap_or(false)
+}
+
+fn end(app_handle: &tauri::AppHandle, id: Option<u64>, why: &str) -> bool {
+    let status = {
+        let state = app_handle.state::<MaintenanceState>();
+        let mut inner = state.0.lock().unwrap();
+        let matches = match (&inner.window, id) {
+            (Some(w), Some(id)) => w.id == id,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if !matches {
+            return false;
+        }
+        inner.window = None;
+        status_of(&inner)
+    };
+    log::info!("MAINTENANCE ► maintenance mode ended ({})", why);
+    let _ = app_handle.emit("maintenance-mode", &status);
+    true
+}
+
+/// Pause background automation for `duration_secs` (capped at four hours).
+/// Calling it again replaces the current window.
+#[tauri::command]
+pub async fn enter_maintenance_mode(
+    app_handle: tauri::AppHandle,
+    duration_secs: u64,
+    reason: Option<String>,
+) -> Result<MaintenanceStatus, String> {
+    if duration_secs == 0 {
+        return Err("duration must be at least one second".into());
+    }
+    let duration = Duration::from_secs(duration_secs).min(MAX_DURATION);
+    let (id, status) = {
+        let state = app_handle.state::<MaintenanceState>();
+        let mut inner = state.0.lock().unwrap();
+        inner.next_id += 1;
+        let id = inner.next_id;
+        inner.window = Some(Window {
+            id,
+            until: Instant::now() + duration,
+            ends_at: chrono::Local::now()
+                + chrono::Duration::from_std(duration).map_err(|e| e.to_string())?,
+            reason: reason.filter(|r| !r.trim().is_empty()),
+        });
+        (id, status_of(&inner))
+    };
+    log::warn!(
+        "MAINTENANCE ► automation paused for {}s{}",
+        duration.as_secs(),
+        status
+            .reason
+            .as_deref()
+            .map(|r| format!(": {}", r))
+            .unwrap_or_default()
+    );
+    let _ = app_handle.emit("maintenance-mode", &status);
+
+    let handle = app_handle.clone();
+    thread::spawn(move || {
+        thread::sleep(duration);
+        end(&handle, Some(id), "window elapsed");
+    });
+    Ok(status)
+}
+
+/// End maintenance mode early. Returns false if it wasn't active.
+#[tauri::command]
+pub async fn exit_maintenance_mode(app_handle: tauri::AppHandle) -> Result<bool, String> {
+    Ok(end(&app_handle, None, "ended by user"))
+}
+
+#[tauri::command]
+pub async fn get_maintenance_state(
+    app_handle: tauri::AppHandle,
+) -> Result<MaintenanceStatus, String> {
+    Ok(status(&app_handle))
+}
@@ -0,0 +1,209 @@
+// Application menu. Menu actions that act on "the current window" (such as
+// opening devtools) are routed to whichever webview window has focus, so
+// secondary windows (viewers, log viewer, dossier windows) get the same
+// behaviour as the main window without per-window wiring.
+//
+// Items the shell can carry out itself (zoom, Reload, Quit, devtools) are
+// handled here; everything else reaches the focused window as a typed
+// `menu-action` event, e.g. `{ "action": "open_recent", "dossier_id": "123" }`.
+// Quit goes through the main window's close, so the unsaved-changes guard
+// still runs. File → Open Recent lists `recent.rs`'s dossiers and is rebuilt
+// whenever that list changes. Labels come from `i18n.rs`.
+
+use crate::{close_guard, i18n, recent, zoom};
+use serde::Serialize;
+use tauri::menu::{Menu, MenuBuilder, MenuEvent, MenuItemBuilder, SubmenuBuilder};
+use tauri::{App, AppHandle, Emitter, Manager, WebviewWindow, Wry};
+
+pub const OPEN_DEVTOOLS_ID: &str = "open_devtools";
+const NEW_DOSSIER_ID: &str = "file_new_dossier";
+const EXPORT_ID: &str = "file_export";
+const QUIT_ID: &str = "file_quit";
+const RECENT_PREFIX: &str = "file_recent:";
+const CLEAR_RECENT_ID: &str = "file_recent_clear";
+const ZOOM_IN_ID: &str = "view_zoom_in";
+const ZOOM_OUT_ID: &str = "view_zoom_out";
+const ZOOM_RESET_ID: &str = "view_zoom_reset";
+const RELOAD_ID: &str = "view_reload";
+const LOGS_ID: &str = "help_logs";
+const DIAGNOSTICS_ID: &str = "help_diagnostics";
+const CHECK_UPDATES_ID: &str = "help_check_updates";
+
+/// Accelerators the menu binds, for conflict checks elsewhere.
+pub const ACCELERATORS: &[&str] = &[
+    "CmdOrCtrl+N",
+    "CmdOrCtrl+E",
+    "CmdOrCtrl+Q",
+    "CmdOrCtrl+=",
+    "CmdOrCtrl+-",
+    "CmdOrCtrl+0",
+    "CmdOrCtrl+R",
+    "CmdOrCtrl+Shift+I",
+];
+
+/// Payload of `menu-action`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum MenuAction {
+    NewDossier,
+    OpenRecent { dossier_id: String },
+    Export,
+    ShowLogs,
+    ShowDiagnostics,
+    CheckForUpdates,
+}
+
+fn build(app_handle: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let tr = |key: &str| i18n::t(app_handle, key);
+    let item = |id: &str, key: &str, accel: Option<&str>| {
+        let mut builder = MenuItemBuilder::with_id(id, tr(key));
+        if let Some(accel) = accel {
+            builder = builder.accelerator(accel);
+        }
+        builder.build(app_handle)
+    };
+
+    let items = recent::load(app_handle);
+    let mut open_recent = SubmenuBuilder::new(app_handle, tr("menu.open_recent"));
+    for entry in &items {
+        let label = if entry.title.is_empty() {
+            entry.id.as_str()
+        } else {
+            entry.title.as_str()
+        };
+        let id = format!("{}{}", RECENT_PREFIX, entry.id);
+        open_recent = open_recent.item(&MenuItemBuilder::with_id(id, label).build(app_handle)?);
+    }
+    if !items.is_empty() {
+        open_recent = open_recent.separator();
+    }
+    let clear = MenuItemBuilder::with_id(CLEAR_RECENT_ID, tr("menu.clear_recent"))
+        .enabled(!items.is_empty())
+        .build(app_handle)?;
+    let open_recent = open_recent.item(&clear).build()?;
+
+    let file = SubmenuBuilder::new(app_handle, tr("menu.file"))
+        .item(&item(
+            NEW_DOSSIER_ID,
+            "menu.new_dossier",
+            Some("CmdOrCtrl+N"),
+        )?)
+        .item(&open_recent)
+        .item(&item(EXPORT_ID, "menu.export", Some("CmdOrCtrl+E"))?)
+        .separator()
+        .item(&item(QUIT_ID, "menu.quit", Some("CmdOrCtrl+Q"))?)
+        .build()?;
+    let view = SubmenuBuilder::new(app_handle, tr("menu.view"))
+        .item(&item(ZOOM_IN_ID, "menu.zoom_in", Some("CmdOrCtrl+="))?)
+        .item(&item(ZOOM_OUT_ID, "menu.zoom_out", Some("CmdOrCtrl+-"))?)
+        .item(&item(
+            ZOOM_RESET_ID,
+            "menu.zoom_reset",
+            Some("CmdOrCtrl+0"),
+        )?)
+        .separator()
+        .item(&item(RELOAD_ID, "menu.reload", Some("CmdOrCtrl+R"))?)
+        .build()?;
+    let tools = SubmenuBuilder::new(app_handle, tr("menu.tools"))
+        .item(&item(
+            OPEN_DEVTOOLS_ID,
+            "menu.open_devtools",
+            Some("CmdOrCtrl+Shift+I"),
+        )?)
+        .build()?;
+    let help = SubmenuBuilder::new(app_handle, tr("menu.help"))
+        .item(&item(LOGS_ID, "menu.logs", None)?)
+        .item(&item(DIAGNOSTICS_ID, "menu.diagnostics", None)?)
+        .separator()
+        .item(&item(CHECK_UPDATES_ID, "menu.check_updates", None)?)
+        .build()?;
+    MenuBuilder::new(app_handle)
+        .item(&file)
+        .item(&view)
+        .item(&tools)
+        .item(&help)
+        .build()
+}
+
+/// Build and install the app menu.
+pub fn install(app: &App) -> tauri::Result<()> {
+    refresh(app.handle())
+}
+
+/// Rebuild the menu, e.g. after the recent list changed.
+pub fn refresh(app_handle: &AppHandle) -> tauri::Result<()> {
+    app_handle.set_menu(build(app_handle)?)?;
+    Ok(())
+}
+
+/// The focused webview window, falling back to the main window.
+pub fn focused_webview_window(app_handle: &AppHandle) -> Option<WebviewWindow> {
+    app_handle
+        .webview_windows()
+        .into_values()
+        .find(|w| w.is_focused().unwrap_or(false))
+        .or_else(|| app_handle.get_webview_window("main"))
+}
+
+fn send(app_handle: &AppHandle, action: MenuAction) {
+    let Some(window) = focused_webview_window(app_handle) else {
+        log::warn!("MENU ► {:?} requested but no window is available", action);
+        return;
+    };
+    log::debug!("MENU ► {:?} → '{}'", action, window.label());
+    let _ = app_handle.emit_to(window.label(), "menu-action", action);
+}
+
+pub fn on_menu_event(app_handle: &AppHandle, event: MenuEvent) {
+    let id = event.id().as_ref();
+    if let Some(dossier_id) = id.strip_prefix(RECENT_PREFIX) {
+        send(
+            app_handle,
+            MenuAction::OpenRecent {
+                dossier_id: dossier_id.to_string(),
+            },
+        );
+        return;
+    }
+    match id {
+        OPEN_DEVTOOLS_ID => match focused_webview_window(app_handle) {
+            Some(window) => {
+                log::debug!("MENU ► opening devtools for '{}'", window.label());
+                window.open_devtools();
+            }
+            None => log::warn!("MENU ► open devtools requested but no window is available"),
+        },
+        RELOAD_ID => {
+            if let Some(window) = focused_webview_window(app_handle) {
+                log::info!("MENU ► reloading '{}'", window.label());
+                let _ = window.reload();
+            }
+        }
+        QUIT_ID => {
+            log::info!("MENU ► quit requested");
+            close_guard::quit(app_handle);
+        }
+        CLEAR_RECENT_ID => {
+            let handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let _ = recent::clear_recent_dossiers(handle).await;
+            });
+        }
+        NEW_DOSSIER_ID => send(app_handle, MenuAction::NewDossier),
+        EXPORT_ID => send(app_handle, MenuAction::Export),
+        ZOOM_IN_ID | ZOOM_OUT_ID | ZOOM_RESET_ID => {
+            let result = match id {
+                ZOOM_IN_ID => zoom::step(app_handle, true),
+                ZOOM_OUT_ID => zoom::step(app_handle, false),
+                _ => zoom::reset(app_handle),
+            };
+            if let Err(e) = result {
+                log::warn!("MENU ► zoom failed: {}", e);
+            }
+        }
+        LOGS_ID => send(app_handle, MenuAction::ShowLogs),
+        DIAGNOSTICS_ID => send(app_handle, MenuAction::ShowDiagnostics),
+        CHECK_UPDATES_ID => send(app_handle, MenuAction::CheckForUpdates),
+        _ => {}
+    }
+}
@@ -0,0 +1,165 @@
+// Versioned migrations of the shell's app data layout (AppLocalData). They
+// run once at startup, before settings are loaded and before the backend is
+// spawned, so nothing holds the files they move. Each applied migration is
+// recorded in `migrations.json`; on the next launch only the newer ones run,
+// so a user skipping several versions gets every step in order.
+//
+// A failed migration stops the run: later steps may depend on it. It is
+// retried on the next launch, and the app starts either way. Migrations
+// must be safe to re-run after a partial attempt.
+//
+// To add one, append to `MIGRATIONS` with the next id. Never renumber or
+// remove an entry that has shipped.
+
+use crate::backend_pid::{self, ProcessIdentity};
+use crate::portable;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MANIFEST_FILE: &str = "migrations.json";
+
+struct Migration {
+    id: u32,
+    name: &'static str,
+    run: fn(&Path) -> Result<(), String>,
+}
+
+/// In order. `run` receives the app data folder.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        id: 1,
+        name: "remove_stale_temp_files",
+        run: remove_stale_temp_files,
+    },
+    Migration {
+        id: 2,
+        name: "move_legacy_pid_file",
+        run: move_legacy_pid_file,
+    },
+];
+
+/// Where dev builds used to keep the backend pid, relative to src-tauri.
+const LEGACY_PID_FILE: &str = "../../backend/.server.pid";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct AppliedMigration {
+    id: u32,
+    name: String,
+    applied_at: String,
+    app_version: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct MigrationManifest {
+    /// Highest migration id applied.
+    version: u32,
+    applied: Vec<AppliedMigration>,
+}
+
+/// Leftovers of interrupted atomic writes (`settings.json.tmp` and the like).
+fn remove_stale_temp_files(dir: &Path) -> Result<(), String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.is_file() && path.to_string_lossy().ends_with(".json.tmp") {
+            fs::remove_file(&path).map_err(|e| format!("remove {:?}: {}", path, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// The old plain-pid file becomes a `backend.pid` identity record when that
+/// process is still running; either way the old file goes.
+fn move_legacy_pid_file(dir: &Path) -> Result<(), String> {
+    let legacy = Path::new(LEGACY_PID_FILE);
+    let Ok(raw) = fs::read_to_string(legacy) else {
+        return Ok(());
+    };
+    if let Some(identity) = raw.trim().parse().ok().and_then(ProcessIdentity::capture) {
+        backend_pid::write_record(&dir.join(backend_pid::PID_FILE), &identity)?;
+    }
+    fs::remove_file(legacy).map_err(|e| format!("remove {:?}: {}", legacy, e))
+}
+
+fn manifest_path(dir: &Path) -> PathBuf {
+    dir.join(MANIFEST_FILE)
+}
+
+fn load_manifest(dir: &Path) -> MigrationManifest {
+    fs::read_to_string(manifest_path(dir))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_manifest(dir: &Path, manifest: &MigrationManifest) -> Result<(), String> {
+    let path = manifest_path(dir);
+    let raw = serde_json::to_string_pretty(manifest).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, raw).map_err(|e| format!("write {:?}: {}", tmp, e))?;
+    fs::rename(&tmp, &path).map_err(|e| format!("rename {:?}: {}", tmp, e))
+}
+
+/// Apply pending migrations. Called from setup before anything else reads
+/// app data.
+pub fn run(app_handle: &tauri::AppHandle) {
+    let dir = match portable::resolve(app_handle, "") {
+        Ok(dir) => dir,
+        Err(e) => {
+            log::warn!("MIGRATIONS ► could not resolve app data folder: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = fs::create_dir_all(&dir) {
+        log::warn!("MIGRATIONS ► could not create {:?}: {}", dir, e);
+        return;
+    }
+    let mut manifest = load_manifest(&dir);
+    let latest = MIGRATIONS.last().map_or(0, |m| m.id);
+    if manifest.version > latest {
+        // Data from a newer build: leave it alone rather than guess.
+        log::warn!(
+            "MIGRATIONS ► app data is at layout {}, this version knows up to {}",
+            manifest.version,
+            latest
+        );
+        return;
+    }
+
+    let version = app_handle.package_info().version.to_string();
+    let from = manifest.version;
+    for migration in MIGRATIONS.iter().filter(|m| m.id > from) {
+        log::info!(
+            "MIGRATIONS ► applying {} ({})",
+            migration.id,
+            migration.name
+        );
+        if let Err(e) = (migration.run)(&dir) {
+            log::error!(
+                "MIGRATIONS ► {} ({}) failed, will retry next launch: {}",
+                migration.id,
+                migration.name,
+                e
+            );
+            return;
+        }
+        manifest.version = migration.id;
+        manifest.applied.push(AppliedMigration {
+            id: migration.id,
+            name: migration.name.to_string(),
+            applied_at: chrono::Local::now().to_rfc3339(),
+            app_version: version.clone(),
+        });
+        // Record each step as it lands so a crash doesn't repeat it.
+        if let Err(e) = save_manifest(&dir, &manifest) {
+            log::error!(
+                "MIGRATIONS ► could not record migration {}: {}",
+                migration.id,
+                e
+            );
+            return;
+        }
+    }
+}
@@ -0,0 +1,269 @@
+// OS notifications, mainly for long backend jobs finishing while the user is
+// in another app ("Transcription finished"). The task registry asks for one
+// when a tracked job ends; the frontend can send its own with
+// `send_notification`. Each notification may carry a frontend route:
+// clicking it focuses the main window and emits `navigate` with that route.
+//
+// There is no notification plugin in the build, so delivery goes through
+// the platform's own tools:
+//   Windows  a toast shown via PowerShell; its click opens
+//            `plattera://notification/<id>`, which comes back through the
+//            deep-link path (`deep_link.rs`)
+//   Linux    `notify-send --wait` with a default action, whose output tells
+//            whether the notification was clicked
+//   macOS    `osascript display notification`, without click-through
+
+use crate::tasks::{BackendTask, TaskKind};
+use crate::{i18n, settings, trace};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+
+/// Clicks on notifications older than this many are ignored.
+const MAX_PENDING: usize = 32;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+    /// Skip notifications while one of the app's windows has focus.
+    pub only_when_unfocused: bool,
+    /// Notify when a tracked backend job finishes.
+    pub task_completion: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        NotificationSettings {
+            enabled: true,
+            only_when_unfocused: true,
+            task_completion: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct NavigatePayload {
+    route: String,
+    source: &'static str,
+}
+
+/// Routes of shown notifications by id, oldest first.
+pub struct Notifications(Mutex<Vec<(String, Option<String>)>>);
+
+impl Notifications {
+    pub fn new() -> Self {
+        Notifications(Mutex::new(Vec::new()))
+    }
+}
+
+fn any_window_focused(app_handle: &tauri::AppHandle) -> bool {
+    app_handle
+        .webview_windows()
+        .values()
+        .any(|w| w.is_focused().unwrap_or(false))
+}
+
+/// Show a notification unless settings or focus say otherwise. Returns its
+/// id when one was shown.
+pub fn notify(
+    app_handle: &tauri::AppHandle,
+    title: &str,
+    body: &str,
+    route: Option<String>,
+) -> Option<String> {
+    let config = settings::current(app_handle).notifications;
+    if !config.enabled || (config.only_when_unfocused && any_window_focused(app_handle)) {
+        return None;
+    }
+    let id = trace::new_id();
+    {
+        let state = app_handle.state::<Notifications>();
+        let mut pending = state.0.lock().unwrap();
+        if pending.len() >= MAX_PENDING {
+            pending.remove(0);
+        }
+        pending.push((id.clone(), route));
+    }
+    log::info!("NOTIFICATIONS ► [{}] {}", id, title);
+    let app_handle = app_handle.clone();
+    let (title, body, notification_id) = (title.to_string(), body.to_string(), id.clone());
+    std::thread::spawn(move || {
+        if let Err(e) = show(&app_handle, &notification_id, &title, &body) {
+            log::warn!("NOTIFICATIONS ► could not show notification: {}", e);
+        }
+    });
+    Some(id)
+}
+
+/// A notification was clicked: focus the main window and navigate to its
+/// route.
+pub fn clicked(app_handle: &tauri::AppHandle, id: &str) {
+    let route = {
+        let state = app_handle.state::<Notifications>();
+        let mut pending = state.0.lock().unwrap();
+        let Some(pos) = pending.iter().position(|(p, _)| p == id) else {
+            return;
+        };
+        pending.remove(pos).1
+    };
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    if let Some(route) = route {
+        let _ = app_handle.emit_to(
+            "main",
+            "navigate",
+            NavigatePayload {
+                route,
+                source: "notification",
+            },
+        );
+    }
+}
+
+/// Notify about a tracked job that ended with status `body`.
+pub fn task_finished(app_handle: &tauri::AppHandle, task: &BackendTask, body: &Value) {
+    if !settings::current(app_handle).notifications.task_completion {
+        return;
+    }
+    let (title, text, route) = match task.kind {
+        TaskKind::ImageToText => match body.get("status").and_then(Value::as_str) {
+            Some("SUCCEEDED") => (
+                "Transcription finished",
+                "The transcription is ready.",
+                "process",
+            ),
+            Some("FAILED") => (
+                "Transcription failed",
+                "Open Plattera to see what went wrong.",
+                "process",
+            ),
+            _ => return,
+        },
+        TaskKind::DossierBulkDelete => (
+            "Dossiers deleted",
+            "The bulk delete has finished.",
+            "process",
+        ),
+        TaskKind::PlssDownload => (
+            "PLSS download finished",
+            "PLSS data for the state is ready.",
+            "mapping",
+        ),
+    };
+    notify(app_handle, title, text, Some(route.to_string()));
+}
+
+#[cfg(windows)]
+fn show(app_handle: &tauri::AppHandle, id: &str, title: &str, body: &str) -> Result<(), String> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    const SCRIPT: &str = "\
+        [Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null; \
+        [Windows.Data.Xml.Dom.XmlDocument, Windows.Data.Xml.Dom.XmlDocument, ContentType = WindowsRuntime] > $null; \
+        $xml = New-Object Windows.Data.Xml.Dom.XmlDocument; \
+        $xml.LoadXml($env:PLATTERA_TOAST_XML); \
+        $toast = New-Object Windows.UI.Notifications.ToastNotification $xml; \
+        [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier($env:PLATTERA_TOAST_APP).Show($toast)";
+
+    fn escape(s: &str) -> String {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+    let xml = format!(
+        "<toast activationType=\"protocol\" launch=\"{}://notification/{}\">\
+         <visual><binding template=\"ToastGeneric\"><text>{}</text><text>{}</text></binding></visual>\
+         </toast>",
+        crate::deep_link::SCHEME,
+        id,
+        escape(title),
+        escape(body)
+    );
+    let status = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+        .env("PLATTERA_TOAST_XML", xml)
+        .env("PLATTERA_TOAST_APP", &app_handle.config().identifier)
+        .creation_flags(CREATE_NO_WINDOW)
+        .status()
+        .map_err(|e| format!("powershell: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("powershell exited with {}", status))
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn show(app_handle: &tauri::AppHandle, id: &str, title: &str, body: &str) -> Result<(), String> {
+    use std::process::Command;
+    // `--wait` blocks until the notification is closed and prints the
+    // action taken; notify-send older than 0.7.9 rejects the flags.
+    let output = Command::new("notify-send")
+        .args([
+            "--app-name=Plattera",
+            "--action=default=Open",
+            "--wait",
+            title,
+            body,
+        ])
+        .output()
+        .map_err(|e| format!("notify-send: {}", e))?;
+    if output.status.success() {
+        if String::from_utf8_lossy(&output.stdout).trim() == "default" {
+            clicked(app_handle, id);
+        }
+        return Ok(());
+    }
+    let status = Command::new("notify-send")
+        .args(["--app-name=Plattera", title, body])
+        .status()
+        .map_err(|e| format!("notify-send: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("notify-send exited with {}", status))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn show(_app_handle: &tauri::AppHandle, _id: &str, title: &str, body: &str) -> Result<(), String> {
+    let status = std::process::Command::new("osascript")
+        .args([
+            "-e",
+            "on run argv",
+            "-e",
+            "display notification (item 2 of argv) with title (item 1 of argv)",
+            "-e",
+            "end run",
+            title,
+            body,
+        ])
+        .status()
+        .map_err(|e| format!("osascript: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("osascript exited with {}", status))
+    }
+}
+
+/// Show an OS notification; clicking it navigates to `route`. Returns the
+/// notification id, or `None` when settings or window focus suppressed it.
+#[tauri::command]
+pub async fn send_notification(
+    app_handle: tauri::AppHandle,
+    title: String,
+    body: String,
+    route: Option<String>,
+) -> Result<Option<String>, String> {
+    if title.trim().is_empty() {
+        return Err(i18n::t(&app_handle, "error.empty_notification_title"));
+    }
+    Ok(notify(&app_handle, &title, &body, route))
+}
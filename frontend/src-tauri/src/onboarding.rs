@@ -0,0 +1,183 @@
+// First-run onboarding hooks. A fresh install (no settings file and no
+// backend data yet) is flagged at startup; once the main window has loaded
+// the shell emits `first-run` so the frontend can walk the user through the
+// data location, backups and update channel. Importing existing data reuses
+// `detect_dev_data` / `import_dev_data`. The flag clears only when the flow
+// calls `complete_onboarding`, so quitting halfway shows it again.
+
+use crate::backend_lifecycle;
+use crate::updates::UpdateChannel;
+use crate::{paths, settings, BackendProcess};
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::webview::{PageLoadEvent, PageLoadPayload};
+use tauri::{Emitter, Manager, Webview};
+
+pub struct OnboardingState {
+    /// Set once `first-run` has been sent to the main window.
+    announced: AtomicBool,
+}
+
+impl OnboardingState {
+    pub fn new() -> Self {
+        OnboardingState {
+            announced: AtomicBool::new(false),
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+pub struct OnboardingInfo {
+    first_run: bool,
+    data_dir: String,
+    default_data_dir: String,
+    custom_data_dir: bool,
+    backups_enabled: bool,
+    update_channel: UpdateChannel,
+}
+
+/// Startup check, run right after settings are loaded. Only a brand-new
+/// install is flagged; upgrades from versions without onboarding are not.
+pub fn detect_first_run(app_handle: &tauri::AppHandle) {
+    if settings::exists(app_handle) {
+        return;
+    }
+    let has_data = settings::default_data_dir(app_handle)
+        .map(|d| d.exists())
+        .unwrap_or(false);
+    if has_data {
+        return;
+    }
+    log::info!("ONBOARDING ► no settings or data found; treating as first run");
+    if let Err(e) = settings::update(app_handle, |s| s.onboarding_completed = false) {
+        log::warn!("ONBOARDING ► could not persist first-run flag: {}", e);
+    }
+}
+
+fn info(app_handle: &tauri::AppHandle) -> Result<OnboardingInfo, String> {
+    let current = settings::current(app_handle);
+    Ok(OnboardingInfo {
+        first_run: !current.onboarding_completed,
+        data_dir: settings::backend_data_dir(app_handle)?
+            .to_string_lossy()
+            .into_owned(),
+        default_data_dir: settings::default_data_dir(app_handle)?
+            .to_string_lossy()
+            .into_owned(),
+        custom_data_dir: current.data_dir.is_some(),
+        backups_enabled: current.backups_enabled,
+        update_channel: current.update_channel,
+    })
+}
+
+/// Page-load hook: the first time the main window finishes loading on a
+/// fresh install, emit `first-run`.
+pub fn on_page_load(webview: &Webview, payload: &PageLoadPayload<'_>) {
+    if webview.label() != "main" || payload.event() != PageLoadEvent::Finished {
+        return;
+    }
+    let app_handle = webview.app_handle();
+    if settings::current(app_handle).onboarding_completed {
+        return;
+    }
+    if app_handle
+        .state::<OnboardingState>()
+        .announced
+        .swap(true, Ordering::SeqCst)
+    {
+        return;
+    }
+    match info(app_handle) {
+        Ok(info) => {
+            log::info!("ONBOARDING ► starting first-run flow");
+            let _ = app_handle.emit("first-run", &info);
+        }
+        Err(e) => log::warn!("ONBOARDING ► could not build first-run payload: {}", e),
+    }
+}
+
+/// The chosen folder must pass `paths::check`; it is created if needed.
+/// Returns the path as it should be stored.
+pub fn validate_data_dir(path: &str) -> Result<String, String> {
+    let check = paths::check(path);
+    if !check.ok {
+        return Err(check.problems.join("; "));
+    }
+    let dir = PathBuf::from(&check.path);
+    fs::create_dir_all(paths::extended(&dir))
+        .map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+    Ok(check.path)
+}
+
+#[tauri::command]
+pub async fn get_onboarding_state(app_handle: tauri::AppHandle) -> Result<OnboardingInfo, String> {
+    info(&app_handle)
+}
+
+/// Store user data under `path`, or back under the default location when
+/// `None`. Existing data is not moved; a running backend is restarted so it
+/// picks up the new root.
+#[tauri::command]
+pub async fn set_data_location(
+    app_handle: tauri::AppHandle,
+    path: Option<String>,
+) -> Result<OnboardingInfo, String> {
+    let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = path.as_deref().map(validate_data_dir).transpose()?;
+        if settings::current(&app_handle).data_dir == path {
+            return info(&app_handle);
+        }
+        match &path {
+            Some(p) => log::info!("ONBOARDING ► data location set to {}", p),
+            None => log::info!("ONBOARDING ► data location reset to default"),
+        }
+        settings::update(&app_handle, |s| s.data_dir = path)?;
+
+        let tracked = app_handle
+            .state::<BackendProcess>()
+            .0
+            .lock()
+            .unwrap()
+            .is_some();
+        if tracked {
+            backend_lifecycle::restart_backend(&app_handle)?;
+        }
+        info(&app_handle)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn set_backups_enabled(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!(
+        "ONBOARDING ► backups {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+    settings::update(&app_handle, |s| s.backups_enabled = enabled)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_update_channel(
+    app_handle: tauri::AppHandle,
+    channel: UpdateChannel,
+) -> Result<(), String> {
+    log::info!("ONBOARDING ► update channel set to {:?}", channel);
+    settings::update(&app_handle, |s| s.update_channel = channel)?;
+    Ok(())
+}
+
+/// Called when the user finishes (or skips) the onboarding flow.
+#[tauri::command]
+pub async fn complete_onboarding(app_handle: tauri::AppHandle) -> Result<(), String> {
+    log::info!("ONBOARDING ► completed");
+    settings::update(&app_handle, |s| s.onboarding_completed = true)?;
+    Ok(())
+}
@@ -0,0 +1,123 @@
+// Orphaned backend sweep, run by the startup sequence before the backend is
+// spawned. A shell that crashed or was killed outright can leave its backend
+// running, holding the port and database files. Candidates are processes
+// whose executable is one of ours (the bundled sidecar next to the app, or
+// the retained last-known-good copy) plus whatever `backend.pid` recorded,
+// which also covers the dev Python fallback. Only candidates whose parent is
+// gone are terminated, so a backend owned by a running shell is left alone,
+// and matching on the full exe path keeps other apps' processes safe. The
+// result is emitted as `startup-cleanup`.
+
+use crate::backend_pid::{self, ProcessIdentity};
+use crate::{correlation, sidecar_fallback};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use sysinfo::{Pid, Process, System};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanedBackend {
+    pub pid: u32,
+    pub exe: Option<String>,
+    pub killed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupCleanup {
+    /// Orphans that were terminated.
+    pub cleaned: usize,
+    pub found: Vec<OrphanedBackend>,
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Whether the process that started `process` is no longer around. On Unix
+/// orphans are re-parented to init (or a subreaper), so pid 1 counts as gone.
+fn parent_gone(sys: &System, process: &Process) -> bool {
+    match process.parent() {
+        None => true,
+        Some(parent) if cfg!(unix) && parent.as_u32() == 1 => true,
+        Some(parent) => sys.process(parent).is_none(),
+    }
+}
+
+fn is_recorded(process: &Process, recorded: Option<&ProcessIdentity>) -> bool {
+    recorded
+        .map(|id| {
+            id.pid == process.pid().as_u32()
+                && id.name == process.name()
+                && id.start_time == process.start_time()
+        })
+        .unwrap_or(false)
+}
+
+/// Terminate orphaned backends. Blocking.
+pub fn sweep(app_handle: &tauri::AppHandle) -> StartupCleanup {
+    let ours: Vec<PathBuf> = [
+        sidecar_fallback::bundled_sidecar_path(),
+        sidecar_fallback::retained_path(app_handle),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let recorded = backend_pid::read_record(app_handle);
+    let own_pid = Pid::from_u32(std::process::id());
+
+    let mut sys = System::new();
+    sys.refresh_processes();
+    let mut found = Vec::new();
+    for process in sys.processes().values() {
+        if process.pid() == own_pid || process.parent() == Some(own_pid) {
+            continue;
+        }
+        let exe = process.exe();
+        let ours_by_exe = exe
+            .map(|exe| ours.iter().any(|p| same_file(exe, p)))
+            .unwrap_or(false);
+        if !ours_by_exe && !is_recorded(process, recorded.as_ref()) {
+            continue;
+        }
+        if !parent_gone(&sys, process) {
+            continue;
+        }
+        let killed = process.kill();
+        log::warn!(
+            "ORPHAN_SWEEP ► {} orphaned backend pid {} ({:?})",
+            if killed {
+                "terminated"
+            } else {
+                "could not terminate"
+            },
+            process.pid(),
+            exe
+        );
+        found.push(OrphanedBackend {
+            pid: process.pid().as_u32(),
+            exe: exe.map(|p| p.to_string_lossy().into_owned()),
+            killed,
+        });
+    }
+    // A record naming a process that is gone (or was just terminated) is
+    // stale; one naming our own live child stays put.
+    if recorded.map(|id| !id.is_alive()).unwrap_or(false) {
+        backend_pid::remove_record(app_handle);
+    }
+
+    let cleanup = StartupCleanup {
+        cleaned: found.iter().filter(|o| o.killed).count(),
+        found,
+    };
+    if !cleanup.found.is_empty() {
+        log::info!(
+            "ORPHAN_SWEEP ► cleaned {} of {} orphaned backend(s)",
+            cleanup.cleaned,
+            cleanup.found.len()
+        );
+    }
+    correlation::emit(app_handle, "startup-cleanup", cleanup.clone());
+    cleanup
+}
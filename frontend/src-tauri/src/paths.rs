@@ -0,0 +1,222 @@
+// Path handling for user-chosen and per-user locations. Windows users with
+// Cyrillic/CJK account names get non-ASCII profile paths, and deep data
+// folders can exceed MAX_PATH (260); both used to surface as sporadic spawn
+// and copy failures. File operations on such paths go through `extended`
+// (the `\\?\` form lifts the length limit); anything handed to a child
+// process or shown to the user goes through `plain`. `validate_data_path`
+// checks a folder before it is saved as a data or backup location.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Classic Windows path limit (including the terminating NUL).
+const MAX_PATH: usize = 260;
+/// Longest root we accept without a warning: the backend nests dossier,
+/// draft and image folders below it.
+const DATA_ROOT_WARN_LEN: usize = 160;
+/// Probe file name with Latin, Cyrillic and CJK characters.
+const UNICODE_PROBE: &str = ".plattera-probe-ßДж漢字";
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// `path` in a form file APIs accept beyond MAX_PATH. Only changes absolute
+/// Windows paths that are long enough to need it; relative paths and paths
+/// with `..` can't use the `\\?\` form and are returned as-is.
+pub fn extended(path: &Path) -> PathBuf {
+    if !cfg!(windows) {
+        return path.to_path_buf();
+    }
+    let raw = path.as_os_str().to_string_lossy();
+    if raw.starts_with(r"\\?\")
+        || raw.len() < MAX_PATH - 12
+        || !path.is_absolute()
+        || path.components().any(|c| matches!(c, Component::ParentDir))
+    {
+        return path.to_path_buf();
+    }
+    let raw = raw.replace('/', "\\");
+    match raw.strip_prefix(r"\\") {
+        Some(unc) => PathBuf::from(format!(r"\\?\UNC\{}", unc)),
+        None => PathBuf::from(format!(r"\\?\{}", raw)),
+    }
+}
+
+/// `path` without a `\\?\` prefix, for child processes (Python without long
+/// path support rejects the prefixed form), env vars and display.
+pub fn plain(path: &Path) -> PathBuf {
+    let raw = path.as_os_str().to_string_lossy();
+    if let Some(unc) = raw.strip_prefix(r"\\?\UNC\") {
+        return PathBuf::from(format!(r"\\{}", unc));
+    }
+    match raw.strip_prefix(r"\\?\") {
+        Some(rest) => PathBuf::from(rest),
+        None => path.to_path_buf(),
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PathCheck {
+    /// The path as it will be stored (trimmed, without `\\?\`).
+    pub path: String,
+    /// False when any `problems` were found.
+    pub ok: bool,
+    pub exists: bool,
+    pub writable: bool,
+    pub non_ascii: bool,
+    /// Whether the file system kept a non-ASCII file name intact; `None`
+    /// when nothing could be written.
+    pub unicode_names_ok: Option<bool>,
+    /// Blocking issues.
+    pub problems: Vec<String>,
+    /// Usable, but worth telling the user about.
+    pub warnings: Vec<String>,
+}
+
+/// Name-level problems Windows would reject (or silently rewrite).
+fn windows_name_problems(path: &Path, problems: &mut Vec<String>) {
+    for component in path.components() {
+        let Component::Normal(name) = component else {
+            continue;
+        };
+        let name = name.to_string_lossy();
+        if name
+            .chars()
+            .any(|c| "<>:\"|?*".contains(c) || (c as u32) < 32)
+        {
+            problems.push(format!(
+                "\"{}\" contains characters Windows doesn't allow",
+                name
+            ));
+        }
+        if name.ends_with(' ') || name.ends_with('.') {
+            problems.push(format!("\"{}\" ends with a space or a dot", name));
+        }
+        let stem = name
+            .split('.')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .to_ascii_uppercase();
+        if RESERVED_NAMES.contains(&stem.as_str()) {
+            problems.push(format!("\"{}\" is a reserved device name", name));
+        }
+    }
+}
+
+/// Write and list a probe file in `dir`: whether the folder is writable, and
+/// whether a non-ASCII name survives the round trip.
+fn probe_dir(dir: &Path) -> Result<bool, String> {
+    let probe = extended(&dir.join(UNICODE_PROBE));
+    fs::write(&probe, b"probe").map_err(|e| e.to_string())?;
+    let listed = fs::read_dir(extended(dir))
+        .map(|entries| {
+            entries
+                .flatten()
+                .any(|e| e.file_name().to_str() == Some(UNICODE_PROBE))
+        })
+        .unwrap_or(false);
+    let _ = fs::remove_file(&probe);
+    Ok(listed)
+}
+
+/// Check `path` as a data or backup location. Nothing is created: a
+/// missing folder is judged by its nearest existing parent.
+pub fn check(path: &str) -> PathCheck {
+    let trimmed = path.trim();
+    let path = plain(Path::new(trimmed));
+    let mut check = PathCheck {
+        path: path.to_string_lossy().into_owned(),
+        non_ascii: !trimmed.is_ascii(),
+        ..PathCheck::default()
+    };
+
+    if trimmed.is_empty() {
+        check.problems.push("no folder given".to_string());
+        return check;
+    }
+    if !path.is_absolute() {
+        check
+            .problems
+            .push(format!("{} is not an absolute path", path.display()));
+        return check;
+    }
+    if cfg!(windows) {
+        windows_name_problems(&path, &mut check.problems);
+    }
+    // Windows counts the limit in UTF-16 units, not bytes.
+    let len = check.path.encode_utf16().count();
+    if len >= MAX_PATH - 12 {
+        check.problems.push(format!(
+            "path is {} characters; Windows folders must stay under {}",
+            len,
+            MAX_PATH - 12
+        ));
+    } else if len > DATA_ROOT_WARN_LEN {
+        check.warnings.push(format!(
+            "path is {} characters; files nested deep inside may exceed the Windows path limit",
+            len
+        ));
+    }
+
+    let target = extended(&path);
+    check.exists = target.exists();
+    if check.exists && !target.is_dir() {
+        check
+            .problems
+            .push(format!("{} is a file, not a folder", path.display()));
+        check.ok = check.problems.is_empty();
+        return check;
+    }
+    let probe_in = if check.exists {
+        Some(path.clone())
+    } else {
+        path.ancestors()
+            .skip(1)
+            .find(|p| extended(p).is_dir())
+            .map(Path::to_path_buf)
+    };
+    match probe_in {
+        Some(dir) => match probe_dir(&dir) {
+            Ok(names_ok) => {
+                check.writable = true;
+                check.unicode_names_ok = Some(names_ok);
+                if !names_ok {
+                    check.warnings.push(
+                        "this drive doesn't keep non-ASCII file names intact; dossiers with such names may fail to save"
+                            .to_string(),
+                    );
+                }
+            }
+            Err(e) => check
+                .problems
+                .push(format!("cannot write to {}: {}", dir.display(), e)),
+        },
+        None => check
+            .problems
+            .push(format!("no existing parent folder for {}", path.display())),
+    }
+
+    check.ok = check.problems.is_empty();
+    check
+}
+
+/// Validate a folder the user picked for data or backups.
+#[tauri::command]
+pub async fn validate_data_path(path: String) -> Result<PathCheck, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = check(&path);
+        if !result.ok {
+            log::info!(
+                "PATHS ► rejected {}: {}",
+                result.path,
+                result.problems.join("; ")
+            );
+        }
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
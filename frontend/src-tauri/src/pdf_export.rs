@@ -0,0 +1,240 @@
+// PDF export through the webview itself. `print_to_pdf` loads a frontend
+// route in a hidden window, waits for the page to finish loading (plus a
+// short settle for data fetched after load), and hands the webview to the
+// platform print pipeline with a file as the target, so reports come out
+// without a printer driver or a print dialog. The hidden window is destroyed
+// afterwards whatever the outcome.
+//
+// WebView2 (Windows) prints through `ICoreWebView2_7::PrintToPdf`, WebKitGTK
+// (Linux) through a print operation aimed at GTK's print-to-file backend.
+// macOS is not wired up yet.
+
+use crate::paths;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::webview::PageLoadEvent;
+use tauri::{WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+const LABEL_PREFIX: &str = "pdf-export-";
+const LOAD_TIMEOUT: Duration = Duration::from_secs(30);
+/// Time given to the page after load to fetch and render its data.
+const SETTLE: Duration = Duration::from_millis(1500);
+const PRINT_TIMEOUT: Duration = Duration::from_secs(60);
+/// A4 at 96 dpi, so the layout matches the printed page width.
+const PAGE_SIZE: (f64, f64) = (794.0, 1123.0);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PdfExport {
+    pub path: String,
+    pub bytes: u64,
+}
+
+pub fn is_export_window(label: &str) -> bool {
+    label.starts_with(LABEL_PREFIX)
+}
+
+/// Only in-app routes such as `/reports/123?print=1`; the hidden window
+/// must never load anything outside the frontend.
+fn validate_route(route: &str) -> Result<&str, String> {
+    let route = route.trim();
+    let ok = route.starts_with('/')
+        && !route.starts_with("//")
+        && !route.contains("://")
+        && !route.contains('\\');
+    if ok {
+        Ok(route.trim_start_matches('/'))
+    } else {
+        Err(format!("'{}' is not an app route", route))
+    }
+}
+
+fn validate_output(output_path: &str) -> Result<PathBuf, String> {
+    let path = paths::plain(Path::new(output_path.trim()));
+    if !path.is_absolute() {
+        return Err(format!("{} is not an absolute path", path.display()));
+    }
+    let is_pdf = path
+        .extension()
+        .map(|e| e.eq_ignore_ascii_case("pdf"))
+        .unwrap_or(false);
+    if !is_pdf {
+        return Err(format!("{} does not end in .pdf", path.display()));
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(paths::extended(parent))
+            .map_err(|e| format!("cannot create {}: {}", parent.display(), e))?;
+    }
+    Ok(path)
+}
+
+async fn wait<T: Send + 'static>(
+    rx: mpsc::Receiver<T>,
+    timeout: Duration,
+    what: &str,
+) -> Result<T, String> {
+    tauri::async_runtime::spawn_blocking(move || rx.recv_timeout(timeout))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|_| format!("timed out waiting for {}", what))
+}
+
+async fn render(window: &WebviewWindow, path: PathBuf) -> Result<(), String> {
+    tokio::time::sleep(SETTLE).await;
+    let (tx, rx) = mpsc::channel();
+    let target = path.clone();
+    window
+        .with_webview(move |webview| imp::print(webview, &target, tx))
+        .map_err(|e| e.to_string())?;
+    wait(rx, PRINT_TIMEOUT, "the PDF").await??;
+    if !path.exists() {
+        return Err(format!("the webview did not write {}", path.display()));
+    }
+    Ok(())
+}
+
+/// Render `route` in a hidden window and save it as a PDF at `output_path`.
+#[tauri::command]
+pub async fn print_to_pdf(
+    app_handle: tauri::AppHandle,
+    route: String,
+    output_path: String,
+) -> Result<PdfExport, String> {
+    let url = validate_route(&route)?.to_string();
+    let path = validate_output(&output_path)?;
+    let label = format!(
+        "{}{}",
+        LABEL_PREFIX,
+        NEXT_ID.fetch_add(1, Ordering::Relaxed)
+    );
+
+    let (loaded_tx, loaded_rx) = mpsc::channel();
+    let loaded_tx = Mutex::new(Some(loaded_tx));
+    let window = WebviewWindowBuilder::new(&app_handle, &label, WebviewUrl::App(url.into()))
+        .title("Plattera - PDF export")
+        .inner_size(PAGE_SIZE.0, PAGE_SIZE.1)
+        .visible(false)
+        .skip_taskbar(true)
+        .on_page_load(move |_, payload| {
+            if payload.event() == PageLoadEvent::Finished {
+                if let Some(tx) = loaded_tx.lock().unwrap().take() {
+                    let _ = tx.send(());
+                }
+            }
+        })
+        .build()
+        .map_err(|e| format!("could not open export window: {}", e))?;
+    log::info!("PDF_EXPORT ► rendering '{}' into {}", route, path.display());
+
+    let result = match wait(loaded_rx, LOAD_TIMEOUT, "the page to load").await {
+        Ok(()) => render(&window, path.clone()).await,
+        Err(e) => Err(e),
+    };
+    let _ = window.destroy();
+    result.map_err(|e| {
+        log::warn!("PDF_EXPORT ► '{}' failed: {}", route, e);
+        e
+    })?;
+
+    let bytes = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    log::info!("PDF_EXPORT ► wrote {} ({} bytes)", path.display(), bytes);
+    Ok(PdfExport {
+        path: path.to_string_lossy().into_owned(),
+        bytes,
+    })
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::path::Path;
+    use std::sync::mpsc::Sender;
+    use tauri::webview::PlatformWebview;
+    use webview2_com::Microsoft::Web::WebView2::Win32::{
+        ICoreWebView2PrintSettings, ICoreWebView2_7,
+    };
+    use webview2_com::PrintToPdfCompletedHandler;
+    use windows::core::{Interface, HSTRING};
+
+    /// Runs on the main thread; the result arrives on `done`.
+    pub fn print(webview: PlatformWebview, path: &Path, done: Sender<Result<(), String>>) {
+        let completed = done.clone();
+        let handler = PrintToPdfCompletedHandler::create(Box::new(move |result, ok| {
+            let _ = completed.send(match result {
+                Ok(()) if ok => Ok(()),
+                Ok(()) => Err("WebView2 could not print the page".to_string()),
+                Err(e) => Err(e.to_string()),
+            });
+            Ok(())
+        }));
+        let started = unsafe {
+            webview
+                .controller()
+                .CoreWebView2()
+                .and_then(|core| core.cast::<ICoreWebView2_7>())
+                .and_then(|core| {
+                    core.PrintToPdf(
+                        &HSTRING::from(path.as_os_str()),
+                        None::<&ICoreWebView2PrintSettings>,
+                        &handler,
+                    )
+                })
+        };
+        if let Err(e) = started {
+            let _ = done.send(Err(format!("PrintToPdf unavailable: {}", e)));
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use std::path::Path;
+    use std::sync::mpsc::Sender;
+    use tauri::webview::PlatformWebview;
+    use webkit2gtk::{PrintOperation, PrintOperationExt};
+
+    /// Runs on the main thread; the result arrives on `done`. `failed` is
+    /// emitted before `finished`, so the receiver sees the error first.
+    pub fn print(webview: PlatformWebview, path: &Path, done: Sender<Result<(), String>>) {
+        let uri = match gtk::glib::filename_to_uri(path, None) {
+            Ok(uri) => uri,
+            Err(e) => {
+                let _ = done.send(Err(e.to_string()));
+                return;
+            }
+        };
+        let settings = gtk::PrintSettings::new();
+        settings.set_printer("Print to File");
+        settings.set(gtk::PRINT_SETTINGS_OUTPUT_FILE_FORMAT, Some("pdf"));
+        settings.set(gtk::PRINT_SETTINGS_OUTPUT_URI, Some(uri.as_str()));
+
+        let operation = PrintOperation::new(&webview.inner());
+        operation.set_print_settings(&settings);
+        let failed = done.clone();
+        operation.connect_failed(move |_, e| {
+            let _ = failed.send(Err(e.to_string()));
+        });
+        operation.connect_finished(move |_| {
+            let _ = done.send(Ok(()));
+        });
+        operation.print();
+    }
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+mod imp {
+    use std::path::Path;
+    use std::sync::mpsc::Sender;
+    use tauri::webview::PlatformWebview;
+
+    pub fn print(_webview: PlatformWebview, _path: &Path, done: Sender<Result<(), String>>) {
+        let _ = done.send(Err(
+            "PDF export is not supported on this platform yet".to_string()
+        ));
+    }
+}
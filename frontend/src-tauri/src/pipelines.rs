@@ -0,0 +1,352 @@
+// Automation pipelines: named sequences of backend API calls and shell
+// actions (a backup, a dossier export) stored in settings. A pipeline can be
+// run on demand through `run_pipeline` or the tray's Run Pipeline menu, or
+// periodically by the scheduler (see `scheduler.rs`).
+
+use crate::dossier_export::ExportFormat;
+use crate::scheduler::Schedule;
+use crate::{backup, dossier_export, settings, tray};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub steps: Vec<PipelineStep>,
+    /// Optional schedule evaluated by the background scheduler.
+    #[serde(default)]
+    pub schedule: Option<Schedule>,
+    /// Keep executing the remaining steps after a failed one.
+    #[serde(default)]
+    pub continue_on_error: bool,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PipelineStep {
+    /// Call a backend endpoint, e.g. `{ "method": "POST", "path": "/api/..." }`.
+    BackendRequest {
+        method: String,
+        path: String,
+        #[serde(default)]
+        body: Option<Value>,
+    },
+    /// Surface a message to the frontend via the `pipeline-notify` event.
+    Notify {
+        title: String,
+        #[serde(default)]
+        body: Option<String>,
+    },
+    /// Pause between steps (for example to let the backend settle).
+    Delay { ms: u64 },
+    /// Back up the data folder and settings to `path`, a .zip file or a
+    /// folder to put a timestamped one in (see `backup.rs`).
+    Backup { path: String },
+    /// Write the dossier list to `path` (see `dossier_export.rs`).
+    ExportDossiers { format: ExportFormat, path: String },
+}
+
+impl PipelineStep {
+    fn kind(&self) -> &'static str {
+        match self {
+            PipelineStep::BackendRequest { .. } => "backend_request",
+            PipelineStep::Notify { .. } => "notify",
+            PipelineStep::Delay { .. } => "delay",
+            PipelineStep::Backup { .. } => "backup",
+            PipelineStep::ExportDossiers { .. } => "export_dossiers",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunTrigger {
+    Manual,
+    Schedule,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StepOutcome {
+    pub index: usize,
+    pub kind: &'static str,
+    pub ok: bool,
+    pub duration_ms: u64,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PipelineRunReport {
+    pub name: String,
+    pub trigger: RunTrigger,
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub ok: bool,
+    pub steps: Vec<StepOutcome>,
+}
+
+/// Names of pipelines currently executing; prevents overlapping runs of the
+/// same pipeline (e.g. a manual run while the scheduled one is in flight).
+pub struct PipelineRuns(pub Mutex<HashSet<String>>);
+
+impl PipelineRuns {
+    pub fn new() -> Self {
+        PipelineRuns(Mutex::new(HashSet::new()))
+    }
+}
+
+/// Takes a pipeline out of `PipelineRuns` when its run ends, including by
+/// a panic in one of its steps.
+struct RunGuard<'a> {
+    app_handle: &'a tauri::AppHandle,
+    name: &'a str,
+}
+
+impl Drop for RunGuard<'_> {
+    fn drop(&mut self) {
+        let runs = self.app_handle.state::<PipelineRuns>();
+        let mut guard = runs.0.lock().unwrap_or_else(|e| e.into_inner());
+        guard.remove(self.name);
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct PipelineNotifyPayload {
+    pipeline: String,
+    title: String,
+    body: Option<String>,
+}
+
+fn find_pipeline(app_handle: &tauri::AppHandle, name: &str) -> Option<PipelineDefinition> {
+    settings::current(app_handle)
+        .pipelines
+        .into_iter()
+        .find(|p| p.name == name)
+}
+
+fn call_backend(
+    app_handle: &tauri::AppHandle,
+    method: &str,
+    path: &str,
+    body: Option<&Value>,
+) -> Result<String, String> {
+    let method = reqwest::Method::from_bytes(method.to_uppercase().as_bytes())
+        .map_err(|_| format!("invalid HTTP method: {}", method))?;
+    let reply = crate::backend_http::call_blocking(
+        app_handle,
+        method,
+        path,
+        body,
+        Duration::from_secs(300),
+        Some("pipeline"),
+    )
+    .map_err(|e| format!("request error: {e}"))?;
+    if reply.status.is_success() {
+        Ok(reply.status.to_string())
+    } else {
+        Err(reply.status.to_string())
+    }
+}
+
+fn run_step(
+    app_handle: &tauri::AppHandle,
+    pipeline: &str,
+    step: &PipelineStep,
+) -> Result<String, String> {
+    match step {
+        PipelineStep::BackendRequest { method, path, body } => {
+            call_backend(app_handle, method, path, body.as_ref())
+        }
+        PipelineStep::Notify { title, body } => {
+            app_handle
+                .emit(
+                    "pipeline-notify",
+                    PipelineNotifyPayload {
+                        pipeline: pipeline.to_string(),
+                        title: title.clone(),
+                        body: body.clone(),
+                    },
+                )
+                .map_err(|e| e.to_string())?;
+            Ok("notified".to_string())
+        }
+        PipelineStep::Delay { ms } => {
+            thread::sleep(Duration::from_millis(*ms));
+            Ok(format!("slept {}ms", ms))
+        }
+        PipelineStep::Backup { path } => {
+            let backup = backup::run(app_handle, path)?;
+            Ok(format!(
+                "backed up {} file(s) to {}",
+                backup.files, backup.path
+            ))
+        }
+        PipelineStep::ExportDossiers { format, path } => {
+            let export = dossier_export::export(app_handle, *format, path)?;
+            Ok(format!(
+                "exported {} dossier(s) to {}",
+                export.count, export.path
+            ))
+        }
+    }
+}
+
+/// Execute a pipeline synchronously. Callers on the async runtime should go
+/// through `spawn_blocking`; the scheduler calls this from its own thread.
+pub fn execute(
+    app_handle: &tauri::AppHandle,
+    pipeline: &PipelineDefinition,
+    trigger: RunTrigger,
+) -> Result<PipelineRunReport, String> {
+    {
+        let runs = app_handle.state::<PipelineRuns>();
+        let mut guard = runs.0.lock().unwrap();
+        if !guard.insert(pipeline.name.clone()) {
+            return Err(format!("pipeline '{}' is already running", pipeline.name));
+        }
+    }
+    let run = RunGuard {
+        app_handle,
+        name: &pipeline.name,
+    };
+
+    log::info!(
+        "PIPELINE ► run '{}' ({} steps, trigger={:?})",
+        pipeline.name,
+        pipeline.steps.len(),
+        trigger
+    );
+    let _ = app_handle.emit("pipeline-started", &pipeline.name);
+
+    let started_at = chrono::Local::now().to_rfc3339();
+    let start = Instant::now();
+    let mut steps = Vec::with_capacity(pipeline.steps.len());
+    let mut ok = true;
+
+    for (index, step) in pipeline.steps.iter().enumerate() {
+        let step_start = Instant::now();
+        let result = run_step(app_handle, &pipeline.name, step);
+        let outcome = StepOutcome {
+            index,
+            kind: step.kind(),
+            ok: result.is_ok(),
+            duration_ms: step_start.elapsed().as_millis() as u64,
+            detail: result.unwrap_or_else(|e| e),
+        };
+        if outcome.ok {
+            log::info!(
+                "PIPELINE ► '{}' step {} ({}) ok: {}",
+                pipeline.name,
+                index,
+                outcome.kind,
+                outcome.detail
+            );
+        } else {
+            log::warn!(
+                "PIPELINE ► '{}' step {} ({}) failed: {}",
+                pipeline.name,
+                index,
+                outcome.kind,
+                outcome.detail
+            );
+            ok = false;
+        }
+        let _ = app_handle.emit("pipeline-step", &outcome);
+        let failed = !outcome.ok;
+        steps.push(outcome);
+        if failed && !pipeline.continue_on_error {
+            break;
+        }
+    }
+
+    let report = PipelineRunReport {
+        name: pipeline.name.clone(),
+        trigger,
+        started_at,
+        duration_ms: start.elapsed().as_millis() as u64,
+        ok,
+        steps,
+    };
+
+    drop(run);
+
+    let _ = app_handle.emit("pipeline-finished", &report);
+    Ok(report)
+}
+
+/// The tray lists the pipelines by name; rebuild it after they change.
+fn refresh_tray(app_handle: &tauri::AppHandle) {
+    if let Err(e) = tray::refresh(app_handle) {
+        log::warn!("PIPELINE ► could not rebuild tray menu: {}", e);
+    }
+}
+
+/// Run the pipeline `name` in the background, e.g. from the tray.
+pub fn run_in_background(app_handle: &tauri::AppHandle, name: &str) {
+    let Some(pipeline) = find_pipeline(app_handle, name) else {
+        log::warn!("PIPELINE ► unknown pipeline '{}'", name);
+        return;
+    };
+    let handle = app_handle.clone();
+    thread::spawn(move || {
+        if let Err(e) = execute(&handle, &pipeline, RunTrigger::Manual) {
+            log::warn!("PIPELINE ► {}", e);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn list_pipelines(
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<PipelineDefinition>, String> {
+    Ok(settings::current(&app_handle).pipelines)
+}
+
+/// Create or replace (by name) a pipeline definition.
+#[tauri::command]
+pub async fn save_pipeline(
+    app_handle: tauri::AppHandle,
+    pipeline: PipelineDefinition,
+) -> Result<(), String> {
+    if pipeline.name.trim().is_empty() {
+        return Err("pipeline name must not be empty".into());
+    }
+    settings::update(&app_handle, |s| {
+        s.pipelines.retain(|p| p.name != pipeline.name);
+        s.pipelines.push(pipeline);
+    })?;
+    refresh_tray(&app_handle);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_pipeline(app_handle: tauri::AppHandle, name: String) -> Result<(), String> {
+    settings::update(&app_handle, |s| s.pipelines.retain(|p| p.name != name))?;
+    refresh_tray(&app_handle);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn run_pipeline(
+    app_handle: tauri::AppHandle,
+    name: String,
+) -> Result<PipelineRunReport, String> {
+    let pipeline =
+        find_pipeline(&app_handle, &name).ok_or_else(|| format!("unknown pipeline '{}'", name))?;
+    tauri::async_runtime::spawn_blocking(move || {
+        execute(&app_handle, &pipeline, RunTrigger::Manual)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,287 @@
+// Who owns the backend's preferred port. When the port is taken by something
+// that isn't a Plattera backend, `spawn_backend` moves to a free port; this
+// module puts a name on the squatter so the log, the `backend-port-conflict`
+// event and `diagnose_port_conflict` can say what is blocking the port
+// instead of leaving users to guess. The listening pid comes from the
+// platform: /proc on Linux, `lsof` on macOS, `netstat -ano` on Windows;
+// name and exe come from sysinfo. The same socket tables give the addresses
+// a port is listening on, which `loopback_bind.rs` checks.
+
+use crate::{correlation, endpoint, port_in_use};
+use serde::Serialize;
+use std::net::{IpAddr, Ipv4Addr};
+use sysinfo::{Pid, ProcessRefreshKind, System};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortOwner {
+    pub pid: u32,
+    pub name: Option<String>,
+    pub exe: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortConflict {
+    pub port: u16,
+    pub in_use: bool,
+    /// The listener answered as a Plattera backend this shell can attach to.
+    pub plattera_backend: bool,
+    /// `None` when the port is free or the owner could not be determined
+    /// (e.g. it belongs to another user).
+    pub owner: Option<PortOwner>,
+}
+
+/// `/proc/net/tcp` address: the raw network-order words printed in host
+/// byte order.
+#[cfg(target_os = "linux")]
+fn proc_addr(hex: &str) -> Option<IpAddr> {
+    let word = |i: usize| {
+        hex.get(i * 8..i * 8 + 8)
+            .and_then(|w| u32::from_str_radix(w, 16).ok())
+            .map(u32::to_ne_bytes)
+    };
+    match hex.len() {
+        8 => Some(IpAddr::V4(Ipv4Addr::from(word(0)?))),
+        32 => {
+            let mut bytes = [0u8; 16];
+            for i in 0..4 {
+                bytes[i * 4..i * 4 + 4].copy_from_slice(&word(i)?);
+            }
+            Some(IpAddr::V6(std::net::Ipv6Addr::from(bytes)))
+        }
+        _ => None,
+    }
+}
+
+/// Sockets in LISTEN state (0A) bound to the port: (address, inode).
+#[cfg(target_os = "linux")]
+fn listening_sockets(port: u16) -> Vec<(Option<IpAddr>, String)> {
+    let mut sockets = Vec::new();
+    for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        let Ok(raw) = std::fs::read_to_string(table) else {
+            continue;
+        };
+        for line in raw.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 || fields[3] != "0A" {
+                continue;
+            }
+            let Some((addr, local_port)) = fields[1].rsplit_once(':') else {
+                continue;
+            };
+            if u16::from_str_radix(local_port, 16).ok() == Some(port) {
+                sockets.push((proc_addr(addr), fields[9].to_string()));
+            }
+        }
+    }
+    sockets
+}
+
+#[cfg(target_os = "linux")]
+fn listening_pid(port: u16) -> Option<u32> {
+    let inodes: Vec<String> = listening_sockets(port)
+        .into_iter()
+        .map(|(_, inode)| format!("socket:[{}]", inode))
+        .collect();
+    if inodes.is_empty() {
+        return None;
+    }
+    for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse().ok()) else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            if let Ok(target) = std::fs::read_link(fd.path()) {
+                if inodes.iter().any(|i| target.as_os_str() == i.as_str()) {
+                    return Some(pid);
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn listening_pid(port: u16) -> Option<u32> {
+    let out = std::process::Command::new("lsof")
+        .args(["-nP", &format!("-iTCP:{}", port), "-sTCP:LISTEN", "-t"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .find_map(|l| l.trim().parse().ok())
+}
+
+#[cfg(windows)]
+fn listening_pid(port: u16) -> Option<u32> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let out = std::process::Command::new("netstat")
+        .args(["-ano", "-p", "TCP"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .ok()?;
+    let suffix = format!(":{}", port);
+    // "TCP  127.0.0.1:8000  0.0.0.0:0  LISTENING  1234". The state column is
+    // localized, so listeners are recognised by their unset remote port.
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .find_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() == 5 && fields[1].ends_with(&suffix) && fields[2].ends_with(":0") {
+                fields[4].parse().ok()
+            } else {
+                None
+            }
+        })
+}
+
+#[cfg(not(any(windows, target_os = "linux", target_os = "macos")))]
+fn listening_pid(_port: u16) -> Option<u32> {
+    None
+}
+
+/// `127.0.0.1:8000`, `[::1]:8000`, `*:8000` (lsof for any address).
+#[cfg(any(windows, target_os = "macos"))]
+fn parse_local_addr(local: &str) -> Option<IpAddr> {
+    let (host, _) = local.rsplit_once(':')?;
+    match host.trim_start_matches('[').trim_end_matches(']') {
+        "*" => Some(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+        host => host.split('%').next()?.parse().ok(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn listening_addresses_raw(port: u16) -> Vec<IpAddr> {
+    listening_sockets(port)
+        .into_iter()
+        .filter_map(|(addr, _)| addr)
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn listening_addresses_raw(port: u16) -> Vec<IpAddr> {
+    let Ok(out) = std::process::Command::new("lsof")
+        .args(["-nP", &format!("-iTCP:{}", port), "-sTCP:LISTEN", "-Fn"])
+        .output()
+    else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|l| l.strip_prefix('n'))
+        .filter_map(parse_local_addr)
+        .collect()
+}
+
+#[cfg(windows)]
+fn listening_addresses_raw(port: u16) -> Vec<IpAddr> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    // Both families: `-p TCP` alone leaves out IPv6 listeners.
+    let Ok(out) = std::process::Command::new("netstat")
+        .args(["-an"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+    else {
+        return Vec::new();
+    };
+    let suffix = format!(":{}", port);
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let listening = fields.len() == 4
+                && fields[0].starts_with("TCP")
+                && fields[1].ends_with(&suffix)
+                && fields[2].ends_with(":0");
+            if listening {
+                parse_local_addr(fields[1])
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(not(any(windows, target_os = "linux", target_os = "macos")))]
+fn listening_addresses_raw(_port: u16) -> Vec<IpAddr> {
+    Vec::new()
+}
+
+/// Local addresses listening on `port`; empty when none are, or the socket
+/// table can't be read.
+pub fn listening_addresses(port: u16) -> Vec<IpAddr> {
+    let mut addrs = listening_addresses_raw(port);
+    addrs.sort();
+    addrs.dedup();
+    addrs
+}
+
+/// Process listening on `port`, if it can be determined.
+pub fn owner(port: u16) -> Option<PortOwner> {
+    let pid = listening_pid(port)?;
+    let mut sys = System::new();
+    let id = Pid::from_u32(pid);
+    sys.refresh_process_specifics(id, ProcessRefreshKind::new());
+    let process = sys.process(id);
+    Some(PortOwner {
+        pid,
+        name: process.map(|p| p.name().to_string()),
+        exe: process
+            .and_then(|p| p.exe())
+            .map(|e| e.to_string_lossy().into_owned()),
+    })
+}
+
+/// Log and emit `backend-port-conflict` for a port held by something that
+/// isn't a Plattera backend. Blocking.
+pub fn report(app_handle: &tauri::AppHandle, port: u16) -> PortConflict {
+    let conflict = PortConflict {
+        port,
+        in_use: true,
+        plattera_backend: false,
+        owner: owner(port),
+    };
+    match &conflict.owner {
+        Some(o) => log::warn!(
+            "PORT_CONFLICT ► port {} is held by pid {} ({})",
+            port,
+            o.pid,
+            o.exe.as_deref().or(o.name.as_deref()).unwrap_or("unknown")
+        ),
+        None => log::warn!(
+            "PORT_CONFLICT ► port {} is held by a process that could not be identified",
+            port
+        ),
+    }
+    correlation::emit(app_handle, "backend-port-conflict", conflict.clone());
+    conflict
+}
+
+fn diagnose(app_handle: &tauri::AppHandle, port: u16) -> PortConflict {
+    let in_use = port_in_use(port);
+    let plattera_backend = in_use && endpoint::is_plattera_backend(app_handle, port);
+    PortConflict {
+        port,
+        in_use,
+        plattera_backend,
+        owner: if in_use { owner(port) } else { None },
+    }
+}
+
+/// What is listening on `port` (the preferred backend port by default).
+#[tauri::command]
+pub async fn diagnose_port_conflict(
+    app_handle: tauri::AppHandle,
+    port: Option<u16>,
+) -> Result<PortConflict, String> {
+    let port = port.unwrap_or_else(|| endpoint::preferred_port(&app_handle));
+    tauri::async_runtime::spawn_blocking(move || diagnose(&app_handle, port))
+        .await
+        .map_err(|e| e.to_string())
+}
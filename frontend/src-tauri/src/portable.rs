@@ -0,0 +1,118 @@
+// Portable mode, for USB sticks and locked-down machines where nothing may
+// be written to the user profile. A `portable.flag` file next to the
+// executable, or `--portable` on the command line, moves everything the app
+// writes into a `data/` folder beside the exe:
+//
+//   data/             shell state (settings, pid file, staging, reports)
+//   data/logs/        shell logs
+//   data/cache/       caches
+//   data/Data/        backend user data when no custom location is set
+//   data/webview/     WebView2 profile (Windows)
+//
+// The mode is decided once at the top of `run()`. Code that would resolve
+// AppLocalData, the log or cache folder goes through the helpers here, which
+// fall back to Tauri's per-user folders when not portable.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tauri::path::BaseDirectory;
+use tauri::Manager;
+
+pub const FLAG_FILE: &str = "portable.flag";
+pub const FLAG_ARG: &str = "--portable";
+const DATA_DIR: &str = "data";
+const LOG_DIR: &str = "logs";
+const CACHE_DIR: &str = "cache";
+const BACKEND_DATA_DIR: &str = "Data";
+#[cfg(windows)]
+const WEBVIEW_DIR: &str = "webview";
+
+/// `Some(data folder)` in portable mode.
+static ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+fn exe_dir() -> Option<PathBuf> {
+    std::env::current_exe()
+        .ok()?
+        .parent()
+        .map(Path::to_path_buf)
+}
+
+/// Decide the mode. Called at the top of `run()`, before any window or
+/// plugin touches the per-user folders.
+pub fn detect() {
+    let root = exe_dir().and_then(|dir| {
+        let requested =
+            dir.join(FLAG_FILE).is_file() || std::env::args().skip(1).any(|a| a == FLAG_ARG);
+        requested.then(|| dir.join(DATA_DIR))
+    });
+    if let Some(root) = &root {
+        if let Err(e) = std::fs::create_dir_all(root) {
+            // Read-only media: fall back to the normal folders.
+            eprintln!(
+                "PORTABLE ► cannot create {:?}, not running portable: {}",
+                root, e
+            );
+            let _ = ROOT.set(None);
+            return;
+        }
+        // WebView2 keeps its profile under LocalAppData unless told otherwise.
+        #[cfg(windows)]
+        std::env::set_var("WEBVIEW2_USER_DATA_FOLDER", root.join(WEBVIEW_DIR));
+    }
+    let _ = ROOT.set(root);
+}
+
+/// The portable data folder, if running portable.
+pub fn root() -> Option<&'static Path> {
+    ROOT.get().and_then(|r| r.as_deref())
+}
+
+pub fn is_portable() -> bool {
+    root().is_some()
+}
+
+/// `rel` under the app data folder: AppLocalData, or `data/` when portable.
+pub fn resolve(app_handle: &tauri::AppHandle, rel: impl AsRef<Path>) -> tauri::Result<PathBuf> {
+    match root() {
+        Some(root) => Ok(root.join(rel)),
+        None => app_handle.path().resolve(rel, BaseDirectory::AppLocalData),
+    }
+}
+
+pub fn app_local_data_dir(app_handle: &tauri::AppHandle) -> tauri::Result<PathBuf> {
+    match root() {
+        Some(root) => Ok(root.to_path_buf()),
+        None => app_handle.path().app_local_data_dir(),
+    }
+}
+
+pub fn app_log_dir(app_handle: &tauri::AppHandle) -> tauri::Result<PathBuf> {
+    match root() {
+        Some(root) => Ok(root.join(LOG_DIR)),
+        None => app_handle.path().app_log_dir(),
+    }
+}
+
+pub fn app_cache_dir(app_handle: &tauri::AppHandle) -> tauri::Result<PathBuf> {
+    match root() {
+        Some(root) => Ok(root.join(CACHE_DIR)),
+        None => app_handle.path().app_cache_dir(),
+    }
+}
+
+/// Backend data root when portable and no custom location is set.
+pub fn backend_data_dir() -> Option<PathBuf> {
+    root().map(|root| root.join(BACKEND_DATA_DIR))
+}
+
+/// Where the bundled backend executable is looked up: beside the exe when
+/// portable, AppLocalData otherwise.
+#[cfg(windows)]
+pub fn backend_exe(app_handle: &tauri::AppHandle, file_name: &str) -> tauri::Result<PathBuf> {
+    match root() {
+        Some(_) => Ok(exe_dir().unwrap_or_default().join(file_name)),
+        None => app_handle
+            .path()
+            .resolve(file_name, BaseDirectory::AppLocalData),
+    }
+}
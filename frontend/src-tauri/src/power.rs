@@ -0,0 +1,190 @@
+// Power-source detection so optional background work (scheduled pipelines,
+// model warmup, update downloads) can stand down on battery. Every platform
+// probe is best effort: `None` means "unknown" and callers should treat it as
+// mains power. A monitor thread reports changes as `power-status-changed`.
+
+use crate::settings;
+use serde::{Deserialize, Serialize};
+use std::thread;
+use std::time::Duration;
+use tauri::Emitter;
+
+const MONITOR_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerSettings {
+    /// Defer background work while on battery below the threshold.
+    pub defer_on_battery: bool,
+    /// Battery level (percent) below which work is deferred. 100 defers
+    /// whenever the machine is on battery.
+    pub battery_threshold_percent: u8,
+}
+
+impl Default for PowerSettings {
+    fn default() -> Self {
+        PowerSettings {
+            defer_on_battery: true,
+            battery_threshold_percent: 50,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct PowerStatus {
+    pub on_battery: Option<bool>,
+    pub battery_percent: Option<u8>,
+}
+
+impl PowerStatus {
+    pub fn is_on_battery(&self) -> bool {
+        self.on_battery.unwrap_or(false)
+    }
+}
+
+#[cfg(windows)]
+pub fn status() -> PowerStatus {
+    use windows_sys::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    unsafe {
+        let mut raw: SYSTEM_POWER_STATUS = std::mem::zeroed();
+        if GetSystemPowerStatus(&mut raw) == 0 {
+            return PowerStatus::default();
+        }
+        PowerStatus {
+            // 0 = offline (battery), 1 = online, 255 = unknown
+            on_battery: match raw.ACLineStatus {
+                0 => Some(true),
+                1 => Some(false),
+                _ => None,
+            },
+            battery_percent: (raw.BatteryLifePercent <= 100).then_some(raw.BatteryLifePercent),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn status() -> PowerStatus {
+    use std::fs;
+
+    let read = |p: std::path::PathBuf| fs::read_to_string(p).ok().map(|s| s.trim().to_string());
+    let Ok(entries) = fs::read_dir("/sys/class/power_supply") else {
+        return PowerStatus::default();
+    };
+
+    let mut mains_online = None;
+    let mut battery_percent = None;
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        match read(dir.join("type")).as_deref() {
+            Some("Mains") => {
+                let online = read(dir.join("online")).as_deref() == Some("1");
+                mains_online = Some(mains_online.unwrap_or(false) || online);
+            }
+            Some("Battery") => {
+                battery_percent = read(dir.join("capacity")).and_then(|c| c.parse().ok());
+            }
+            _ => {}
+        }
+    }
+
+    PowerStatus {
+        on_battery: match (mains_online, battery_percent) {
+            (_, None) => Some(false),
+            (Some(online), Some(_)) => Some(!online),
+            (None, Some(_)) => None,
+        },
+        battery_percent,
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub fn status() -> PowerStatus {
+    let Ok(out) = std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+    else {
+        return PowerStatus::default();
+    };
+    let text = String::from_utf8_lossy(&out.stdout);
+    let on_battery = if text.contains("'Battery Power'") {
+        Some(true)
+    } else if text.contains("'AC Power'") {
+        Some(false)
+    } else {
+        None
+    };
+    let battery_percent = text
+        .split_whitespace()
+        .find(|w| w.ends_with("%;") || w.ends_with('%'))
+        .and_then(|w| w.trim_end_matches(';').trim_end_matches('%').parse().ok());
+    PowerStatus {
+        on_battery,
+        battery_percent,
+    }
+}
+
+#[cfg(not(any(windows, target_os = "linux", target_os = "macos")))]
+pub fn status() -> PowerStatus {
+    PowerStatus::default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PowerReport {
+    #[serde(flatten)]
+    pub status: PowerStatus,
+    /// Whether background work is currently being held back.
+    pub defer_background_work: bool,
+}
+
+fn defer_for(cfg: &PowerSettings, status: &PowerStatus) -> bool {
+    if !cfg.defer_on_battery || !status.is_on_battery() {
+        return false;
+    }
+    // Unknown charge on battery counts as low.
+    status
+        .battery_percent
+        .map(|p| p < cfg.battery_threshold_percent)
+        .unwrap_or(true)
+}
+
+pub fn report(app_handle: &tauri::AppHandle) -> PowerReport {
+    let status = status();
+    PowerReport {
+        defer_background_work: defer_for(&settings::current(app_handle).power, &status),
+        status,
+    }
+}
+
+/// Whether optional background work should be postponed right now.
+pub fn should_defer_background_work(app_handle: &tauri::AppHandle) -> bool {
+    report(app_handle).defer_background_work
+}
+
+/// Poll the power source and emit `power-status-changed` when it changes.
+pub fn spawn_monitor(app_handle: tauri::AppHandle) {
+    thread::spawn(move || {
+        let mut last: Option<PowerReport> = None;
+        loop {
+            let current = report(&app_handle);
+            if last != Some(current) {
+                if last.map(|l| l.defer_background_work) != Some(current.defer_background_work) {
+                    log::info!(
+                        "POWER ► on_battery={:?} percent={:?} defer_background_work={}",
+                        current.status.on_battery,
+                        current.status.battery_percent,
+                        current.defer_background_work
+                    );
+                }
+                let _ = app_handle.emit("power-status-changed", current);
+                last = Some(current);
+            }
+            thread::sleep(MONITOR_INTERVAL);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_power_status(app_handle: tauri::AppHandle) -> Result<PowerReport, String> {
+    Ok(report(&app_handle))
+}
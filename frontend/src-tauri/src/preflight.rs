@@ -0,0 +1,230 @@
+// Pre-flight checks run before the backend is spawned. A missing VC++
+// runtime, a read-only data folder or a sidecar eaten by antivirus otherwise
+// surface as an opaque spawn error (or a backend that dies silently); here
+// they become structured issues the frontend can explain.
+
+use crate::{portable, settings, sidecar_fallback};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use sysinfo::System;
+use tauri::Emitter;
+
+const MIN_AVAILABLE_MEMORY_MB: u64 = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The backend cannot run; spawning is skipped.
+    Error,
+    /// Likely trouble, but worth trying.
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PreflightIssue {
+    pub check: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    pub hint: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PreflightReport {
+    pub ok: bool,
+    pub issues: Vec<PreflightIssue>,
+}
+
+impl PreflightReport {
+    fn issue(
+        &mut self,
+        check: &'static str,
+        severity: Severity,
+        message: String,
+        hint: Option<&str>,
+    ) {
+        self.issues.push(PreflightIssue {
+            check,
+            severity,
+            message,
+            hint: hint.map(str::to_string),
+        });
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &PreflightIssue> {
+        self.issues.iter().filter(|i| i.severity == Severity::Error)
+    }
+}
+
+#[cfg(windows)]
+fn check_vc_runtime(report: &mut PreflightReport) {
+    let system_root = std::env::var("SystemRoot").unwrap_or_else(|_| r"C:\Windows".to_string());
+    let system32 = Path::new(&system_root).join("System32");
+    for dll in ["vcruntime140.dll", "msvcp140.dll"] {
+        if !system32.join(dll).exists() {
+            report.issue(
+                "vc_runtime",
+                Severity::Error,
+                format!("{} is missing from {}", dll, system32.display()),
+                Some("Install the Microsoft Visual C++ 2015-2022 Redistributable (x64)."),
+            );
+        }
+    }
+}
+
+#[cfg(not(windows))]
+fn check_vc_runtime(_report: &mut PreflightReport) {}
+
+fn check_writable(dir: &Path, report: &mut PreflightReport) {
+    let probe = dir.join(".plattera_write_probe");
+    let result = fs::create_dir_all(dir)
+        .and_then(|_| fs::write(&probe, b"probe"))
+        .and_then(|_| fs::remove_file(&probe));
+    if let Err(e) = result {
+        report.issue(
+            "data_dir_writable",
+            Severity::Error,
+            format!("cannot write to {}: {}", dir.display(), e),
+            Some("Check folder permissions, disk space, or whether the folder is redirected or locked by sync/backup software."),
+        );
+    }
+}
+
+fn check_data_dirs(app_handle: &tauri::AppHandle, report: &mut PreflightReport) {
+    if let Ok(dir) = portable::resolve(app_handle, "") {
+        check_writable(&dir, report);
+    }
+    if let Ok(dir) = settings::backend_data_dir(app_handle) {
+        check_writable(&dir, report);
+    }
+}
+
+/// A sidecar that vanished or was truncated after install is almost always
+/// antivirus quarantine.
+fn check_sidecar(app_handle: &tauri::AppHandle, report: &mut PreflightReport) {
+    let path = match sidecar_fallback::active_override(app_handle)
+        .or_else(sidecar_fallback::bundled_sidecar_path)
+    {
+        Some(p) => p,
+        None => return,
+    };
+    // Dev builds run the Python backend when no sidecar is present.
+    let missing_severity = if cfg!(debug_assertions) {
+        Severity::Warning
+    } else {
+        Severity::Error
+    };
+    let av_hint = Some(
+        "Your antivirus may have quarantined the backend. Restore it from quarantine and add an exception, or reinstall Plattera.",
+    );
+
+    match fs::metadata(&path) {
+        Err(_) => report.issue(
+            "sidecar_present",
+            missing_severity,
+            format!("backend executable not found at {}", path.display()),
+            av_hint,
+        ),
+        Ok(meta) if meta.len() == 0 => report.issue(
+            "sidecar_present",
+            Severity::Error,
+            format!("backend executable at {} is empty", path.display()),
+            av_hint,
+        ),
+        Ok(_) => check_quarantine_marker(&path, report),
+    }
+}
+
+#[cfg(windows)]
+fn check_quarantine_marker(path: &Path, report: &mut PreflightReport) {
+    // Mark-of-the-web stream; SmartScreen/AV may block or scan-lock the exe.
+    let ads = format!("{}:Zone.Identifier", path.display());
+    if Path::new(&ads).exists() {
+        report.issue(
+            "sidecar_quarantine",
+            Severity::Warning,
+            format!("{} carries a downloaded-file (Zone.Identifier) mark", path.display()),
+            Some("If the backend is blocked, unblock the file in its Properties dialog or reinstall."),
+        );
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn check_quarantine_marker(path: &Path, report: &mut PreflightReport) {
+    let marked = std::process::Command::new("xattr")
+        .args(["-p", "com.apple.quarantine"])
+        .arg(path)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+    if marked {
+        report.issue(
+            "sidecar_quarantine",
+            Severity::Warning,
+            format!("{} has the com.apple.quarantine attribute", path.display()),
+            Some("Gatekeeper may block the backend; reinstall from the signed package."),
+        );
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn check_quarantine_marker(_path: &Path, _report: &mut PreflightReport) {}
+
+fn check_memory(report: &mut PreflightReport) {
+    let mut sys = System::new();
+    sys.refresh_memory();
+    let available_mb = sys.available_memory() / (1024 * 1024);
+    if available_mb > 0 && available_mb < MIN_AVAILABLE_MEMORY_MB {
+        report.issue(
+            "memory",
+            Severity::Warning,
+            format!(
+                "only {} MB of memory available (recommended at least {} MB)",
+                available_mb, MIN_AVAILABLE_MEMORY_MB
+            ),
+            Some("Close other applications; large documents may fail to process."),
+        );
+    }
+}
+
+/// Run every check. Blocking but quick.
+pub fn run(app_handle: &tauri::AppHandle) -> PreflightReport {
+    let mut report = PreflightReport::default();
+    check_vc_runtime(&mut report);
+    check_data_dirs(app_handle, &mut report);
+    check_sidecar(app_handle, &mut report);
+    check_memory(&mut report);
+    let has_errors = report.errors().next().is_some();
+    report.ok = !has_errors;
+
+    for issue in &report.issues {
+        match issue.severity {
+            Severity::Error => log::error!("PREFLIGHT ► {}: {}", issue.check, issue.message),
+            Severity::Warning => log::warn!("PREFLIGHT ► {}: {}", issue.check, issue.message),
+        }
+    }
+    report
+}
+
+/// Run the checks before a spawn. Errors are reported to the frontend as
+/// `backend-preflight-failed` and turned into the spawn error.
+pub fn check_before_spawn(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let report = run(app_handle);
+    if report.ok {
+        return Ok(());
+    }
+    let summary = report
+        .errors()
+        .map(|i| i.message.clone())
+        .collect::<Vec<_>>()
+        .join("; ");
+    let _ = app_handle.emit("backend-preflight-failed", &report);
+    Err(format!("backend pre-flight failed: {}", summary))
+}
+
+#[tauri::command]
+pub async fn run_preflight(app_handle: tauri::AppHandle) -> Result<PreflightReport, String> {
+    tauri::async_runtime::spawn_blocking(move || run(&app_handle))
+        .await
+        .map_err(|e| e.to_string())
+}
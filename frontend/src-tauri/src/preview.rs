@@ -0,0 +1,232 @@
+// Preview images for dossier attachments. The dossier file list used to send
+// every file through the Python backend just to show a thumbnail; the shell
+// now renders previews itself and caches them in AppCache/previews, where the
+// webview loads them over the asset protocol (`convertFileSrc`).
+//
+// Images are shown as-is (the file is added to the asset scope). PDFs (first
+// page) and office documents go through the `plattera-preview` converter.
+// It is not bundled: it is looked up next to the app executable when a
+// preview is needed, and without it those files simply get no preview. It
+// is invoked as:
+//
+//     plattera-preview <input> <output.png> --max-size <px>
+//
+// It writes a PNG no larger than `--max-size` on either edge and exits 0.
+// Cache entries are keyed by path, size and mtime, so an edited file gets a
+// fresh preview; the cache is trimmed oldest-first past `MAX_CACHE_BYTES`.
+
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+use tauri::path::BaseDirectory;
+use tauri::Manager;
+use tauri_plugin_shell::process::CommandEvent;
+use tauri_plugin_shell::ShellExt;
+use tokio::sync::Semaphore;
+
+const PREVIEW_DIR: &str = "previews";
+const CONVERTER: &str = "plattera-preview";
+/// Longest edge of a generated preview, in pixels.
+const MAX_EDGE: u32 = 512;
+const CONVERT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Conversions running at once; office documents are slow and memory-hungry.
+const MAX_CONCURRENT: usize = 2;
+const MAX_CACHE_BYTES: u64 = 200 * 1024 * 1024;
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "bmp"];
+const CONVERTED_EXTENSIONS: &[&str] = &["pdf", "docx", "doc", "odt", "rtf", "pptx", "xlsx"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreviewKind {
+    /// The source file itself is the preview.
+    Image,
+    /// Rendered by the converter sidecar.
+    Converted,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Preview {
+    pub source: String,
+    /// File to load through the asset protocol.
+    pub path: String,
+    pub kind: PreviewKind,
+    pub cached: bool,
+}
+
+pub struct PreviewState(Semaphore);
+
+impl PreviewState {
+    pub fn new() -> Self {
+        PreviewState(Semaphore::new(MAX_CONCURRENT))
+    }
+}
+
+fn cache_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app_handle
+        .path()
+        .resolve(PREVIEW_DIR, BaseDirectory::AppCache)
+        .map_err(|e| e.to_string())
+}
+
+fn cache_key(path: &Path, meta: &fs::Metadata) -> String {
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    meta.len().hash(&mut hasher);
+    meta.modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .hash(&mut hasher);
+    MAX_EDGE.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Remove the oldest previews until the cache fits in `MAX_CACHE_BYTES`.
+fn prune(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<(PathBuf, u64, std::time::SystemTime)> = entries
+        .flatten()
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            meta.is_file()
+                .then(|| (e.path(), meta.len(), meta.modified().unwrap_or(UNIX_EPOCH)))
+        })
+        .collect();
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    if total <= MAX_CACHE_BYTES {
+        return;
+    }
+    files.sort_by_key(|(_, _, modified)| *modified);
+    let mut removed = 0;
+    for (path, len, _) in files {
+        if total <= MAX_CACHE_BYTES {
+            break;
+        }
+        if fs::remove_file(&path).is_ok() {
+            total -= len;
+            removed += 1;
+        }
+    }
+    log::info!("PREVIEW ► pruned {} cached preview(s)", removed);
+}
+
+async fn convert(app_handle: &tauri::AppHandle, input: &Path, output: &Path) -> Result<(), String> {
+    let command = app_handle
+        .shell()
+        .sidecar(CONVERTER)
+        .map_err(|e| format!("preview converter unavailable: {}", e))?
+        .args([
+            input.as_os_str(),
+            output.as_os_str(),
+            "--max-size".as_ref(),
+            MAX_EDGE.to_string().as_ref(),
+        ]);
+    let (mut rx, child) = command
+        .spawn()
+        .map_err(|e| format!("cannot start preview converter: {}", e))?;
+
+    let waited = tokio::time::timeout(CONVERT_TIMEOUT, async {
+        let mut stderr = String::new();
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Stderr(line) => stderr.push_str(&String::from_utf8_lossy(&line)),
+                CommandEvent::Error(e) => stderr.push_str(&e),
+                CommandEvent::Terminated(payload) => return (payload.code, stderr),
+                _ => {}
+            }
+        }
+        (None, stderr)
+    })
+    .await;
+
+    match waited {
+        Err(_) => {
+            let _ = child.kill();
+            Err(format!(
+                "preview converter timed out after {:?}",
+                CONVERT_TIMEOUT
+            ))
+        }
+        Ok((Some(0), _)) if output.is_file() => Ok(()),
+        Ok((code, stderr)) => Err(format!(
+            "preview converter failed (exit {:?}): {}",
+            code,
+            stderr.trim()
+        )),
+    }
+}
+
+/// Preview image for an attachment, generated on first use and cached.
+#[tauri::command]
+pub async fn generate_preview(
+    app_handle: tauri::AppHandle,
+    path: String,
+) -> Result<Preview, String> {
+    let source = PathBuf::from(path.trim());
+    let meta =
+        fs::metadata(&source).map_err(|e| format!("cannot read {}: {}", source.display(), e))?;
+    if !meta.is_file() {
+        return Err(format!("{} is not a file", source.display()));
+    }
+    let extension = source
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    if IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        app_handle
+            .asset_protocol_scope()
+            .allow_file(&source)
+            .map_err(|e| e.to_string())?;
+        return Ok(Preview {
+            source: source.to_string_lossy().into_owned(),
+            path: source.to_string_lossy().into_owned(),
+            kind: PreviewKind::Image,
+            cached: true,
+        });
+    }
+    if !CONVERTED_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(format!("no preview for .{} files", extension));
+    }
+
+    let dir = cache_dir(&app_handle)?;
+    let target = dir.join(format!("{}.png", cache_key(&source, &meta)));
+    let preview = |cached| Preview {
+        source: source.to_string_lossy().into_owned(),
+        path: target.to_string_lossy().into_owned(),
+        kind: PreviewKind::Converted,
+        cached,
+    };
+    if target.is_file() {
+        return Ok(preview(true));
+    }
+
+    let state = app_handle.state::<PreviewState>();
+    let _permit = state.0.acquire().await.map_err(|e| e.to_string())?;
+    // Another request may have rendered it while this one waited.
+    if target.is_file() {
+        return Ok(preview(true));
+    }
+    fs::create_dir_all(&dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+    let partial = target.with_extension("partial.png");
+    let started = std::time::Instant::now();
+    if let Err(e) = convert(&app_handle, &source, &partial).await {
+        let _ = fs::remove_file(&partial);
+        log::warn!("PREVIEW ► {}: {}", source.display(), e);
+        return Err(e);
+    }
+    fs::rename(&partial, &target).map_err(|e| e.to_string())?;
+    log::info!(
+        "PREVIEW ► rendered {} in {:?}",
+        source.display(),
+        started.elapsed()
+    );
+    prune(&dir);
+    Ok(preview(false))
+}
@@ -0,0 +1,140 @@
+// Cache prewarm. Once the backend is healthy, the startup sequence requests
+// a configurable list of GET paths (the dossier list by default) so the
+// first screens render from warm caches. Targets run in ascending `order`,
+// each after its own optional delay, with at most `concurrency` requests in
+// flight. Every result is logged and recorded in the readiness report.
+// `run_prewarm` runs the list again on demand, e.g. after a profile switch
+// pointed the backend at other data.
+
+use crate::readiness::{self, PrewarmResult};
+use crate::{backend_http, settings};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrewarmTarget {
+    /// Backend GET path, starting with `/`.
+    pub path: String,
+    /// Targets run in ascending order; ties keep their listed order.
+    pub order: i32,
+    /// Pause before this target is started.
+    pub delay_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrewarmSettings {
+    /// Pause after the backend is healthy before the startup prewarm.
+    pub initial_delay_ms: u64,
+    /// Requests in flight at once; 1 runs the targets one after another.
+    pub concurrency: usize,
+    pub timeout_ms: u64,
+    pub targets: Vec<PrewarmTarget>,
+}
+
+impl Default for PrewarmSettings {
+    fn default() -> Self {
+        PrewarmSettings {
+            initial_delay_ms: 1_000,
+            concurrency: 1,
+            timeout_ms: 8_000,
+            targets: vec![PrewarmTarget {
+                path: "/api/dossier-management/list?limit=50&offset=0".to_string(),
+                ..PrewarmTarget::default()
+            }],
+        }
+    }
+}
+
+impl PrewarmSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.concurrency == 0 {
+            return Err("prewarm.concurrency must be at least 1".into());
+        }
+        if self.timeout_ms == 0 {
+            return Err("prewarm.timeout_ms must be greater than zero".into());
+        }
+        if let Some(bad) = self.targets.iter().find(|t| !t.path.starts_with('/')) {
+            return Err(format!(
+                "prewarm target '{}' must be a path starting with '/'",
+                bad.path
+            ));
+        }
+        Ok(())
+    }
+}
+
+async fn warm(app_handle: tauri::AppHandle, path: String, timeout: Duration) -> PrewarmResult {
+    let started = Instant::now();
+    let res = backend_http::call(
+        &app_handle,
+        reqwest::Method::GET,
+        &path,
+        None,
+        timeout,
+        None,
+    )
+    .await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    match &res {
+        Ok(reply) if reply.status.is_success() => {
+            log::info!("PREWARM ► {} ok in {}ms", path, latency_ms)
+        }
+        Ok(reply) => log::warn!(
+            "PREWARM ► {} returned {} after {}ms",
+            path,
+            reply.status,
+            latency_ms
+        ),
+        Err(e) => log::warn!("PREWARM ► {} failed after {}ms: {}", path, latency_ms, e),
+    }
+    readiness::record_prewarm(&app_handle, "prewarm", &path, latency_ms, &res)
+}
+
+/// Request every configured target; results come back in start order.
+pub async fn run(app_handle: &tauri::AppHandle) -> Vec<PrewarmResult> {
+    let cfg = settings::current(app_handle).prewarm;
+    let mut targets = cfg.targets;
+    targets.sort_by_key(|t| t.order);
+    let timeout = Duration::from_millis(cfg.timeout_ms);
+    let permits = Arc::new(Semaphore::new(cfg.concurrency.max(1)));
+
+    let mut tasks = Vec::new();
+    for target in targets {
+        if target.delay_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(target.delay_ms)).await;
+        }
+        let Ok(permit) = permits.clone().acquire_owned().await else {
+            break;
+        };
+        let handle = app_handle.clone();
+        tasks.push(tauri::async_runtime::spawn(async move {
+            let result = warm(handle, target.path, timeout).await;
+            drop(permit);
+            result
+        }));
+    }
+    let mut results = Vec::new();
+    for task in tasks {
+        if let Ok(result) = task.await {
+            results.push(result);
+        }
+    }
+    results
+}
+
+/// Startup prewarm: waits `initial_delay_ms` first.
+pub async fn run_after_startup(app_handle: &tauri::AppHandle) {
+    let delay = settings::current(app_handle).prewarm.initial_delay_ms;
+    tokio::time::sleep(Duration::from_millis(delay)).await;
+    run(app_handle).await;
+}
+
+/// Run the prewarm now, e.g. after switching profiles.
+#[tauri::command]
+pub async fn run_prewarm(app_handle: tauri::AppHandle) -> Result<Vec<PrewarmResult>, String> {
+    Ok(run(&app_handle).await)
+}
@@ -0,0 +1,157 @@
+// Process environment diagnostics. Many "backend won't start" reports come
+// down to an unexpected environment or working directory (a stray
+// PYTHONPATH, a CWD on a disconnected drive, a sidecar override left behind
+// by recovery). `get_process_environment` collects both sides: the shell's
+// own process and the running backend. The backend side is read from the OS
+// process table for the tracked pid; there is no handshake that reports it.
+//
+// Values of variables that look like secrets (tokens, keys, passwords) are
+// replaced before anything leaves this module, so the result can be pasted
+// into a bug report as-is.
+
+use crate::{sidecar_fallback, BackendProcess};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use sysinfo::{Pid, ProcessRefreshKind, System, UpdateKind};
+use tauri::Manager;
+
+pub const REDACTED: &str = "<redacted>";
+/// Name fragments marking a variable as sensitive (matched case-insensitively).
+const SENSITIVE: &[&str] = &[
+    "TOKEN",
+    "SECRET",
+    "PASSWORD",
+    "PASSWD",
+    "CREDENTIAL",
+    "API_KEY",
+    "APIKEY",
+    "AUTH",
+    "COOKIE",
+    "SESSION",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessSnapshot {
+    pub pid: u32,
+    pub exe: Option<String>,
+    pub cwd: Option<String>,
+    pub argv: Vec<String>,
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessEnvironment {
+    pub shell: ProcessSnapshot,
+    /// `None` when no backend is tracked or the OS wouldn't let us read it.
+    pub backend: Option<ProcessSnapshot>,
+    /// Sidecar the next spawn would run: the recovery override if one is
+    /// active, else the bundled binary.
+    pub sidecar_path: Option<String>,
+    pub sidecar_exists: bool,
+    pub sidecar_override: bool,
+}
+
+pub fn is_sensitive(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    SENSITIVE.iter().any(|s| upper.contains(s))
+}
+
+fn sanitize<I: IntoIterator<Item = (String, String)>>(vars: I) -> BTreeMap<String, String> {
+    vars.into_iter()
+        .map(|(name, value)| {
+            let value = if is_sensitive(&name) && !value.is_empty() {
+                REDACTED.to_string()
+            } else {
+                value
+            };
+            (name, value)
+        })
+        .collect()
+}
+
+fn display(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+fn shell_snapshot() -> ProcessSnapshot {
+    ProcessSnapshot {
+        pid: std::process::id(),
+        exe: std::env::current_exe().ok().map(|p| display(&p)),
+        cwd: std::env::current_dir().ok().map(|p| display(&p)),
+        argv: std::env::args_os()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect(),
+        env: sanitize(std::env::vars_os().map(|(k, v)| {
+            (
+                k.to_string_lossy().into_owned(),
+                v.to_string_lossy().into_owned(),
+            )
+        })),
+    }
+}
+
+fn backend_snapshot(pid: u32) -> Option<ProcessSnapshot> {
+    let mut sys = System::new();
+    let sys_pid = Pid::from_u32(pid);
+    let refresh = ProcessRefreshKind::new()
+        .with_exe(UpdateKind::Always)
+        .with_cmd(UpdateKind::Always)
+        .with_cwd(UpdateKind::Always)
+        .with_environ(UpdateKind::Always);
+    if !sys.refresh_process_specifics(sys_pid, refresh) {
+        return None;
+    }
+    let process = sys.process(sys_pid)?;
+    Some(ProcessSnapshot {
+        pid,
+        exe: process.exe().map(display),
+        cwd: process.cwd().map(display),
+        argv: process.cmd().to_vec(),
+        env: sanitize(
+            process
+                .environ()
+                .iter()
+                .map(|entry| match entry.split_once('=') {
+                    Some((k, v)) => (k.to_string(), v.to_string()),
+                    None => (entry.clone(), String::new()),
+                }),
+        ),
+    })
+}
+
+/// Environment, CWD, argv and sidecar location of the shell and the backend.
+#[tauri::command]
+pub async fn get_process_environment(
+    app_handle: tauri::AppHandle,
+) -> Result<ProcessEnvironment, String> {
+    let backend_pid = app_handle
+        .state::<BackendProcess>()
+        .0
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|c| c.pid());
+    let override_path = sidecar_fallback::active_override(&app_handle);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let sidecar_override = override_path.is_some();
+        let sidecar = override_path.or_else(sidecar_fallback::bundled_sidecar_path);
+        let backend = backend_pid.and_then(|pid| {
+            let snapshot = backend_snapshot(pid);
+            if snapshot.is_none() {
+                log::info!("PROCESS_ENV ► could not read backend process {}", pid);
+            }
+            snapshot
+        });
+        ProcessEnvironment {
+            shell: shell_snapshot(),
+            backend,
+            sidecar_exists: sidecar.as_ref().is_some_and(|p| p.is_file()),
+            sidecar_path: sidecar.as_deref().map(display),
+            sidecar_override,
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
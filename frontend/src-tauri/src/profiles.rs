@@ -0,0 +1,285 @@
+// Profiles: separate workspaces, each with its own data folder and backend
+// configuration, e.g. one per client or county project. The implicit
+// `default` profile is the app's regular data location (`data_dir` or the
+// installed default); other profiles live in `Plattera/Profiles/<id>` beside
+// it unless created with a custom folder. Only one profile is active at a
+// time: switching restarts the backend pointed at the selected profile's
+// data, and the new profile is announced as `profile-changed`. Should the
+// restarted backend land on another port, the webview follows it through
+// `backend-endpoint-changed` (`endpoint.rs`) like after any restart.
+//
+// A profile's backend settings override the global ones for that profile
+// only: the transport, and extra environment variables for the sidecar.
+
+use crate::endpoint::BackendTransport;
+use crate::{
+    backend_config, backend_lifecycle, correlation, i18n, onboarding, portable, settings,
+    BackendProcess,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tauri::path::BaseDirectory;
+use tauri::Manager;
+
+pub const DEFAULT_PROFILE: &str = "default";
+/// Parent of non-default profile folders, under LocalData.
+const PROFILES_DIR: &str = "Plattera/Profiles";
+/// Portable-mode counterpart, under the portable data folder.
+const PORTABLE_PROFILES_DIR: &str = "Profiles";
+const MAX_NAME_LEN: usize = 64;
+
+/// Backend configuration specific to one profile.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileBackend {
+    /// Overrides the global `backend_transport`.
+    pub transport: Option<BackendTransport>,
+    /// Extra environment variables for the backend process.
+    pub env: BTreeMap<String, String>,
+}
+
+impl ProfileBackend {
+    pub fn validate(&self) -> Result<(), String> {
+        backend_config::validate_env(&self.env)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+    /// Custom data folder; `None` uses `Profiles/<id>`.
+    #[serde(default)]
+    pub data_dir: Option<String>,
+    #[serde(default)]
+    pub backend: ProfileBackend,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileInfo {
+    pub id: String,
+    pub name: String,
+    pub data_dir: String,
+    pub active: bool,
+    pub is_default: bool,
+    pub created_at: Option<String>,
+}
+
+/// The active profile, or `None` for the default one.
+pub fn active(app_handle: &tauri::AppHandle) -> Option<Profile> {
+    let current = settings::current(app_handle);
+    let id = current.active_profile?;
+    current.profiles.into_iter().find(|p| p.id == id)
+}
+
+fn profile_dir(app_handle: &tauri::AppHandle, profile: &Profile) -> Result<PathBuf, String> {
+    if let Some(dir) = &profile.data_dir {
+        return Ok(PathBuf::from(dir));
+    }
+    let parent = match portable::root() {
+        Some(root) => root.join(PORTABLE_PROFILES_DIR),
+        None => app_handle
+            .path()
+            .resolve(PROFILES_DIR, BaseDirectory::LocalData)
+            .map_err(|e| e.to_string())?,
+    };
+    Ok(parent.join(&profile.id))
+}
+
+/// Data folder of the active profile, if a non-default one is active.
+pub fn active_data_dir(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    active(app_handle).and_then(|p| profile_dir(app_handle, &p).ok())
+}
+
+/// Transport for the next backend spawn, honouring the profile override.
+pub fn transport(app_handle: &tauri::AppHandle) -> BackendTransport {
+    active(app_handle)
+        .and_then(|p| p.backend.transport)
+        .unwrap_or_else(|| settings::current(app_handle).backend_transport)
+}
+
+/// Extra backend environment of the active profile.
+pub fn backend_env(app_handle: &tauri::AppHandle) -> BTreeMap<String, String> {
+    active(app_handle)
+        .map(|p| p.backend.env)
+        .unwrap_or_default()
+}
+
+fn list(app_handle: &tauri::AppHandle) -> Vec<ProfileInfo> {
+    let current = settings::current(app_handle);
+    let default_dir = match &current.data_dir {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => settings::default_data_dir(app_handle),
+    };
+    let mut out = vec![ProfileInfo {
+        id: DEFAULT_PROFILE.to_string(),
+        name: "Default".to_string(),
+        data_dir: default_dir
+            .map(|d| d.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        active: current.active_profile.is_none(),
+        is_default: true,
+        created_at: None,
+    }];
+    out.extend(current.profiles.iter().map(|p| {
+        ProfileInfo {
+            id: p.id.clone(),
+            name: p.name.clone(),
+            data_dir: profile_dir(app_handle, p)
+                .map(|d| d.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            active: current.active_profile.as_deref() == Some(p.id.as_str()),
+            is_default: false,
+            created_at: Some(p.created_at.clone()),
+        }
+    }));
+    out
+}
+
+/// Folder-safe id from a display name, unique among `existing`.
+fn make_id(name: &str, existing: &[Profile]) -> String {
+    let mut base: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    base = base
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if base.is_empty() || base == DEFAULT_PROFILE {
+        base = "profile".to_string();
+    }
+    let mut id = base.clone();
+    let mut n = 2;
+    while existing.iter().any(|p| p.id == id) {
+        id = format!("{}-{}", base, n);
+        n += 1;
+    }
+    id
+}
+
+fn info_for(app_handle: &tauri::AppHandle, id: &str) -> Result<ProfileInfo, String> {
+    list(app_handle)
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| i18n::tf(app_handle, "error.unknown_profile", &[("id", id)]))
+}
+
+#[tauri::command]
+pub async fn list_profiles(app_handle: tauri::AppHandle) -> Result<Vec<ProfileInfo>, String> {
+    Ok(list(&app_handle))
+}
+
+/// Create a profile named `name`, with its data in `data_dir` or in its own
+/// folder under `Profiles/`. It is not activated.
+#[tauri::command]
+pub async fn create_profile(
+    app_handle: tauri::AppHandle,
+    name: String,
+    data_dir: Option<String>,
+    backend: Option<ProfileBackend>,
+) -> Result<ProfileInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let name = name.trim().to_string();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+            let max = MAX_NAME_LEN.to_string();
+            return Err(i18n::tf(
+                &app_handle,
+                "error.profile_name_length",
+                &[("max", &max)],
+            ));
+        }
+        let current = settings::current(&app_handle);
+        if current
+            .profiles
+            .iter()
+            .any(|p| p.name.eq_ignore_ascii_case(&name))
+        {
+            return Err(i18n::tf(
+                &app_handle,
+                "error.profile_exists",
+                &[("name", &name)],
+            ));
+        }
+        let backend = backend.unwrap_or_default();
+        backend.validate()?;
+        let data_dir = data_dir
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .map(|p| onboarding::validate_data_dir(&p))
+            .transpose()?;
+        let profile = Profile {
+            id: make_id(&name, &current.profiles),
+            name,
+            created_at: chrono::Local::now().to_rfc3339(),
+            data_dir,
+            backend,
+        };
+        let dir = profile_dir(&app_handle, &profile)?;
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+        log::info!(
+            "PROFILES ► created '{}' ({}) at {:?}",
+            profile.name,
+            profile.id,
+            dir
+        );
+        let id = profile.id.clone();
+        settings::update(&app_handle, |s| s.profiles.push(profile))?;
+        info_for(&app_handle, &id)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Make `id` the active profile and restart the backend on its data.
+#[tauri::command]
+pub async fn switch_profile(
+    app_handle: tauri::AppHandle,
+    id: String,
+) -> Result<ProfileInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let current = settings::current(&app_handle);
+        let next = if id == DEFAULT_PROFILE {
+            None
+        } else if current.profiles.iter().any(|p| p.id == id) {
+            Some(id.clone())
+        } else {
+            return Err(i18n::tf(
+                &app_handle,
+                "error.unknown_profile",
+                &[("id", &id)],
+            ));
+        };
+        if current.active_profile == next {
+            return info_for(&app_handle, &id);
+        }
+        let _op = correlation::begin("profile-switch");
+        log::info!("PROFILES ► switching to '{}'", id);
+        settings::update(&app_handle, |s| s.active_profile = next)?;
+
+        let tracked = app_handle
+            .state::<BackendProcess>()
+            .0
+            .lock()
+            .unwrap()
+            .is_some();
+        if tracked {
+            backend_lifecycle::restart_backend(&app_handle)?;
+        }
+        let info = info_for(&app_handle, &id)?;
+        correlation::emit(&app_handle, "profile-changed", info.clone());
+        Ok(info)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,376 @@
+// Proxy for outbound HTTP: the app updater, the backend update feed and
+// downloads, release notes, telemetry, connectivity probes and
+// `debug_updater_endpoint`. Traffic to the backend is loopback and never
+// goes through a proxy.
+//
+// The `proxy` setting picks the proxy per request target:
+//   system  (default) `HTTPS_PROXY` / `HTTP_PROXY` / `ALL_PROXY` with
+//           `NO_PROXY`, else the OS setting: Internet Options on Windows,
+//           network preferences on macOS. Linux desktops export their proxy
+//           through the same variables. PAC scripts are not evaluated.
+//   manual  the URL in the setting, bypassed for hosts in `bypass`
+//   off     always direct
+// Only HTTP proxies are supported (`http://host:port`, credentials allowed).
+
+use crate::{connectivity, settings, tls_trust};
+use serde::{Deserialize, Serialize};
+use tauri_plugin_updater::{Updater, UpdaterExt};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyMode {
+    #[default]
+    System,
+    Manual,
+    Off,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxySettings {
+    pub mode: ProxyMode,
+    /// Proxy for `manual`, e.g. `http://proxy.corp.example:8080`.
+    pub url: Option<String>,
+    /// Hosts reached directly in `manual` mode: exact names, `.example.com`
+    /// or `*.example.com` suffixes, or `*` for everything.
+    pub bypass: Vec<String>,
+}
+
+impl ProxySettings {
+    pub fn validate(&self) -> Result<(), String> {
+        match (&self.mode, self.url.as_deref()) {
+            (ProxyMode::Manual, None) => Err("proxy.url is required in manual mode".to_string()),
+            (_, Some(url)) => check_url(url),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxySource {
+    Settings,
+    Environment,
+    System,
+    Direct,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedProxy {
+    pub target: String,
+    /// `None` means a direct connection.
+    pub proxy: Option<String>,
+    pub source: ProxySource,
+}
+
+/// `url` with an `http://` scheme added when it has none.
+fn normalize(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("http://{}", url)
+    }
+}
+
+fn check_url(url: &str) -> Result<(), String> {
+    let url = normalize(url);
+    if !url.starts_with("http://") {
+        return Err(format!("proxy '{}' is not an HTTP proxy", url));
+    }
+    reqwest::Proxy::all(&url)
+        .map(|_| ())
+        .map_err(|e| format!("invalid proxy '{}': {}", url, e))
+}
+
+fn host_of(target: &str) -> String {
+    let rest = target.split_once("://").map(|(_, r)| r).unwrap_or(target);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host = authority
+        .rsplit_once('@')
+        .map(|(_, h)| h)
+        .unwrap_or(authority);
+    let host = match host.strip_prefix('[') {
+        Some(v6) => v6.split(']').next().unwrap_or(v6),
+        None => host.split(':').next().unwrap_or(host),
+    };
+    host.to_ascii_lowercase()
+}
+
+/// Whether `host` is on a bypass list (`NO_PROXY`, `ProxyOverride`, ...).
+fn bypassed(host: &str, list: &[String]) -> bool {
+    if matches!(host, "localhost" | "127.0.0.1" | "::1") {
+        return true;
+    }
+    list.iter().any(|entry| {
+        let entry = entry.trim().to_ascii_lowercase();
+        match entry.as_str() {
+            "" => false,
+            "*" => true,
+            // Windows: any host without a dot.
+            "<local>" => !host.contains('.'),
+            _ => {
+                let suffix = entry.trim_start_matches('*');
+                if suffix.starts_with('.') {
+                    host.ends_with(suffix) || host == &suffix[1..]
+                } else {
+                    host == suffix
+                }
+            }
+        }
+    })
+}
+
+fn env_var(names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .filter_map(|n| std::env::var(n).ok())
+        .map(|v| v.trim().to_string())
+        .find(|v| !v.is_empty())
+}
+
+fn from_env(target: &str) -> Option<(String, Vec<String>)> {
+    let scheme_vars: &[&str] = if target.starts_with("https://") {
+        &["HTTPS_PROXY", "https_proxy"]
+    } else {
+        &["HTTP_PROXY", "http_proxy"]
+    };
+    let proxy = env_var(scheme_vars).or_else(|| env_var(&["ALL_PROXY", "all_proxy"]))?;
+    let no_proxy = env_var(&["NO_PROXY", "no_proxy"])
+        .map(|v| v.split(',').map(str::to_string).collect())
+        .unwrap_or_default();
+    Some((proxy, no_proxy))
+}
+
+/// The proxy to use for `target`.
+pub fn resolve(app_handle: &tauri::AppHandle, target: &str) -> ResolvedProxy {
+    let cfg = settings::current(app_handle).proxy;
+    let host = host_of(target);
+    let resolved = |proxy: Option<String>, source| ResolvedProxy {
+        target: target.to_string(),
+        proxy: proxy.map(|p| normalize(&p)),
+        source,
+    };
+    let direct = resolved(None, ProxySource::Direct);
+    match cfg.mode {
+        ProxyMode::Off => direct,
+        ProxyMode::Manual => match cfg.url {
+            Some(url) if !bypassed(&host, &cfg.bypass) => {
+                resolved(Some(url), ProxySource::Settings)
+            }
+            _ => direct,
+        },
+        ProxyMode::System => {
+            if let Some((proxy, no_proxy)) = from_env(target) {
+                if bypassed(&host, &no_proxy) {
+                    return direct;
+                }
+                return resolved(Some(proxy), ProxySource::Environment);
+            }
+            match imp::system_proxy(target.starts_with("https://")) {
+                Some((proxy, exceptions)) if !bypassed(&host, &exceptions) => {
+                    resolved(Some(proxy), ProxySource::System)
+                }
+                _ => direct,
+            }
+        }
+    }
+}
+
+/// Client builder for requests to `target`, with the proxy and any extra
+/// trusted roots (`tls_trust.rs`) applied. Callers add their own timeouts;
+/// blocking code sends through `backend_http::block_on`.
+pub fn client_builder(app_handle: &tauri::AppHandle, target: &str) -> reqwest::ClientBuilder {
+    let mut builder = reqwest::Client::builder();
+    if let Some(config) = tls_trust::tls_config(app_handle) {
+        builder = builder.use_preconfigured_tls(config);
+    }
+    // `resolve` has already looked at the environment and the OS setting.
+    let Some(url) = resolve(app_handle, target).proxy else {
+        return builder.no_proxy();
+    };
+    match reqwest::Proxy::all(&url) {
+        Ok(proxy) => builder.proxy(proxy),
+        Err(e) => {
+            log::warn!("PROXY ► ignoring invalid proxy '{}': {}", url, e);
+            builder.no_proxy()
+        }
+    }
+}
+
+/// The app updater, with the proxy for its first endpoint applied.
+pub fn updater(app_handle: &tauri::AppHandle) -> Result<Updater, String> {
+    let builder = app_handle.updater_builder();
+    let target = connectivity::updater_endpoints(app_handle)
+        .into_iter()
+        .next()
+        .unwrap_or_default();
+    let builder = match resolve(app_handle, &target).proxy {
+        Some(url) => {
+            let url =
+                tauri::Url::parse(&url).map_err(|e| format!("invalid proxy '{}': {}", url, e))?;
+            builder.proxy(url)
+        }
+        None => builder.no_proxy(),
+    };
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// Which proxy a request to `url` would use; defaults to the update
+/// endpoint. For the network section of the settings page.
+#[tauri::command]
+pub async fn get_proxy_info(
+    app_handle: tauri::AppHandle,
+    url: Option<String>,
+) -> Result<ResolvedProxy, String> {
+    let target = url
+        .or_else(|| {
+            connectivity::updater_endpoints(&app_handle)
+                .into_iter()
+                .next()
+        })
+        .unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || resolve(&app_handle, &target))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(windows)]
+mod imp {
+    use windows_sys::Win32::System::Registry::{
+        RegGetValueW, HKEY_CURRENT_USER, RRF_RT_DWORD, RRF_RT_REG_SZ,
+    };
+
+    const INTERNET_SETTINGS: &str =
+        "Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings";
+
+    fn wide(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    fn read_dword(name: &str) -> Option<u32> {
+        let (key, name) = (wide(INTERNET_SETTINGS), wide(name));
+        let mut value = 0u32;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        let status = unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                key.as_ptr(),
+                name.as_ptr(),
+                RRF_RT_DWORD,
+                std::ptr::null_mut(),
+                &mut value as *mut u32 as *mut _,
+                &mut size,
+            )
+        };
+        (status == 0).then_some(value)
+    }
+
+    fn read_string(name: &str) -> Option<String> {
+        let (key, name) = (wide(INTERNET_SETTINGS), wide(name));
+        let mut buf = vec![0u16; 2048];
+        let mut size = (buf.len() * 2) as u32;
+        let status = unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                key.as_ptr(),
+                name.as_ptr(),
+                RRF_RT_REG_SZ,
+                std::ptr::null_mut(),
+                buf.as_mut_ptr() as *mut _,
+                &mut size,
+            )
+        };
+        if status != 0 {
+            return None;
+        }
+        let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+        Some(String::from_utf16_lossy(&buf[..len]))
+    }
+
+    /// Internet Options: `ProxyServer` is either `host:port` or per scheme,
+    /// `http=host:port;https=host:port`.
+    pub fn system_proxy(https: bool) -> Option<(String, Vec<String>)> {
+        if read_dword("ProxyEnable")? == 0 {
+            return None;
+        }
+        let server = read_string("ProxyServer")?;
+        let proxy = if server.contains('=') {
+            let wanted = if https { "https" } else { "http" };
+            server.split(';').find_map(|part| {
+                let (scheme, addr) = part.split_once('=')?;
+                scheme
+                    .trim()
+                    .eq_ignore_ascii_case(wanted)
+                    .then(|| addr.trim().to_string())
+            })?
+        } else {
+            server.trim().to_string()
+        };
+        let exceptions = read_string("ProxyOverride")
+            .map(|v| v.split(';').map(str::to_string).collect())
+            .unwrap_or_default();
+        Some((proxy, exceptions))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::process::Command;
+
+    /// `scutil --proxy` prints the active network service's proxy
+    /// dictionary, e.g. `HTTPSEnable : 1`, `HTTPSProxy : proxy.corp`,
+    /// `HTTPSPort : 8080` and an `ExceptionsList` array.
+    pub fn system_proxy(https: bool) -> Option<(String, Vec<String>)> {
+        let output = Command::new("scutil").arg("--proxy").output().ok()?;
+        let text = String::from_utf8_lossy(&output.stdout);
+        let prefix = if https { "HTTPS" } else { "HTTP" };
+        let mut enabled = false;
+        let mut host = None;
+        let mut port = None;
+        let mut exceptions = Vec::new();
+        let mut in_exceptions = false;
+        for line in text.lines() {
+            let line = line.trim();
+            if in_exceptions {
+                if line == "}" {
+                    in_exceptions = false;
+                } else if let Some((_, value)) = line.split_once(" : ") {
+                    exceptions.push(value.trim().to_string());
+                }
+                continue;
+            }
+            let Some((key, value)) = line.split_once(" : ") else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "ExceptionsList" => in_exceptions = true,
+                k if k == format!("{}Enable", prefix) => enabled = value == "1",
+                k if k == format!("{}Proxy", prefix) => host = Some(value.to_string()),
+                k if k == format!("{}Port", prefix) => port = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        if !enabled {
+            return None;
+        }
+        let host = host?;
+        Some((
+            match port {
+                Some(port) => format!("{}:{}", host, port),
+                None => host,
+            },
+            exceptions,
+        ))
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod imp {
+    /// Linux desktops export their proxy settings as environment variables,
+    /// which `resolve` has already looked at.
+    pub fn system_proxy(_https: bool) -> Option<(String, Vec<String>)> {
+        None
+    }
+}
@@ -0,0 +1,137 @@
+// Record of how the backend came up: each startup health attempt and each
+// prewarm / model-warmup request with its latency. Kept in memory for the
+// current launch and served by `get_readiness_report` to the frontend's
+// connection details panel.
+
+use crate::backend_http::BackendReply;
+use crate::telemetry;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::Manager;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthAttempt {
+    pub at: String,
+    /// Milliseconds since the startup sequence began.
+    pub elapsed_ms: u64,
+    pub ok: bool,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PrewarmResult {
+    /// "prewarm" (shell startup requests) or "model_warmup".
+    pub kind: &'static str,
+    pub path: String,
+    pub ok: bool,
+    pub status: Option<u16>,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReadinessReport {
+    pub started_at: Option<String>,
+    pub health_attempts: Vec<HealthAttempt>,
+    pub ready: bool,
+    pub time_to_ready_ms: Option<u64>,
+    pub prewarm: Vec<PrewarmResult>,
+}
+
+#[derive(Default)]
+struct ReadinessInner {
+    started: Option<Instant>,
+    report: ReadinessReport,
+}
+
+pub struct ReadinessLog(Mutex<ReadinessInner>);
+
+impl ReadinessLog {
+    pub fn new() -> Self {
+        ReadinessLog(Mutex::new(ReadinessInner::default()))
+    }
+}
+
+fn now() -> String {
+    chrono::Local::now().to_rfc3339()
+}
+
+/// Start a fresh timeline (a new startup sequence).
+pub fn begin(app_handle: &tauri::AppHandle) {
+    let log = app_handle.state::<ReadinessLog>();
+    let mut inner = log.0.lock().unwrap();
+    inner.started = Some(Instant::now());
+    inner.report = ReadinessReport {
+        started_at: Some(now()),
+        ..ReadinessReport::default()
+    };
+}
+
+pub fn record_health_attempt(
+    app_handle: &tauri::AppHandle,
+    latency_ms: u64,
+    result: Result<(), String>,
+) {
+    let log = app_handle.state::<ReadinessLog>();
+    let mut inner = log.0.lock().unwrap();
+    let elapsed_ms = inner
+        .started
+        .map(|t| t.elapsed().as_millis() as u64)
+        .unwrap_or(0);
+    let ok = result.is_ok();
+    inner.report.health_attempts.push(HealthAttempt {
+        at: now(),
+        elapsed_ms,
+        ok,
+        latency_ms,
+        error: result.err(),
+    });
+    if ok && !inner.report.ready {
+        inner.report.ready = true;
+        inner.report.time_to_ready_ms = Some(elapsed_ms);
+        drop(inner);
+        telemetry::record(
+            app_handle,
+            "backend_ready",
+            serde_json::json!({ "time_to_ready_ms": elapsed_ms }),
+        );
+    }
+}
+
+/// Record one prewarm request from a backend call result.
+pub fn record_prewarm(
+    app_handle: &tauri::AppHandle,
+    kind: &'static str,
+    path: &str,
+    latency_ms: u64,
+    result: &Result<BackendReply, String>,
+) -> PrewarmResult {
+    let (ok, status, error) = match result {
+        Ok(reply) => (reply.status.is_success(), Some(reply.status.as_u16()), None),
+        Err(e) => (false, None, Some(e.clone())),
+    };
+    let entry = PrewarmResult {
+        kind,
+        path: path.to_string(),
+        ok,
+        status,
+        latency_ms,
+        error,
+    };
+    let log = app_handle.state::<ReadinessLog>();
+    log.0.lock().unwrap().report.prewarm.push(entry.clone());
+    entry
+}
+
+pub fn report(app_handle: &tauri::AppHandle) -> ReadinessReport {
+    let log = app_handle.state::<ReadinessLog>();
+    let report = log.0.lock().unwrap().report.clone();
+    report
+}
+
+#[tauri::command]
+pub async fn get_readiness_report(app_handle: tauri::AppHandle) -> Result<ReadinessReport, String> {
+    Ok(report(&app_handle))
+}
@@ -0,0 +1,82 @@
+// Most-recently-used dossiers for File → Open Recent. The frontend reports a
+// dossier as opened with `add_recent_dossier`; the list is kept newest first
+// in `recent.json` and the menu is rebuilt on every change.
+
+use crate::{i18n, menu, portable};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+const RECENT_FILE: &str = "recent.json";
+pub const MAX_RECENT: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentDossier {
+    pub id: String,
+    pub title: String,
+    pub opened_at: String,
+}
+
+fn recent_path(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    portable::resolve(app_handle, RECENT_FILE).ok()
+}
+
+/// The saved list, newest first.
+pub fn load(app_handle: &tauri::AppHandle) -> Vec<RecentDossier> {
+    recent_path(app_handle)
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save(app_handle: &tauri::AppHandle, items: &[RecentDossier]) -> Result<(), String> {
+    let path = recent_path(app_handle).ok_or("cannot resolve recent list path")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("create {:?}: {}", parent, e))?;
+    }
+    let raw = serde_json::to_string_pretty(items).map_err(|e| e.to_string())?;
+    fs::write(&path, raw).map_err(|e| format!("write {:?}: {}", path, e))?;
+    if let Err(e) = menu::refresh(app_handle) {
+        log::warn!("RECENT ► could not rebuild menu: {}", e);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_recent_dossiers(
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<RecentDossier>, String> {
+    Ok(load(&app_handle))
+}
+
+/// Move `id` to the top of the list, adding it if new.
+#[tauri::command]
+pub async fn add_recent_dossier(
+    app_handle: tauri::AppHandle,
+    id: String,
+    title: String,
+) -> Result<Vec<RecentDossier>, String> {
+    let id = id.trim().to_string();
+    if id.is_empty() {
+        return Err(i18n::t(&app_handle, "error.empty_dossier_id"));
+    }
+    let mut items = load(&app_handle);
+    items.retain(|item| item.id != id);
+    items.insert(
+        0,
+        RecentDossier {
+            id,
+            title: title.trim().to_string(),
+            opened_at: chrono::Local::now().to_rfc3339(),
+        },
+    );
+    items.truncate(MAX_RECENT);
+    save(&app_handle, &items)?;
+    Ok(items)
+}
+
+#[tauri::command]
+pub async fn clear_recent_dossiers(app_handle: tauri::AppHandle) -> Result<(), String> {
+    log::info!("RECENT ► cleared");
+    save(&app_handle, &[])
+}
@@ -0,0 +1,325 @@
+// Recovery console. If the main webview never reports that its bundle loaded
+// (dev server down, corrupted or missing resources), the user would otherwise
+// be left with a blank window. After a timeout the shell opens a small
+// window whose static HTML is served from Rust over the `plattera-recovery`
+// scheme; its buttons are plain form posts handled by the same protocol, so
+// the console works without the frontend bundle or IPC. Only the console's
+// own webview is served, and the actions answer POST only, so no other page
+// can trigger them with a link or an image.
+
+use crate::{backend_lifecycle, backend_log, paths, portable, repair, session, trace};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+use tauri::http::{Method, Request, Response};
+use tauri::{Manager, UriSchemeContext, UriSchemeResponder, WebviewUrl, WebviewWindowBuilder};
+
+pub const SCHEME: &str = "plattera-recovery";
+const WINDOW_LABEL: &str = "recovery";
+/// One-shot marker: present at startup means "launch in safe mode".
+const SAFE_MODE_FLAG: &str = "safe_mode.flag";
+
+/// How long the main window gets to report ready. Dev builds compile the
+/// frontend on first load, so they get longer.
+fn ui_ready_timeout() -> Duration {
+    if cfg!(debug_assertions) {
+        Duration::from_secs(120)
+    } else {
+        Duration::from_secs(30)
+    }
+}
+
+pub struct RecoveryState {
+    ui_ready: AtomicBool,
+    console_open: AtomicBool,
+    safe_mode: bool,
+}
+
+impl RecoveryState {
+    pub fn new(safe_mode: bool) -> Self {
+        RecoveryState {
+            ui_ready: AtomicBool::new(false),
+            console_open: AtomicBool::new(false),
+            safe_mode,
+        }
+    }
+}
+
+fn flag_path(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    portable::resolve(app_handle, SAFE_MODE_FLAG).ok()
+}
+
+/// Consume the safe-mode marker left by a "relaunch in safe mode" request.
+pub fn take_safe_mode_flag(app_handle: &tauri::AppHandle) -> bool {
+    let Some(path) = flag_path(app_handle).filter(|p| p.exists()) else {
+        return false;
+    };
+    let _ = fs::remove_file(&path);
+    log::warn!("RECOVERY ► starting in safe mode (session restore, warmup and pipelines disabled)");
+    true
+}
+
+/// Whether this launch runs in safe mode: optional startup work (session
+/// restore, model warmup, scheduled pipelines) is skipped.
+pub fn safe_mode(app_handle: &tauri::AppHandle) -> bool {
+    app_handle
+        .try_state::<RecoveryState>()
+        .map(|s| s.safe_mode)
+        .unwrap_or(false)
+}
+
+/// Open the recovery console if the main window hasn't reported ready in
+/// time.
+pub fn spawn_ui_watchdog(app_handle: tauri::AppHandle) {
+    thread::spawn(move || {
+        thread::sleep(ui_ready_timeout());
+        if app_handle
+            .state::<RecoveryState>()
+            .ui_ready
+            .load(Ordering::SeqCst)
+        {
+            return;
+        }
+        log::error!(
+            "RECOVERY ► main window did not report ready within {}s; opening recovery console",
+            ui_ready_timeout().as_secs()
+        );
+        if let Err(e) = open(&app_handle) {
+            log::error!("RECOVERY ► could not open recovery console: {}", e);
+        }
+    });
+}
+
+fn console_url() -> Result<tauri::Url, String> {
+    // Windows and Android serve custom schemes as http://<scheme>.localhost.
+    let raw = if cfg!(any(windows, target_os = "android")) {
+        format!("http://{}.localhost/", SCHEME)
+    } else {
+        format!("{}://localhost/", SCHEME)
+    };
+    raw.parse()
+        .map_err(|e| format!("invalid recovery url {}: {}", raw, e))
+}
+
+pub fn open(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(WINDOW_LABEL) {
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(());
+    }
+    let state = app_handle.state::<RecoveryState>();
+    if state.console_open.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    let result = WebviewWindowBuilder::new(
+        app_handle,
+        WINDOW_LABEL,
+        WebviewUrl::CustomProtocol(console_url()?),
+    )
+    .title("Plattera - Recovery")
+    .inner_size(560.0, 520.0)
+    .resizable(true)
+    .center()
+    .build();
+    if let Err(e) = result {
+        state.console_open.store(false, Ordering::SeqCst);
+        return Err(e.to_string());
+    }
+    Ok(())
+}
+
+fn empty(status: u16) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .body(Vec::new())
+        .unwrap_or_default()
+}
+
+/// Protocol handler for the console. Actions may block (repair, copying
+/// logs), so they run off the webview thread.
+pub fn handle_protocol(
+    ctx: UriSchemeContext<'_, tauri::Wry>,
+    request: Request<Vec<u8>>,
+    responder: UriSchemeResponder,
+) {
+    if ctx.webview_label() != WINDOW_LABEL {
+        log::warn!(
+            "RECOVERY ► refused request from webview '{}'",
+            ctx.webview_label()
+        );
+        responder.respond(empty(403));
+        return;
+    }
+    let app_handle = ctx.app_handle().clone();
+    let path = request.uri().path().to_string();
+    if path != "/" && request.method() != Method::POST {
+        responder.respond(empty(405));
+        return;
+    }
+    thread::spawn(move || {
+        let message = match path.as_str() {
+            "/" => None,
+            "/export-logs" => Some(
+                export_logs(&app_handle).map(|dir| format!("Logs copied to {}", dir.display())),
+            ),
+            "/safe-mode" => Some(relaunch_in_safe_mode(&app_handle)),
+            "/repair" => Some(run_repair(&app_handle)),
+            "/factory-reset" => Some(
+                crate::factory_reset(&app_handle)
+                    .map(|_| "All local data was deleted; restarting Plattera…".to_string()),
+            ),
+            _ => {
+                responder.respond(empty(404));
+                return;
+            }
+        };
+        if let Some(result) = &message {
+            match result {
+                Ok(m) => log::info!("RECOVERY ► {}: {}", path, m),
+                Err(e) => log::error!("RECOVERY ► {} failed: {}", path, e),
+            }
+        }
+        responder.respond(
+            Response::builder()
+                .header("Content-Type", "text/html; charset=utf-8")
+                .body(render(message).into_bytes())
+                .unwrap_or_default(),
+        );
+    });
+}
+
+fn copy_files(from: &Path, to: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(paths::extended(from)) else {
+        return 0;
+    };
+    let to = paths::extended(to);
+    if fs::create_dir_all(&to).is_err() {
+        return 0;
+    }
+    entries
+        .flatten()
+        .filter(|e| e.path().is_file())
+        .filter(|e| fs::copy(e.path(), to.join(e.file_name())).is_ok())
+        .count()
+}
+
+/// Copy shell and backend logs into a timestamped folder under Downloads
+/// (or the desktop) and show it.
+fn export_logs(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let paths = app_handle.path();
+    let base = paths
+        .download_dir()
+        .or_else(|_| paths.desktop_dir())
+        .map_err(|e| format!("no download or desktop folder: {}", e))?;
+    let target = base.join(format!(
+        "Plattera-logs-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+
+    let mut copied = 0;
+    if let Ok(dir) = portable::app_log_dir(app_handle) {
+        copied += copy_files(&dir, &target.join("shell"));
+    }
+    if let Some(dir) = trace::backend_log_dir(app_handle) {
+        copied += copy_files(&dir, &target.join("backend"));
+    }
+    if let Some(dir) = backend_log::log_dir(app_handle) {
+        copied += copy_files(&dir, &target.join("backend_output"));
+    }
+    if copied == 0 {
+        return Err("no log files found".to_string());
+    }
+    let _ = backend_log::open_path(&target);
+    Ok(target)
+}
+
+fn relaunch_in_safe_mode(app_handle: &tauri::AppHandle) -> Result<String, String> {
+    let path = flag_path(app_handle).ok_or("could not resolve safe-mode marker path")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("create {:?}: {}", parent, e))?;
+    }
+    fs::write(&path, b"1").map_err(|e| format!("write {:?}: {}", path, e))?;
+    let handle = app_handle.clone();
+    thread::spawn(move || {
+        // Let the response reach the console before the app goes away.
+        thread::sleep(Duration::from_millis(500));
+        session::persist(&handle);
+        backend_lifecycle::shutdown_backend_for_exit(&handle);
+        handle.restart();
+    });
+    Ok("Restarting Plattera in safe mode…".to_string())
+}
+
+fn run_repair(app_handle: &tauri::AppHandle) -> Result<String, String> {
+    let report = repair::repair(app_handle);
+    let summary = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    Ok(format!("Repair finished:\n{}", summary))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render(message: Option<Result<String, String>>) -> String {
+    let status = match message {
+        None => String::new(),
+        Some(Ok(m)) => format!("<pre class=\"ok\">{}</pre>", escape(&m)),
+        Some(Err(e)) => format!("<pre class=\"err\">{}</pre>", escape(&e)),
+    };
+    PAGE.replace("{{status}}", &status)
+}
+
+const PAGE: &str = r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Plattera - Recovery</title>
+<style>
+  body { font-family: system-ui, sans-serif; margin: 24px; color: #222; background: #f6f6f4; }
+  h1 { font-size: 20px; margin: 0 0 8px; }
+  p { margin: 0 0 16px; line-height: 1.4; }
+  form { margin: 0; }
+  button.action { display: block; width: 100%; margin: 8px 0; padding: 10px 12px; border: 1px solid #bbb;
+                  border-radius: 6px; background: #fff; color: #222; font: inherit; text-align: left;
+                  cursor: pointer; }
+  button.action:hover { background: #eef; }
+  button.danger { border-color: #c66; color: #a22; }
+  small { display: block; color: #666; margin-top: 2px; }
+  pre { white-space: pre-wrap; padding: 10px; border-radius: 6px; max-height: 180px; overflow: auto; }
+  pre.ok { background: #e8f5e9; }
+  pre.err { background: #fdecea; }
+</style>
+</head>
+<body>
+<h1>Plattera could not load its interface</h1>
+<p>The main window did not finish loading. The options below work without it.</p>
+{{status}}
+<form method="post" action="/export-logs"><button class="action">Export logs<small>Copy shell and backend logs to your Downloads folder.</small></button></form>
+<form method="post" action="/safe-mode"><button class="action">Relaunch in safe mode<small>Skip session restore, model warmup and scheduled pipelines.</small></button></form>
+<form method="post" action="/repair"><button class="action">Repair installation<small>Check the backend, recreate missing folders and clear stale files.</small></button></form>
+<form method="post" action="/factory-reset"
+      onsubmit="return confirm('Delete all local Plattera data and restart?')"><button class="action danger">Factory reset<small>Delete all local data and restart.</small></button></form>
+</body>
+</html>
+"#;
+
+/// Called by the frontend once its bundle has mounted.
+#[tauri::command]
+pub async fn report_ui_ready(app_handle: tauri::AppHandle) -> Result<(), String> {
+    let state = app_handle.state::<RecoveryState>();
+    if !state.ui_ready.swap(true, Ordering::SeqCst) {
+        log::info!("RECOVERY ► main window reported ready");
+    }
+    Ok(())
+}
+
+/// Whether this launch is in safe mode.
+#[tauri::command]
+pub async fn is_safe_mode(app_handle: tauri::AppHandle) -> Result<bool, String> {
+    Ok(safe_mode(&app_handle))
+}
@@ -0,0 +1,209 @@
+// "Repair installation": one action for the broken-install states we see
+// most (missing or corrupt sidecar, half-finished lock probes, leftover temp files,
+// deleted data folders). Re-checks what can be checked, fixes what can be
+// fixed, restarts the backend and reports every step to the frontend.
+
+use crate::backend_lifecycle::shutdown_backend_for_exit;
+use crate::{
+    portable, settings, sidecar_fallback, sidecar_integrity, spawn_backend, supervisor, trace,
+};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+/// Data folders the frozen backend expects under %LOCALAPPDATA%\Plattera\Data.
+const BACKEND_DATA_DIRS: &[&str] = &["dossiers_data", "plss"];
+/// Suffixes of interrupted writes / probes left behind by a crash.
+const STALE_SUFFIXES: &[&str] = &[".__lockprobe__", ".json.tmp", ".partial"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepairCheck {
+    pub name: String,
+    pub ok: bool,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RepairReport {
+    pub checks: Vec<RepairCheck>,
+    /// Changes made to the installation or data folders.
+    pub actions: Vec<String>,
+    pub backend_restarted: bool,
+    pub backend_message: Option<String>,
+}
+
+impl RepairReport {
+    fn check(&mut self, name: &str, ok: bool, detail: Option<String>) {
+        if !ok {
+            log::warn!("REPAIR ► check '{}' failed: {:?}", name, detail);
+        }
+        self.checks.push(RepairCheck {
+            name: name.to_string(),
+            ok,
+            detail,
+        });
+    }
+
+    fn action(&mut self, action: String) {
+        log::info!("REPAIR ► {}", action);
+        self.actions.push(action);
+    }
+}
+
+fn verify_bundle(app_handle: &tauri::AppHandle, report: &mut RepairReport) {
+    match sidecar_fallback::bundled_sidecar_path() {
+        Some(path) => match fs::metadata(&path) {
+            Ok(meta) if meta.is_file() && meta.len() > 0 => report.check(
+                "sidecar_present",
+                true,
+                Some(format!("{} ({} bytes)", path.display(), meta.len())),
+            ),
+            Ok(_) => report.check(
+                "sidecar_present",
+                false,
+                Some(format!("{} is empty or not a file", path.display())),
+            ),
+            Err(e) => report.check(
+                "sidecar_present",
+                false,
+                Some(format!("{}: {}", path.display(), e)),
+            ),
+        },
+        None => report.check(
+            "sidecar_present",
+            false,
+            Some("could not resolve the app executable directory".to_string()),
+        ),
+    }
+
+    let (ok, detail, action) = sidecar_integrity::repair(app_handle);
+    if let Some(action) = action {
+        report.action(action);
+    }
+    report.check("sidecar_integrity", ok, Some(detail));
+
+    match app_handle.path().resource_dir() {
+        Ok(dir) if dir.is_dir() => {
+            report.check("resource_dir", true, Some(dir.display().to_string()))
+        }
+        Ok(dir) => report.check(
+            "resource_dir",
+            false,
+            Some(format!("{} is missing", dir.display())),
+        ),
+        Err(e) => report.check("resource_dir", false, Some(e.to_string())),
+    }
+}
+
+fn ensure_dirs(app_handle: &tauri::AppHandle, report: &mut RepairReport) {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Ok(dir) = portable::resolve(app_handle, "") {
+        dirs.push(dir);
+    }
+    if let Some(dir) = trace::backend_log_dir(app_handle) {
+        dirs.push(dir);
+    }
+    if let Ok(root) = settings::backend_data_dir(app_handle) {
+        dirs.extend(BACKEND_DATA_DIRS.iter().map(|rel| root.join(rel)));
+    }
+
+    for dir in dirs {
+        if dir.is_dir() {
+            continue;
+        }
+        match fs::create_dir_all(&dir) {
+            Ok(()) => report.action(format!("created missing folder {}", dir.display())),
+            Err(e) => report.check(
+                "data_dirs",
+                false,
+                Some(format!("could not create {}: {}", dir.display(), e)),
+            ),
+        }
+    }
+}
+
+/// A rename probe interrupted between its two renames leaves the backend
+/// exe under its probe name; put it back instead of deleting it.
+fn clear_stale_in(dir: &Path, report: &mut RepairReport) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|e| e.path()).filter(|p| p.is_file()) {
+        let name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+        let Some(suffix) = STALE_SUFFIXES.iter().find(|s| name.ends_with(**s)) else {
+            continue;
+        };
+
+        if *suffix == ".__lockprobe__" {
+            let original = dir.join(name.trim_end_matches(suffix));
+            if !original.exists() {
+                match fs::rename(&path, &original) {
+                    Ok(()) => report.action(format!(
+                        "restored {} from interrupted lock probe",
+                        original.display()
+                    )),
+                    Err(e) => report.check(
+                        "stale_artifacts",
+                        false,
+                        Some(format!("could not restore {}: {}", original.display(), e)),
+                    ),
+                }
+                continue;
+            }
+        }
+
+        match fs::remove_file(&path) {
+            Ok(()) => report.action(format!("removed stale {}", path.display())),
+            Err(e) => report.check(
+                "stale_artifacts",
+                false,
+                Some(format!("could not remove {}: {}", path.display(), e)),
+            ),
+        }
+    }
+}
+
+fn clear_stale_artifacts(app_handle: &tauri::AppHandle, report: &mut RepairReport) {
+    if let Ok(dir) = portable::resolve(app_handle, "") {
+        clear_stale_in(&dir, report);
+        clear_stale_in(&dir.join("backend_fallback"), report);
+    }
+}
+
+/// Run all repair steps and restart the backend. Blocking.
+pub fn repair(app_handle: &tauri::AppHandle) -> RepairReport {
+    log::warn!("REPAIR ► repair installation requested");
+    let mut report = RepairReport::default();
+
+    // Stop the backend first so nothing holds the files we may touch.
+    shutdown_backend_for_exit(app_handle);
+
+    verify_bundle(app_handle, &mut report);
+    ensure_dirs(app_handle, &mut report);
+    clear_stale_artifacts(app_handle, &mut report);
+
+    supervisor::reset(app_handle);
+    match spawn_backend(app_handle) {
+        Ok(msg) => {
+            report.backend_restarted = true;
+            report.backend_message = Some(msg);
+        }
+        Err(e) => {
+            log::error!("REPAIR ► backend restart failed: {}", e);
+            report.backend_message = Some(e);
+        }
+    }
+    report
+}
+
+#[tauri::command]
+pub async fn repair_installation(app_handle: tauri::AppHandle) -> Result<RepairReport, String> {
+    tauri::async_runtime::spawn_blocking(move || repair(&app_handle))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,371 @@
+// Rollback after a failed update. Right before the updater installs a new
+// version, `prepare_update` copies the running app and backend binaries into
+// a staging dir and writes a post-update marker. The first launch of the new
+// version then gives the backend a time budget to report healthy: success
+// clears the marker, failure emits `update-rollback-available` so the
+// frontend can offer `rollback_update`.
+//
+// The running exe can't be overwritten on Windows, so the restore is done by
+// a temporary copy of this exe started with `--apply-rollback`: it waits for
+// the app to exit, copies the staged files back, relaunches the restored
+// version and exits without starting Tauri.
+
+use crate::backend_lifecycle::shutdown_backend_for_update;
+use crate::{correlation, health, portable, settings, telemetry};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+
+const STAGING_DIR: &str = "rollback";
+const MARKER_FILE: &str = "update_pending.json";
+/// Result of the last `--apply-rollback` run, read on the next launch.
+const RESULT_FILE: &str = "rollback_result.json";
+const APPLY_FLAG: &str = "--apply-rollback";
+const SIDECAR_NAME: &str = "plattera-backend";
+/// How long the restore helper waits for the app to exit.
+const APPLY_WAIT: Duration = Duration::from_secs(60);
+
+/// Written before an update installs; removed once the new version proves
+/// healthy (or turns out not to have been installed).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UpdateMarker {
+    from_version: String,
+    to_version: String,
+    created_at: String,
+    install_dir: PathBuf,
+    staged_dir: PathBuf,
+    /// File names staged from `install_dir`.
+    files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RollbackOffer {
+    pub from_version: String,
+    pub to_version: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RollbackResult {
+    ok: bool,
+    version: String,
+    detail: String,
+    at: String,
+}
+
+pub struct RollbackState(Mutex<Option<RollbackOffer>>);
+
+impl RollbackState {
+    pub fn new() -> Self {
+        RollbackState(Mutex::new(None))
+    }
+}
+
+fn staging_root(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    portable::resolve(app_handle, STAGING_DIR).map_err(|e| e.to_string())
+}
+
+fn marker_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(staging_root(app_handle)?.join(MARKER_FILE))
+}
+
+fn read_marker(path: &Path) -> Option<UpdateMarker> {
+    let raw = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+/// The app exe and the backend sidecar next to it (the sidecar is missing
+/// in dev builds).
+fn install_files() -> Result<(PathBuf, Vec<String>), String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let dir = exe
+        .parent()
+        .ok_or_else(|| format!("{} has no parent directory", exe.display()))?
+        .to_path_buf();
+    let mut files = Vec::new();
+    if let Some(name) = exe.file_name() {
+        files.push(name.to_string_lossy().into_owned());
+    }
+    let sidecar = format!("{}{}", SIDECAR_NAME, std::env::consts::EXE_SUFFIX);
+    if dir.join(&sidecar).is_file() {
+        files.push(sidecar);
+    }
+    Ok((dir, files))
+}
+
+fn stage(app_handle: &tauri::AppHandle, target_version: &str) -> Result<UpdateMarker, String> {
+    let current = app_handle.package_info().version.to_string();
+    let root = staging_root(app_handle)?;
+    let staged_dir = root.join(&current);
+    // Only the version being replaced is kept.
+    if root.exists() {
+        fs::remove_dir_all(&root).map_err(|e| format!("cannot clear {}: {}", root.display(), e))?;
+    }
+    fs::create_dir_all(&staged_dir)
+        .map_err(|e| format!("cannot create {}: {}", staged_dir.display(), e))?;
+
+    let (install_dir, files) = install_files()?;
+    for name in &files {
+        fs::copy(install_dir.join(name), staged_dir.join(name))
+            .map_err(|e| format!("cannot stage {}: {}", name, e))?;
+    }
+
+    let marker = UpdateMarker {
+        from_version: current,
+        to_version: target_version.trim().trim_start_matches('v').to_string(),
+        created_at: chrono::Local::now().to_rfc3339(),
+        install_dir,
+        staged_dir,
+        files,
+    };
+    let json = serde_json::to_string_pretty(&marker).map_err(|e| e.to_string())?;
+    fs::write(root.join(MARKER_FILE), json).map_err(|e| e.to_string())?;
+    Ok(marker)
+}
+
+fn report_last_rollback(app_handle: &tauri::AppHandle) {
+    let Ok(path) = staging_root(app_handle).map(|r| r.join(RESULT_FILE)) else {
+        return;
+    };
+    let Some(result) = fs::read_to_string(&path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<RollbackResult>(&raw).ok())
+    else {
+        return;
+    };
+    if result.ok {
+        log::info!("ROLLBACK ► restored {} at {}", result.version, result.at);
+    } else {
+        log::error!(
+            "ROLLBACK ► restoring {} failed: {}",
+            result.version,
+            result.detail
+        );
+    }
+    let _ = fs::remove_file(&path);
+}
+
+/// Startup check: on the first launch after an update, watch for the backend
+/// becoming healthy within the configured budget.
+pub fn check_after_update(app_handle: &tauri::AppHandle) {
+    report_last_rollback(app_handle);
+    let Ok(path) = marker_path(app_handle) else {
+        return;
+    };
+    let Some(marker) = read_marker(&path) else {
+        return;
+    };
+    let current = app_handle.package_info().version.to_string();
+    if current != marker.to_version {
+        // Still on the old version (the install never ran) or a stale marker.
+        log::info!(
+            "ROLLBACK ► update to {} not in effect (running {}); clearing marker",
+            marker.to_version,
+            current
+        );
+        let _ = fs::remove_file(&path);
+        return;
+    }
+
+    let budget = Duration::from_secs(settings::current(app_handle).update_verify_timeout_secs);
+    log::info!(
+        "ROLLBACK ► first launch after {} → {}; waiting up to {:?} for a healthy backend",
+        marker.from_version,
+        marker.to_version,
+        budget
+    );
+    let app_handle = app_handle.clone();
+    thread::spawn(move || {
+        let _op = correlation::begin("update_verify");
+        if health::wait_until_healthy(&app_handle, budget) {
+            log::info!("ROLLBACK ► update to {} verified", marker.to_version);
+            telemetry::record(
+                &app_handle,
+                "update_outcome",
+                serde_json::json!({ "kind": "app", "outcome": "verified" }),
+            );
+            let _ = fs::remove_file(&path);
+            return;
+        }
+        let offer = RollbackOffer {
+            from_version: marker.from_version.clone(),
+            to_version: marker.to_version.clone(),
+            reason: format!(
+                "backend not healthy within {}s of the update",
+                budget.as_secs()
+            ),
+        };
+        log::warn!(
+            "ROLLBACK ► {}; offering rollback to {}",
+            offer.reason,
+            offer.from_version
+        );
+        telemetry::record(
+            &app_handle,
+            "update_outcome",
+            serde_json::json!({ "kind": "app", "outcome": "unhealthy" }),
+        );
+        *app_handle.state::<RollbackState>().0.lock().unwrap() = Some(offer.clone());
+        correlation::emit(&app_handle, "update-rollback-available", &offer);
+    });
+}
+
+/// Stage the running version before the updater installs `version`.
+pub fn stage_for_update(app_handle: &tauri::AppHandle, version: &str) -> Result<(), String> {
+    let marker = stage(app_handle, version)?;
+    log::info!(
+        "ROLLBACK ► staged {} ({} file(s)) before update to {}",
+        marker.from_version,
+        marker.files.len(),
+        marker.to_version
+    );
+    Ok(())
+}
+
+/// `stage_for_update` for installs driven by the webview updater. Call right
+/// before `downloadAndInstall`.
+#[tauri::command]
+pub async fn prepare_update(app_handle: tauri::AppHandle, version: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || stage_for_update(&app_handle, &version))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// The rollback currently on offer, if any.
+pub fn offer(app_handle: &tauri::AppHandle) -> Option<RollbackOffer> {
+    app_handle
+        .state::<RollbackState>()
+        .0
+        .lock()
+        .unwrap()
+        .clone()
+}
+
+#[tauri::command]
+pub async fn get_rollback_state(
+    app_handle: tauri::AppHandle,
+) -> Result<Option<RollbackOffer>, String> {
+    Ok(offer(&app_handle))
+}
+
+/// Reinstall the staged previous version. Stops the backend, hands over to
+/// the restore helper and exits; the helper relaunches the app.
+#[tauri::command]
+pub async fn rollback_update(app_handle: tauri::AppHandle) -> Result<(), String> {
+    let path = marker_path(&app_handle)?;
+    let marker = read_marker(&path).ok_or("no staged version to roll back to")?;
+    if !marker.staged_dir.is_dir() {
+        return Err(format!(
+            "staged files missing at {}",
+            marker.staged_dir.display()
+        ));
+    }
+
+    // A copy of this exe runs the restore, so the installed one is free to
+    // be overwritten once the app exits.
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let helper =
+        staging_root(&app_handle)?.join(format!("rollback-helper{}", std::env::consts::EXE_SUFFIX));
+    fs::copy(&exe, &helper).map_err(|e| format!("cannot prepare rollback helper: {}", e))?;
+
+    log::warn!(
+        "ROLLBACK ► rolling back {} → {}",
+        marker.to_version,
+        marker.from_version
+    );
+    Command::new(&helper)
+        .arg(APPLY_FLAG)
+        .arg(&path)
+        .arg(std::process::id().to_string())
+        .spawn()
+        .map_err(|e| format!("cannot start rollback helper: {}", e))?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let _op = correlation::begin("rollback");
+        shutdown_backend_for_update(&app_handle);
+        app_handle.exit(0);
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+fn wait_for_exit(pid: u32, timeout: Duration) -> bool {
+    let start = Instant::now();
+    let mut sys = sysinfo::System::new();
+    while start.elapsed() < timeout {
+        if !sys.refresh_process(sysinfo::Pid::from_u32(pid)) {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(250));
+    }
+    false
+}
+
+/// Copy with a few retries: the exiting app may hold its files briefly.
+fn restore_file(from: &Path, to: &Path) -> Result<(), String> {
+    let mut last_err = String::new();
+    for _ in 0..10 {
+        match fs::copy(from, to) {
+            Ok(_) => return Ok(()),
+            Err(e) => last_err = e.to_string(),
+        }
+        thread::sleep(Duration::from_millis(500));
+    }
+    Err(format!("{}: {}", to.display(), last_err))
+}
+
+fn apply(marker_path: &Path, parent_pid: u32) -> Result<(), String> {
+    let marker = read_marker(marker_path).ok_or("rollback marker unreadable")?;
+    if !wait_for_exit(parent_pid, APPLY_WAIT) {
+        return Err(format!("app (pid {}) did not exit", parent_pid));
+    }
+    for name in &marker.files {
+        restore_file(
+            &marker.staged_dir.join(name),
+            &marker.install_dir.join(name),
+        )?;
+    }
+    let _ = fs::remove_file(marker_path);
+    Ok(())
+}
+
+/// Entry point for the restore helper. Returns true when this process was
+/// started as the helper (the caller must then exit instead of running the
+/// app).
+pub fn apply_from_args() -> bool {
+    let args: Vec<std::ffi::OsString> = std::env::args_os().collect();
+    if args.len() < 4 || args[1] != APPLY_FLAG {
+        return false;
+    }
+    let marker_path = PathBuf::from(&args[2]);
+    let parent_pid = args[3].to_string_lossy().parse::<u32>().unwrap_or(0);
+    let marker = read_marker(&marker_path);
+
+    let result = apply(&marker_path, parent_pid);
+    let record = RollbackResult {
+        ok: result.is_ok(),
+        version: marker
+            .as_ref()
+            .map(|m| m.from_version.clone())
+            .unwrap_or_default(),
+        detail: result.err().unwrap_or_default(),
+        at: chrono::Local::now().to_rfc3339(),
+    };
+    if let (Some(dir), Ok(json)) = (marker_path.parent(), serde_json::to_string_pretty(&record)) {
+        let _ = fs::write(dir.join(RESULT_FILE), json);
+    }
+    // Relaunch whatever is installed now so the user isn't left without an
+    // app when the restore failed.
+    if let Some(exe) = marker
+        .as_ref()
+        .and_then(|m| m.files.first().map(|f| m.install_dir.join(f)))
+    {
+        let _ = Command::new(exe).spawn();
+    }
+    true
+}
@@ -0,0 +1,52 @@
+// Runtime information about the shell and the backend it manages, for the
+// frontend's about/diagnostics views.
+
+use crate::{backend_http, version_pin};
+use serde::Serialize;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuntimeInfo {
+    pub app_version: String,
+    /// Version reported by the running backend, `None` if it isn't reachable.
+    pub backend_version: Option<String>,
+    pub backend_pinned: bool,
+    pub pinned_backend_version: Option<String>,
+    pub os: &'static str,
+    pub arch: &'static str,
+}
+
+/// Ask the backend root endpoint for its version. Best effort: any failure
+/// (not started yet, old backend without the field) yields `None`.
+pub fn probe_backend_version(app_handle: &tauri::AppHandle) -> Option<String> {
+    let reply = backend_http::call_blocking(
+        app_handle,
+        reqwest::Method::GET,
+        "/",
+        None,
+        Duration::from_millis(2_000),
+        None,
+    )
+    .ok()
+    .filter(|r| r.status.is_success())?;
+    reply.body.get("version")?.as_str().map(str::to_string)
+}
+
+#[tauri::command]
+pub async fn get_runtime_info(app_handle: tauri::AppHandle) -> Result<RuntimeInfo, String> {
+    let probe_handle = app_handle.clone();
+    let backend_version =
+        tauri::async_runtime::spawn_blocking(move || probe_backend_version(&probe_handle))
+            .await
+            .map_err(|e| e.to_string())?;
+    let pinned_backend_version = version_pin::pinned_version(&app_handle);
+
+    Ok(RuntimeInfo {
+        app_version: app_handle.package_info().version.to_string(),
+        backend_version,
+        backend_pinned: pinned_backend_version.is_some(),
+        pinned_backend_version,
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+    })
+}
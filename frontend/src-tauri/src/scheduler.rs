@@ -0,0 +1,221 @@
+// Background task scheduler. Wakes up periodically and runs whatever is
+// due: scheduled pipelines and the scheduled dossier export. Last-run times
+// are saved under AppLocalData, so a restart doesn't fire a daily job twice
+// in one day. On low battery, due pipelines are held back (not marked as
+// run) and fire on the first tick after power returns.
+
+use crate::pipelines::{self, RunTrigger};
+use crate::{dossier_export, maintenance, portable, power, recovery, settings};
+use chrono::{DateTime, Local, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+const STATE_FILE: &str = "scheduler_state.json";
+const TICK: Duration = Duration::from_secs(30);
+/// Last-run key of the scheduled dossier export (can't clash with a
+/// pipeline name, which the UI limits to plain text).
+const EXPORT_KEY: &str = "::dossier_export";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Schedule {
+    /// Run every `minutes` minutes, measured from the previous run (or from
+    /// app start for the first run).
+    Interval { minutes: u64 },
+    /// Run once per day at local time `at` ("HH:MM").
+    Daily { at: String },
+}
+
+impl Schedule {
+    fn is_due(&self, last_run: Option<DateTime<Local>>, now: DateTime<Local>) -> bool {
+        match self {
+            Schedule::Interval { minutes } => match last_run {
+                Some(last) => now - last >= chrono::Duration::minutes((*minutes).max(1) as i64),
+                None => false,
+            },
+            Schedule::Daily { at } => {
+                let Ok(at) = NaiveTime::parse_from_str(at, "%H:%M") else {
+                    return false;
+                };
+                let ran_today = last_run
+                    .map(|l| l.date_naive() == now.date_naive())
+                    .unwrap_or(false);
+                now.time() >= at && !ran_today
+            }
+        }
+    }
+}
+
+/// When a schedule counts from: interval schedules that have never run
+/// count from app start.
+fn baseline(
+    schedule: &Schedule,
+    last: Option<DateTime<Local>>,
+    started: DateTime<Local>,
+) -> Option<DateTime<Local>> {
+    match schedule {
+        Schedule::Interval { .. } => last.or(Some(started)),
+        Schedule::Daily { .. } => last,
+    }
+}
+
+fn state_path(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    portable::resolve(app_handle, STATE_FILE).ok()
+}
+
+fn load_last_runs(app_handle: &tauri::AppHandle) -> HashMap<String, DateTime<Local>> {
+    state_path(app_handle)
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_last_runs(app_handle: &tauri::AppHandle, last_runs: &HashMap<String, DateTime<Local>>) {
+    let Some(path) = state_path(app_handle) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(raw) = serde_json::to_string_pretty(last_runs) {
+        if let Err(e) = fs::write(&path, raw) {
+            log::warn!("SCHEDULER ► could not persist state at {:?}: {}", path, e);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduledJob {
+    /// Pipeline name, or `dossier_export`.
+    pub name: String,
+    pub schedule: Schedule,
+    pub enabled: bool,
+    pub last_run: Option<String>,
+}
+
+/// Every scheduled job with its persisted last run.
+pub fn status(app_handle: &tauri::AppHandle) -> Vec<ScheduledJob> {
+    let last_runs = load_last_runs(app_handle);
+    let last = |key: &str| last_runs.get(key).map(|t| t.to_rfc3339());
+    let current = settings::current(app_handle);
+    let mut jobs: Vec<ScheduledJob> = current
+        .pipelines
+        .into_iter()
+        .filter_map(|p| {
+            Some(ScheduledJob {
+                last_run: last(&p.name),
+                schedule: p.schedule?,
+                enabled: p.enabled,
+                name: p.name,
+            })
+        })
+        .collect();
+    if let Some(export) = current.dossier_export {
+        jobs.push(ScheduledJob {
+            name: "dossier_export".to_string(),
+            schedule: export.schedule,
+            enabled: export.enabled,
+            last_run: last(EXPORT_KEY),
+        });
+    }
+    jobs
+}
+
+/// Start the scheduler loop on a dedicated thread. Not started in safe mode.
+pub fn spawn(app_handle: tauri::AppHandle) {
+    if recovery::safe_mode(&app_handle) {
+        log::info!("SCHEDULER ► not started (safe mode)");
+        return;
+    }
+    thread::spawn(move || {
+        let mut last_runs = load_last_runs(&app_handle);
+        let started = Local::now();
+        let mut deferred: HashSet<String> = HashSet::new();
+
+        loop {
+            thread::sleep(TICK);
+            // Due runs simply wait until maintenance mode ends.
+            if maintenance::active(&app_handle) {
+                continue;
+            }
+            let now = Local::now();
+            let mut changed = false;
+            let defer = power::should_defer_background_work(&app_handle);
+            if !defer {
+                deferred.clear();
+            }
+
+            for pipeline in settings::current(&app_handle).pipelines {
+                let Some(schedule) = pipeline.schedule.clone() else {
+                    continue;
+                };
+                if !pipeline.enabled {
+                    continue;
+                }
+
+                let last = last_runs.get(&pipeline.name).copied();
+                if !schedule.is_due(baseline(&schedule, last, started), now) {
+                    continue;
+                }
+
+                if defer {
+                    if deferred.insert(pipeline.name.clone()) {
+                        log::info!(
+                            "SCHEDULER ► pipeline '{}' deferred (on battery)",
+                            pipeline.name
+                        );
+                    }
+                    continue;
+                }
+
+                log::info!("SCHEDULER ► pipeline '{}' is due", pipeline.name);
+                last_runs.insert(pipeline.name.clone(), now);
+                changed = true;
+
+                let handle = app_handle.clone();
+                thread::spawn(move || {
+                    if let Err(e) = pipelines::execute(&handle, &pipeline, RunTrigger::Schedule) {
+                        log::warn!("SCHEDULER ► pipeline '{}' not run: {}", pipeline.name, e);
+                    }
+                });
+            }
+
+            if let Some(export) = settings::current(&app_handle)
+                .dossier_export
+                .filter(|e| e.enabled)
+            {
+                let last = last_runs.get(EXPORT_KEY).copied();
+                if export
+                    .schedule
+                    .is_due(baseline(&export.schedule, last, started), now)
+                {
+                    if defer {
+                        if deferred.insert(EXPORT_KEY.to_string()) {
+                            log::info!("SCHEDULER ► dossier export deferred (on battery)");
+                        }
+                    } else {
+                        log::info!("SCHEDULER ► dossier export is due");
+                        last_runs.insert(EXPORT_KEY.to_string(), now);
+                        changed = true;
+                        let handle = app_handle.clone();
+                        thread::spawn(move || {
+                            if let Err(e) =
+                                dossier_export::export(&handle, export.format, &export.path)
+                            {
+                                log::warn!("SCHEDULER ► dossier export failed: {}", e);
+                            }
+                        });
+                    }
+                }
+            }
+
+            if changed {
+                save_last_runs(&app_handle, &last_runs);
+            }
+        }
+    });
+}
@@ -0,0 +1,236 @@
+// Session restore. The frontend reports each window's route and view state
+// (open dossier, scroll, zoom) as it changes; the shell persists the latest
+// picture to `session.json` on exit and hands it back on the next launch so
+// users pick up where they left off. A window the user closes during the
+// session is dropped from the picture, so it doesn't come back on restore;
+// windows closed by the app exiting are kept.
+
+use crate::{close_guard, portable, recovery, settings, window_state};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::webview::{PageLoadEvent, PageLoadPayload};
+use tauri::{Emitter, Manager, Webview};
+
+const SESSION_FILE: &str = "session.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowSession {
+    pub label: String,
+    pub route: String,
+    #[serde(default)]
+    pub dossier_id: Option<String>,
+    /// Scroll position as reported by the frontend (opaque to the shell).
+    #[serde(default)]
+    pub scroll: Option<Value>,
+    #[serde(default)]
+    pub zoom: Option<f64>,
+    /// Any additional view state the frontend wants back verbatim.
+    #[serde(default)]
+    pub extra: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub saved_at: String,
+    pub windows: Vec<WindowSession>,
+}
+
+/// Live per-window state reported by the frontend, keyed by window label.
+pub struct SessionState {
+    windows: Mutex<HashMap<String, WindowSession>>,
+    /// Set once the launch-time restore has been offered to the main window.
+    restore_offered: AtomicBool,
+}
+
+impl SessionState {
+    pub fn new() -> Self {
+        SessionState {
+            windows: Mutex::new(HashMap::new()),
+            restore_offered: AtomicBool::new(false),
+        }
+    }
+
+    fn report(&self, entry: WindowSession) {
+        self.windows
+            .lock()
+            .unwrap()
+            .insert(entry.label.clone(), entry);
+    }
+
+    fn forget(&self, label: &str) -> bool {
+        self.windows.lock().unwrap().remove(label).is_some()
+    }
+
+    /// The reported windows, ordered by label.
+    fn windows(&self) -> Vec<WindowSession> {
+        let mut windows: Vec<WindowSession> =
+            self.windows.lock().unwrap().values().cloned().collect();
+        windows.sort_by(|a, b| a.label.cmp(&b.label));
+        windows
+    }
+}
+
+fn session_path(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    portable::resolve(app_handle, SESSION_FILE).ok()
+}
+
+fn load_snapshot(app_handle: &tauri::AppHandle) -> Option<SessionSnapshot> {
+    let raw = fs::read_to_string(session_path(app_handle)?).ok()?;
+    serde_json::from_str(&raw)
+        .map_err(|e| log::warn!("SESSION ► ignoring unreadable session file: {}", e))
+        .ok()
+}
+
+/// Write the current session to disk. Called from the exit paths; a session
+/// with no reported windows leaves the previous file untouched.
+pub fn persist(app_handle: &tauri::AppHandle) {
+    // Window geometry is saved on the same occasions.
+    window_state::persist(app_handle);
+    let Some(state) = app_handle.try_state::<SessionState>() else {
+        return;
+    };
+    let windows = state.windows();
+    if windows.is_empty() {
+        return;
+    }
+
+    let snapshot = SessionSnapshot {
+        saved_at: chrono::Local::now().to_rfc3339(),
+        windows,
+    };
+    let Some(path) = session_path(app_handle) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    match serde_json::to_string_pretty(&snapshot) {
+        Ok(raw) => match fs::write(&path, raw) {
+            Ok(_) => log::info!(
+                "SESSION ► saved {} window(s) to {:?}",
+                snapshot.windows.len(),
+                path
+            ),
+            Err(e) => log::warn!("SESSION ► could not save session to {:?}: {}", path, e),
+        },
+        Err(e) => log::warn!("SESSION ► could not serialize session: {}", e),
+    }
+}
+
+/// Drop a window the user closed from the session. Called for every
+/// destroyed window; while the app is exiting the session is already saved
+/// and the windows stay in it.
+pub fn forget_window(app_handle: &tauri::AppHandle, label: &str) {
+    if close_guard::exiting(app_handle) {
+        return;
+    }
+    if app_handle.state::<SessionState>().forget(label) {
+        log::debug!("SESSION ► '{}' closed; no longer restored", label);
+    }
+}
+
+/// Page-load hook: the first time the main window finishes loading, offer
+/// the previous session if auto-restore is enabled.
+pub fn on_page_load(webview: &Webview, payload: &PageLoadPayload<'_>) {
+    if webview.label() != "main" || payload.event() != PageLoadEvent::Finished {
+        return;
+    }
+    let app_handle = webview.app_handle();
+    let state = app_handle.state::<SessionState>();
+    if state.restore_offered.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    if !settings::current(app_handle).restore_session_on_launch || recovery::safe_mode(app_handle) {
+        return;
+    }
+    if let Some(snapshot) = load_snapshot(app_handle) {
+        log::info!(
+            "SESSION ► offering last session ({} window(s), saved {})",
+            snapshot.windows.len(),
+            snapshot.saved_at
+        );
+        let _ = app_handle.emit("session-restore", &snapshot);
+    }
+}
+
+/// Called by the frontend whenever a window's route or view state changes.
+#[tauri::command]
+pub async fn report_session_state(
+    window: tauri::Window,
+    app_handle: tauri::AppHandle,
+    route: String,
+    dossier_id: Option<String>,
+    scroll: Option<Value>,
+    zoom: Option<f64>,
+    extra: Option<Value>,
+) -> Result<(), String> {
+    app_handle.state::<SessionState>().report(WindowSession {
+        label: window.label().to_string(),
+        route,
+        dossier_id,
+        scroll,
+        zoom,
+        extra,
+    });
+    Ok(())
+}
+
+/// Return the last saved session and broadcast it as `session-restore` so
+/// every open window can re-apply its part.
+#[tauri::command]
+pub async fn restore_last_session(
+    app_handle: tauri::AppHandle,
+) -> Result<Option<SessionSnapshot>, String> {
+    let snapshot = load_snapshot(&app_handle);
+    if let Some(ref s) = snapshot {
+        app_handle
+            .emit("session-restore", s)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(label: &str) -> WindowSession {
+        WindowSession {
+            label: label.to_string(),
+            route: format!("/{}", label),
+            dossier_id: None,
+            scroll: None,
+            zoom: None,
+            extra: None,
+        }
+    }
+
+    #[test]
+    fn closed_window_is_not_restored() {
+        let state = SessionState::new();
+        state.report(window("main"));
+        state.report(window("dossier-1"));
+        state.report(window("dossier-2"));
+
+        assert!(state.forget("dossier-1"));
+
+        let labels: Vec<String> = state.windows().into_iter().map(|w| w.label).collect();
+        assert_eq!(labels, ["dossier-2", "main"]);
+    }
+
+    #[test]
+    fn window_reported_again_after_close_is_restored() {
+        let state = SessionState::new();
+        state.report(window("dossier-1"));
+        state.forget("dossier-1");
+        assert!(state.windows().is_empty());
+
+        state.report(window("dossier-1"));
+        assert_eq!(state.windows().len(), 1);
+    }
+}
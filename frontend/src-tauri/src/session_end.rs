@@ -0,0 +1,123 @@
+// OS-initiated exits (logoff, restart, shutdown). The window close event
+// never fires for these, so without this the backend was killed mid-write
+// and could leave the SQLite journal dirty. On Windows a hidden top-level
+// window listens for WM_QUERYENDSESSION / WM_ENDSESSION; elsewhere SIGTERM
+// and SIGHUP reach the termination handler installed in `lib.rs`. Both run
+// `shutdown_backend_for_session_end`, a short time-bounded shutdown.
+
+use crate::{backend_lifecycle, session};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static HANDLED: AtomicBool = AtomicBool::new(false);
+
+/// Persist the session and stop the backend within the session-end budget.
+/// Runs at most once per process.
+pub fn handle(app_handle: &tauri::AppHandle, source: &str) {
+    if HANDLED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    log::warn!("SESSION_END ► {}; stopping backend", source);
+    session::persist(app_handle);
+    backend_lifecycle::shutdown_backend_for_session_end(app_handle);
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::sync::OnceLock;
+    use std::thread;
+    use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+    use windows_sys::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows_sys::Win32::System::Shutdown::{
+        ShutdownBlockReasonCreate, ShutdownBlockReasonDestroy,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+        TranslateMessage, MSG, WM_ENDSESSION, WM_QUERYENDSESSION, WNDCLASSW,
+    };
+
+    static APP: OnceLock<tauri::AppHandle> = OnceLock::new();
+
+    fn wide(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    unsafe extern "system" fn wnd_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        match msg {
+            WM_QUERYENDSESSION => {
+                // Allow the session to end, but ask Windows to show why we
+                // need a moment while WM_ENDSESSION is handled.
+                let reason = wide("Saving your work and stopping the Plattera backend…");
+                ShutdownBlockReasonCreate(hwnd, reason.as_ptr());
+                1
+            }
+            WM_ENDSESSION => {
+                if wparam != 0 {
+                    if let Some(app) = APP.get() {
+                        super::handle(app, "Windows session is ending");
+                    }
+                }
+                ShutdownBlockReasonDestroy(hwnd);
+                0
+            }
+            _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+        }
+    }
+
+    /// Create the hidden listener window on its own thread with its own
+    /// message loop, independent of the webview event loop.
+    pub fn install(app_handle: tauri::AppHandle) {
+        if APP.set(app_handle).is_err() {
+            return;
+        }
+        thread::spawn(|| unsafe {
+            let class_name = wide("PlatteraSessionEndListener");
+            let hinstance = GetModuleHandleW(std::ptr::null());
+            let mut class: WNDCLASSW = std::mem::zeroed();
+            class.lpfnWndProc = Some(wnd_proc);
+            class.hInstance = hinstance;
+            class.lpszClassName = class_name.as_ptr();
+            if RegisterClassW(&class) == 0 {
+                log::warn!("SESSION_END ► could not register listener window class");
+                return;
+            }
+            // A real (never shown) top-level window: message-only windows
+            // don't receive the session-end broadcast.
+            let hwnd = CreateWindowExW(
+                0,
+                class_name.as_ptr(),
+                class_name.as_ptr(),
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                hinstance,
+                std::ptr::null(),
+            );
+            if hwnd == 0 {
+                log::warn!("SESSION_END ► could not create listener window");
+                return;
+            }
+            let mut msg: MSG = std::mem::zeroed();
+            while GetMessageW(&mut msg, 0, 0, 0) > 0 {
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        });
+    }
+}
+
+#[cfg(not(windows))]
+mod imp {
+    /// SIGTERM/SIGHUP are handled by the termination handler in `lib.rs`.
+    pub fn install(_app_handle: tauri::AppHandle) {}
+}
+
+pub use imp::install;
@@ -0,0 +1,479 @@
+// Persisted shell settings. Stored as `settings.json` under AppLocalData so
+// they survive updates and are removed together with the rest of the user
+// data by `factory_reset_data`. The frontend reads them with `get_settings`
+// and changes them with `update_settings` (a JSON merge patch); every change
+// is announced as `settings-changed` with the keys that changed.
+
+use crate::auto_restart::AutoRestartSettings;
+use crate::backend_config::BackendLaunchSettings;
+use crate::backend_lifecycle::LifecycleSettings;
+use crate::backend_log::BackendLogSettings;
+use crate::backend_mode::{BackendMode, ExternalBackend};
+use crate::connectivity::ConnectivitySettings;
+use crate::data_encryption::DataEncryptionSettings;
+use crate::dossier_export::ScheduledDossierExport;
+use crate::endpoint::BackendTransport;
+use crate::health_checks::HealthDefinition;
+use crate::health_watchdog::HealthWatchdogSettings;
+use crate::i18n;
+use crate::notifications::NotificationSettings;
+use crate::onboarding;
+use crate::paths;
+use crate::pipelines::PipelineDefinition;
+use crate::portable;
+use crate::power::PowerSettings;
+use crate::prewarm::PrewarmSettings;
+use crate::profiles::{self, Profile};
+use crate::proxy::ProxySettings;
+use crate::shortcuts::{self, ShortcutAction};
+use crate::supervisor::SupervisorSettings;
+use crate::telemetry::TelemetrySettings;
+use crate::theme::{self, ThemePreference};
+use crate::tls_trust;
+use crate::updates::UpdateChannel;
+use crate::warmup::ModelWarmupSettings;
+use crate::whats_new::UpgradeRecord;
+use crate::zoom;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::path::BaseDirectory;
+use tauri::{Emitter, Manager};
+
+const SETTINGS_FILE: &str = "settings.json";
+/// Environment variable the backend reads to override its data root.
+pub const DATA_DIR_ENV: &str = "PLATTERA_DATA_DIR";
+/// Backend user data root under LocalData when no custom `data_dir` is set.
+const DEFAULT_DATA_DIR: &str = "Plattera/Data";
+/// Bookkeeping the shell keeps itself; `update_settings` refuses it.
+const INTERNAL_KEYS: &[&str] = &["onboarding_completed", "last_run_version", "last_upgrade"];
+/// Settings with a command of their own that checks them or acts on the
+/// change, and the command to use instead of `update_settings`.
+const COMMAND_KEYS: &[(&str, &str)] = &[
+    ("profiles", "create_profile"),
+    ("active_profile", "switch_profile"),
+    ("backend_mode", "set_backend_mode"),
+    ("health", "set_health_definition"),
+    ("dossier_export", "schedule_dossier_export"),
+    ("pipelines", "save_pipeline"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// User-defined automation pipelines (see `pipelines.rs`).
+    pub pipelines: Vec<PipelineDefinition>,
+    /// When set, updates that would replace the backend are declined.
+    pub pinned_backend_version: Option<String>,
+    /// Offer the previous session (open dossiers, view state) on launch.
+    pub restore_session_on_launch: bool,
+    /// Post-ready preloading of heavy backend resources.
+    pub model_warmup: ModelWarmupSettings,
+    /// When background work stands down for battery power.
+    pub power: PowerSettings,
+    /// Automatic restart of a crashed backend.
+    pub backend_supervisor: SupervisorSettings,
+    /// Rotation and retention of the persisted backend output.
+    pub backend_log: BackendLogSettings,
+    /// Restart the backend via a warm standby instead of stop-then-start.
+    /// Off by default: each standby restart moves the backend to a new port.
+    pub warm_standby_restarts: bool,
+    /// Countdown and triggers for automatic backend restarts.
+    pub auto_restart: AutoRestartSettings,
+    /// How long a backend gets to exit on its own before it is killed.
+    pub backend_shutdown_grace_ms: u64,
+    /// Custom root for backend user data; `None` uses the installed default.
+    pub data_dir: Option<String>,
+    /// Whether scheduled backups of the data directory run.
+    pub backups_enabled: bool,
+    /// Release channel the app updater follows.
+    pub update_channel: UpdateChannel,
+    /// Cleared on a fresh install until the onboarding flow finishes.
+    pub onboarding_completed: bool,
+    /// TCP port or a local socket / named pipe for shell ↔ backend traffic.
+    pub backend_transport: BackendTransport,
+    /// App version of the previous launch, for upgrade detection.
+    pub last_run_version: Option<String>,
+    /// Most recent upgrade and whether its what's-new was shown.
+    pub last_upgrade: Option<UpgradeRecord>,
+    /// Seconds the first launch after an update has to reach a healthy
+    /// backend before a rollback is offered.
+    pub update_verify_timeout_secs: u64,
+    /// Install a downloaded update when the app exits instead of asking
+    /// for a restart.
+    pub install_updates_on_exit: bool,
+    /// Periodic export of the dossier list (see `dossier_export.rs`).
+    pub dossier_export: Option<ScheduledDossierExport>,
+    /// What counts as a healthy backend (see `health_checks.rs`).
+    pub health: HealthDefinition,
+    /// Opt-in anonymous usage events (see `telemetry.rs`).
+    pub telemetry: TelemetrySettings,
+    /// Workspaces besides the default one (see `profiles.rs`).
+    pub profiles: Vec<Profile>,
+    /// Id of the active profile; `None` is the default profile.
+    pub active_profile: Option<String>,
+    /// Backend port and startup health probes.
+    pub lifecycle: LifecycleSettings,
+    /// OS notifications for finished jobs (see `notifications.rs`).
+    pub notifications: NotificationSettings,
+    /// Global shortcut overrides; `""` disables an action (see `shortcuts.rs`).
+    pub shortcuts: BTreeMap<ShortcutAction, String>,
+    /// UI language for shell-side strings; `None` follows the OS.
+    pub locale: Option<String>,
+    /// Closing the main window hides it to the tray and keeps the backend
+    /// running; Quit from the tray or File menu exits.
+    pub close_to_tray: bool,
+    /// Follow the OS theme or pin light/dark (see `theme.rs`).
+    pub theme: ThemePreference,
+    /// Webview zoom factor for every window (see `zoom.rs`).
+    pub zoom: f64,
+    /// Reachability checks of the update endpoint and cloud services (see
+    /// `connectivity.rs`).
+    pub connectivity: ConnectivitySettings,
+    /// Proxy for outbound requests (see `proxy.rs`).
+    pub proxy: ProxySettings,
+    /// PEM file of extra root certificates for outbound HTTPS (see
+    /// `tls_trust.rs`).
+    pub ca_bundle: Option<String>,
+    /// Backend paths requested after startup to warm caches (see
+    /// `prewarm.rs`).
+    pub prewarm: PrewarmSettings,
+    /// Extra backend environment and command-line flags (see
+    /// `backend_config.rs`).
+    pub backend_launch: BackendLaunchSettings,
+    /// Bundled sidecar, dev Python backend or an external one (see
+    /// `backend_mode.rs`).
+    pub backend_mode: BackendMode,
+    /// Host and port attached to in `external` backend mode.
+    pub external_backend: ExternalBackend,
+    /// Periodic health pings after startup (see `health_watchdog.rs`).
+    pub health_watchdog: HealthWatchdogSettings,
+    /// Sensitive data folders kept encrypted at rest (see
+    /// `data_encryption.rs`).
+    pub data_encryption: DataEncryptionSettings,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        AppSettings {
+            pipelines: Vec::new(),
+            pinned_backend_version: None,
+            restore_session_on_launch: true,
+            model_warmup: ModelWarmupSettings::default(),
+            power: PowerSettings::default(),
+            backend_supervisor: SupervisorSettings::default(),
+            backend_log: BackendLogSettings::default(),
+            warm_standby_restarts: false,
+            auto_restart: AutoRestartSettings::default(),
+            backend_shutdown_grace_ms: 5_000,
+            data_dir: None,
+            backups_enabled: false,
+            update_channel: UpdateChannel::default(),
+            // Existing installs predate onboarding and must not be prompted.
+            onboarding_completed: true,
+            backend_transport: BackendTransport::default(),
+            last_run_version: None,
+            last_upgrade: None,
+            update_verify_timeout_secs: 120,
+            install_updates_on_exit: true,
+            dossier_export: None,
+            health: HealthDefinition::default(),
+            telemetry: TelemetrySettings::default(),
+            profiles: Vec::new(),
+            active_profile: None,
+            lifecycle: LifecycleSettings::default(),
+            notifications: NotificationSettings::default(),
+            shortcuts: BTreeMap::new(),
+            locale: None,
+            close_to_tray: false,
+            theme: ThemePreference::default(),
+            zoom: zoom::DEFAULT_ZOOM,
+            connectivity: ConnectivitySettings::default(),
+            proxy: ProxySettings::default(),
+            ca_bundle: None,
+            prewarm: PrewarmSettings::default(),
+            backend_launch: BackendLaunchSettings::default(),
+            backend_mode: BackendMode::default(),
+            external_backend: ExternalBackend::default(),
+            health_watchdog: HealthWatchdogSettings::default(),
+            data_encryption: DataEncryptionSettings::default(),
+        }
+    }
+}
+
+pub struct SettingsState(pub Mutex<AppSettings>);
+
+fn settings_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    portable::resolve(app_handle, SETTINGS_FILE).map_err(|e| e.to_string())
+}
+
+/// Whether a settings file has ever been written.
+pub fn exists(app_handle: &tauri::AppHandle) -> bool {
+    settings_path(app_handle)
+        .map(|p| p.exists())
+        .unwrap_or(false)
+}
+
+/// Root the backend stores user data under when no custom location is set.
+pub fn default_data_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    if let Some(dir) = portable::backend_data_dir() {
+        return Ok(dir);
+    }
+    app_handle
+        .path()
+        .resolve(DEFAULT_DATA_DIR, BaseDirectory::LocalData)
+        .map_err(|e| e.to_string())
+}
+
+/// Root the backend stores user data under (dossiers, PLSS): the active
+/// profile's folder, the custom location or the default.
+pub fn backend_data_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    if let Some(dir) = profiles::active_data_dir(app_handle) {
+        return Ok(dir);
+    }
+    match current(app_handle).data_dir {
+        Some(dir) => Ok(paths::plain(Path::new(&dir))),
+        None => default_data_dir(app_handle),
+    }
+}
+
+/// Data root to hand the backend via `DATA_DIR_ENV`: the active profile's
+/// folder, the custom location, or the portable data folder. `None` leaves
+/// the backend on its default.
+pub fn data_dir_override(app_handle: &tauri::AppHandle) -> Option<String> {
+    if let Some(dir) = profiles::active_data_dir(app_handle) {
+        return Some(dir.to_string_lossy().into_owned());
+    }
+    current(app_handle)
+        .data_dir
+        .or_else(|| portable::backend_data_dir().map(|dir| dir.to_string_lossy().into_owned()))
+}
+
+/// Read settings from disk. A missing or unreadable file yields defaults so a
+/// corrupt settings file can never keep the app from starting.
+pub fn load(app_handle: &tauri::AppHandle) -> AppSettings {
+    let path = match settings_path(app_handle) {
+        Ok(p) => p,
+        Err(e) => {
+            log::warn!("SETTINGS ► could not resolve settings path: {}", e);
+            return AppSettings::default();
+        }
+    };
+
+    match fs::read_to_string(&path) {
+        Ok(raw) => serde_json::from_str(&raw).unwrap_or_else(|e| {
+            log::warn!(
+                "SETTINGS ► invalid settings at {:?}, using defaults: {}",
+                path,
+                e
+            );
+            AppSettings::default()
+        }),
+        Err(_) => AppSettings::default(),
+    }
+}
+
+/// Write settings atomically (temp file + rename) so a crash mid-write
+/// leaves the previous file intact.
+pub fn save(app_handle: &tauri::AppHandle, settings: &AppSettings) -> Result<(), String> {
+    let path = settings_path(app_handle)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("create {:?}: {}", parent, e))?;
+    }
+    let raw = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, raw).map_err(|e| format!("write {:?}: {}", tmp, e))?;
+    fs::rename(&tmp, &path).map_err(|e| format!("rename {:?}: {}", tmp, e))?;
+    Ok(())
+}
+
+/// Snapshot of the current in-memory settings.
+pub fn current(app_handle: &tauri::AppHandle) -> AppSettings {
+    app_handle
+        .state::<SettingsState>()
+        .0
+        .lock()
+        .unwrap()
+        .clone()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsChanged {
+    /// Top-level keys whose value changed.
+    pub keys: Vec<String>,
+    pub settings: AppSettings,
+}
+
+/// Top-level keys that differ between `a` and `b`.
+fn changed_keys(a: &AppSettings, b: &AppSettings) -> Vec<String> {
+    let (Ok(Value::Object(a)), Ok(Value::Object(b))) =
+        (serde_json::to_value(a), serde_json::to_value(b))
+    else {
+        return Vec::new();
+    };
+    b.iter()
+        .filter(|(k, v)| a.get(*k) != Some(*v))
+        .map(|(k, _)| k.clone())
+        .collect()
+}
+
+/// Apply `f` to the settings, persist the result and return the new value.
+/// Any change is announced as `settings-changed`.
+pub fn update<F>(app_handle: &tauri::AppHandle, f: F) -> Result<AppSettings, String>
+where
+    F: FnOnce(&mut AppSettings),
+{
+    try_update(app_handle, |s| {
+        f(s);
+        Ok(())
+    })
+}
+
+/// `update` for changes that can fail; an error leaves the settings as
+/// they were. `f` runs under the settings lock, so it sees and replaces the
+/// latest settings in one step.
+pub fn try_update<F>(app_handle: &tauri::AppHandle, f: F) -> Result<AppSettings, String>
+where
+    F: FnOnce(&mut AppSettings) -> Result<(), String>,
+{
+    let state = app_handle.state::<SettingsState>();
+    let mut guard = state.0.lock().unwrap();
+    let mut next = guard.clone();
+    f(&mut next)?;
+    let keys = changed_keys(&guard, &next);
+    save(app_handle, &next)?;
+    *guard = next.clone();
+    drop(guard);
+    if keys.is_empty() {
+        return Ok(next);
+    }
+    if keys.iter().any(|k| k == "shortcuts") {
+        shortcuts::apply(app_handle);
+    }
+    if keys.iter().any(|k| k == "locale") {
+        i18n::apply(app_handle);
+    }
+    if keys.iter().any(|k| k == "theme") {
+        theme::apply(app_handle);
+    }
+    if keys.iter().any(|k| k == "zoom") {
+        zoom::apply(app_handle);
+    }
+    let _ = app_handle.emit(
+        "settings-changed",
+        &SettingsChanged {
+            keys,
+            settings: next.clone(),
+        },
+    );
+    Ok(next)
+}
+
+/// Values `update_settings` refuses.
+fn validate(settings: &AppSettings) -> Result<(), String> {
+    settings.lifecycle.validate()?;
+    shortcuts::validate(&settings.shortcuts)?;
+    i18n::validate(settings.locale.as_deref())?;
+    zoom::validate(settings.zoom)?;
+    settings.connectivity.validate()?;
+    settings.proxy.validate()?;
+    tls_trust::validate(settings.ca_bundle.as_deref())?;
+    settings.prewarm.validate()?;
+    settings.backend_launch.validate()?;
+    settings.external_backend.validate()?;
+    settings.health_watchdog.validate()?;
+    settings.data_encryption.validate()?;
+    for profile in &settings.profiles {
+        profile
+            .backend
+            .validate()
+            .map_err(|e| format!("profile '{}': {}", profile.id, e))?;
+    }
+    if let Some(id) = &settings.active_profile {
+        if !settings.profiles.iter().any(|p| &p.id == id) {
+            return Err(format!("active_profile '{}' is not a known profile", id));
+        }
+    }
+    Ok(())
+}
+
+/// RFC 7386 merge patch: objects merge key by key, `null` removes a key
+/// (restoring its default), anything else replaces.
+fn merge(target: &mut Value, patch: Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                if value.is_null() {
+                    target.remove(&key);
+                } else {
+                    merge(target.entry(key).or_insert(Value::Null), value);
+                }
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+#[tauri::command]
+pub async fn get_settings(app_handle: tauri::AppHandle) -> Result<AppSettings, String> {
+    Ok(current(&app_handle))
+}
+
+/// Top-level keys of `fields` a patch must not touch.
+fn check_patch_keys(fields: &serde_json::Map<String, Value>) -> Result<(), String> {
+    let known = serde_json::to_value(AppSettings::default()).map_err(|e| e.to_string())?;
+    for key in fields.keys() {
+        if known.get(key.as_str()).is_none() {
+            return Err(format!("unknown setting '{}'", key));
+        }
+        if INTERNAL_KEYS.contains(&key.as_str()) {
+            return Err(format!("'{}' is kept by the app and can't be set", key));
+        }
+        if let Some((_, command)) = COMMAND_KEYS.iter().find(|(k, _)| k == key) {
+            return Err(format!("'{}' is changed with {}", key, command));
+        }
+    }
+    Ok(())
+}
+
+/// Change settings with a JSON merge patch, e.g.
+/// `{ "lifecycle": { "preferred_port": 8100 } }`. Unknown top-level keys,
+/// keys in `INTERNAL_KEYS` and `COMMAND_KEYS`, and values that don't fit the
+/// settings types are rejected; `data_dir` is checked like in onboarding.
+/// Lifecycle changes apply from the next backend start.
+#[tauri::command]
+pub async fn update_settings(
+    app_handle: tauri::AppHandle,
+    mut patch: Value,
+) -> Result<AppSettings, String> {
+    let Value::Object(fields) = &mut patch else {
+        return Err("settings patch must be an object".into());
+    };
+    check_patch_keys(fields)?;
+    if let Some(Value::String(dir)) = fields.get("data_dir") {
+        let dir = dir.trim().to_string();
+        let checked = if dir.is_empty() {
+            Value::Null
+        } else {
+            tauri::async_runtime::spawn_blocking(move || onboarding::validate_data_dir(&dir))
+                .await
+                .map_err(|e| e.to_string())??
+                .into()
+        };
+        fields.insert("data_dir".to_string(), checked);
+    }
+    let updated = try_update(&app_handle, |s| {
+        let mut value = serde_json::to_value(&*s).map_err(|e| e.to_string())?;
+        merge(&mut value, patch);
+        let next: AppSettings =
+            serde_json::from_value(value).map_err(|e| format!("invalid settings: {}", e))?;
+        validate(&next)?;
+        *s = next;
+        Ok(())
+    })?;
+    log::info!("SETTINGS ► updated via update_settings");
+    Ok(updated)
+}
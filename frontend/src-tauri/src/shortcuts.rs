@@ -0,0 +1,454 @@
+// Global keyboard shortcuts: accelerators that work while the app is in the
+// background, such as showing/hiding the main window or quick capture. Each
+// action has a default accelerator; `shortcuts` in settings overrides it
+// (an empty string disables the action). The effective set is registered
+// with the OS at startup and again whenever the setting changes.
+//
+// Conflicts are checked at three levels: two actions may not share an
+// accelerator, an accelerator may not shadow one of the app's own menu
+// shortcuts, and the OS may refuse one that another application already
+// holds. The last kind is only known after registering, so it is reported
+// per shortcut by `list_shortcuts` rather than rejected up front.
+//
+// Registration uses RegisterHotKey on Windows, on a thread with its own
+// message loop (hotkeys belong to the thread that registered them). Other
+// platforms have no registration backend in this build yet; their
+// shortcuts are listed as not registered.
+
+use crate::{menu, settings};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::{Emitter, Manager};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutAction {
+    /// Show and focus the main window, or hide it when it has focus.
+    ToggleWindow,
+    /// Bring up the main window and ask the frontend for quick capture.
+    QuickCapture,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 2] =
+        [ShortcutAction::ToggleWindow, ShortcutAction::QuickCapture];
+
+    fn default_accelerator(self) -> &'static str {
+        match self {
+            ShortcutAction::ToggleWindow => "CmdOrCtrl+Alt+P",
+            ShortcutAction::QuickCapture => "CmdOrCtrl+Alt+N",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ShortcutAction::ToggleWindow => "Show or hide Plattera",
+            ShortcutAction::QuickCapture => "Quick capture",
+        }
+    }
+}
+
+const MOD_CTRL: u8 = 1;
+const MOD_ALT: u8 = 2;
+const MOD_SHIFT: u8 = 4;
+const MOD_SUPER: u8 = 8;
+
+/// A parsed accelerator. `key` uses Windows virtual-key codes, which cover
+/// every key accepted here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Accelerator {
+    mods: u8,
+    key: u32,
+}
+
+fn parse_key(name: &str) -> Option<u32> {
+    let upper = name.to_ascii_uppercase();
+    let mut chars = upper.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_alphanumeric() {
+            return Some(c as u32);
+        }
+    }
+    if let Some(n) = upper.strip_prefix('F').and_then(|n| n.parse::<u32>().ok()) {
+        if (1..=24).contains(&n) {
+            return Some(0x6F + n);
+        }
+    }
+    match upper.as_str() {
+        "SPACE" => Some(0x20),
+        "ENTER" | "RETURN" => Some(0x0D),
+        "TAB" => Some(0x09),
+        "HOME" => Some(0x24),
+        "END" => Some(0x23),
+        "PAGEUP" => Some(0x21),
+        "PAGEDOWN" => Some(0x22),
+        "INSERT" => Some(0x2D),
+        "LEFT" => Some(0x25),
+        "UP" => Some(0x26),
+        "RIGHT" => Some(0x27),
+        "DOWN" => Some(0x28),
+        "=" => Some(0xBB),
+        "-" => Some(0xBD),
+        _ => None,
+    }
+}
+
+impl Accelerator {
+    /// Parse `Mod+Mod+Key`, e.g. `CmdOrCtrl+Shift+K`. At least one modifier
+    /// is required: a bare global key would swallow it in every other app.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = raw.split('+').map(str::trim).collect();
+        let (key, mods) = parts.split_last().ok_or("empty accelerator")?;
+        let mut bits = 0;
+        for m in mods {
+            bits |= match m.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => MOD_CTRL,
+                "alt" | "option" => MOD_ALT,
+                "shift" => MOD_SHIFT,
+                "super" | "cmd" | "command" | "meta" => MOD_SUPER,
+                "cmdorctrl" | "commandorcontrol" => {
+                    if cfg!(target_os = "macos") {
+                        MOD_SUPER
+                    } else {
+                        MOD_CTRL
+                    }
+                }
+                other => return Err(format!("unknown modifier '{}' in '{}'", other, raw)),
+            };
+        }
+        if bits == 0 {
+            return Err(format!("'{}' needs at least one modifier", raw));
+        }
+        let key = parse_key(key).ok_or_else(|| format!("unknown key '{}' in '{}'", key, raw))?;
+        Ok(Accelerator { mods: bits, key })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ShortcutInfo {
+    pub action: ShortcutAction,
+    pub label: &'static str,
+    /// Effective accelerator; `None` when disabled.
+    pub accelerator: Option<String>,
+    pub is_default: bool,
+    pub registered: bool,
+    pub error: Option<String>,
+}
+
+/// Registration outcome per action, from the last `apply`.
+pub struct ShortcutState(std::sync::Mutex<BTreeMap<ShortcutAction, Result<(), String>>>);
+
+impl ShortcutState {
+    pub fn new() -> Self {
+        ShortcutState(std::sync::Mutex::new(BTreeMap::new()))
+    }
+}
+
+/// Effective accelerator per enabled action.
+fn effective(overrides: &BTreeMap<ShortcutAction, String>) -> Vec<(ShortcutAction, String)> {
+    ShortcutAction::ALL
+        .iter()
+        .filter_map(|&action| {
+            let accel = overrides
+                .get(&action)
+                .map(|s| s.trim().to_string())
+                .unwrap_or_else(|| action.default_accelerator().to_string());
+            (!accel.is_empty()).then_some((action, accel))
+        })
+        .collect()
+}
+
+/// Reject overrides that don't parse, shadow a menu shortcut or collide
+/// with another action. Called from settings validation.
+pub fn validate(overrides: &BTreeMap<ShortcutAction, String>) -> Result<(), String> {
+    let reserved: Vec<Accelerator> = menu::ACCELERATORS
+        .iter()
+        .filter_map(|r| Accelerator::parse(r).ok())
+        .collect();
+    let mut seen: Vec<(ShortcutAction, Accelerator)> = Vec::new();
+    for (action, raw) in effective(overrides) {
+        let accel = Accelerator::parse(&raw)?;
+        if reserved.contains(&accel) {
+            return Err(format!("'{}' is already used by the app menu", raw));
+        }
+        if let Some((other, _)) = seen.iter().find(|(_, a)| *a == accel) {
+            return Err(format!(
+                "'{}' is already assigned to {}",
+                raw,
+                other.label()
+            ));
+        }
+        seen.push((action, accel));
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(windows), allow(dead_code))]
+fn show_main(app_handle: &tauri::AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Run `action`. Called from the registration backend.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn trigger(app_handle: &tauri::AppHandle, action: ShortcutAction) {
+    log::info!("SHORTCUTS ► {:?}", action);
+    match action {
+        ShortcutAction::ToggleWindow => {
+            let window = app_handle.get_webview_window("main");
+            let focused = window.as_ref().is_some_and(|w| {
+                w.is_visible().unwrap_or(false) && w.is_focused().unwrap_or(false)
+            });
+            if focused {
+                if let Some(w) = window {
+                    let _ = w.hide();
+                }
+            } else {
+                show_main(app_handle);
+            }
+        }
+        ShortcutAction::QuickCapture => {
+            show_main(app_handle);
+            let _ = app_handle.emit_to("main", "quick-capture", ());
+        }
+    }
+    let _ = app_handle.emit("global-shortcut", action);
+}
+
+/// Register the effective shortcuts from settings, replacing any registered
+/// before. Called at startup and whenever `shortcuts` changes.
+pub fn apply(app_handle: &tauri::AppHandle) {
+    let wanted: Vec<(ShortcutAction, Accelerator, String)> =
+        effective(&settings::current(app_handle).shortcuts)
+            .into_iter()
+            .filter_map(|(action, raw)| match Accelerator::parse(&raw) {
+                Ok(accel) => Some((action, accel, raw)),
+                Err(e) => {
+                    log::warn!("SHORTCUTS ► ignoring {:?}: {}", action, e);
+                    None
+                }
+            })
+            .collect();
+    let results = imp::register(app_handle, &wanted);
+    for (action, result) in &results {
+        if let Err(e) = result {
+            log::warn!("SHORTCUTS ► {:?} not registered: {}", action, e);
+        }
+    }
+    *app_handle.state::<ShortcutState>().0.lock().unwrap() = results;
+}
+
+fn list(app_handle: &tauri::AppHandle) -> Vec<ShortcutInfo> {
+    let overrides = settings::current(app_handle).shortcuts;
+    let enabled = effective(&overrides);
+    let results = app_handle
+        .state::<ShortcutState>()
+        .0
+        .lock()
+        .unwrap()
+        .clone();
+    ShortcutAction::ALL
+        .iter()
+        .map(|&action| {
+            let accelerator = enabled
+                .iter()
+                .find(|(a, _)| *a == action)
+                .map(|(_, raw)| raw.clone());
+            let result = results.get(&action);
+            ShortcutInfo {
+                action,
+                label: action.label(),
+                is_default: !overrides.contains_key(&action),
+                registered: matches!(result, Some(Ok(()))),
+                error: match result {
+                    Some(Err(e)) => Some(e.clone()),
+                    _ => None,
+                },
+                accelerator,
+            }
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn list_shortcuts(app_handle: tauri::AppHandle) -> Result<Vec<ShortcutInfo>, String> {
+    Ok(list(&app_handle))
+}
+
+/// Set the accelerator for `action`: a new accelerator, `""` to disable it,
+/// or `None` to go back to the default. Returns the resulting list, with the
+/// OS registration outcome for each shortcut.
+#[tauri::command]
+pub async fn set_shortcut(
+    app_handle: tauri::AppHandle,
+    action: ShortcutAction,
+    accelerator: Option<String>,
+) -> Result<Vec<ShortcutInfo>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut overrides = settings::current(&app_handle).shortcuts;
+        match accelerator {
+            Some(accel) => overrides.insert(action, accel.trim().to_string()),
+            None => overrides.remove(&action),
+        };
+        validate(&overrides)?;
+        // Saving triggers `apply` through the settings change hook.
+        settings::update(&app_handle, |s| s.shortcuts = overrides)?;
+        Ok(list(&app_handle))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+type Registration = BTreeMap<ShortcutAction, Result<(), String>>;
+
+#[cfg(windows)]
+mod imp {
+    use super::{Accelerator, Registration, ShortcutAction};
+    use super::{MOD_ALT, MOD_CTRL, MOD_SHIFT, MOD_SUPER};
+    use std::sync::mpsc::{channel, Receiver, Sender};
+    use std::sync::{Mutex, OnceLock};
+    use std::thread;
+    use windows_sys::Win32::System::Threading::GetCurrentThreadId;
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+        RegisterHotKey, UnregisterHotKey, HOT_KEY_MODIFIERS, MOD_NOREPEAT,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetMessageW, PeekMessageW, PostThreadMessageW, MSG, PM_NOREMOVE, WM_APP, WM_HOTKEY,
+    };
+
+    const WM_RELOAD: u32 = WM_APP + 1;
+
+    type Request = (
+        Vec<(ShortcutAction, Accelerator, String)>,
+        Sender<Registration>,
+    );
+
+    struct Worker {
+        thread_id: u32,
+        requests: Sender<Request>,
+    }
+
+    static WORKER: OnceLock<Mutex<Worker>> = OnceLock::new();
+
+    fn os_mods(mods: u8) -> HOT_KEY_MODIFIERS {
+        let mut out = MOD_NOREPEAT;
+        if mods & MOD_CTRL != 0 {
+            out |= windows_sys::Win32::UI::Input::KeyboardAndMouse::MOD_CONTROL;
+        }
+        if mods & MOD_ALT != 0 {
+            out |= windows_sys::Win32::UI::Input::KeyboardAndMouse::MOD_ALT;
+        }
+        if mods & MOD_SHIFT != 0 {
+            out |= windows_sys::Win32::UI::Input::KeyboardAndMouse::MOD_SHIFT;
+        }
+        if mods & MOD_SUPER != 0 {
+            out |= windows_sys::Win32::UI::Input::KeyboardAndMouse::MOD_WIN;
+        }
+        out
+    }
+
+    /// The hotkey thread: registers what it is asked to and dispatches
+    /// WM_HOTKEY. Hotkey ids are indexes into `ShortcutAction::ALL`.
+    fn run(app_handle: tauri::AppHandle, requests: Receiver<Request>, ready: Sender<u32>) {
+        unsafe {
+            // Create the thread's message queue before anyone posts to it.
+            let mut msg: MSG = std::mem::zeroed();
+            PeekMessageW(&mut msg, 0, 0, 0, PM_NOREMOVE);
+            let _ = ready.send(GetCurrentThreadId());
+
+            let mut registered: Vec<i32> = Vec::new();
+            while GetMessageW(&mut msg, 0, 0, 0) > 0 {
+                match msg.message {
+                    WM_HOTKEY => {
+                        if let Some(&action) = ShortcutAction::ALL.get(msg.wParam) {
+                            super::trigger(&app_handle, action);
+                        }
+                    }
+                    WM_RELOAD => {
+                        while let Ok((wanted, reply)) = requests.try_recv() {
+                            for id in registered.drain(..) {
+                                UnregisterHotKey(0, id);
+                            }
+                            let mut results = Registration::new();
+                            for (action, accel, raw) in wanted {
+                                let id = ShortcutAction::ALL
+                                    .iter()
+                                    .position(|a| *a == action)
+                                    .unwrap_or_default()
+                                    as i32;
+                                let ok = RegisterHotKey(0, id, os_mods(accel.mods), accel.key) != 0;
+                                let result = if ok {
+                                    registered.push(id);
+                                    Ok(())
+                                } else {
+                                    Err(format!("'{}' is in use by another application", raw))
+                                };
+                                results.insert(action, result);
+                            }
+                            let _ = reply.send(results);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn worker(app_handle: &tauri::AppHandle) -> Option<&'static Mutex<Worker>> {
+        if let Some(w) = WORKER.get() {
+            return Some(w);
+        }
+        let (requests, rx) = channel();
+        let (ready_tx, ready_rx) = channel();
+        let app_handle = app_handle.clone();
+        thread::spawn(move || run(app_handle, rx, ready_tx));
+        let thread_id = ready_rx.recv().ok()?;
+        Some(WORKER.get_or_init(|| {
+            Mutex::new(Worker {
+                thread_id,
+                requests,
+            })
+        }))
+    }
+
+    pub fn register(
+        app_handle: &tauri::AppHandle,
+        wanted: &[(ShortcutAction, Accelerator, String)],
+    ) -> Registration {
+        let Some(worker) = worker(app_handle) else {
+            return Registration::new();
+        };
+        let worker = worker.lock().unwrap();
+        let (reply, results) = channel();
+        if worker.requests.send((wanted.to_vec(), reply)).is_err() {
+            return Registration::new();
+        }
+        unsafe {
+            PostThreadMessageW(worker.thread_id, WM_RELOAD, 0, 0);
+        }
+        results.recv().unwrap_or_default()
+    }
+}
+
+#[cfg(not(windows))]
+mod imp {
+    use super::{Accelerator, Registration, ShortcutAction};
+
+    pub fn register(
+        _app_handle: &tauri::AppHandle,
+        wanted: &[(ShortcutAction, Accelerator, String)],
+    ) -> Registration {
+        wanted
+            .iter()
+            .map(|(action, _, _)| {
+                (
+                    *action,
+                    Err("global shortcuts are not supported on this platform yet".to_string()),
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,242 @@
+// Last-known-good backend retention. After every successful boot the
+// bundled sidecar is copied (only when it changed) into AppLocalData. If a
+// newly shipped sidecar then crash-loops during startup, the shell falls
+// back to that retained copy and emits `backend-downgraded` so a bad backend
+// release never leaves the app without a working engine.
+
+use crate::{correlation, portable};
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use tauri::Manager;
+
+const RETAINED_DIR: &str = "backend_fallback";
+const STAMP_FILE: &str = "retained.json";
+/// Exits within this window after spawn (and before the first healthy
+/// check) count as boot failures.
+const BOOT_WINDOW: Duration = Duration::from_secs(90);
+const MAX_BOOT_ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Default)]
+struct BootState {
+    spawned_at: Option<Instant>,
+    boot_ok: bool,
+    failed_attempts: u32,
+    use_retained: bool,
+}
+
+pub struct BootTracker(Mutex<BootState>);
+
+impl BootTracker {
+    pub fn new() -> Self {
+        BootTracker(Mutex::new(BootState::default()))
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct DowngradePayload {
+    reason: String,
+    failed_attempts: u32,
+    fallback_path: String,
+}
+
+fn sidecar_file_name() -> String {
+    format!("plattera-backend{}", std::env::consts::EXE_SUFFIX)
+}
+
+/// Path of the sidecar shipped next to the app executable (same rule the
+/// shell plugin uses to resolve `sidecar("plattera-backend")`).
+pub fn bundled_sidecar_path() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    Some(exe.parent()?.join(sidecar_file_name()))
+}
+
+pub fn retained_path(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    portable::resolve(
+        app_handle,
+        format!("{}/{}", RETAINED_DIR, sidecar_file_name()),
+    )
+    .ok()
+}
+
+/// Cheap identity for a binary: size plus modification time.
+fn fingerprint(path: &PathBuf) -> Option<String> {
+    let meta = fs::metadata(path).ok()?;
+    let modified = meta
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+    Some(format!("{}:{}", meta.len(), modified))
+}
+
+/// The binary to spawn instead of the bundled sidecar, if a downgrade is
+/// in effect.
+pub fn active_override(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    let tracker = app_handle.state::<BootTracker>();
+    let use_retained = tracker.0.lock().unwrap().use_retained;
+    if !use_retained {
+        return None;
+    }
+    retained_path(app_handle).filter(|p| p.exists())
+}
+
+/// Record that a backend was just spawned; starts the boot window.
+pub fn note_spawn(app_handle: &tauri::AppHandle) {
+    let tracker = app_handle.state::<BootTracker>();
+    let mut state = tracker.0.lock().unwrap();
+    state.spawned_at = Some(Instant::now());
+    state.boot_ok = false;
+}
+
+/// Whether a spawned backend is still inside its boot window without
+/// having reported healthy yet.
+pub fn boot_in_progress(app_handle: &tauri::AppHandle) -> bool {
+    let tracker = app_handle.state::<BootTracker>();
+    let state = tracker.0.lock().unwrap();
+    !state.boot_ok
+        && state
+            .spawned_at
+            .map(|t| t.elapsed() < BOOT_WINDOW)
+            .unwrap_or(false)
+}
+
+/// Record a healthy boot. When running the bundled sidecar, refresh the
+/// retained copy so it always reflects the last binary known to work.
+pub fn mark_boot_ok(app_handle: &tauri::AppHandle) {
+    let use_retained = {
+        let tracker = app_handle.state::<BootTracker>();
+        let mut state = tracker.0.lock().unwrap();
+        state.boot_ok = true;
+        state.failed_attempts = 0;
+        state.use_retained
+    };
+    if !use_retained {
+        if let Err(e) = retain_bundled(app_handle) {
+            log::warn!("SIDECAR_FALLBACK ► could not retain backend binary: {}", e);
+        }
+    }
+}
+
+fn retain_bundled(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let Some(source) = bundled_sidecar_path().filter(|p| p.exists()) else {
+        // Dev runs use the Python fallback; nothing to retain.
+        return Ok(());
+    };
+    let target = retained_path(app_handle).ok_or("could not resolve retained path")?;
+    let dir = target.parent().ok_or("retained path has no parent")?;
+    let stamp_path = dir.join(STAMP_FILE);
+
+    let current = fingerprint(&source).ok_or("could not stat bundled sidecar")?;
+    let stamped = fs::read_to_string(&stamp_path).unwrap_or_default();
+    if target.exists() && stamped.trim() == current {
+        return Ok(());
+    }
+
+    fs::create_dir_all(dir).map_err(|e| format!("create {:?}: {}", dir, e))?;
+    let tmp = target.with_extension("partial");
+    fs::copy(&source, &tmp).map_err(|e| format!("copy {:?}: {}", source, e))?;
+    fs::rename(&tmp, &target).map_err(|e| format!("rename {:?}: {}", tmp, e))?;
+    fs::write(&stamp_path, &current).map_err(|e| format!("write {:?}: {}", stamp_path, e))?;
+    log::info!(
+        "SIDECAR_FALLBACK ► retained last-known-good backend at {:?} ({})",
+        target,
+        current
+    );
+    Ok(())
+}
+
+enum ExitDecision {
+    Ignore,
+    Retry(u32),
+    Downgrade(u32, PathBuf),
+    GiveUp(u32),
+}
+
+/// Called when the tracked sidecar exits without being asked to. Exits
+/// during boot are retried a few times; if the bundled binary keeps dying,
+/// switch to the retained copy. Returns false when the exit happened outside
+/// the boot window and is left to the crash supervisor.
+pub fn on_unexpected_exit(app_handle: &tauri::AppHandle, code: Option<i32>) -> bool {
+    let decision = {
+        let tracker = app_handle.state::<BootTracker>();
+        let mut state = tracker.0.lock().unwrap();
+        let in_boot_window = state
+            .spawned_at
+            .map(|t| t.elapsed() < BOOT_WINDOW)
+            .unwrap_or(false);
+
+        if state.boot_ok || !in_boot_window {
+            ExitDecision::Ignore
+        } else {
+            state.failed_attempts += 1;
+            if state.failed_attempts < MAX_BOOT_ATTEMPTS {
+                ExitDecision::Retry(state.failed_attempts)
+            } else {
+                match retained_path(app_handle).filter(|p| p.exists()) {
+                    Some(path) if !state.use_retained => {
+                        let attempts = state.failed_attempts;
+                        state.use_retained = true;
+                        state.failed_attempts = 0;
+                        ExitDecision::Downgrade(attempts, path)
+                    }
+                    _ => ExitDecision::GiveUp(state.failed_attempts),
+                }
+            }
+        }
+    };
+
+    match decision {
+        ExitDecision::Ignore => return false,
+        ExitDecision::Retry(attempt) => {
+            log::warn!(
+                "SIDECAR_FALLBACK ► backend exited during boot (code={:?}, attempt {}/{}); retrying",
+                code,
+                attempt,
+                MAX_BOOT_ATTEMPTS
+            );
+            respawn_later(app_handle.clone());
+        }
+        ExitDecision::Downgrade(attempts, path) => {
+            let reason = format!(
+                "bundled backend exited {} times during startup (last code {:?}); using last known-good copy",
+                attempts, code
+            );
+            log::error!("SIDECAR_FALLBACK ► {}", reason);
+            correlation::emit(
+                app_handle,
+                "backend-downgraded",
+                DowngradePayload {
+                    reason,
+                    failed_attempts: attempts,
+                    fallback_path: path.to_string_lossy().into_owned(),
+                },
+            );
+            respawn_later(app_handle.clone());
+        }
+        ExitDecision::GiveUp(attempts) => {
+            log::error!(
+                "SIDECAR_FALLBACK ► backend failed to boot {} times and no fallback is available",
+                attempts
+            );
+        }
+    }
+    true
+}
+
+fn respawn_later(app_handle: tauri::AppHandle) {
+    let op = correlation::current();
+    thread::spawn(move || {
+        let _op = correlation::adopt(op);
+        thread::sleep(RETRY_DELAY);
+        match crate::spawn_backend(&app_handle) {
+            Ok(msg) => log::info!("SIDECAR_FALLBACK ► {}", msg),
+            Err(e) => log::error!("SIDECAR_FALLBACK ► respawn failed: {}", e),
+        }
+    });
+}
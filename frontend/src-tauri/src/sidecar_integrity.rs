@@ -0,0 +1,216 @@
+// Sidecar integrity check. The build records the SHA-256 of the bundled
+// `plattera-backend` in a manifest compiled into the app (`build.rs`);
+// before the bundled sidecar is spawned its hash must match. A truncated
+// download or a partial antivirus quarantine restore otherwise shows up as
+// an opaque crash loop. On a mismatch the shell refuses to start it and
+// emits `backend-corrupt`; "Repair installation" then restores the retained
+// last-known-good copy when that copy is the same build. A backend patch
+// installed by `backend_update.rs` is accepted by the hash it was recorded
+// with.
+//
+// Hashing a PyInstaller onefile takes a moment, so a verified binary is
+// remembered by size and mtime until it changes on disk.
+
+use crate::{backend_update, correlation, sidecar_fallback};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::UNIX_EPOCH;
+
+const MANIFEST: &str = include_str!(concat!(env!("OUT_DIR"), "/sidecar-manifest.json"));
+const SIDECAR_NAME: &str = "plattera-backend";
+
+#[derive(Debug, Clone, Deserialize)]
+struct ManifestEntry {
+    sha256: String,
+    size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CorruptPayload {
+    pub path: String,
+    pub expected: String,
+    pub actual: String,
+    /// Whether "Repair installation" can restore a matching copy.
+    pub repairable: bool,
+}
+
+/// Size and mtime of the last binary that passed, so restarts don't rehash.
+static VERIFIED: Mutex<Option<(PathBuf, u64, u64)>> = Mutex::new(None);
+
+fn expected() -> Option<&'static ManifestEntry> {
+    static ENTRY: OnceLock<Option<ManifestEntry>> = OnceLock::new();
+    ENTRY
+        .get_or_init(|| {
+            serde_json::from_str::<HashMap<String, ManifestEntry>>(MANIFEST)
+                .ok()
+                .and_then(|mut m| m.remove(SIDECAR_NAME))
+        })
+        .as_ref()
+}
+
+fn stamp(path: &Path) -> Option<(u64, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    let modified = meta
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+    Some((meta.len(), modified))
+}
+
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1 << 20];
+    loop {
+        let n = file
+            .read(&mut buf)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// Builds the sidecar may be: the bundled one, or an installed patch.
+fn accepted(app_handle: &tauri::AppHandle) -> Vec<ManifestEntry> {
+    let mut entries: Vec<ManifestEntry> = expected().cloned().into_iter().collect();
+    if let Some(patch) = backend_update::installed_patch(app_handle) {
+        entries.push(ManifestEntry {
+            sha256: patch.sha256,
+            size: patch.size,
+        });
+    }
+    entries
+}
+
+/// Check `path` against the accepted builds; the error carries what was
+/// found instead. Passes when there is nothing to check against (dev
+/// builds).
+fn verify(app_handle: &tauri::AppHandle, path: &Path) -> Result<(), String> {
+    if expected().is_none() {
+        return Ok(());
+    }
+    let accepted = accepted(app_handle);
+    let stamp = stamp(path);
+    if let (Some((len, modified)), Some((p, l, m))) = (stamp, VERIFIED.lock().unwrap().as_ref()) {
+        if p == path && *l == len && *m == modified {
+            return Ok(());
+        }
+    }
+    let Some((len, modified)) = stamp else {
+        return Err("unreadable".to_string());
+    };
+    if !accepted.iter().any(|e| e.size == len) {
+        // Cheap reject before hashing.
+        return Err(format!("{} bytes", len));
+    }
+    let actual = sha256_file(path)?;
+    if !accepted
+        .iter()
+        .any(|e| actual.eq_ignore_ascii_case(&e.sha256))
+    {
+        return Err(actual);
+    }
+    *VERIFIED.lock().unwrap() = Some((path.to_path_buf(), len, modified));
+    Ok(())
+}
+
+/// Retained last-known-good copy, if it is the same build as the manifest.
+fn matching_retained(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    let retained = sidecar_fallback::retained_path(app_handle).filter(|p| p.is_file())?;
+    let entry = expected()?;
+    let hash = sha256_file(&retained).ok()?;
+    hash.eq_ignore_ascii_case(&entry.sha256).then_some(retained)
+}
+
+/// Check the bundled sidecar before it is spawned. Refuses (and emits
+/// `backend-corrupt`) when it doesn't match the manifest.
+pub fn check_before_spawn(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let Some(path) = sidecar_fallback::bundled_sidecar_path().filter(|p| p.exists()) else {
+        // Dev runs use the Python fallback.
+        return Ok(());
+    };
+    let Err(actual) = verify(app_handle, &path) else {
+        return Ok(());
+    };
+    let expected = expected().map(|e| e.sha256.clone()).unwrap_or_default();
+    log::error!(
+        "SIDECAR_INTEGRITY ► {} does not match the bundled manifest (expected {}, got {}); not starting it",
+        path.display(),
+        expected,
+        actual
+    );
+    let payload = CorruptPayload {
+        path: path.display().to_string(),
+        expected,
+        actual,
+        repairable: matching_retained(app_handle).is_some(),
+    };
+    correlation::emit(app_handle, "backend-corrupt", &payload);
+    Err(format!(
+        "backend binary at {} failed its integrity check; run Repair installation or reinstall Plattera",
+        payload.path
+    ))
+}
+
+/// Repair step: report the bundled sidecar's state and, when corrupt,
+/// restore the retained copy if it is the same build. Returns the check
+/// result (`ok`, detail) and the action taken, if any.
+pub fn repair(app_handle: &tauri::AppHandle) -> (bool, String, Option<String>) {
+    let Some(path) = sidecar_fallback::bundled_sidecar_path().filter(|p| p.exists()) else {
+        return (true, "no bundled sidecar to verify".to_string(), None);
+    };
+    let Err(actual) = verify(app_handle, &path) else {
+        return (
+            true,
+            format!("{} matches the manifest", path.display()),
+            None,
+        );
+    };
+    let Some(retained) = matching_retained(app_handle) else {
+        return (
+            false,
+            format!(
+                "{} is corrupt ({}) and no matching copy is available; reinstall Plattera",
+                path.display(),
+                actual
+            ),
+            None,
+        );
+    };
+    let tmp = path.with_extension("partial");
+    let restored = fs::copy(&retained, &tmp)
+        .and_then(|_| fs::rename(&tmp, &path))
+        .map_err(|e| e.to_string());
+    match restored.and_then(|_| verify(app_handle, &path)) {
+        Ok(()) => (
+            true,
+            format!("{} restored", path.display()),
+            Some(format!(
+                "restored backend binary from {}",
+                retained.display()
+            )),
+        ),
+        Err(e) => {
+            let _ = fs::remove_file(&tmp);
+            (
+                false,
+                format!("could not restore {}: {}", path.display(), e),
+                None,
+            )
+        }
+    }
+}
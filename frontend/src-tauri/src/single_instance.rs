@@ -0,0 +1,37 @@
+// Single-instance guard. A second launch would start another backend and
+// fight the first over its port; instead the plugin hands the second
+// launch's argv to this instance, which focuses its main window and passes
+// the arguments on to the frontend as `second-instance`. A `plattera://`
+// link among them is also delivered as `deep-link`, and `.plattera` files
+// are imported.
+
+use crate::{deep_link, file_import};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+#[derive(Clone, Serialize)]
+struct SecondInstancePayload {
+    args: Vec<String>,
+    cwd: String,
+}
+
+pub fn on_second_instance(app_handle: &AppHandle, args: Vec<String>, cwd: String) {
+    log::info!(
+        "SINGLE_INSTANCE ► second launch forwarded (args={:?})",
+        args
+    );
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    let links = deep_link::from_args(&args);
+    let files = file_import::from_args(&args, std::path::Path::new(&cwd));
+    let _ = app_handle.emit_to(
+        "main",
+        "second-instance",
+        SecondInstancePayload { args, cwd },
+    );
+    deep_link::handle(app_handle, links);
+    file_import::handle(app_handle, files);
+}
@@ -0,0 +1,279 @@
+// Launch splash. The main window is declared hidden in tauri.conf.json and
+// stays hidden while the backend starts; a small frameless window served
+// over the `plattera-splash` scheme shows progress instead. Its page polls
+// `/status` on the same scheme, so it needs neither the frontend bundle nor
+// IPC. Once `startup.rs` sees the backend healthy the main window is shown
+// and the splash closes. If the backend never answers, the splash switches
+// to a failure page with the error and every health attempt, and offers to
+// retry, continue without the backend, open the recovery console, or quit.
+// The scheme only answers the splash webview itself, so no other page can
+// show the main window or quit the app through it.
+
+use crate::{backend_lifecycle, readiness, recovery, startup};
+use serde::Serialize;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::http::{Request, Response};
+use tauri::{Manager, UriSchemeContext, UriSchemeResponder, WebviewUrl, WebviewWindowBuilder};
+
+pub const SCHEME: &str = "plattera-splash";
+const WINDOW_LABEL: &str = "splash";
+const MAIN_WINDOW: &str = "main";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "phase", rename_all = "snake_case")]
+enum Phase {
+    Starting { message: String },
+    Ready,
+    Failed { error: String },
+}
+
+pub struct SplashState(Mutex<Phase>);
+
+impl SplashState {
+    pub fn new() -> Self {
+        SplashState(Mutex::new(Phase::Starting {
+            message: "Starting Plattera…".to_string(),
+        }))
+    }
+}
+
+fn set_phase(app_handle: &tauri::AppHandle, phase: Phase) {
+    *app_handle.state::<SplashState>().0.lock().unwrap() = phase;
+}
+
+fn page_url() -> Result<tauri::Url, String> {
+    // Windows and Android serve custom schemes as http://<scheme>.localhost.
+    let raw = if cfg!(any(windows, target_os = "android")) {
+        format!("http://{}.localhost/", SCHEME)
+    } else {
+        format!("{}://localhost/", SCHEME)
+    };
+    raw.parse()
+        .map_err(|e| format!("invalid splash url {}: {}", raw, e))
+}
+
+/// Open the splash. Without one the main window is shown right away.
+pub fn open(app_handle: &tauri::AppHandle) {
+    let result = page_url().and_then(|url| {
+        WebviewWindowBuilder::new(app_handle, WINDOW_LABEL, WebviewUrl::CustomProtocol(url))
+            .title("Plattera")
+            .inner_size(460.0, 300.0)
+            .resizable(false)
+            .decorations(false)
+            .center()
+            .build()
+            .map_err(|e| e.to_string())
+    });
+    if let Err(e) = result {
+        log::warn!("SPLASH ► could not open splash window: {}", e);
+        show_main(app_handle);
+    }
+}
+
+pub fn set_status(app_handle: &tauri::AppHandle, message: &str) {
+    set_phase(
+        app_handle,
+        Phase::Starting {
+            message: message.to_string(),
+        },
+    );
+}
+
+/// The backend is healthy: show the main window and close the splash.
+pub fn finish(app_handle: &tauri::AppHandle) {
+    set_phase(app_handle, Phase::Ready);
+    show_main(app_handle);
+}
+
+/// Switch the splash to the failure page. With no splash to show it on, the
+/// main window comes up instead and reports the backend as unavailable.
+pub fn fail(app_handle: &tauri::AppHandle, error: String) {
+    set_phase(app_handle, Phase::Failed { error });
+    if app_handle.get_webview_window(WINDOW_LABEL).is_none() {
+        show_main(app_handle);
+    }
+}
+
+fn show_main(app_handle: &tauri::AppHandle) {
+    if let Some(window) = app_handle.get_webview_window(MAIN_WINDOW) {
+        if let Err(e) = window.show() {
+            log::warn!("SPLASH ► could not show main window: {}", e);
+        }
+        let _ = window.set_focus();
+    }
+    if let Some(splash) = app_handle.get_webview_window(WINDOW_LABEL) {
+        let _ = splash.destroy();
+    }
+}
+
+/// Protocol handler for the splash page and its buttons. Actions may block
+/// (shutting the backend down), so they run off the webview thread.
+pub fn handle_protocol(
+    ctx: UriSchemeContext<'_, tauri::Wry>,
+    request: Request<Vec<u8>>,
+    responder: UriSchemeResponder,
+) {
+    if ctx.webview_label() != WINDOW_LABEL {
+        log::warn!(
+            "SPLASH ► refused request from webview '{}'",
+            ctx.webview_label()
+        );
+        responder.respond(
+            Response::builder()
+                .status(403)
+                .body(Vec::new())
+                .unwrap_or_default(),
+        );
+        return;
+    }
+    let app_handle = ctx.app_handle().clone();
+    let path = request.uri().path().to_string();
+    thread::spawn(move || {
+        let html = |body: String| {
+            Response::builder()
+                .header("Content-Type", "text/html; charset=utf-8")
+                .body(body.into_bytes())
+                .unwrap_or_default()
+        };
+        let response = match path.as_str() {
+            "/" => html(page(STARTING_PAGE)),
+            "/status" => {
+                let phase = app_handle.state::<SplashState>().0.lock().unwrap().clone();
+                Response::builder()
+                    .header("Content-Type", "application/json")
+                    .body(serde_json::to_vec(&phase).unwrap_or_default())
+                    .unwrap_or_default()
+            }
+            "/failed" => html(render_failure(&app_handle)),
+            "/retry" => {
+                log::info!("SPLASH ► retrying backend startup");
+                set_status(&app_handle, "Starting the backend…");
+                startup::spawn(app_handle.clone());
+                html(page(STARTING_PAGE))
+            }
+            "/continue" => {
+                log::warn!("SPLASH ► continuing without a healthy backend");
+                show_main(&app_handle);
+                html(String::new())
+            }
+            "/recovery" => {
+                if let Err(e) = recovery::open(&app_handle) {
+                    log::error!("SPLASH ► could not open recovery console: {}", e);
+                }
+                html(render_failure(&app_handle))
+            }
+            "/quit" => {
+                let handle = app_handle.clone();
+                thread::spawn(move || {
+                    // Let the response reach the splash before the app goes away.
+                    thread::sleep(Duration::from_millis(200));
+                    backend_lifecycle::shutdown_backend_for_exit(&handle);
+                    handle.exit(0);
+                });
+                html(String::new())
+            }
+            _ => Response::builder()
+                .status(404)
+                .body(Vec::new())
+                .unwrap_or_default(),
+        };
+        responder.respond(response);
+    });
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn page(template: &str) -> String {
+    template.replace("{{style}}", STYLE)
+}
+
+fn render_failure(app_handle: &tauri::AppHandle) -> String {
+    let error = match &*app_handle.state::<SplashState>().0.lock().unwrap() {
+        Phase::Failed { error } => error.clone(),
+        _ => "the backend did not become ready".to_string(),
+    };
+    let attempts: String = readiness::report(app_handle)
+        .health_attempts
+        .iter()
+        .map(|a| {
+            format!(
+                "<tr><td>{:.1}s</td><td>{} ms</td><td>{}</td></tr>",
+                a.elapsed_ms as f64 / 1000.0,
+                a.latency_ms,
+                escape(a.error.as_deref().unwrap_or("ok"))
+            )
+        })
+        .collect();
+    page(FAILED_PAGE)
+        .replace("{{error}}", &escape(&error))
+        .replace("{{attempts}}", &attempts)
+}
+
+const STYLE: &str = r#"<style>
+  body { font-family: system-ui, sans-serif; margin: 24px; color: #222; background: #f6f6f4;
+         user-select: none; cursor: default; }
+  h1 { font-size: 20px; margin: 0 0 8px; }
+  p { margin: 0 0 12px; line-height: 1.4; }
+  .bar { height: 4px; background: #ddd; border-radius: 2px; overflow: hidden; margin-top: 24px; }
+  .bar div { width: 30%; height: 100%; background: #557; animation: slide 1.2s infinite ease-in-out; }
+  @keyframes slide { from { margin-left: -30%; } to { margin-left: 100%; } }
+  pre { white-space: pre-wrap; padding: 8px; border-radius: 6px; background: #fdecea; margin: 0 0 8px; }
+  table { width: 100%; font-size: 12px; border-collapse: collapse; margin-bottom: 12px; }
+  td { padding: 2px 4px; border-bottom: 1px solid #ddd; vertical-align: top; }
+  a.action { display: inline-block; margin: 4px 4px 0 0; padding: 6px 10px; border: 1px solid #bbb;
+             border-radius: 6px; background: #fff; color: #222; text-decoration: none; }
+  a.action:hover { background: #eef; }
+</style>"#;
+
+const STARTING_PAGE: &str = r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Plattera</title>
+{{style}}
+</head>
+<body>
+<h1>Plattera</h1>
+<p id="status">Starting Plattera…</p>
+<div class="bar"><div></div></div>
+<script>
+  async function poll() {
+    try {
+      const res = await fetch('/status');
+      const state = await res.json();
+      if (state.phase === 'failed') { location.href = '/failed'; return; }
+      if (state.phase === 'starting') { document.getElementById('status').textContent = state.message; }
+    } catch (e) {}
+    setTimeout(poll, 400);
+  }
+  poll();
+</script>
+</body>
+</html>
+"#;
+
+const FAILED_PAGE: &str = r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Plattera</title>
+{{style}}
+</head>
+<body>
+<h1>The backend did not start</h1>
+<pre>{{error}}</pre>
+<table>{{attempts}}</table>
+<a class="action" href="/retry">Retry</a>
+<a class="action" href="/continue">Continue anyway</a>
+<a class="action" href="/recovery">Recovery console</a>
+<a class="action" href="/quit">Quit</a>
+</body>
+</html>
+"#;
@@ -0,0 +1,111 @@
+// Warm-standby backend restarts. Instead of stopping the backend and then
+// starting a new one (leaving a gap where requests fail), start the
+// replacement on a free secondary port, wait until it reports healthy, swap
+// the tracked child and endpoint in one step, and only then retire the old
+// instance.
+
+use crate::{
+    backend_http, backend_mode, backend_pid, cleanup_via_http, endpoint, port_in_use,
+    spawn_backend_child, BackendProcess,
+};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+
+const STANDBY_READY_TIMEOUT: Duration = Duration::from_secs(60);
+const STANDBY_POLL: Duration = Duration::from_millis(500);
+const RETIRE_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn wait_until_healthy(app_handle: &tauri::AppHandle, port: u16, timeout: Duration) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        let healthy = backend_http::call_port_blocking(
+            app_handle,
+            port,
+            reqwest::Method::GET,
+            "/api/health",
+            Duration::from_millis(2_000),
+        )
+        .map(|reply| reply.status.is_success())
+        .unwrap_or(false);
+        if healthy {
+            return true;
+        }
+        thread::sleep(STANDBY_POLL);
+    }
+    false
+}
+
+/// Restart the backend without a connection gap. On failure the old
+/// instance keeps serving and the standby is discarded.
+pub fn restart_with_standby(app_handle: &tauri::AppHandle) -> Result<String, String> {
+    if backend_mode::external(app_handle).is_some() {
+        return Err("an external backend is not restarted by the app".to_string());
+    }
+    if endpoint::socket(app_handle).is_some() {
+        return Err("warm standby needs the TCP transport".to_string());
+    }
+    let old_port = endpoint::port(app_handle);
+    let new_port = endpoint::free_loopback_port()?;
+    log::info!(
+        "STANDBY ► starting standby backend on port {} (active on {})",
+        new_port,
+        old_port
+    );
+
+    let (child, _is_sidecar) = spawn_backend_child(app_handle, new_port)?;
+    if !wait_until_healthy(app_handle, new_port, STANDBY_READY_TIMEOUT) {
+        log::warn!(
+            "STANDBY ► standby on port {} never became healthy; keeping current backend",
+            new_port
+        );
+        let _ = child.kill();
+        endpoint::forget_spawned(app_handle, new_port);
+        return Err(format!(
+            "standby backend on port {} did not become ready",
+            new_port
+        ));
+    }
+
+    // Swap atomically from the app's point of view: the tracked child and the
+    // endpoint change under the BackendProcess lock.
+    let old_child = {
+        let backend = app_handle.state::<BackendProcess>();
+        let mut guard = backend.0.lock().unwrap();
+        let pid = child.pid();
+        let old = guard.replace(child);
+        backend_pid::track(app_handle, pid);
+        endpoint::set_address(app_handle, endpoint::LOOPBACK_HOST, new_port);
+        old
+    };
+    log::info!("STANDBY ► switched backend endpoint to port {}", new_port);
+
+    // Retire the old instance through the normal cleanup path.
+    cleanup_via_http(app_handle, old_port, 1_500);
+    if let Some(old) = old_child {
+        let _ = old.kill();
+    }
+    endpoint::forget_spawned(app_handle, old_port);
+    let start = Instant::now();
+    while port_in_use(old_port) && start.elapsed() < RETIRE_TIMEOUT {
+        thread::sleep(Duration::from_millis(250));
+    }
+    if port_in_use(old_port) {
+        log::warn!(
+            "STANDBY ► old backend still holds port {} after retire",
+            old_port
+        );
+    }
+
+    Ok(format!(
+        "Backend restarted on port {} (previous port {})",
+        new_port, old_port
+    ))
+}
+
+#[tauri::command]
+pub async fn warm_restart_backend(app_handle: tauri::AppHandle) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || restart_with_standby(&app_handle))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,181 @@
+// Launch sequence, run as one async task once setup has finished: sweep
+// orphaned backends (`orphan_sweep.rs`), spawn the backend, poll its health
+// with the `lifecycle` attempts, delays and overall timeout, then hand over
+// from the splash to the main window and warm caches in the background.
+// Progress is mirrored on the splash (`splash.rs`) and in the backend state
+// (`backend_state.rs`), and every probe is recorded in `readiness.rs`. A
+// backend that can't be spawned at all goes straight to the splash's failure
+// page as `backend-start-failed`, without waiting out the health timeout; one
+// that never comes up is reported as `backend-start-timeout`. Both events
+// carry the tail of the backend's stderr. A backend outside the supported
+// version range (`compat.rs`) also stops at the failure page. A backend listening
+// beyond loopback is reported, and restarted if that is enforced
+// (`loopback_bind.rs`). The splash's "Retry" button runs the sequence again.
+
+use crate::{
+    backend_http, backend_log, backend_state, compat, correlation, loopback_bind, orphan_sweep,
+    prewarm, readiness, settings, sidecar_fallback, splash, warmup,
+};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Backend stderr lines attached to a start failure.
+const STDERR_TAIL_LINES: usize = 40;
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize)]
+struct StartFailure {
+    error: String,
+    waited_ms: u64,
+    /// Last lines the backend wrote to stderr, oldest first.
+    stderr_tail: Vec<String>,
+}
+
+/// Start the sequence unless it is already running.
+pub fn spawn(app_handle: tauri::AppHandle) {
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        run(&app_handle).await;
+        RUNNING.store(false, Ordering::SeqCst);
+    });
+}
+
+/// Probe `/api/health` with the `lifecycle` attempts and delays, giving up
+/// at `startup_timeout_ms`. Returns the last error when the backend never
+/// answered.
+pub async fn wait_until_healthy(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let lifecycle = settings::current(app_handle).lifecycle;
+    let delays = &lifecycle.health_retry_delays_ms;
+    let attempts = lifecycle
+        .health_attempts
+        .map(|n| n as usize)
+        .unwrap_or(delays.len());
+    let deadline = Instant::now() + Duration::from_millis(lifecycle.startup_timeout_ms);
+    let mut last_error = "the backend did not answer".to_string();
+    for attempt in 0..attempts {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(format!(
+                "no healthy answer within {}ms: {}",
+                lifecycle.startup_timeout_ms, last_error
+            ));
+        }
+        let started = Instant::now();
+        let res = backend_http::call(
+            app_handle,
+            reqwest::Method::GET,
+            "/api/health",
+            None,
+            Duration::from_millis(lifecycle.health_timeout_ms).min(remaining),
+            None,
+        )
+        .await
+        .and_then(|reply| {
+            if reply.status.is_success() {
+                Ok(())
+            } else {
+                Err(format!("health check returned {}", reply.status))
+            }
+        });
+        readiness::record_health_attempt(
+            app_handle,
+            started.elapsed().as_millis() as u64,
+            res.clone(),
+        );
+        match res {
+            Ok(()) => return Ok(()),
+            Err(e) => last_error = e,
+        }
+        let delay = delays
+            .get(attempt)
+            .or(delays.last())
+            .copied()
+            .unwrap_or_default();
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        tokio::time::sleep(Duration::from_millis(delay).min(remaining)).await;
+    }
+    Err(last_error)
+}
+
+/// Report a backend that didn't come up as `event` and show the splash's
+/// failure page.
+fn fail(app_handle: &tauri::AppHandle, event: &str, error: String, waited: Duration) {
+    let stderr_tail = backend_log::recent_stderr(app_handle, STDERR_TAIL_LINES);
+    for line in &stderr_tail {
+        log::error!("STARTUP ► backend stderr: {}", line);
+    }
+    correlation::emit(
+        app_handle,
+        event,
+        StartFailure {
+            error: error.clone(),
+            waited_ms: waited.as_millis() as u64,
+            stderr_tail,
+        },
+    );
+    backend_state::set_failed(app_handle, &error);
+    splash::fail(app_handle, error);
+}
+
+async fn run(app_handle: &tauri::AppHandle) {
+    readiness::begin(app_handle);
+    backend_state::set_starting(app_handle);
+    // Clear out backends a crashed shell left running before starting ours
+    let handle = app_handle.clone();
+    let _ = tauri::async_runtime::spawn_blocking(move || orphan_sweep::sweep(&handle)).await;
+    splash::set_status(app_handle, "Starting the backend…");
+    match crate::start_backend(app_handle.clone()).await {
+        Ok(msg) => log::info!("✅ {}", msg),
+        Err(e) => {
+            log::error!("❌ Failed to start backend: {}", e);
+            fail(app_handle, "backend-start-failed", e, Duration::ZERO);
+            return;
+        }
+    }
+
+    splash::set_status(app_handle, "Waiting for the backend to respond…");
+    let waiting = Instant::now();
+    if let Err(e) = wait_until_healthy(app_handle).await {
+        log::error!("STARTUP ► backend did not become healthy: {}", e);
+        fail(app_handle, "backend-start-timeout", e, waiting.elapsed());
+        return;
+    }
+
+    splash::set_status(app_handle, "Checking the backend version…");
+    let handle = app_handle.clone();
+    if let Ok(check) = tauri::async_runtime::spawn_blocking(move || compat::check(&handle)).await {
+        if !check.compatible {
+            let message = compat::mismatch_message(&check);
+            log::error!("STARTUP ► {}", message);
+            correlation::emit(app_handle, "version-mismatch", check);
+            backend_state::set_failed(app_handle, &message);
+            splash::fail(app_handle, message);
+            return;
+        }
+    }
+    splash::set_status(app_handle, "Checking the backend's network binding…");
+    if let Err(e) = loopback_bind::check_after_startup(app_handle).await {
+        log::error!(
+            "STARTUP ► backend restarted on loopback did not come back: {}",
+            e
+        );
+        backend_state::set_failed(app_handle, &e);
+        splash::fail(app_handle, e);
+        return;
+    }
+    backend_state::set_ready(app_handle);
+    splash::finish(app_handle);
+
+    let handle = app_handle.clone();
+    let _ =
+        tauri::async_runtime::spawn_blocking(move || sidecar_fallback::mark_boot_ok(&handle)).await;
+    // Warm the configured endpoints, e.g. the dossier list (ignore errors)
+    prewarm::run_after_startup(app_handle).await;
+    // Optional heavier warmup (models/engines), configurable in settings
+    let handle = app_handle.clone();
+    let _ = tauri::async_runtime::spawn_blocking(move || warmup::run(&handle)).await;
+}
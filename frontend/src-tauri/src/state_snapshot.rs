@@ -0,0 +1,142 @@
+// One JSON document describing the shell's state, for support sessions.
+// `get_state_snapshot` gathers what would otherwise take half a dozen
+// commands: backend lifecycle and health, the startup readiness timeline,
+// process metrics, scheduled jobs, tracked tasks, maintenance mode and
+// pending updates, plus the settings with secret-looking values redacted.
+
+use crate::readiness::{self, ReadinessReport};
+use crate::{
+    backend_update, health, maintenance, portable, process_env, recovery, rollback, scheduler,
+    settings, supervisor, tasks, updates, BackendProcess,
+};
+use serde::Serialize;
+use serde_json::Value;
+use sysinfo::{Pid, ProcessRefreshKind, System};
+use tauri::Manager;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Lifecycle {
+    pub health: health::BackendHealth,
+    pub safe_mode: bool,
+    pub portable: bool,
+    pub maintenance: maintenance::MaintenanceStatus,
+    /// Crash restarts since the backend last ran stably.
+    pub supervisor_restarts: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessMetrics {
+    pub pid: u32,
+    pub memory_mb: u64,
+    pub virtual_memory_mb: u64,
+    pub run_time_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Metrics {
+    pub shell: Option<ProcessMetrics>,
+    pub backend: Option<ProcessMetrics>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingUpdates {
+    pub app: updates::UpdateStatus,
+    pub rollback: Option<rollback::RollbackOffer>,
+    pub backend_patch: Option<backend_update::InstalledPatch>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StateSnapshot {
+    pub created_at: String,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub lifecycle: Lifecycle,
+    /// Startup health attempts and prewarm results of this launch.
+    pub readiness: ReadinessReport,
+    pub metrics: Metrics,
+    pub scheduler: Vec<scheduler::ScheduledJob>,
+    pub tasks: Vec<tasks::BackendTask>,
+    pub updates: PendingUpdates,
+    pub settings: Value,
+}
+
+/// Replace string values under secret-looking keys, at any depth. Flags such
+/// as `restore_session_on_launch` keep their value.
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if process_env::is_sensitive(key) && v.is_string() {
+                    *v = Value::String(process_env::REDACTED.to_string());
+                } else {
+                    redact(v);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+fn process_metrics(sys: &mut System, pid: u32) -> Option<ProcessMetrics> {
+    let id = Pid::from_u32(pid);
+    sys.refresh_process_specifics(id, ProcessRefreshKind::new().with_memory());
+    sys.process(id).map(|p| ProcessMetrics {
+        pid,
+        memory_mb: p.memory() / 1024 / 1024,
+        virtual_memory_mb: p.virtual_memory() / 1024 / 1024,
+        run_time_secs: p.run_time(),
+    })
+}
+
+fn snapshot(app_handle: &tauri::AppHandle) -> Result<StateSnapshot, String> {
+    let backend_pid = app_handle
+        .state::<BackendProcess>()
+        .0
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|c| c.pid());
+    let mut sys = System::new();
+    let metrics = Metrics {
+        shell: process_metrics(&mut sys, std::process::id()),
+        backend: backend_pid.and_then(|pid| process_metrics(&mut sys, pid)),
+    };
+
+    let mut settings_json =
+        serde_json::to_value(settings::current(app_handle)).map_err(|e| e.to_string())?;
+    redact(&mut settings_json);
+
+    Ok(StateSnapshot {
+        created_at: chrono::Local::now().to_rfc3339(),
+        app_version: app_handle.package_info().version.to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        lifecycle: Lifecycle {
+            health: health::probe(app_handle),
+            safe_mode: recovery::safe_mode(app_handle),
+            portable: portable::is_portable(),
+            maintenance: maintenance::status(app_handle),
+            supervisor_restarts: supervisor::consecutive_restarts(app_handle),
+        },
+        readiness: readiness::report(app_handle),
+        metrics,
+        scheduler: scheduler::status(app_handle),
+        tasks: tasks::snapshot(app_handle),
+        updates: PendingUpdates {
+            app: updates::status(app_handle),
+            rollback: rollback::offer(app_handle),
+            backend_patch: backend_update::installed_patch(app_handle),
+        },
+        settings: settings_json,
+    })
+}
+
+/// Everything support usually asks for, in one document.
+#[tauri::command]
+pub async fn get_state_snapshot(app_handle: tauri::AppHandle) -> Result<StateSnapshot, String> {
+    tauri::async_runtime::spawn_blocking(move || snapshot(&app_handle))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,196 @@
+// Disk space checks and the storage report. Operations that write a lot
+// (backups, restores, update downloads) or that must not stop halfway (factory reset)
+// first check the free space on the volume they write to; below the
+// threshold they fail with `InsufficientSpace`, which is also emitted as
+// `insufficient-disk-space` so the frontend can explain it instead of
+// showing a bare error string. `get_storage_report` breaks down what the
+// app's folders use.
+
+use crate::{paths, portable, settings};
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use sysinfo::Disks;
+use tauri::Emitter;
+
+/// Free space a factory reset needs to recreate its folders and settings.
+pub const RESET_MIN_FREE: u64 = 50 * 1024 * 1024;
+/// Free space an app update needs for the installer and the unpacked files.
+pub const UPDATE_MIN_FREE: u64 = 500 * 1024 * 1024;
+/// Headroom kept on top of an operation's own estimate.
+pub const MARGIN: u64 = 200 * 1024 * 1024;
+
+/// Typed error for a volume without enough free space.
+#[derive(Debug, Clone, Serialize)]
+pub struct InsufficientSpace {
+    /// "factory_reset", "backup", "restore", "update_download", "backend_update".
+    pub operation: &'static str,
+    pub path: String,
+    pub mount_point: Option<String>,
+    pub available_bytes: u64,
+    pub required_bytes: u64,
+}
+
+impl fmt::Display for InsufficientSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "not enough disk space for {}: {} MB free on {}, {} MB needed",
+            self.operation.replace('_', " "),
+            self.available_bytes / 1024 / 1024,
+            self.mount_point.as_deref().unwrap_or(&self.path),
+            self.required_bytes / 1024 / 1024
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VolumeSpace {
+    pub mount_point: String,
+    pub available_bytes: u64,
+    pub total_bytes: u64,
+}
+
+/// The volume holding `path` (or its nearest existing parent).
+pub fn volume_of(path: &Path) -> Option<VolumeSpace> {
+    let existing = path
+        .ancestors()
+        .find(|p| paths::extended(p).exists())?
+        .to_path_buf();
+    let resolved = fs::canonicalize(&existing)
+        .map(|p| paths::plain(&p))
+        .unwrap_or(existing);
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|d| resolved.starts_with(d.mount_point()))
+        .max_by_key(|d| d.mount_point().as_os_str().len())
+        .map(|d| VolumeSpace {
+            mount_point: d.mount_point().to_string_lossy().into_owned(),
+            available_bytes: d.available_space(),
+            total_bytes: d.total_space(),
+        })
+}
+
+/// Check that the volume holding `path` has `required` bytes free. An
+/// unknown volume passes: better to try than to block on a failed probe.
+pub fn ensure_free_space(
+    operation: &'static str,
+    path: &Path,
+    required: u64,
+) -> Result<(), InsufficientSpace> {
+    let Some(volume) = volume_of(path) else {
+        log::debug!(
+            "STORAGE ► no volume found for {}; skipping space check",
+            path.display()
+        );
+        return Ok(());
+    };
+    if volume.available_bytes >= required {
+        return Ok(());
+    }
+    Err(InsufficientSpace {
+        operation,
+        path: path.to_string_lossy().into_owned(),
+        mount_point: Some(volume.mount_point),
+        available_bytes: volume.available_bytes,
+        required_bytes: required,
+    })
+}
+
+/// `ensure_free_space` for command paths: a shortfall is logged, emitted as
+/// `insufficient-disk-space` and returned as the command's error string.
+pub fn check(
+    app_handle: &tauri::AppHandle,
+    operation: &'static str,
+    path: &Path,
+    required: u64,
+) -> Result<(), String> {
+    ensure_free_space(operation, path, required).map_err(|e| {
+        log::warn!("STORAGE ► {}", e);
+        let _ = app_handle.emit("insufficient-disk-space", &e);
+        e.to_string()
+    })
+}
+
+/// Total size of the files below `path`. Unreadable entries are skipped.
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(paths::extended(path)) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    fs::read_dir(paths::extended(path))
+        .map(|entries| entries.flatten().map(|e| dir_size(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderEntry {
+    pub name: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderUsage {
+    pub path: String,
+    pub total_bytes: u64,
+    /// Top-level entries, largest first.
+    pub entries: Vec<FolderEntry>,
+    pub volume: Option<VolumeSpace>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageReport {
+    /// Shell state: settings, logs, retained binaries, update staging.
+    pub app_data: Option<FolderUsage>,
+    /// Backend user data (dossiers, images, databases).
+    pub backend_data: Option<FolderUsage>,
+    /// Regenerable caches (previews).
+    pub cache: Option<FolderUsage>,
+}
+
+fn usage(path: PathBuf) -> FolderUsage {
+    let mut entries: Vec<FolderEntry> = fs::read_dir(paths::extended(&path))
+        .map(|list| {
+            list.flatten()
+                .map(|e| FolderEntry {
+                    name: e.file_name().to_string_lossy().into_owned(),
+                    bytes: dir_size(&e.path()),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.bytes));
+    FolderUsage {
+        total_bytes: entries.iter().map(|e| e.bytes).sum(),
+        volume: volume_of(&path),
+        path: path.to_string_lossy().into_owned(),
+        entries,
+    }
+}
+
+fn report(app_handle: &tauri::AppHandle) -> StorageReport {
+    let app_data = portable::resolve(app_handle, "").ok();
+    let backend_data = settings::backend_data_dir(app_handle)
+        .ok()
+        // The default backend root may sit inside AppLocalData on some
+        // platforms; don't count it twice as its own section.
+        .filter(|dir| app_data.as_ref().map_or(true, |app| !dir.starts_with(app)));
+    StorageReport {
+        app_data: app_data.map(usage),
+        backend_data: backend_data.map(usage),
+        cache: portable::app_cache_dir(app_handle).ok().map(usage),
+    }
+}
+
+/// Disk usage of the app's folders, broken down by subfolder.
+#[tauri::command]
+pub async fn get_storage_report(app_handle: tauri::AppHandle) -> Result<StorageReport, String> {
+    tauri::async_runtime::spawn_blocking(move || report(&app_handle))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,215 @@
+// Crash supervisor. When the tracked backend exits without being asked to
+// (shutdown paths untrack the child before killing it), tell the webview via
+// `backend-crashed` and restart it with exponential backoff, up to a
+// configurable number of consecutive attempts. Exits during the boot window
+// of a sidecar are left to `sidecar_fallback`, which owns retry/downgrade
+// for binaries that never come up. A restarted backend counts as ready again
+// once it answers its health check (see `backend_state.rs`).
+
+use crate::{backend_state, correlation, maintenance, settings, startup, telemetry};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+
+/// A backend that stayed up this long counts as recovered; the next crash
+/// starts a fresh backoff sequence.
+const STABLE_AFTER: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SupervisorSettings {
+    pub enabled: bool,
+    /// Consecutive restarts before giving up.
+    pub max_restarts: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+}
+
+impl Default for SupervisorSettings {
+    fn default() -> Self {
+        SupervisorSettings {
+            enabled: true,
+            max_restarts: 5,
+            initial_backoff_ms: 1_000,
+            max_backoff_ms: 30_000,
+        }
+    }
+}
+
+#[derive(Default)]
+struct SupervisorState {
+    consecutive_restarts: u32,
+    last_restart: Option<Instant>,
+}
+
+pub struct CrashSupervisor(Mutex<SupervisorState>);
+
+impl CrashSupervisor {
+    pub fn new() -> Self {
+        CrashSupervisor(Mutex::new(SupervisorState::default()))
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct CrashedPayload {
+    pid: u32,
+    code: Option<i32>,
+    signal: Option<i32>,
+    /// Restart attempt about to be made, `None` if no restart is scheduled.
+    restart_attempt: Option<u32>,
+    max_restarts: u32,
+    retry_in_ms: Option<u64>,
+    /// Recovery is handled elsewhere (boot-failure fallback).
+    handled_by_fallback: bool,
+}
+
+fn backoff(cfg: &SupervisorSettings, attempt: u32) -> Duration {
+    let factor = 1u64 << attempt.saturating_sub(1).min(16);
+    Duration::from_millis(
+        cfg.initial_backoff_ms
+            .saturating_mul(factor)
+            .min(cfg.max_backoff_ms),
+    )
+}
+
+/// Forget previous crashes, e.g. after the user started the backend by hand.
+pub fn reset(app_handle: &tauri::AppHandle) {
+    let supervisor = app_handle.state::<CrashSupervisor>();
+    *supervisor.0.lock().unwrap() = SupervisorState::default();
+}
+
+/// Restarts made since the backend last ran stably.
+pub fn consecutive_restarts(app_handle: &tauri::AppHandle) -> u32 {
+    app_handle
+        .state::<CrashSupervisor>()
+        .0
+        .lock()
+        .unwrap()
+        .consecutive_restarts
+}
+
+/// Called for every unexpected exit of the tracked backend.
+pub fn on_backend_exit(
+    app_handle: &tauri::AppHandle,
+    pid: u32,
+    code: Option<i32>,
+    signal: Option<i32>,
+    handled_by_fallback: bool,
+) {
+    let cfg = settings::current(app_handle).backend_supervisor;
+    let in_maintenance = maintenance::active(app_handle);
+
+    let attempt = if handled_by_fallback || !cfg.enabled || in_maintenance {
+        None
+    } else {
+        let supervisor = app_handle.state::<CrashSupervisor>();
+        let mut state = supervisor.0.lock().unwrap();
+        if state
+            .last_restart
+            .map(|t| t.elapsed() >= STABLE_AFTER)
+            .unwrap_or(false)
+        {
+            state.consecutive_restarts = 0;
+        }
+        if state.consecutive_restarts < cfg.max_restarts {
+            state.consecutive_restarts += 1;
+            Some(state.consecutive_restarts)
+        } else {
+            None
+        }
+    };
+    let delay = attempt.map(|a| backoff(&cfg, a));
+    telemetry::record(
+        app_handle,
+        "backend_restart",
+        serde_json::json!({
+            "trigger": "crash",
+            "exit_code": code,
+            "attempt": attempt,
+        }),
+    );
+
+    match (attempt, delay) {
+        (Some(a), Some(d)) => log::warn!(
+            "SUPERVISOR ► backend pid {} crashed (code={:?}, signal={:?}); restart {}/{} in {}ms",
+            pid,
+            code,
+            signal,
+            a,
+            cfg.max_restarts,
+            d.as_millis()
+        ),
+        _ if in_maintenance => log::warn!(
+            "SUPERVISOR ► backend pid {} exited (code={:?}); not restarting during maintenance mode",
+            pid,
+            code
+        ),
+        _ if handled_by_fallback => log::warn!(
+            "SUPERVISOR ► backend pid {} exited during boot; recovery left to sidecar fallback",
+            pid
+        ),
+        _ => log::error!(
+            "SUPERVISOR ► backend pid {} crashed (code={:?}, signal={:?}); not restarting",
+            pid,
+            code,
+            signal
+        ),
+    }
+
+    correlation::emit(
+        app_handle,
+        "backend-crashed",
+        CrashedPayload {
+            pid,
+            code,
+            signal,
+            restart_attempt: attempt,
+            max_restarts: cfg.max_restarts,
+            retry_in_ms: delay.map(|d| d.as_millis() as u64),
+            handled_by_fallback,
+        },
+    );
+    if delay.is_some() {
+        backend_state::set_starting(app_handle);
+    } else if !handled_by_fallback {
+        backend_state::set_failed(
+            app_handle,
+            &format!(
+                "backend exited unexpectedly (code={:?}, signal={:?})",
+                code, signal
+            ),
+        );
+    }
+
+    if let Some(delay) = delay {
+        let handle = app_handle.clone();
+        let op = correlation::current();
+        thread::spawn(move || {
+            let _op = correlation::adopt(op);
+            thread::sleep(delay);
+            handle
+                .state::<CrashSupervisor>()
+                .0
+                .lock()
+                .unwrap()
+                .last_restart = Some(Instant::now());
+            match crate::spawn_backend(&handle) {
+                Ok(msg) => {
+                    log::info!("SUPERVISOR ► {}", msg);
+                    match tauri::async_runtime::block_on(startup::wait_until_healthy(&handle)) {
+                        Ok(()) => backend_state::set_ready(&handle),
+                        Err(e) => backend_state::set_failed(&handle, &e),
+                    }
+                }
+                Err(e) => {
+                    // Treat a failed spawn like another crash so backoff and
+                    // the retry limit still apply.
+                    log::error!("SUPERVISOR ► restart failed: {}", e);
+                    on_backend_exit(&handle, pid, None, None, false);
+                }
+            }
+        });
+    }
+}
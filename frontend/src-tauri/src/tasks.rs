@@ -0,0 +1,407 @@
+// Shell-side registry of long-running backend jobs. Jobs are picked up from
+// the start responses that pass through `backend_request` (and can be
+// registered directly by other shell code), then polled on their status
+// endpoints until they finish. Tray, taskbar progress, notifications and
+// sleep inhibition all read this one list instead of tracking jobs
+// themselves; every change is announced as `tasks-changed`, and each job's
+// progress as `task-progress`. The frontend can also start jobs through
+// `start_backend_task`, query one with `get_task_progress` and stop one with
+// `cancel_task`; the close guard lists running jobs so closing the
+// app can warn before they are cut off.
+
+use crate::{backend_http, notifications};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+const STATUS_TIMEOUT: Duration = Duration::from_secs(5);
+/// A task whose status endpoint keeps failing is dropped after this many
+/// consecutive errors (the backend restarted, or the job was forgotten).
+const MAX_POLL_FAILURES: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskKind {
+    ImageToText,
+    DossierBulkDelete,
+    PlssDownload,
+}
+
+impl TaskKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            TaskKind::ImageToText => "image_to_text",
+            TaskKind::DossierBulkDelete => "dossier_bulk_delete",
+            TaskKind::PlssDownload => "plss_download",
+        }
+    }
+
+    /// Kind of job a POST to `path` starts, if any.
+    fn for_start_path(path: &str) -> Option<TaskKind> {
+        let path = path.split('?').next().unwrap_or(path).trim_end_matches('/');
+        if path == "/api/image-to-text/jobs" {
+            Some(TaskKind::ImageToText)
+        } else if path == "/api/dossier-management/bulk/start" {
+            Some(TaskKind::DossierBulkDelete)
+        } else {
+            let rest = path.strip_prefix("/api/mapping/download-plss/")?;
+            let state = rest.strip_suffix("/start").unwrap_or(rest);
+            (!state.is_empty() && !state.contains('/')).then_some(TaskKind::PlssDownload)
+        }
+    }
+
+    fn status_path(self, backend_id: &str) -> String {
+        match self {
+            TaskKind::ImageToText => format!("/api/image-to-text/jobs/{}", backend_id),
+            TaskKind::DossierBulkDelete => {
+                format!("/api/dossier-management/bulk/status/{}", backend_id)
+            }
+            TaskKind::PlssDownload => format!("/api/mapping/download-plss/{}/status", backend_id),
+        }
+    }
+
+    fn cancel_path(self, backend_id: &str) -> Option<String> {
+        match self {
+            TaskKind::PlssDownload => {
+                Some(format!("/api/mapping/download-plss/{}/cancel", backend_id))
+            }
+            TaskKind::ImageToText | TaskKind::DossierBulkDelete => None,
+        }
+    }
+
+    /// Read a status response: `(finished, progress 0..=1 when known)`.
+    fn read_status(self, body: &Value) -> (bool, Option<f64>) {
+        match self {
+            TaskKind::ImageToText => {
+                let status = body.get("status").and_then(Value::as_str).unwrap_or("");
+                (matches!(status, "SUCCEEDED" | "FAILED" | "CANCELED"), None)
+            }
+            TaskKind::DossierBulkDelete => {
+                let total = body.get("total").and_then(Value::as_u64).unwrap_or(0);
+                let done = body.get("done").and_then(Value::as_u64).unwrap_or(0);
+                let progress = (total > 0).then(|| done as f64 / total as f64);
+                (done >= total, progress)
+            }
+            TaskKind::PlssDownload => {
+                let active = body
+                    .get("download_active")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                (!active, None)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendTask {
+    /// Registry ID: `<kind>:<backend id>`.
+    pub id: String,
+    pub kind: TaskKind,
+    /// The job's ID on the backend (a state code for PLSS downloads).
+    pub backend_id: String,
+    pub started_at: String,
+    pub progress: Option<f64>,
+    pub cancellable: bool,
+    pub cancelling: bool,
+    #[serde(skip)]
+    poll_failures: u32,
+}
+
+pub struct TaskRegistry(Mutex<HashMap<String, BackendTask>>);
+
+impl TaskRegistry {
+    pub fn new() -> Self {
+        TaskRegistry(Mutex::new(HashMap::new()))
+    }
+}
+
+pub fn snapshot(app_handle: &tauri::AppHandle) -> Vec<BackendTask> {
+    let registry = app_handle.state::<TaskRegistry>();
+    let mut tasks: Vec<BackendTask> = registry.0.lock().unwrap().values().cloned().collect();
+    tasks.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+    tasks
+}
+
+fn announce(app_handle: &tauri::AppHandle) {
+    let _ = app_handle.emit("tasks-changed", snapshot(app_handle));
+}
+
+/// Start tracking a backend job. Re-registering a known job is a no-op.
+pub fn register(app_handle: &tauri::AppHandle, kind: TaskKind, backend_id: &str) {
+    let id = format!("{}:{}", kind.as_str(), backend_id);
+    {
+        let registry = app_handle.state::<TaskRegistry>();
+        let mut tasks = registry.0.lock().unwrap();
+        if tasks.contains_key(&id) {
+            return;
+        }
+        log::info!("TASKS ► tracking {}", id);
+        tasks.insert(
+            id.clone(),
+            BackendTask {
+                id,
+                kind,
+                backend_id: backend_id.to_string(),
+                started_at: chrono::Local::now().to_rfc3339(),
+                progress: None,
+                cancellable: kind.cancel_path(backend_id).is_some(),
+                cancelling: false,
+                poll_failures: 0,
+            },
+        );
+    }
+    announce(app_handle);
+}
+
+/// Whether any backend job is still running.
+pub fn has_active(app_handle: &tauri::AppHandle) -> bool {
+    !app_handle
+        .state::<TaskRegistry>()
+        .0
+        .lock()
+        .unwrap()
+        .is_empty()
+}
+
+/// Pick up jobs from a successful response proxied by `backend_request`.
+pub fn observe_response(app_handle: &tauri::AppHandle, method: &str, path: &str, body: &Value) {
+    if !method.eq_ignore_ascii_case("POST") {
+        return;
+    }
+    let Some(kind) = TaskKind::for_start_path(path) else {
+        return;
+    };
+    match kind {
+        TaskKind::ImageToText => {
+            let ids = body.get("job_ids").and_then(Value::as_array);
+            for id in ids.into_iter().flatten().filter_map(Value::as_str) {
+                register(app_handle, kind, id);
+            }
+        }
+        TaskKind::DossierBulkDelete => {
+            if let Some(id) = body.get("job_id").and_then(Value::as_str) {
+                register(app_handle, kind, id);
+            }
+        }
+        TaskKind::PlssDownload => {
+            let path = path.split('?').next().unwrap_or(path).trim_end_matches('/');
+            let rest = path.trim_start_matches("/api/mapping/download-plss/");
+            register(
+                app_handle,
+                kind,
+                rest.strip_suffix("/start").unwrap_or(rest),
+            );
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TaskProgressPayload {
+    id: String,
+    kind: TaskKind,
+    progress: Option<f64>,
+    finished: bool,
+}
+
+fn emit_progress(app_handle: &tauri::AppHandle, task: &BackendTask, finished: bool) {
+    let _ = app_handle.emit(
+        "task-progress",
+        TaskProgressPayload {
+            id: task.id.clone(),
+            kind: task.kind,
+            progress: task.progress,
+            finished,
+        },
+    );
+}
+
+fn poll_once(app_handle: &tauri::AppHandle) {
+    let mut changed = false;
+    let mut finished_tasks = Vec::new();
+    for task in snapshot(app_handle) {
+        let result = backend_http::call_blocking(
+            app_handle,
+            reqwest::Method::GET,
+            &task.kind.status_path(&task.backend_id),
+            None,
+            STATUS_TIMEOUT,
+            None,
+        )
+        .and_then(|reply| {
+            if reply.status.is_success() {
+                Ok(reply.body)
+            } else {
+                Err(format!("status returned {}", reply.status))
+            }
+        });
+
+        let registry = app_handle.state::<TaskRegistry>();
+        let mut tasks = registry.0.lock().unwrap();
+        let Some(entry) = tasks.get_mut(&task.id) else {
+            continue;
+        };
+        match result {
+            Ok(body) => {
+                entry.poll_failures = 0;
+                let (finished, progress) = task.kind.read_status(&body);
+                if finished {
+                    log::info!("TASKS ► {} finished", task.id);
+                    tasks.remove(&task.id);
+                    changed = true;
+                    emit_progress(app_handle, &task, true);
+                    finished_tasks.push((task, body));
+                } else if progress.is_some() && entry.progress != progress {
+                    entry.progress = progress;
+                    changed = true;
+                    emit_progress(app_handle, entry, false);
+                }
+            }
+            Err(e) => {
+                entry.poll_failures += 1;
+                if entry.poll_failures >= MAX_POLL_FAILURES {
+                    log::warn!("TASKS ► dropping {}: status unavailable ({})", task.id, e);
+                    tasks.remove(&task.id);
+                    changed = true;
+                }
+            }
+        }
+    }
+    if changed {
+        announce(app_handle);
+    }
+    for (task, body) in finished_tasks {
+        notifications::task_finished(app_handle, &task, &body);
+    }
+}
+
+/// Poll tracked jobs until they finish.
+pub fn spawn_poller(app_handle: tauri::AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(POLL_INTERVAL);
+        if has_active(&app_handle) {
+            poll_once(&app_handle);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn list_active_tasks(app_handle: tauri::AppHandle) -> Result<Vec<BackendTask>, String> {
+    Ok(snapshot(&app_handle))
+}
+
+/// Start a backend job by POSTing `body` to its start endpoint (e.g.
+/// `/api/dossier-management/bulk/start`) and track it. Returns the tasks the
+/// response registered.
+#[tauri::command]
+pub async fn start_backend_task(
+    app_handle: tauri::AppHandle,
+    path: String,
+    body: Option<Value>,
+) -> Result<Vec<BackendTask>, String> {
+    if TaskKind::for_start_path(&path).is_none() {
+        return Err(format!("{} does not start a known backend job", path));
+    }
+    let before: Vec<String> = snapshot(&app_handle).into_iter().map(|t| t.id).collect();
+    let reply = backend_http::call(
+        &app_handle,
+        reqwest::Method::POST,
+        &path,
+        body.as_ref(),
+        Duration::from_secs(30),
+        Some("start_backend_task"),
+    )
+    .await?;
+    if !reply.status.is_success() {
+        return Err(format!("{} returned {}", path, reply.status));
+    }
+    observe_response(&app_handle, "POST", &path, &reply.body);
+    Ok(snapshot(&app_handle)
+        .into_iter()
+        .filter(|t| !before.contains(&t.id))
+        .collect())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskProgress {
+    pub task: BackendTask,
+    /// The job's status as the backend reports it right now.
+    pub status: Value,
+}
+
+/// Current state of a tracked job, read from its status endpoint now
+/// rather than waiting for the next poll.
+#[tauri::command]
+pub async fn get_task_progress(
+    app_handle: tauri::AppHandle,
+    id: String,
+) -> Result<TaskProgress, String> {
+    let task = snapshot(&app_handle)
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| format!("no active task {}", id))?;
+    let reply = backend_http::call(
+        &app_handle,
+        reqwest::Method::GET,
+        &task.kind.status_path(&task.backend_id),
+        None,
+        STATUS_TIMEOUT,
+        None,
+    )
+    .await?;
+    if !reply.status.is_success() {
+        return Err(format!("status returned {}", reply.status));
+    }
+    let (_, progress) = task.kind.read_status(&reply.body);
+    let mut task = task;
+    task.progress = progress.or(task.progress);
+    Ok(TaskProgress {
+        task,
+        status: reply.body,
+    })
+}
+
+/// Ask the backend to cancel a tracked job. The task stays listed (marked
+/// `cancelling`) until its status endpoint reports it stopped.
+#[tauri::command]
+pub async fn cancel_task(app_handle: tauri::AppHandle, id: String) -> Result<(), String> {
+    let task = snapshot(&app_handle)
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| format!("no active task {}", id))?;
+    let path = task
+        .kind
+        .cancel_path(&task.backend_id)
+        .ok_or_else(|| format!("{} cannot be cancelled", id))?;
+
+    let reply = backend_http::call(
+        &app_handle,
+        reqwest::Method::POST,
+        &path,
+        None,
+        Duration::from_secs(10),
+        Some("cancel_task"),
+    )
+    .await?;
+    if !reply.status.is_success() {
+        return Err(format!("cancel returned {}", reply.status));
+    }
+
+    log::info!("TASKS ► cancel requested for {}", id);
+    if let Some(task) = app_handle
+        .state::<TaskRegistry>()
+        .0
+        .lock()
+        .unwrap()
+        .get_mut(&id)
+    {
+        task.cancelling = true;
+    }
+    announce(&app_handle);
+    Ok(())
+}
@@ -0,0 +1,242 @@
+// Opt-in, anonymous telemetry. Off by default; nothing is recorded until the
+// user enables it. Events carry a name, a timestamp, the app version, the OS
+// and a few numbers or short labels (time to a ready backend, why the
+// backend restarted, how an update ended) and never paths, dossier content
+// or identifiers.
+//
+// Events go to a queue persisted under AppLocalData, so nothing is lost to a
+// restart or a stretch offline. A background thread posts the queue in
+// batches to the configured collector and drops what was accepted; a failed
+// post (offline, collector down) leaves the queue for the next round.
+// Without a collector endpoint events simply accumulate, capped at
+// `MAX_QUEUED`. Turning telemetry off clears the queue.
+
+use crate::{backend_http, portable, proxy, settings};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::Manager;
+
+const QUEUE_FILE: &str = "telemetry_queue.json";
+/// Oldest events are dropped beyond this many.
+const MAX_QUEUED: usize = 1_000;
+const BATCH_SIZE: usize = 100;
+const FIRST_FLUSH_AFTER: Duration = Duration::from_secs(60);
+const FLUSH_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetrySettings {
+    pub enabled: bool,
+    /// Collector URL the queue is posted to; `None` keeps events local.
+    pub endpoint: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryEvent {
+    pub name: String,
+    pub at: String,
+    pub app_version: String,
+    pub os: String,
+    pub props: Value,
+}
+
+#[derive(Default)]
+struct QueueInner {
+    /// `None` until the persisted queue has been read.
+    events: Option<Vec<TelemetryEvent>>,
+    last_flush: Option<String>,
+    last_error: Option<String>,
+}
+
+pub struct TelemetryQueue(Mutex<QueueInner>);
+
+impl TelemetryQueue {
+    pub fn new() -> Self {
+        TelemetryQueue(Mutex::new(QueueInner::default()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TelemetryStatus {
+    pub enabled: bool,
+    pub endpoint_configured: bool,
+    pub queued: usize,
+    pub last_flush: Option<String>,
+    pub last_error: Option<String>,
+}
+
+fn queue_path(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    portable::resolve(app_handle, QUEUE_FILE).ok()
+}
+
+fn events<'a>(
+    app_handle: &tauri::AppHandle,
+    inner: &'a mut QueueInner,
+) -> &'a mut Vec<TelemetryEvent> {
+    inner.events.get_or_insert_with(|| {
+        queue_path(app_handle)
+            .and_then(|p| fs::read_to_string(p).ok())
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    })
+}
+
+fn persist(app_handle: &tauri::AppHandle, events: &[TelemetryEvent]) {
+    let Some(path) = queue_path(app_handle) else {
+        return;
+    };
+    if events.is_empty() {
+        let _ = fs::remove_file(&path);
+        return;
+    }
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(raw) = serde_json::to_string(events) {
+        if let Err(e) = fs::write(&path, raw) {
+            log::warn!("TELEMETRY ► could not persist queue at {:?}: {}", path, e);
+        }
+    }
+}
+
+/// Queue an event. A no-op unless the user opted in.
+pub fn record(app_handle: &tauri::AppHandle, name: &str, props: Value) {
+    if !settings::current(app_handle).telemetry.enabled {
+        return;
+    }
+    let Some(queue) = app_handle.try_state::<TelemetryQueue>() else {
+        return;
+    };
+    let mut inner = queue.0.lock().unwrap();
+    let events = events(app_handle, &mut inner);
+    events.push(TelemetryEvent {
+        name: name.to_string(),
+        at: chrono::Utc::now().to_rfc3339(),
+        app_version: app_handle.package_info().version.to_string(),
+        os: std::env::consts::OS.to_string(),
+        props,
+    });
+    let excess = events.len().saturating_sub(MAX_QUEUED);
+    events.drain(..excess);
+    persist(app_handle, events);
+}
+
+fn post(
+    app_handle: &tauri::AppHandle,
+    endpoint: &str,
+    batch: &[TelemetryEvent],
+) -> Result<(), String> {
+    let timeout = Duration::from_millis(15_000);
+    let req = proxy::client_builder(app_handle, endpoint)
+        .connect_timeout(Duration::from_millis(5_000))
+        .timeout(timeout)
+        .build()
+        .map_err(|e| e.to_string())?
+        .post(endpoint)
+        .json(&serde_json::json!({ "events": batch }));
+    backend_http::block_on(timeout, async move {
+        req.send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    })
+}
+
+/// Post queued events in batches until the queue is empty or a post fails.
+/// Blocking.
+fn flush(app_handle: &tauri::AppHandle) {
+    let cfg = settings::current(app_handle).telemetry;
+    let Some(endpoint) = cfg.endpoint.filter(|_| cfg.enabled) else {
+        return;
+    };
+    let queue = app_handle.state::<TelemetryQueue>();
+    loop {
+        // Send without holding the lock so `record` never waits on the network.
+        let batch: Vec<TelemetryEvent> = {
+            let mut inner = queue.0.lock().unwrap();
+            let events = events(app_handle, &mut inner);
+            events.iter().take(BATCH_SIZE).cloned().collect()
+        };
+        if batch.is_empty() {
+            return;
+        }
+        let result = post(app_handle, &endpoint, &batch);
+        let mut inner = queue.0.lock().unwrap();
+        match result {
+            Ok(()) => {
+                let events = events(app_handle, &mut inner);
+                // Events trimmed by the cap in the meantime are already gone.
+                let sent = batch.len().min(events.len());
+                events.drain(..sent);
+                persist(app_handle, events);
+                inner.last_flush = Some(chrono::Local::now().to_rfc3339());
+                inner.last_error = None;
+                log::debug!("TELEMETRY ► sent {} event(s)", sent);
+            }
+            Err(e) => {
+                log::debug!("TELEMETRY ► flush deferred: {}", e);
+                inner.last_error = Some(e);
+                return;
+            }
+        }
+    }
+}
+
+/// Start the periodic flush on a dedicated thread.
+pub fn spawn_flusher(app_handle: tauri::AppHandle) {
+    thread::spawn(move || {
+        thread::sleep(FIRST_FLUSH_AFTER);
+        loop {
+            flush(&app_handle);
+            thread::sleep(FLUSH_INTERVAL);
+        }
+    });
+}
+
+fn status(app_handle: &tauri::AppHandle) -> TelemetryStatus {
+    let cfg = settings::current(app_handle).telemetry;
+    let queue = app_handle.state::<TelemetryQueue>();
+    let mut inner = queue.0.lock().unwrap();
+    let queued = events(app_handle, &mut inner).len();
+    TelemetryStatus {
+        enabled: cfg.enabled,
+        endpoint_configured: cfg.endpoint.is_some(),
+        queued,
+        last_flush: inner.last_flush.clone(),
+        last_error: inner.last_error.clone(),
+    }
+}
+
+/// Opt in or out. Opting out discards everything still queued.
+#[tauri::command]
+pub async fn set_telemetry_enabled(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+) -> Result<TelemetryStatus, String> {
+    settings::update(&app_handle, |s| s.telemetry.enabled = enabled)?;
+    if !enabled {
+        let queue = app_handle.state::<TelemetryQueue>();
+        queue.0.lock().unwrap().events = Some(Vec::new());
+        persist(&app_handle, &[]);
+    }
+    log::info!(
+        "TELEMETRY ► {}",
+        if enabled {
+            "enabled"
+        } else {
+            "disabled; queue cleared"
+        }
+    );
+    Ok(status(&app_handle))
+}
+
+#[tauri::command]
+pub async fn get_telemetry_status(app_handle: tauri::AppHandle) -> Result<TelemetryStatus, String> {
+    Ok(status(&app_handle))
+}
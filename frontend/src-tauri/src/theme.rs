@@ -0,0 +1,127 @@
+// Light/dark theme. The OS theme is read from the main window at startup
+// and followed through the windows' `ThemeChanged` events, which the
+// frontend receives as `theme-changed`. The `theme` setting can pin the app
+// to light or dark; the choice is applied to the native surfaces too (title
+// bar and menu on Windows and macOS; Linux follows the GTK theme).
+//
+// While a theme is pinned the windows report the pinned theme, so the OS
+// theme shown is the last one seen while following the system.
+
+use crate::settings;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{Emitter, Manager, Theme};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemePreference {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeName {
+    Light,
+    Dark,
+}
+
+impl From<Theme> for ThemeName {
+    fn from(theme: Theme) -> Self {
+        match theme {
+            Theme::Dark => ThemeName::Dark,
+            _ => ThemeName::Light,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ThemeInfo {
+    pub system: ThemeName,
+    pub preference: ThemePreference,
+    /// What the app should render: the preference, or the OS theme.
+    pub effective: ThemeName,
+}
+
+/// Last OS theme seen.
+pub struct SystemTheme(Mutex<ThemeName>);
+
+impl SystemTheme {
+    pub fn new() -> Self {
+        SystemTheme(Mutex::new(ThemeName::Light))
+    }
+}
+
+fn info(app_handle: &tauri::AppHandle) -> ThemeInfo {
+    let system = *app_handle.state::<SystemTheme>().0.lock().unwrap();
+    let preference = settings::current(app_handle).theme;
+    let effective = match preference {
+        ThemePreference::System => system,
+        ThemePreference::Light => ThemeName::Light,
+        ThemePreference::Dark => ThemeName::Dark,
+    };
+    ThemeInfo {
+        system,
+        preference,
+        effective,
+    }
+}
+
+/// Read the OS theme and apply the preference to native surfaces. Called
+/// from setup and whenever the `theme` setting changes.
+pub fn apply(app_handle: &tauri::AppHandle) {
+    let preference = settings::current(app_handle).theme;
+    if preference == ThemePreference::System {
+        // Unpin first so the window reports the OS theme again.
+        app_handle.set_theme(None);
+        if let Some(theme) = app_handle
+            .get_webview_window("main")
+            .and_then(|w| w.theme().ok())
+        {
+            *app_handle.state::<SystemTheme>().0.lock().unwrap() = theme.into();
+        }
+    } else {
+        app_handle.set_theme(Some(match preference {
+            ThemePreference::Dark => Theme::Dark,
+            _ => Theme::Light,
+        }));
+    }
+    let _ = app_handle.emit("theme-changed", info(app_handle));
+}
+
+/// `WindowEvent::ThemeChanged`. Every window reports the change; only the
+/// first report of a new OS theme is announced.
+pub fn on_theme_changed(app_handle: &tauri::AppHandle, theme: Theme) {
+    if settings::current(app_handle).theme != ThemePreference::System {
+        return;
+    }
+    {
+        let state = app_handle.state::<SystemTheme>();
+        let mut current = state.0.lock().unwrap();
+        let next = ThemeName::from(theme);
+        if *current == next {
+            return;
+        }
+        *current = next;
+    }
+    log::info!("THEME ► OS theme is now {:?}", ThemeName::from(theme));
+    let _ = app_handle.emit("theme-changed", info(app_handle));
+}
+
+#[tauri::command]
+pub async fn get_system_theme(app_handle: tauri::AppHandle) -> Result<ThemeInfo, String> {
+    Ok(info(&app_handle))
+}
+
+/// Follow the OS theme or pin light/dark, native surfaces included.
+#[tauri::command]
+pub async fn set_theme_preference(
+    app_handle: tauri::AppHandle,
+    preference: ThemePreference,
+) -> Result<ThemeInfo, String> {
+    // Saving triggers `apply` through the settings change hook.
+    settings::update(&app_handle, |s| s.theme = preference)?;
+    Ok(info(&app_handle))
+}
@@ -0,0 +1,208 @@
+// Extra trusted root certificates for outbound HTTPS. Networks that inspect
+// TLS re-sign traffic with their own root CA, which the bundled web roots
+// don't know; the `ca_bundle` setting points at a PEM file whose
+// certificates are trusted in addition to them. The bundle applies to the
+// clients built by `proxy::client_builder`: the backend update feed and
+// download, `debug_updater_endpoint`, release notes, telemetry and
+// connectivity probes. The app updater plugin builds its own client and
+// keeps using the OS trust store.
+//
+// `validate_ca_bundle` reads a bundle without saving it and reports each
+// certificate (subject, issuer, validity, fingerprint), whether its issuer
+// is in the bundle too, and optionally whether the update endpoint can be
+// reached with it.
+
+use crate::{backend_http, connectivity, proxy, settings};
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::CertificateDer;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: String,
+    pub not_after: String,
+    /// Currently inside its validity period.
+    pub valid_now: bool,
+    pub is_ca: bool,
+    pub self_signed: bool,
+    /// Whether the issuing certificate is in the same bundle (always true
+    /// for self-signed roots).
+    pub issuer_in_bundle: bool,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CaBundleReport {
+    pub path: String,
+    pub certificates: Vec<CertificateInfo>,
+    /// Problems that make the bundle unusable or suspicious.
+    pub warnings: Vec<String>,
+    /// Set when a connection test ran: the URL tried and its outcome.
+    pub tested_url: Option<String>,
+    pub test_error: Option<String>,
+}
+
+/// Every certificate in the PEM file at `path`.
+fn load(path: &Path) -> Result<Vec<CertificateDer<'static>>, String> {
+    let certs: Vec<CertificateDer<'static>> = CertificateDer::pem_file_iter(path)
+        .map_err(|e| format!("cannot read {}: {}", path.display(), e))?
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("{} is not a valid PEM bundle: {}", path.display(), e))?;
+    if certs.is_empty() {
+        return Err(format!("{} contains no certificates", path.display()));
+    }
+    Ok(certs)
+}
+
+fn client_config(extra: Vec<CertificateDer<'static>>) -> Result<rustls::ClientConfig, String> {
+    let mut roots = rustls::RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    for cert in extra {
+        roots
+            .add(cert)
+            .map_err(|e| format!("certificate rejected: {}", e))?;
+    }
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    Ok(rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| e.to_string())?
+        .with_root_certificates(roots)
+        .with_no_client_auth())
+}
+
+/// TLS config with the `ca_bundle` roots, or `None` to keep reqwest's
+/// defaults. A broken bundle is logged and ignored rather than cutting the
+/// app off from the network.
+pub fn tls_config(app_handle: &tauri::AppHandle) -> Option<rustls::ClientConfig> {
+    let path = settings::current(app_handle).ca_bundle?;
+    match load(Path::new(&path)).and_then(client_config) {
+        Ok(config) => Some(config),
+        Err(e) => {
+            log::warn!("TLS_TRUST ► ignoring ca_bundle: {}", e);
+            None
+        }
+    }
+}
+
+/// Rejects a `ca_bundle` setting that can't be loaded.
+pub fn validate(path: Option<&str>) -> Result<(), String> {
+    match path {
+        Some(path) => load(Path::new(path)).and_then(client_config).map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+fn describe(certs: &[CertificateDer<'static>]) -> (Vec<CertificateInfo>, Vec<String>) {
+    let mut parsed = Vec::new();
+    let mut warnings = Vec::new();
+    for (i, der) in certs.iter().enumerate() {
+        match x509_parser::parse_x509_certificate(der.as_ref()) {
+            Ok((_, cert)) => parsed.push(cert),
+            Err(e) => warnings.push(format!("certificate {} could not be parsed: {}", i + 1, e)),
+        }
+    }
+    let subjects: Vec<String> = parsed.iter().map(|c| c.subject().to_string()).collect();
+    let infos: Vec<CertificateInfo> = parsed
+        .iter()
+        .zip(certs)
+        .map(|(cert, der)| {
+            let subject = cert.subject().to_string();
+            let issuer = cert.issuer().to_string();
+            let self_signed = subject == issuer;
+            let validity = cert.validity();
+            CertificateInfo {
+                not_before: validity.not_before.to_rfc2822().unwrap_or_default(),
+                not_after: validity.not_after.to_rfc2822().unwrap_or_default(),
+                valid_now: validity.is_valid(),
+                is_ca: cert.is_ca(),
+                self_signed,
+                issuer_in_bundle: self_signed || subjects.contains(&issuer),
+                sha256: Sha256::digest(der.as_ref())
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect(),
+                subject,
+                issuer,
+            }
+        })
+        .collect();
+    for info in &infos {
+        if !info.valid_now {
+            warnings.push(format!("'{}' is outside its validity period", info.subject));
+        }
+        if !info.is_ca {
+            warnings.push(format!(
+                "'{}' is not a CA certificate and can't anchor a chain",
+                info.subject
+            ));
+        }
+    }
+    if !infos.iter().any(|i| i.self_signed) {
+        warnings.push("the bundle has no self-signed root".to_string());
+    }
+    (infos, warnings)
+}
+
+/// Inspect a PEM bundle before saving it as `ca_bundle`. With `test_url`
+/// (or `test: true` for the update endpoint) a request is made through the
+/// configured proxy with the bundle's roots trusted.
+#[tauri::command]
+pub async fn validate_ca_bundle(
+    app_handle: tauri::AppHandle,
+    path: String,
+    test: Option<bool>,
+    test_url: Option<String>,
+) -> Result<CaBundleReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let certs = load(Path::new(path.trim()))?;
+        let (certificates, warnings) = describe(&certs);
+        let tested_url = test_url.or_else(|| {
+            test.unwrap_or(false)
+                .then(|| {
+                    connectivity::updater_endpoints(&app_handle)
+                        .into_iter()
+                        .next()
+                })
+                .flatten()
+        });
+        let test_error = match &tested_url {
+            Some(url) => {
+                let timeout = Duration::from_millis(10_000);
+                let req = proxy::client_builder(&app_handle, url)
+                    .use_preconfigured_tls(client_config(certs)?)
+                    .connect_timeout(Duration::from_millis(5_000))
+                    .timeout(timeout)
+                    .build()
+                    .map_err(|e| e.to_string())?
+                    .head(url);
+                // Any HTTP status means the handshake went through.
+                backend_http::block_on(timeout, async move {
+                    req.send().await.map(|_| ()).map_err(|e| e.to_string())
+                })
+                .err()
+            }
+            None => None,
+        };
+        log::info!(
+            "TLS_TRUST ► checked {}: {} certificate(s), {} warning(s)",
+            path,
+            certificates.len(),
+            warnings.len()
+        );
+        Ok(CaBundleReport {
+            path,
+            certificates,
+            warnings,
+            tested_url,
+            test_error,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,121 @@
+// Correlation IDs for shell → backend requests. Every request the shell
+// forwards carries an `X-Correlation-ID` header; the shell logs the ID with
+// the call and the backend tags its own log lines with it, so
+// `find_trace(id)` can pull one action's story out of both logs. Calls made
+// inside a lifecycle operation send that operation's ID (`correlation.rs`).
+
+use crate::portable;
+use serde::Serialize;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const HEADER: &str = "X-Correlation-ID";
+/// Environment variable the backend reads for its log directory.
+pub const BACKEND_LOG_DIR_ENV: &str = "LOG_DIR";
+
+const MAX_MATCHES: usize = 1_000;
+
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A short ID unique within this machine: time, process and a counter.
+pub fn new_id() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    let seq = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!(
+        "{:08x}{:04x}{:04x}",
+        (nanos >> 16) as u32,
+        std::process::id() as u16,
+        seq as u16
+    )
+}
+
+/// Where the sidecar backend writes its rotating log.
+pub fn backend_log_dir(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    portable::resolve(app_handle, "logs/backend").ok()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceMatch {
+    /// "shell" or "backend".
+    pub source: &'static str,
+    pub file: String,
+    pub line_number: usize,
+    pub line: String,
+}
+
+fn scan_dir(dir: &Path, source: &'static str, id: &str, out: &mut Vec<TraceMatch>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.is_file()
+                && p.file_name()
+                    .map(|n| n.to_string_lossy().contains(".log"))
+                    .unwrap_or(false)
+        })
+        .collect();
+    files.sort();
+
+    for path in files {
+        let Ok(file) = fs::File::open(&path) else {
+            continue;
+        };
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let Ok(line) = line else {
+                break;
+            };
+            if line.contains(id) {
+                out.push(TraceMatch {
+                    source,
+                    file: path.to_string_lossy().into_owned(),
+                    line_number: i + 1,
+                    line,
+                });
+                if out.len() >= MAX_MATCHES {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// All log lines, shell and backend, mentioning correlation ID `id`.
+pub fn find(app_handle: &tauri::AppHandle, id: &str) -> Vec<TraceMatch> {
+    let mut out = Vec::new();
+    if let Ok(dir) = portable::app_log_dir(app_handle) {
+        scan_dir(&dir, "shell", id, &mut out);
+    }
+    if let Some(dir) = backend_log_dir(app_handle) {
+        scan_dir(&dir, "backend", id, &mut out);
+    }
+    if let Some(dir) = crate::backend_log::log_dir(app_handle) {
+        scan_dir(&dir, "backend", id, &mut out);
+    }
+    // Dev runs of the Python backend keep the default log location.
+    scan_dir(Path::new("../../backend/logs"), "backend", id, &mut out);
+    out.truncate(MAX_MATCHES);
+    out
+}
+
+#[tauri::command]
+pub async fn find_trace(
+    app_handle: tauri::AppHandle,
+    id: String,
+) -> Result<Vec<TraceMatch>, String> {
+    let id = id.trim().to_string();
+    if id.len() < 8 {
+        return Err("correlation id too short".to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || find(&app_handle, &id))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,128 @@
+// System tray icon with quick window and backend controls, and a Run
+// Pipeline submenu listing the enabled pipelines (`pipelines.rs`). Backend restarts
+// go through `backend_lifecycle::restart_backend` like every other restart
+// path; "Check for Updates" hands off to the main window as the same
+// `menu-action` the application menu sends, since the frontend owns the
+// updater dialog flow. Quit goes through the close guard like the menu's.
+
+use crate::menu::MenuAction;
+use crate::{backend_lifecycle, close_guard, i18n, pipelines, settings};
+use std::thread;
+use tauri::menu::{Menu, MenuBuilder, MenuEvent, MenuItemBuilder, SubmenuBuilder};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use tauri::{App, AppHandle, Emitter, Manager, Wry};
+
+pub const TRAY_ID: &str = "main";
+const TOGGLE_WINDOW_ID: &str = "tray_toggle_window";
+const RESTART_BACKEND_ID: &str = "tray_restart_backend";
+const CHECK_UPDATES_ID: &str = "tray_check_updates";
+const QUIT_ID: &str = "tray_quit";
+const RUN_PIPELINE_PREFIX: &str = "tray_run_pipeline:";
+
+fn build_menu(app_handle: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let item = |id: &str, key: &str| {
+        MenuItemBuilder::with_id(id, i18n::t(app_handle, key)).build(app_handle)
+    };
+    let pipelines: Vec<_> = settings::current(app_handle)
+        .pipelines
+        .into_iter()
+        .filter(|p| p.enabled)
+        .collect();
+    let label = i18n::t(app_handle, "tray.run_pipeline");
+    let mut run_pipeline = SubmenuBuilder::new(app_handle, label).enabled(!pipelines.is_empty());
+    for pipeline in &pipelines {
+        let id = format!("{}{}", RUN_PIPELINE_PREFIX, pipeline.name);
+        run_pipeline =
+            run_pipeline.item(&MenuItemBuilder::with_id(id, &pipeline.name).build(app_handle)?);
+    }
+    MenuBuilder::new(app_handle)
+        .item(&item(TOGGLE_WINDOW_ID, "tray.toggle_window")?)
+        .item(&item(RESTART_BACKEND_ID, "tray.restart_backend")?)
+        .item(&item(CHECK_UPDATES_ID, "tray.check_updates")?)
+        .item(&run_pipeline.build()?)
+        .separator()
+        .item(&item(QUIT_ID, "tray.quit")?)
+        .build()
+}
+
+/// Build the tray icon and its menu.
+pub fn install(app: &App) -> tauri::Result<()> {
+    let menu = build_menu(app.handle())?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Plattera")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(on_menu_event)
+        .on_tray_icon_event(on_tray_icon_event);
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
+
+/// Rebuild the tray menu, e.g. after the locale or the pipelines changed.
+pub fn refresh(app_handle: &AppHandle) -> tauri::Result<()> {
+    if let Some(tray) = app_handle.tray_by_id(TRAY_ID) {
+        tray.set_menu(Some(build_menu(app_handle)?))?;
+    }
+    Ok(())
+}
+
+fn show_main_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn toggle_main_window(app_handle: &AppHandle) {
+    let Some(window) = app_handle.get_webview_window("main") else {
+        return;
+    };
+    if window.is_visible().unwrap_or(false) {
+        let _ = window.hide();
+    } else {
+        show_main_window(app_handle);
+    }
+}
+
+fn on_tray_icon_event(tray: &TrayIcon, event: TrayIconEvent) {
+    if let TrayIconEvent::Click {
+        button: MouseButton::Left,
+        button_state: MouseButtonState::Up,
+        ..
+    } = event
+    {
+        toggle_main_window(tray.app_handle());
+    }
+}
+
+fn on_menu_event(app_handle: &AppHandle, event: MenuEvent) {
+    if let Some(name) = event.id().as_ref().strip_prefix(RUN_PIPELINE_PREFIX) {
+        log::info!("TRAY ► running pipeline '{}'", name);
+        pipelines::run_in_background(app_handle, name);
+        return;
+    }
+    match event.id().as_ref() {
+        TOGGLE_WINDOW_ID => toggle_main_window(app_handle),
+        RESTART_BACKEND_ID => {
+            let handle = app_handle.clone();
+            thread::spawn(move || match backend_lifecycle::restart_backend(&handle) {
+                Ok(msg) => log::info!("TRAY ► {}", msg),
+                Err(e) => log::error!("TRAY ► backend restart failed: {}", e),
+            });
+        }
+        CHECK_UPDATES_ID => {
+            show_main_window(app_handle);
+            let _ = app_handle.emit_to("main", "menu-action", MenuAction::CheckForUpdates);
+        }
+        QUIT_ID => {
+            log::info!("TRAY ► quit requested");
+            close_guard::quit(app_handle);
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,44 @@
+// Unix counterpart of windows_job.rs: keep the backend from outliving the
+// app on Linux and macOS. The shell plugin offers no hook between fork and
+// exec, so the backend sets itself up once it knows our pid (see
+// backend/utils/parent_watch.py): it becomes its own process-group leader,
+// sets PR_SET_PDEATHSIG on Linux and watches this process with pidfd
+// (Linux) or kqueue NOTE_EXIT (macOS), stopping when we go away. On Windows
+// these helpers are no-ops; the Job Object covers it.
+
+/// Environment variable telling the backend which process to watch.
+pub const PARENT_PID_ENV: &str = "PLATTERA_PARENT_PID";
+
+#[cfg(unix)]
+mod imp {
+    use super::PARENT_PID_ENV;
+
+    /// Environment the backend needs to supervise itself.
+    pub fn child_env() -> Vec<(&'static str, String)> {
+        vec![(PARENT_PID_ENV, std::process::id().to_string())]
+    }
+
+    /// SIGKILL the process group led by `pid` (the backend and any workers
+    /// it started). Best effort; returns whether the signal was delivered.
+    pub fn kill_group(pid: u32) -> bool {
+        std::process::Command::new("kill")
+            .args(["-KILL", "--", &format!("-{}", pid)])
+            .stderr(std::process::Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    pub fn child_env() -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    pub fn kill_group(_pid: u32) -> bool {
+        false
+    }
+}
+
+pub use imp::{child_env, kill_group};
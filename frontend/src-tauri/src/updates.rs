@@ -0,0 +1,414 @@
+// Background update downloads. The update package is fetched on the async
+// runtime while the app stays usable; progress goes out as
+// `update-download-progress` (bytes, percent, ETA). The updater plugin
+// verifies the package signature before handing the bytes back, and only
+// then is `update-ready` emitted so the frontend can prompt. Installing
+// stages the running version for rollback, stops the backend through
+// `shutdown_backend_for_update` and runs the installer.
+//
+// A ready update can also be left for exit: with `install_updates_on_exit`
+// the package is flagged when the download completes, and the window close
+// and Ctrl+C paths call `install_on_exit` instead of the normal backend
+// shutdown, so the user is never interrupted mid-session.
+//
+// The `update_channel` setting filters what is offered, for app and
+// backend-only updates alike: the stable channel skips pre-releases.
+
+use crate::backend_lifecycle::shutdown_backend_for_update;
+use crate::{connectivity, correlation, power, proxy, rollback, settings, storage, telemetry};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+use tauri_plugin_updater::Update;
+
+/// Minimum spacing between progress events.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+impl UpdateChannel {
+    /// Stable takes releases only; beta takes pre-releases too.
+    pub fn allows(self, remote: &semver::Version) -> bool {
+        remote.pre.is_empty() || self == UpdateChannel::Beta
+    }
+}
+
+/// Whether the configured channel takes `remote`.
+pub fn channel_allows(app_handle: &tauri::AppHandle, remote: &semver::Version) -> bool {
+    if settings::current(app_handle).update_channel.allows(remote) {
+        return true;
+    }
+    log::info!(
+        "UPDATES ► pre-release {} skipped on the stable channel",
+        remote
+    );
+    false
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadPhase {
+    Idle,
+    Downloading,
+    Ready,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateStatus {
+    pub phase: DownloadPhase,
+    pub version: Option<String>,
+    pub notes: Option<String>,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+    pub error: Option<String>,
+    /// Whether the ready update installs when the app exits.
+    pub install_on_exit: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DownloadProgress {
+    version: String,
+    downloaded: u64,
+    total: Option<u64>,
+    percent: Option<u8>,
+    eta_secs: Option<u64>,
+}
+
+struct UpdateInner {
+    status: UpdateStatus,
+    /// Verified package waiting to be installed.
+    ready: Option<(Update, Vec<u8>)>,
+}
+
+pub struct UpdateManager {
+    inner: Mutex<UpdateInner>,
+    /// Set once an install has started (on exit or on request), so the
+    /// package is only handed to the installer once.
+    installing: AtomicBool,
+}
+
+impl UpdateManager {
+    pub fn new() -> Self {
+        UpdateManager {
+            inner: Mutex::new(UpdateInner {
+                status: UpdateStatus {
+                    phase: DownloadPhase::Idle,
+                    version: None,
+                    notes: None,
+                    downloaded: 0,
+                    total: None,
+                    error: None,
+                    install_on_exit: false,
+                },
+                ready: None,
+            }),
+            installing: AtomicBool::new(false),
+        }
+    }
+}
+
+fn set_status<F: FnOnce(&mut UpdateStatus)>(app_handle: &tauri::AppHandle, f: F) -> UpdateStatus {
+    let manager = app_handle.state::<UpdateManager>();
+    let mut inner = manager.inner.lock().unwrap();
+    f(&mut inner.status);
+    inner.status.clone()
+}
+
+fn eta_secs(downloaded: u64, total: Option<u64>, elapsed: Duration) -> Option<u64> {
+    let total = total?;
+    let secs = elapsed.as_secs_f64();
+    if downloaded == 0 || secs < 1.0 {
+        return None;
+    }
+    let rate = downloaded as f64 / secs;
+    Some((total.saturating_sub(downloaded) as f64 / rate).ceil() as u64)
+}
+
+async fn download(app_handle: tauri::AppHandle, update: Update) {
+    let version = update.version.clone();
+    let started = Instant::now();
+    let mut last_emit: Option<Instant> = None;
+    let mut downloaded = 0u64;
+    // Async: the ID is adopted around each log line and event.
+    let op = Some(correlation::new_id("update_download"));
+
+    let result = update
+        .download(
+            |chunk, total| {
+                downloaded += chunk as u64;
+                let due = last_emit.map_or(true, |t| t.elapsed() >= PROGRESS_INTERVAL);
+                if !due && Some(downloaded) != total {
+                    return;
+                }
+                last_emit = Some(Instant::now());
+                let _op = correlation::adopt(op.clone());
+                set_status(&app_handle, |s| {
+                    s.downloaded = downloaded;
+                    s.total = total;
+                });
+                correlation::emit(
+                    &app_handle,
+                    "update-download-progress",
+                    DownloadProgress {
+                        version: version.clone(),
+                        downloaded,
+                        total,
+                        percent: total
+                            .filter(|t| *t > 0)
+                            .map(|t| ((downloaded * 100 / t).min(100)) as u8),
+                        eta_secs: eta_secs(downloaded, total, started.elapsed()),
+                    },
+                );
+            },
+            || {},
+        )
+        .await;
+
+    let _op = correlation::adopt(op);
+    match result {
+        Ok(bytes) => {
+            log::info!(
+                "UPDATES ► {} downloaded and verified ({} bytes in {:?})",
+                version,
+                bytes.len(),
+                started.elapsed()
+            );
+            let status = {
+                let manager = app_handle.state::<UpdateManager>();
+                let mut inner = manager.inner.lock().unwrap();
+                inner.ready = Some((update, bytes));
+                inner.status.phase = DownloadPhase::Ready;
+                inner.status.install_on_exit =
+                    settings::current(&app_handle).install_updates_on_exit;
+                inner.status.clone()
+            };
+            correlation::emit(&app_handle, "update-ready", &status);
+        }
+        Err(e) => {
+            log::warn!("UPDATES ► download of {} failed: {}", version, e);
+            let status = set_status(&app_handle, |s| {
+                s.phase = DownloadPhase::Failed;
+                s.error = Some(e.to_string());
+            });
+            correlation::emit(&app_handle, "update-download-failed", &status);
+            telemetry::record(
+                &app_handle,
+                "update_outcome",
+                serde_json::json!({ "kind": "app", "outcome": "download_failed" }),
+            );
+        }
+    }
+}
+
+/// Check for an update and download it in the background. `background`
+/// marks an automatic check, which stands down on battery like other
+/// optional work. Returns the version being downloaded, if any.
+#[tauri::command]
+pub async fn start_update_download(
+    app_handle: tauri::AppHandle,
+    background: Option<bool>,
+) -> Result<Option<String>, String> {
+    if background.unwrap_or(false) && power::should_defer_background_work(&app_handle) {
+        log::info!("UPDATES ► background download deferred (on battery)");
+        return Ok(None);
+    }
+    if background.unwrap_or(false) && connectivity::updater_offline(&app_handle) {
+        log::debug!("UPDATES ► background check skipped (update server unreachable)");
+        return Ok(None);
+    }
+    {
+        let manager = app_handle.state::<UpdateManager>();
+        let inner = manager.inner.lock().unwrap();
+        if matches!(
+            inner.status.phase,
+            DownloadPhase::Downloading | DownloadPhase::Ready
+        ) {
+            return Ok(inner.status.version.clone());
+        }
+    }
+
+    let updater = proxy::updater(&app_handle)?;
+    let Some(update) = updater.check().await.map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+    let version = update.version.clone();
+    storage::check(
+        &app_handle,
+        "update_download",
+        &std::env::temp_dir(),
+        storage::UPDATE_MIN_FREE,
+    )?;
+    log::info!("UPDATES ► downloading {} in the background", version);
+    set_status(&app_handle, |s| {
+        *s = UpdateStatus {
+            phase: DownloadPhase::Downloading,
+            version: Some(version.clone()),
+            notes: update.body.clone(),
+            downloaded: 0,
+            total: None,
+            error: None,
+            install_on_exit: false,
+        };
+    });
+    tauri::async_runtime::spawn(download(app_handle.clone(), update));
+    Ok(Some(version))
+}
+
+pub fn status(app_handle: &tauri::AppHandle) -> UpdateStatus {
+    app_handle
+        .state::<UpdateManager>()
+        .inner
+        .lock()
+        .unwrap()
+        .status
+        .clone()
+}
+
+#[tauri::command]
+pub async fn get_update_status(app_handle: tauri::AppHandle) -> Result<UpdateStatus, String> {
+    Ok(status(&app_handle))
+}
+
+/// Flag (or unflag) the ready update for installation on exit.
+#[tauri::command]
+pub async fn set_install_on_exit(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let manager = app_handle.state::<UpdateManager>();
+    let mut inner = manager.inner.lock().unwrap();
+    if inner.ready.is_none() {
+        return Err("no downloaded update is ready".into());
+    }
+    inner.status.install_on_exit = enabled;
+    log::info!(
+        "UPDATES ► {} {} on exit",
+        inner.status.version.as_deref().unwrap_or("update"),
+        if enabled {
+            "will install"
+        } else {
+            "will not install"
+        }
+    );
+    Ok(())
+}
+
+/// Whether a ready update is flagged to install on exit.
+pub fn install_pending_on_exit(app_handle: &tauri::AppHandle) -> bool {
+    let manager = app_handle.state::<UpdateManager>();
+    let inner = manager.inner.lock().unwrap();
+    inner.ready.is_some() && inner.status.install_on_exit
+}
+
+/// Take the ready package for installation; `None` if there is none or an
+/// install already started.
+fn take_ready(app_handle: &tauri::AppHandle, on_exit: bool) -> Option<(Update, Vec<u8>)> {
+    let manager = app_handle.state::<UpdateManager>();
+    let mut inner = manager.inner.lock().unwrap();
+    if on_exit && !inner.status.install_on_exit {
+        return None;
+    }
+    if manager.installing.swap(true, Ordering::SeqCst) {
+        return None;
+    }
+    let ready = inner.ready.take();
+    if ready.is_none() {
+        manager.installing.store(false, Ordering::SeqCst);
+    }
+    ready
+}
+
+/// Stage, stop the backend and run the installer. Blocking.
+fn run_installer(
+    app_handle: &tauri::AppHandle,
+    update: &Update,
+    bytes: &[u8],
+) -> Result<(), String> {
+    let _op = correlation::begin("update");
+    if let Err(e) = rollback::stage_for_update(app_handle, &update.version) {
+        log::warn!(
+            "UPDATES ► could not stage rollback copy ({}); installing anyway",
+            e
+        );
+    }
+    log::info!("UPDATES ► installing {}", update.version);
+    shutdown_backend_for_update(app_handle);
+    update.install(bytes).map_err(|e| {
+        log::error!("UPDATES ► install of {} failed: {}", update.version, e);
+        app_handle
+            .state::<UpdateManager>()
+            .installing
+            .store(false, Ordering::SeqCst);
+        set_status(app_handle, |s| {
+            s.phase = DownloadPhase::Failed;
+            s.error = Some(e.to_string());
+        });
+        e.to_string()
+    })
+}
+
+/// Exit path hook (window close, Ctrl+C). When a ready update is flagged for
+/// exit, installs it and returns true: the backend has then already been
+/// stopped and the caller should skip its own shutdown. On Windows the
+/// installer takes over and this doesn't return.
+pub fn install_on_exit(app_handle: &tauri::AppHandle) -> bool {
+    let Some((update, bytes)) = take_ready(app_handle, true) else {
+        return false;
+    };
+    log::info!("UPDATES ► installing {} on exit", update.version);
+    // Even when the install fails the backend is already down, which is all
+    // the exit path needs.
+    let _ = run_installer(app_handle, &update, &bytes);
+    true
+}
+
+/// Install the downloaded update: stage the running version for rollback,
+/// stop the backend, then run the installer (which exits the app on
+/// Windows; elsewhere the app restarts into the new version).
+#[tauri::command]
+pub async fn install_update(app_handle: tauri::AppHandle) -> Result<(), String> {
+    let (update, bytes) = take_ready(&app_handle, false).ok_or("no downloaded update is ready")?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let _op = correlation::begin("update");
+        if let Err(e) = run_installer(&app_handle, &update, &bytes) {
+            // The backend was stopped for the installer; bring it back.
+            let _ = crate::spawn_backend(&app_handle);
+            return Err(e);
+        }
+        app_handle.restart();
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(v: &str) -> semver::Version {
+        semver::Version::parse(v).unwrap()
+    }
+
+    #[test]
+    fn stable_channel_skips_pre_releases() {
+        assert!(UpdateChannel::Stable.allows(&version("1.4.0")));
+        assert!(!UpdateChannel::Stable.allows(&version("1.5.0-beta.1")));
+        assert!(!UpdateChannel::Stable.allows(&version("1.5.0-rc.2")));
+    }
+
+    #[test]
+    fn beta_channel_takes_releases_and_pre_releases() {
+        assert!(UpdateChannel::Beta.allows(&version("1.4.0")));
+        assert!(UpdateChannel::Beta.allows(&version("1.5.0-beta.1")));
+    }
+}
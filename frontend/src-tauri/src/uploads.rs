@@ -0,0 +1,321 @@
+// Streaming uploads of local files to the backend. `upload_file_to_backend`
+// sends a file in `CHUNK_SIZE` pieces, each a multipart POST to the given
+// endpoint carrying `upload_id`, `offset`, `total_size`, `file_name` and the
+// bytes as `chunk`, so a multi-GB source document is read from disk a chunk
+// at a time and never passes through the webview. The backend answers each
+// chunk with how many bytes of the upload it now holds (`received`) and the
+// next chunk starts there, so the backend decides where a resumed upload
+// picks up. A chunk that fails is retried a few times; after that the
+// upload is kept as failed and `resume_upload` continues it under the same
+// upload ID. Progress goes out as `upload-progress`; the completed event
+// carries the backend's answer to the last chunk.
+
+use crate::{backend_http, correlation};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::Manager;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+const CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(120);
+/// Attempts per chunk before the upload is marked failed.
+const CHUNK_ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadPhase {
+    Uploading,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadStatus {
+    pub id: String,
+    pub path: String,
+    pub endpoint: String,
+    pub phase: UploadPhase,
+    /// Bytes the backend has acknowledged.
+    pub sent: u64,
+    pub total: u64,
+    pub percent: u8,
+    /// The backend's answer to the last chunk, once completed.
+    pub response: Option<Value>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Upload {
+    path: PathBuf,
+    endpoint: String,
+    file_name: String,
+    total: u64,
+    /// Bytes the backend has acknowledged; the next chunk starts here.
+    offset: u64,
+    running: bool,
+    cancelled: bool,
+}
+
+/// Uploads in flight or failed and resumable, by ID.
+pub struct Uploads(Mutex<HashMap<String, Upload>>);
+
+impl Uploads {
+    pub fn new() -> Self {
+        Uploads(Mutex::new(HashMap::new()))
+    }
+}
+
+fn status(id: &str, upload: &Upload, phase: UploadPhase) -> UploadStatus {
+    UploadStatus {
+        id: id.to_string(),
+        path: upload.path.to_string_lossy().into_owned(),
+        endpoint: upload.endpoint.clone(),
+        phase,
+        sent: upload.offset,
+        total: upload.total,
+        percent: (upload.offset * 100)
+            .checked_div(upload.total)
+            .map_or(100, |p| p.min(100) as u8),
+        response: None,
+        error: None,
+    }
+}
+
+fn emit(app_handle: &tauri::AppHandle, status: UploadStatus) {
+    correlation::emit(app_handle, "upload-progress", status);
+}
+
+async fn read_chunk(upload: &Upload) -> Result<Vec<u8>, String> {
+    let mut file = tokio::fs::File::open(&upload.path)
+        .await
+        .map_err(|e| format!("cannot open {:?}: {}", upload.path, e))?;
+    file.seek(SeekFrom::Start(upload.offset))
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut chunk = Vec::with_capacity(CHUNK_SIZE.min(upload.total - upload.offset) as usize);
+    file.take(CHUNK_SIZE)
+        .read_to_end(&mut chunk)
+        .await
+        .map_err(|e| format!("cannot read {:?}: {}", upload.path, e))?;
+    Ok(chunk)
+}
+
+/// Send the chunk at the upload's offset; the backend's answer and the
+/// offset it acknowledged.
+async fn send_chunk(
+    app_handle: &tauri::AppHandle,
+    id: &str,
+    upload: &Upload,
+) -> Result<(Value, u64), String> {
+    let chunk = read_chunk(upload).await?;
+    let len = chunk.len() as u64;
+    let form = reqwest::multipart::Form::new()
+        .text("upload_id", id.to_string())
+        .text("offset", upload.offset.to_string())
+        .text("total_size", upload.total.to_string())
+        .text("file_name", upload.file_name.clone())
+        .part(
+            "chunk",
+            reqwest::multipart::Part::bytes(chunk).file_name(upload.file_name.clone()),
+        );
+    let reply =
+        backend_http::post_multipart(app_handle, &upload.endpoint, form, CHUNK_TIMEOUT).await?;
+    if !reply.status.is_success() {
+        return Err(format!("{} returned {}", upload.endpoint, reply.status));
+    }
+    let received = reply
+        .body
+        .get("received")
+        .and_then(Value::as_u64)
+        .unwrap_or(upload.offset + len)
+        .min(upload.total);
+    Ok((reply.body, received))
+}
+
+fn snapshot(app_handle: &tauri::AppHandle, id: &str) -> Option<Upload> {
+    let uploads = app_handle.state::<Uploads>();
+    let guard = uploads.0.lock().unwrap();
+    guard.get(id).cloned()
+}
+
+/// Upload chunks until the backend holds the whole file, the upload is
+/// cancelled, or a chunk keeps failing.
+async fn run(app_handle: tauri::AppHandle, id: String, op: Option<String>) {
+    loop {
+        let Some(upload) = snapshot(&app_handle, &id) else {
+            return;
+        };
+        if upload.cancelled {
+            let _op = correlation::adopt(op.clone());
+            app_handle.state::<Uploads>().0.lock().unwrap().remove(&id);
+            log::info!("UPLOADS ► {} cancelled at {} bytes", id, upload.offset);
+            emit(&app_handle, status(&id, &upload, UploadPhase::Cancelled));
+            return;
+        }
+
+        let mut attempt = 1;
+        let result = loop {
+            match send_chunk(&app_handle, &id, &upload).await {
+                Err(e) if attempt < CHUNK_ATTEMPTS => {
+                    {
+                        let _op = correlation::adopt(op.clone());
+                        log::warn!(
+                            "UPLOADS ► {} chunk at {} failed (attempt {}): {}",
+                            id,
+                            upload.offset,
+                            attempt,
+                            e
+                        );
+                    }
+                    attempt += 1;
+                    tokio::time::sleep(RETRY_DELAY).await;
+                }
+                other => break other,
+            }
+        };
+
+        let _op = correlation::adopt(op.clone());
+        let uploads = app_handle.state::<Uploads>();
+        let mut guard = uploads.0.lock().unwrap();
+        let Some(entry) = guard.get_mut(&id) else {
+            return;
+        };
+        match result {
+            Ok((response, received)) => {
+                entry.offset = received;
+                let mut current = status(&id, entry, UploadPhase::Uploading);
+                if received >= entry.total {
+                    log::info!(
+                        "UPLOADS ► {} complete: {:?} -> {}",
+                        id,
+                        entry.path,
+                        entry.endpoint
+                    );
+                    guard.remove(&id);
+                    current.phase = UploadPhase::Completed;
+                    current.response = Some(response);
+                    drop(guard);
+                    emit(&app_handle, current);
+                    return;
+                }
+                drop(guard);
+                emit(&app_handle, current);
+            }
+            Err(e) => {
+                log::warn!("UPLOADS ► {} failed at {} bytes: {}", id, entry.offset, e);
+                entry.running = false;
+                let mut current = status(&id, entry, UploadPhase::Failed);
+                current.error = Some(e);
+                drop(guard);
+                emit(&app_handle, current);
+                return;
+            }
+        }
+    }
+}
+
+/// Stream the file at `path` to the backend `endpoint`. Returns once the
+/// upload has started; it reports through `upload-progress`.
+#[tauri::command]
+pub async fn upload_file_to_backend(
+    app_handle: tauri::AppHandle,
+    path: String,
+    endpoint: String,
+) -> Result<UploadStatus, String> {
+    if !endpoint.starts_with('/') {
+        return Err(format!("'{}' is not a backend path", endpoint));
+    }
+    let path = PathBuf::from(path.trim());
+    if !path.is_absolute() {
+        return Err(format!("{:?} is not an absolute path", path));
+    }
+    let meta = tokio::fs::metadata(&path)
+        .await
+        .map_err(|e| format!("cannot read {:?}: {}", path, e))?;
+    if !meta.is_file() {
+        return Err(format!("{:?} is not a file", path));
+    }
+
+    let id = format!("upload-{}", NEXT_ID.fetch_add(1, Ordering::SeqCst));
+    let upload = Upload {
+        file_name: path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "upload".to_string()),
+        path,
+        endpoint,
+        total: meta.len(),
+        offset: 0,
+        running: true,
+        cancelled: false,
+    };
+    log::info!(
+        "UPLOADS ► {} started: {:?} ({} bytes) -> {}",
+        id,
+        upload.path,
+        upload.total,
+        upload.endpoint
+    );
+    let started = status(&id, &upload, UploadPhase::Uploading);
+    app_handle
+        .state::<Uploads>()
+        .0
+        .lock()
+        .unwrap()
+        .insert(id.clone(), upload);
+    tauri::async_runtime::spawn(run(app_handle, id, Some(correlation::new_id("upload"))));
+    Ok(started)
+}
+
+/// Continue a failed upload from the last offset the backend acknowledged.
+#[tauri::command]
+pub async fn resume_upload(
+    app_handle: tauri::AppHandle,
+    id: String,
+) -> Result<UploadStatus, String> {
+    let resumed = {
+        let uploads = app_handle.state::<Uploads>();
+        let mut guard = uploads.0.lock().unwrap();
+        let upload = guard
+            .get_mut(&id)
+            .ok_or_else(|| format!("no upload {}", id))?;
+        if upload.running {
+            return Err(format!("{} is still running", id));
+        }
+        upload.running = true;
+        status(&id, upload, UploadPhase::Uploading)
+    };
+    log::info!("UPLOADS ► {} resuming at {} bytes", id, resumed.sent);
+    tauri::async_runtime::spawn(run(app_handle, id, Some(correlation::new_id("upload"))));
+    Ok(resumed)
+}
+
+/// Stop an upload (after the chunk in flight), or forget a failed one.
+#[tauri::command]
+pub async fn cancel_upload(app_handle: tauri::AppHandle, id: String) -> Result<(), String> {
+    let uploads = app_handle.state::<Uploads>();
+    let mut guard = uploads.0.lock().unwrap();
+    let upload = guard
+        .get_mut(&id)
+        .ok_or_else(|| format!("no upload {}", id))?;
+    if upload.running {
+        upload.cancelled = true;
+        return Ok(());
+    }
+    let cancelled = status(&id, upload, UploadPhase::Cancelled);
+    guard.remove(&id);
+    drop(guard);
+    log::info!("UPLOADS ► {} dropped", id);
+    emit(&app_handle, cancelled);
+    Ok(())
+}
@@ -0,0 +1,46 @@
+// Backend version pinning. Users with validated workflows can freeze the
+// processing engine at a specific version; while a pin is set, anything
+// that would replace the backend (the app updater, backend-only updates)
+// logs a warning and declines instead of installing.
+
+use crate::settings;
+use tauri::AppHandle;
+
+/// The pinned backend version, if any.
+pub fn pinned_version(app_handle: &AppHandle) -> Option<String> {
+    settings::current(app_handle).pinned_backend_version
+}
+
+/// Decide whether the app updater may offer `remote`. Mirrors the plugin's
+/// default comparison (`remote > current`) unless a pin is active.
+pub fn allow_app_update(app_handle: &AppHandle, current: &str, remote: &str, newer: bool) -> bool {
+    if !newer {
+        return false;
+    }
+    match pinned_version(app_handle) {
+        Some(pin) => {
+            log::warn!(
+                "VERSION_PIN ► update {} -> {} available but backend is pinned at {}; not offering it",
+                current,
+                remote,
+                pin
+            );
+            false
+        }
+        None => true,
+    }
+}
+
+/// Pin the backend to `version`, or clear the pin when `None`.
+#[tauri::command]
+pub async fn set_backend_pin(app_handle: AppHandle, version: Option<String>) -> Result<(), String> {
+    let version = version
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    match &version {
+        Some(v) => log::info!("VERSION_PIN ► pinning backend at {}", v),
+        None => log::info!("VERSION_PIN ► clearing backend pin"),
+    }
+    settings::update(&app_handle, |s| s.pinned_backend_version = version)?;
+    Ok(())
+}
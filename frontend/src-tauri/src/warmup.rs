@@ -0,0 +1,138 @@
+// Optional post-ready model warmup. Once the backend is healthy, call
+// endpoints that load heavy resources (alignment engine, model registry, …)
+// so the user's first real action doesn't pay the load cost. Progress is
+// reported through `model-warmup-progress` events.
+
+use crate::{backend_http, power, readiness, recovery, settings};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelWarmupSettings {
+    pub enabled: bool,
+    /// Skip the warmup while background work is deferred for battery power
+    /// (see `PowerSettings`).
+    pub skip_on_battery: bool,
+    /// Backend GET paths called in order.
+    pub endpoints: Vec<String>,
+}
+
+impl Default for ModelWarmupSettings {
+    fn default() -> Self {
+        ModelWarmupSettings {
+            enabled: true,
+            skip_on_battery: true,
+            endpoints: vec![
+                "/api/alignment/align-drafts/status".to_string(),
+                "/api/models".to_string(),
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum WarmupPhase {
+    Started,
+    Done,
+    Failed,
+    Skipped,
+}
+
+#[derive(Clone, Serialize)]
+struct WarmupProgress {
+    phase: WarmupPhase,
+    step: usize,
+    total: usize,
+    path: Option<String>,
+    duration_ms: Option<u64>,
+    detail: Option<String>,
+}
+
+/// Run the configured warmup. Blocking; call from a background thread after
+/// the backend reported healthy.
+pub fn run(app_handle: &tauri::AppHandle) {
+    let cfg = settings::current(app_handle).model_warmup;
+    if !cfg.enabled || cfg.endpoints.is_empty() || recovery::safe_mode(app_handle) {
+        return;
+    }
+    let total = cfg.endpoints.len();
+
+    if cfg.skip_on_battery && power::should_defer_background_work(app_handle) {
+        log::info!("MODEL_WARMUP ► skipped (deferred on battery)");
+        let _ = app_handle.emit(
+            "model-warmup-progress",
+            WarmupProgress {
+                phase: WarmupPhase::Skipped,
+                step: 0,
+                total,
+                path: None,
+                duration_ms: None,
+                detail: Some("on battery".to_string()),
+            },
+        );
+        return;
+    }
+
+    for (i, path) in cfg.endpoints.iter().enumerate() {
+        let step = i + 1;
+        let _ = app_handle.emit(
+            "model-warmup-progress",
+            WarmupProgress {
+                phase: WarmupPhase::Started,
+                step,
+                total,
+                path: Some(path.clone()),
+                duration_ms: None,
+                detail: None,
+            },
+        );
+
+        let start = Instant::now();
+        let result = backend_http::call_blocking(
+            app_handle,
+            reqwest::Method::GET,
+            path,
+            None,
+            Duration::from_secs(120),
+            Some("warmup"),
+        )
+        .and_then(|reply| {
+            if reply.status.is_success() {
+                Ok(reply)
+            } else {
+                Err(format!("backend returned {}", reply.status))
+            }
+        });
+        let duration_ms = start.elapsed().as_millis() as u64;
+        readiness::record_prewarm(app_handle, "model_warmup", path, duration_ms, &result);
+        let (phase, detail) = match result {
+            Ok(reply) => {
+                log::info!("MODEL_WARMUP ► {} ok in {}ms", path, duration_ms);
+                (WarmupPhase::Done, Some(reply.status.as_u16().to_string()))
+            }
+            Err(e) => {
+                log::warn!(
+                    "MODEL_WARMUP ► {} failed after {}ms: {}",
+                    path,
+                    duration_ms,
+                    e
+                );
+                (WarmupPhase::Failed, Some(e.to_string()))
+            }
+        };
+        let _ = app_handle.emit(
+            "model-warmup-progress",
+            WarmupProgress {
+                phase,
+                step,
+                total,
+                path: Some(path.clone()),
+                duration_ms: Some(duration_ms),
+                detail,
+            },
+        );
+    }
+}
@@ -0,0 +1,247 @@
+// What's-new tracking. The version that last ran is kept in settings; when
+// startup finds a newer one the upgrade is recorded, and once the main
+// window has loaded the shell emits `whats-new` with the release notes for
+// every version since the previous one. The record is marked announced right
+// away so the dialog shows once per upgrade; `get_whats_new` can still fetch
+// the notes later (e.g. from a Help menu).
+//
+// Notes come from `release-notes.json`, bundled at build time, and fall back
+// to the `notes` of the updater's release feed for versions the bundle lacks.
+
+use crate::{backend_http, proxy, settings};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+use tauri::webview::{PageLoadEvent, PageLoadPayload};
+use tauri::{Emitter, Manager, Webview};
+
+const BUNDLED_NOTES: &str = include_str!("../release-notes.json");
+/// Same manifest the updater reads (`plugins.updater.endpoints`).
+const RELEASE_FEED: &str =
+    "https://raw.githubusercontent.com/bwanedead/Plattera/main/releases/latest.json";
+
+/// The most recent upgrade seen at startup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradeRecord {
+    /// Previous version; `None` when it predates last-run tracking.
+    pub from: Option<String>,
+    pub to: String,
+    /// Whether `whats-new` has been emitted for this upgrade.
+    pub announced: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseNotes {
+    pub version: String,
+    #[serde(default)]
+    pub date: Option<String>,
+    pub notes: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WhatsNew {
+    pub from: Option<String>,
+    pub to: String,
+    /// Newest first.
+    pub releases: Vec<ReleaseNotes>,
+}
+
+pub struct WhatsNewState {
+    /// Set once `whats-new` has been sent this run.
+    announced: AtomicBool,
+}
+
+impl WhatsNewState {
+    pub fn new() -> Self {
+        WhatsNewState {
+            announced: AtomicBool::new(false),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct FeedManifest {
+    version: String,
+    #[serde(default)]
+    notes: String,
+    #[serde(default)]
+    pub_date: Option<String>,
+}
+
+fn parse_version(v: &str) -> Option<semver::Version> {
+    semver::Version::parse(v.trim().trim_start_matches('v')).ok()
+}
+
+/// Startup check, run after `onboarding::detect_first_run` so a fresh
+/// install (flagged for onboarding) isn't mistaken for an upgrade.
+pub fn detect_upgrade(app_handle: &tauri::AppHandle) {
+    let current = app_handle.package_info().version.to_string();
+    let previous = settings::current(app_handle).last_run_version;
+    if previous.as_deref() == Some(current.as_str()) {
+        return;
+    }
+
+    let upgraded = match &previous {
+        Some(prev) => match (parse_version(prev), parse_version(&current)) {
+            (Some(prev), Some(cur)) => prev < cur,
+            _ => false,
+        },
+        // Installs from before last-run tracking have settings but no version.
+        None => settings::exists(app_handle) && settings::current(app_handle).onboarding_completed,
+    };
+    if upgraded {
+        log::info!(
+            "WHATS_NEW ► upgrade detected: {} → {}",
+            previous.as_deref().unwrap_or("unknown"),
+            current
+        );
+    }
+
+    let result = settings::update(app_handle, |s| {
+        if upgraded {
+            // An upgrade that was never announced widens the range instead
+            // of being replaced.
+            let from = match &s.last_upgrade {
+                Some(record) if !record.announced => record.from.clone(),
+                _ => previous.clone(),
+            };
+            s.last_upgrade = Some(UpgradeRecord {
+                from,
+                to: current.clone(),
+                announced: false,
+            });
+        }
+        s.last_run_version = Some(current.clone());
+    });
+    if let Err(e) = result {
+        log::warn!("WHATS_NEW ► could not persist last-run version: {}", e);
+    }
+}
+
+fn bundled_notes() -> Vec<ReleaseNotes> {
+    serde_json::from_str(BUNDLED_NOTES).unwrap_or_else(|e| {
+        log::warn!("WHATS_NEW ► bundled release notes unreadable: {}", e);
+        Vec::new()
+    })
+}
+
+fn feed_notes(app_handle: &tauri::AppHandle) -> Option<ReleaseNotes> {
+    let timeout = Duration::from_millis(5_000);
+    let req = proxy::client_builder(app_handle, RELEASE_FEED)
+        .connect_timeout(Duration::from_millis(2_000))
+        .timeout(timeout)
+        .build()
+        .ok()?
+        .get(RELEASE_FEED);
+    let fetched = backend_http::block_on(timeout, async move {
+        let res = req
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(|e| e.to_string())?;
+        res.json::<FeedManifest>().await.map_err(|e| e.to_string())
+    });
+    let manifest = match fetched {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            log::debug!("WHATS_NEW ► release feed unavailable: {}", e);
+            return None;
+        }
+    };
+    if manifest.notes.trim().is_empty() {
+        return None;
+    }
+    Some(ReleaseNotes {
+        version: manifest.version,
+        date: manifest.pub_date,
+        notes: manifest.notes,
+    })
+}
+
+/// Notes for versions in `(from, to]`; just `to` when `from` is unknown.
+/// Blocking (may read the release feed).
+fn collect(app_handle: &tauri::AppHandle, from: Option<&str>, to: &str) -> Vec<ReleaseNotes> {
+    let to_v = parse_version(to);
+    let from_v = from.and_then(parse_version);
+    let in_range = |version: &str| -> bool {
+        let Some(v) = parse_version(version) else {
+            return false;
+        };
+        match (&from_v, &to_v) {
+            (Some(from), Some(to)) => v > *from && v <= *to,
+            (None, Some(to)) => v == *to,
+            _ => version == to,
+        }
+    };
+
+    let mut releases: Vec<ReleaseNotes> = bundled_notes()
+        .into_iter()
+        .filter(|r| in_range(&r.version))
+        .collect();
+    if !releases.iter().any(|r| parse_version(&r.version) == to_v) {
+        if let Some(feed) = feed_notes(app_handle).filter(|r| in_range(&r.version)) {
+            releases.push(feed);
+        }
+    }
+    releases.sort_by_key(|r| std::cmp::Reverse(parse_version(&r.version)));
+    releases
+}
+
+fn build(app_handle: &tauri::AppHandle) -> WhatsNew {
+    let current = app_handle.package_info().version.to_string();
+    let (from, to) = match settings::current(app_handle).last_upgrade {
+        Some(record) if record.to == current => (record.from, record.to),
+        _ => (None, current),
+    };
+    let releases = collect(app_handle, from.as_deref(), &to);
+    WhatsNew { from, to, releases }
+}
+
+/// Page-load hook: after an upgrade, emit `whats-new` once the main window
+/// has finished loading.
+pub fn on_page_load(webview: &Webview, payload: &PageLoadPayload<'_>) {
+    if webview.label() != "main" || payload.event() != PageLoadEvent::Finished {
+        return;
+    }
+    let app_handle = webview.app_handle().clone();
+    let pending = matches!(
+        settings::current(&app_handle).last_upgrade,
+        Some(record) if !record.announced
+    );
+    if !pending
+        || app_handle
+            .state::<WhatsNewState>()
+            .announced
+            .swap(true, Ordering::SeqCst)
+    {
+        return;
+    }
+    // The release feed may be slow; keep it off the event loop.
+    thread::spawn(move || {
+        let whats_new = build(&app_handle);
+        log::info!(
+            "WHATS_NEW ► announcing {} release(s) since {}",
+            whats_new.releases.len(),
+            whats_new.from.as_deref().unwrap_or("unknown")
+        );
+        let _ = app_handle.emit("whats-new", &whats_new);
+        let result = settings::update(&app_handle, |s| {
+            if let Some(record) = s.last_upgrade.as_mut() {
+                record.announced = true;
+            }
+        });
+        if let Err(e) = result {
+            log::warn!("WHATS_NEW ► could not mark upgrade announced: {}", e);
+        }
+    });
+}
+
+/// Release notes for the most recent upgrade, or for the running version
+/// when it wasn't reached by an upgrade.
+#[tauri::command]
+pub async fn get_whats_new(app_handle: tauri::AppHandle) -> Result<WhatsNew, String> {
+    tauri::async_runtime::spawn_blocking(move || build(&app_handle))
+        .await
+        .map_err(|e| e.to_string())
+}
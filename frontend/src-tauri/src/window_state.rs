@@ -0,0 +1,192 @@
+// Main window geometry across launches. The window is declared hidden in
+// tauri.conf.json; setup restores the saved size, position and maximized
+// state before `splash.rs` shows it, so there is no visible jump from the
+// default placement. Bounds are tracked from move/resize events while the window is
+// in its normal state (a maximized or minimized window reports bounds that
+// are useless to restore) and written to `window_state.json` together with
+// the session.
+//
+// A saved position is only reused when its title bar would land on a
+// connected monitor; after a monitor was unplugged the window is centred on
+// the primary monitor instead, shrunk to fit if needed.
+
+use crate::portable;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{Manager, Monitor, PhysicalPosition, PhysicalSize, WebviewWindow, Window};
+
+const STATE_FILE: &str = "window_state.json";
+const MAIN_WINDOW: &str = "main";
+/// Part of the window (from its top-left corner) that must be on a monitor
+/// for the saved position to be reused: enough title bar to grab it.
+const MIN_VISIBLE: (i32, i32) = (120, 40);
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Bounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    /// Physical pixels of the last normal (not maximized) placement.
+    pub bounds: Bounds,
+    pub maximized: bool,
+    /// Monitor the window was on, as reported by the OS.
+    #[serde(default)]
+    pub monitor: Option<String>,
+}
+
+/// Geometry per window label, updated from window events.
+pub struct WindowStates(Mutex<HashMap<String, WindowGeometry>>);
+
+impl WindowStates {
+    pub fn new() -> Self {
+        WindowStates(Mutex::new(HashMap::new()))
+    }
+}
+
+fn state_path(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    portable::resolve(app_handle, STATE_FILE).ok()
+}
+
+fn load(app_handle: &tauri::AppHandle) -> HashMap<String, WindowGeometry> {
+    state_path(app_handle)
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Record the current geometry of `window`. Called on move and resize.
+pub fn track(window: &Window) {
+    let Some(states) = window.app_handle().try_state::<WindowStates>() else {
+        return;
+    };
+    let maximized = window.is_maximized().unwrap_or(false);
+    let minimized = window.is_minimized().unwrap_or(false);
+    if minimized {
+        return;
+    }
+    let mut states = states.0.lock().unwrap();
+    let entry = states.entry(window.label().to_string()).or_default();
+    entry.maximized = maximized;
+    entry.monitor = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .and_then(|m| m.name().cloned());
+    if !maximized {
+        if let (Ok(pos), Ok(size)) = (window.outer_position(), window.outer_size()) {
+            entry.bounds = Bounds {
+                x: pos.x,
+                y: pos.y,
+                width: size.width,
+                height: size.height,
+            };
+        }
+    }
+}
+
+/// Write the tracked geometry. Windows never moved this run keep what was
+/// saved before.
+pub fn persist(app_handle: &tauri::AppHandle) {
+    let Some(states) = app_handle.try_state::<WindowStates>() else {
+        return;
+    };
+    let current = states.0.lock().unwrap().clone();
+    if current.is_empty() {
+        return;
+    }
+    let mut saved = load(app_handle);
+    saved.extend(current);
+    let Some(path) = state_path(app_handle) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    match serde_json::to_string_pretty(&saved) {
+        Ok(raw) => {
+            if let Err(e) = fs::write(&path, raw) {
+                log::warn!("WINDOW_STATE ► could not save {:?}: {}", path, e);
+            }
+        }
+        Err(e) => log::warn!("WINDOW_STATE ► could not serialize: {}", e),
+    }
+}
+
+/// Whether the top-left corner strip of `bounds` lies on `monitor`.
+fn visible_on(bounds: &Bounds, monitor: &Monitor) -> bool {
+    let area = monitor.work_area();
+    let (left, top) = (area.position.x, area.position.y);
+    let right = left + area.size.width as i32;
+    let bottom = top + area.size.height as i32;
+    bounds.x + MIN_VISIBLE.0 > left
+        && bounds.x < right - MIN_VISIBLE.0
+        && bounds.y >= top - MIN_VISIBLE.1 / 2
+        && bounds.y + MIN_VISIBLE.1 < bottom
+}
+
+/// `bounds` shrunk to fit and centred on `monitor`.
+fn centred_on(bounds: &Bounds, monitor: &Monitor) -> Bounds {
+    let area = monitor.work_area();
+    let width = bounds.width.min(area.size.width);
+    let height = bounds.height.min(area.size.height);
+    Bounds {
+        x: area.position.x + (area.size.width - width) as i32 / 2,
+        y: area.position.y + (area.size.height - height) as i32 / 2,
+        width,
+        height,
+    }
+}
+
+fn apply(window: &WebviewWindow, geometry: &WindowGeometry) {
+    let monitors = window.available_monitors().unwrap_or_default();
+    let bounds = if monitors.iter().any(|m| visible_on(&geometry.bounds, m)) {
+        Some(geometry.bounds)
+    } else {
+        log::info!(
+            "WINDOW_STATE ► saved position is off-screen (monitor {:?} gone?); centring",
+            geometry.monitor
+        );
+        window
+            .primary_monitor()
+            .ok()
+            .flatten()
+            .or_else(|| monitors.into_iter().next())
+            .map(|m| centred_on(&geometry.bounds, &m))
+    };
+    // A window that is maximized in the config must be restored first, or
+    // the size below is ignored.
+    let _ = window.unmaximize();
+    if let Some(b) = bounds {
+        let _ = window.set_size(PhysicalSize::new(b.width, b.height));
+        let _ = window.set_position(PhysicalPosition::new(b.x, b.y));
+    }
+    if geometry.maximized {
+        let _ = window.maximize();
+    }
+}
+
+/// Restore the main window's saved geometry, then show it. Called from
+/// setup; without saved state the config's placement is used.
+pub fn restore(app_handle: &tauri::AppHandle) {
+    let Some(window) = app_handle.get_webview_window(MAIN_WINDOW) else {
+        return;
+    };
+    let saved = load(app_handle).remove(MAIN_WINDOW);
+    if let Some(geometry) = saved.filter(|g| g.bounds.width > 0 && g.bounds.height > 0) {
+        apply(&window, &geometry);
+        app_handle
+            .state::<WindowStates>()
+            .0
+            .lock()
+            .unwrap()
+            .insert(MAIN_WINDOW.to_string(), geometry);
+    }
+}
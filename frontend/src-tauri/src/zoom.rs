@@ -0,0 +1,112 @@
+// Webview zoom. One factor applies to every window; it is kept in the
+// `zoom` setting, restored when a window is created, and changed from View →
+// Zoom In/Out/Actual Size or the zoom commands. Steps follow the usual
+// browser ladder so repeated zooming lands on the same values. Every change
+// is announced as `zoom-changed`.
+
+use crate::{pdf_export, settings};
+use serde::Serialize;
+use tauri::{Emitter, Manager, WebviewWindow};
+
+pub const DEFAULT_ZOOM: f64 = 1.0;
+const STEPS: &[f64] = &[
+    0.5, 0.67, 0.75, 0.8, 0.9, 1.0, 1.1, 1.25, 1.5, 1.75, 2.0, 2.5, 3.0,
+];
+pub const MIN_ZOOM: f64 = 0.25;
+pub const MAX_ZOOM: f64 = 5.0;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ZoomChanged {
+    pub factor: f64,
+}
+
+/// Reject a `zoom` setting out of range. Called from settings validation.
+pub fn validate(factor: f64) -> Result<(), String> {
+    if factor.is_finite() && (MIN_ZOOM..=MAX_ZOOM).contains(&factor) {
+        Ok(())
+    } else {
+        Err(format!(
+            "zoom must be between {} and {}",
+            MIN_ZOOM, MAX_ZOOM
+        ))
+    }
+}
+
+/// Apply the saved factor to a newly created window.
+pub fn restore(window: &WebviewWindow) {
+    let factor = settings::current(window.app_handle()).zoom;
+    if factor != DEFAULT_ZOOM {
+        if let Err(e) = window.set_zoom(factor) {
+            log::warn!(
+                "ZOOM ► could not restore zoom for '{}': {}",
+                window.label(),
+                e
+            );
+        }
+    }
+}
+
+/// Apply the `zoom` setting to every window. Called whenever it changes.
+pub fn apply(app_handle: &tauri::AppHandle) {
+    let factor = settings::current(app_handle).zoom;
+    for (label, window) in app_handle.webview_windows() {
+        // PDF exports render at 100% regardless.
+        if !pdf_export::is_export_window(&label) {
+            let _ = window.set_zoom(factor);
+        }
+    }
+    let _ = app_handle.emit("zoom-changed", ZoomChanged { factor });
+}
+
+fn set(app_handle: &tauri::AppHandle, factor: f64) -> Result<f64, String> {
+    validate(factor)?;
+    // Saving triggers `apply` through the settings change hook.
+    settings::update(app_handle, |s| s.zoom = factor)?;
+    Ok(factor)
+}
+
+fn next_step(current: f64, up: bool) -> f64 {
+    let step = if up {
+        STEPS.iter().copied().find(|s| *s > current + 0.001)
+    } else {
+        STEPS.iter().rev().copied().find(|s| *s < current - 0.001)
+    };
+    step.unwrap_or(current)
+}
+
+/// One step in or out. Used by the View menu.
+pub fn step(app_handle: &tauri::AppHandle, up: bool) -> Result<f64, String> {
+    let current = settings::current(app_handle).zoom;
+    set(app_handle, next_step(current, up))
+}
+
+/// Back to 100%. Used by the View menu.
+pub fn reset(app_handle: &tauri::AppHandle) -> Result<f64, String> {
+    set(app_handle, DEFAULT_ZOOM)
+}
+
+#[tauri::command]
+pub async fn get_zoom(app_handle: tauri::AppHandle) -> Result<f64, String> {
+    Ok(settings::current(&app_handle).zoom)
+}
+
+#[tauri::command]
+pub async fn zoom_in(app_handle: tauri::AppHandle) -> Result<f64, String> {
+    step(&app_handle, true)
+}
+
+#[tauri::command]
+pub async fn zoom_out(app_handle: tauri::AppHandle) -> Result<f64, String> {
+    step(&app_handle, false)
+}
+
+#[tauri::command]
+pub async fn reset_zoom(app_handle: tauri::AppHandle) -> Result<f64, String> {
+    reset(&app_handle)
+}
+
+/// Set an exact factor, e.g. from a zoom slider.
+#[tauri::command]
+pub async fn set_zoom(app_handle: tauri::AppHandle, factor: f64) -> Result<f64, String> {
+    set(&app_handle, factor)
+}
@@ -1,4 +1,5 @@
 import React, { useState } from 'react';
+import { backendUrl } from '@/services/backendClient';
 
 type ApiKeyModalProps = {
   open: boolean;
@@ -16,7 +17,7 @@ export const ApiKeyModal: React.FC<ApiKeyModalProps> = ({ open, onClose, onSaved
     if (!apiKey) return;
     setSaving(true);
     try {
-      const res = await fetch('http://127.0.0.1:8000/config/key', {
+      const res = await fetch(backendUrl('/config/key'), {
         method: 'POST',
         headers: { 'Content-Type': 'application/json' },
         body: JSON.stringify({ apiKey })
@@ -1,4 +1,5 @@
 import React, { useState, useEffect } from 'react'
+import { backendUrl } from '@/services/backendClient';
 
 interface EnhancementSettings {
   contrast: number;
@@ -62,7 +63,7 @@ const ImageBatchProcessor: React.FC<ImageBatchProcessorProps> = ({
   useEffect(() => {
     const loadModels = async () => {
       try {
-        const response = await fetch('http://localhost:8000/api/models')
+        const response = await fetch(backendUrl('/api/models'))
         
         if (!response.ok) {
           throw new Error(`HTTP ${response.status}: ${response.statusText}`)
@@ -87,7 +88,7 @@ const ImageBatchProcessor: React.FC<ImageBatchProcessorProps> = ({
     
     const loadExtractionModes = async () => {
       try {
-        const response = await fetch('http://localhost:8000/api/process/types')
+        const response = await fetch(backendUrl('/api/process/types'))
         const data = await response.json()
         
         if (data.status === 'success' && data.processing_types?.['image-to-text']?.extraction_modes) {
@@ -145,7 +146,7 @@ const ImageBatchProcessor: React.FC<ImageBatchProcessorProps> = ({
         formData.append('brightness', enhancementSettings.brightness.toString())
         formData.append('color', enhancementSettings.color.toString())
 
-        const response = await fetch('http://localhost:8000/api/process', {
+        const response = await fetch(backendUrl('/api/process'), {
           method: 'POST',
           body: formData
         })
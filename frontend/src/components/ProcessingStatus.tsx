@@ -1,4 +1,5 @@
 import React, { useState, useEffect } from 'react';
+import { backendUrl } from '@/services/backendClient';
 
 interface ProcessingStatusProps {
   isProcessing: boolean;
@@ -43,7 +44,7 @@ const ProcessingStatus: React.FC<ProcessingStatusProps> = ({
   const checkHealth = async () => {
     setIsCheckingHealth(true);
     try {
-      const response = await fetch('http://localhost:8000/api/health');
+      const response = await fetch(backendUrl('/api/health'));
       if (response.ok) {
         const health = await response.json();
         setHealthStatus(health);
@@ -60,7 +61,7 @@ const ProcessingStatus: React.FC<ProcessingStatusProps> = ({
   const performCleanup = async () => {
     setIsPerformingCleanup(true);
     try {
-      const response = await fetch('http://localhost:8000/api/cleanup', {
+      const response = await fetch(backendUrl('/api/cleanup'), {
         method: 'POST',
       });
       if (response.ok) {
@@ -1,4 +1,5 @@
 import React, { useState } from 'react'
+import { backendUrl } from '@/services/backendClient';
 
 interface TextItem {
   id: string
@@ -53,7 +54,7 @@ const TextBatchProcessor: React.FC<TextBatchProcessorProps> = ({ onResults }) =>
       if (!item.text.trim()) continue
 
       try {
-        const response = await fetch('http://localhost:8000/api/process/text-to-schema', {
+        const response = await fetch(backendUrl('/api/process/text-to-schema'), {
           method: 'POST',
           headers: {
             'Content-Type': 'application/json',
@@ -16,6 +16,7 @@ import { SchemaTab, TextToSchemaResult } from '../types/textToSchema';
 import { SchemaManager } from './schema/SchemaManager';
 import { StableAllotmentContainer } from './layout/StableAllotmentContainer';
 import { schemaApi } from '../services/schema/schemaApi';
+import { backendUrl } from '@/services/backendClient';
 
 interface TextToSchemaWorkspaceProps {
   onExit: () => void;
@@ -206,7 +207,7 @@ export const TextToSchemaWorkspace: React.FC<TextToSchemaWorkspaceProps> = ({
       const delays = [400, 800, 1200, 2000, 3000, 5000, 8000];
       for (let i = 0; i < delays.length; i++) {
         try {
-          const r = await fetch('http://localhost:8000/api/health', { cache: 'no-store' as RequestCache });
+          const r = await fetch(backendUrl('/api/health'), { cache: 'no-store' as RequestCache });
           if (r.ok) return true;
         } catch {}
         await new Promise(r => setTimeout(r, delays[i]));
@@ -18,6 +18,7 @@ import { ConfirmDeleteModal } from './modals/ConfirmDeleteModal';
 import { dossierApi } from '../../services/dossier/dossierApi';
 import { finalizedApi } from '../../services/dossier/finalizedApi';
 import { BulkDeleteView } from './bulk/BulkDeleteView';
+import { backendReady, backendUrl, withBackendToken } from '@/services/backendClient';
 
 // ============================================================================
 // MAIN DOSSIER MANAGER COMPONENT
@@ -161,7 +162,7 @@ export const DossierManager: React.FC<DossierManagerProps> = ({
     let reconnectTimer: number | null = null;
     const connect = () => {
       try {
-        es = new EventSource('http://localhost:8000/api/dossier/events');
+        es = new EventSource(withBackendToken(backendUrl('/api/dossier/events')));
         es.onmessage = (ev) => {
           try {
             const data = JSON.parse(ev.data || '{}');
@@ -201,6 +202,8 @@ export const DossierManager: React.FC<DossierManagerProps> = ({
       try {
         const ok = await dossierApi.health(1000);
         if (ok) {
+          // The stream URL carries the shell's token; wait until it is known
+          await backendReady();
           connect();
           // kick a soft refresh immediately after health success
           try { refreshSoftRef.current?.(); } catch {}
@@ -20,6 +20,7 @@ import { FinalDraftSelector } from './FinalDraftSelector';
 import { useDossierManager } from '../../hooks/useDossierManager';
 import { saveDossierEditAPI } from '../../services/imageProcessingApi';
 import { StableAllotmentContainer } from '../layout/StableAllotmentContainer';
+import { backendUrl } from '@/services/backendClient';
 
 
 interface ImageProcessingWorkspaceProps {
@@ -729,7 +730,7 @@ export const ImageProcessingWorkspace: React.FC<ImageProcessingWorkspaceProps> =
                       form.append('transcription_id', String(transcriptionId || ''));
                       form.append('alignment_draft_index', String(i));
                       form.append('purge', 'true');
-                      calls.push(fetch('http://localhost:8000/api/dossier/versions/revert-to-v1', { method: 'POST', body: form }).catch(() => null));
+                      calls.push(fetch(backendUrl('/api/dossier/versions/revert-to-v1'), { method: 'POST', body: form }).catch(() => null));
                     }
                     await Promise.all(calls);
                   } catch {}
@@ -1,5 +1,6 @@
 import React, { useEffect, useRef, useState } from 'react';
 import maplibregl from 'maplibre-gl';
+import { backendUrl, transformBackendRequest } from '@/services/backendClient';
 
 interface CleanMapProps {
   center?: { lat: number; lon: number };
@@ -28,12 +29,13 @@ export const CleanMap: React.FC<CleanMapProps> = ({
 
     const map = new maplibregl.Map({
       container: containerRef.current,
+      transformRequest: transformBackendRequest,
       style: {
         version: 8,
         sources: {
           'raster-tiles': {
             type: 'raster',
-            tiles: ['http://localhost:8000/api/mapping/tile/usgs_topo/{z}/{x}/{y}'],
+            tiles: [backendUrl('/api/mapping/tile/usgs_topo/{z}/{x}/{y}')],
             tileSize: 256,
             minzoom: 0,
             maxzoom: 16,
@@ -2,6 +2,7 @@ import React, { useEffect, useRef, useMemo } from 'react';
 import maplibregl, { Map as MapLibreMap, LngLatBoundsLike } from 'maplibre-gl';
 // CSS is imported globally in pages/_app.tsx
 import { mappingApi, type PLSSDescription } from '../../services/mappingApi';
+import { backendUrl, transformBackendRequest } from '@/services/backendClient';
 
 interface MapViewerProps {
   polygonData?: any;
@@ -12,7 +13,7 @@ interface MapViewerProps {
   plssPadding?: number;
 }
 
-const API_BASE = 'http://localhost:8000/api/mapping';
+const API_BASE = () => backendUrl('/api/mapping');
 
 export const MapViewer: React.FC<MapViewerProps> = ({
   polygonData,
@@ -111,6 +112,7 @@ export const MapViewer: React.FC<MapViewerProps> = ({
 
     const map = new maplibregl.Map({
       container: containerRef.current,
+      transformRequest: transformBackendRequest,
       style: { version: 8, sources: {}, layers: [] } as any,
       center: [bestCenter.lon, bestCenter.lat],
       zoom: bestZoom,
@@ -128,7 +130,7 @@ export const MapViewer: React.FC<MapViewerProps> = ({
       if (!map.getSource(rasterId)) {
         map.addSource(rasterId, {
           type: 'raster',
-          tiles: [`${API_BASE}/tile/${provider}/{z}/{x}/{y}`],
+          tiles: [`${API_BASE()}/tile/${provider}/{z}/{x}/{y}`],
           tileSize: 256,
           attribution: '© USGS'
         });
@@ -1,5 +1,6 @@
 import React, { useEffect, useRef } from 'react';
 import maplibregl, { Map as MapLibreMap } from 'maplibre-gl';
+import { backendUrl, transformBackendRequest } from '@/services/backendClient';
 
 export interface MapEngineProps {
 	center: { lat: number; lon: number };
@@ -34,7 +35,7 @@ export const MapEngine: React.FC<MapEngineProps> = ({
 				sources: {
 					'raster-tiles': {
 						type: 'raster',
-						tiles: ['http://localhost:8000/api/mapping/tile/usgs_topo/{z}/{x}/{y}'],
+						tiles: [backendUrl('/api/mapping/tile/usgs_topo/{z}/{x}/{y}')],
 						tileSize: 256,
 						minzoom: 0,
 						maxzoom: 16,
@@ -48,6 +49,7 @@ export const MapEngine: React.FC<MapEngineProps> = ({
 
 		const map = new maplibregl.Map({
 			container: containerRef.current,
+			transformRequest: transformBackendRequest,
 			style,
 			center: [center.lon, center.lat],
 			zoom,
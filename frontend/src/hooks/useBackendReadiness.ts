@@ -1,4 +1,5 @@
 import { useEffect, useState } from 'react';
+import { backendUrl } from '@/services/backendClient';
 
 interface BackendStatus {
   ready: boolean;
@@ -53,7 +54,7 @@ export function useBackendReadiness(): BackendStatus {
     const poll = async () => {
       while (!cancelled) {
         try {
-          const res = await fetch('http://127.0.0.1:8000/api/health', {
+          const res = await fetch(backendUrl('/api/health'), {
             cache: 'no-store',
           });
           if (res.ok) {
@@ -10,6 +10,7 @@ import { useProcessingPoller } from './useProcessingPoller';
 import { Dossier, DossierPath, DossierManagerState, DossierAction, SortOption } from '../types/dossier';
 import { dossierApi, DossierApiError } from '../services/dossier/dossierApi';
 import { getCachedDossiers } from '@/services/dossier/dossierPreload';
+import { backendUrl, withBackendToken } from '@/services/backendClient';
 
 // ============================================================================
 // INITIAL STATE
@@ -629,7 +630,7 @@ export function useDossierManager() {
 
     try {
       // New job-based bulk delete: start job, drive progress via SSE then polling
-      const startRes = await fetch('http://localhost:8000/api/dossier-management/bulk/start', {
+      const startRes = await fetch(backendUrl('/api/dossier-management/bulk/start'), {
         method: 'POST',
         headers: { 'Content-Type': 'application/json' },
         body: JSON.stringify({ targetIds: itemIds })
@@ -651,7 +652,7 @@ export function useDossierManager() {
           if (ok) resolve(); else reject(new Error('Bulk delete failed'));
         };
         try {
-          es = new EventSource(`http://localhost:8000/api/dossier/bulk/progress/${encodeURIComponent(jobId)}`);
+          es = new EventSource(withBackendToken(backendUrl(`/api/dossier/bulk/progress/${encodeURIComponent(jobId)}`)));
           es.onmessage = (ev) => {
             try {
               const data = JSON.parse(ev.data || '{}');
@@ -672,7 +673,7 @@ export function useDossierManager() {
             const poll = async () => {
               try {
                 while (!completed) {
-                  const stRes = await fetch(`http://localhost:8000/api/dossier-management/bulk/status/${encodeURIComponent(jobId)}`);
+                  const stRes = await fetch(backendUrl(`/api/dossier-management/bulk/status/${encodeURIComponent(jobId)}`));
                   if (!stRes.ok) throw new Error('status failed');
                   const st = await stRes.json();
                   doneCount = st.done || 0;
@@ -1,6 +1,7 @@
 import { useState, useCallback, useEffect } from 'react';
 import { ProcessingResult, EnhancementSettings, RedundancySettings, ConsensusSettings } from '../types/imageProcessing';
 import { fetchModelsAPI, processFilesAPI } from '../services/imageProcessingApi';
+import { backendUrl } from '@/services/backendClient';
 
 interface UseImageProcessingOptions {
   onProcessingComplete?: () => void;
@@ -206,7 +207,7 @@ export const useImageProcessing = (options?: UseImageProcessingOptions) => {
             const maxAttempts = 600; // ~5 minutes at 500ms interval
             while (attempts < maxAttempts) {
               try {
-                const resp = await fetch(`http://localhost:8000/api/image-to-text/jobs/${jobId}`);
+                const resp = await fetch(backendUrl(`/api/image-to-text/jobs/${jobId}`));
                 const data = await resp.json();
                 if (data && typeof data.status === 'string') {
                   if (data.status === 'SUCCEEDED') {
@@ -310,7 +311,7 @@ export const useImageProcessing = (options?: UseImageProcessingOptions) => {
     
     const loadExtractionModes = async () => {
       try {
-        const response = await fetch('http://localhost:8000/api/process/types')
+        const response = await fetch(backendUrl('/api/process/types'))
         const data = await response.json()
         
         if (data.status === 'success' && data.processing_types?.['image-to-text']?.extraction_modes) {
@@ -347,7 +348,7 @@ export const useImageProcessing = (options?: UseImageProcessingOptions) => {
     const interval = setInterval(async () => {
       if (cancelled) return;
       try {
-        const resp = await fetch('http://localhost:8000/api/image-to-text/jobs');
+        const resp = await fetch(backendUrl('/api/image-to-text/jobs'));
         if (!resp.ok) return;
         const payload = await resp.json();
         const jobs = (payload?.jobs || []) as Array<{ id: string; status: string; result?: any; error?: string }>;
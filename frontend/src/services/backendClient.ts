@@ -0,0 +1,140 @@
+// Address of the backend and the shell's token for the webview's direct requests.
+// A backend spawned by the desktop shell only answers callers that present the
+// token it was started with: `X-Plattera-Token`, or `?plattera_token=` for
+// EventSource streams and map tiles, which can't set headers. `installBackendFetch`
+// wraps window.fetch so every request to the backend carries the token;
+// EventSource URLs go through `withBackendToken` and maps through
+// `transformBackendRequest`. The shell may put the backend on another port
+// than 8000 (busy port, warm-standby restart): the base comes from
+// `get_backend_url` and follows `backend-endpoint-changed`, and URLs built
+// against an earlier base are moved over. In a plain browser (next dev
+// against a backend started by hand) there is no shell and no token.
+
+const TOKEN_HEADER = 'X-Plattera-Token';
+const TOKEN_PARAM = 'plattera_token';
+/** Endpoints the backend leaves open; they never wait for the token. */
+const OPEN_PATHS = ['/', '/api/health'];
+
+const DEFAULT_BASE = (
+  (typeof process !== 'undefined' && process.env && (process.env.NEXT_PUBLIC_API_BASE as string)) ||
+  'http://localhost:8000'
+).replace(/\/+$/, '');
+
+let base = DEFAULT_BASE;
+let token: string | null = null;
+let ready: Promise<void> | null = null;
+/** Bases the backend was reachable at earlier in this session. */
+const previousBases = new Set<string>();
+
+const isTauri = () => typeof window !== 'undefined' && !!(window as any).__TAURI__;
+
+export function backendBase(): string {
+  return base;
+}
+
+/** Absolute URL for a backend path such as `/api/health`. */
+export function backendUrl(path: string): string {
+  return `${base}${path}`;
+}
+
+function setBase(url: string | null | undefined): void {
+  const next = (url || '').replace(/\/+$/, '');
+  if (!next || next === base) return;
+  previousBases.add(base);
+  previousBases.delete(next);
+  base = next;
+}
+
+function matchesBase(url: string, candidate: string): boolean {
+  return url === candidate || url.startsWith(`${candidate}/`) || url.startsWith(`${candidate}?`);
+}
+
+/** `url` moved onto the current base if it was built against an earlier one. */
+function rebase(url: string): string {
+  for (const old of Array.from(previousBases)) {
+    if (matchesBase(url, old)) return `${base}${url.slice(old.length)}`;
+  }
+  return url;
+}
+
+function backendPath(url: string): string | null {
+  if (!matchesBase(url, base)) return null;
+  return url.slice(base.length).split('?')[0] || '/';
+}
+
+/** `url` with the token as a query parameter, for requests that can't send headers. */
+export function withBackendToken(url: string): string {
+  if (!token || backendPath(url) === null) return url;
+  return `${url}${url.includes('?') ? '&' : '?'}${TOKEN_PARAM}=${encodeURIComponent(token)}`;
+}
+
+/** maplibre `transformRequest`: adds the token to tile requests for the backend. */
+export function transformBackendRequest(url: string): { url: string } {
+  return { url: withBackendToken(rebase(url)) };
+}
+
+async function refreshToken(): Promise<void> {
+  const { invoke } = await import('@tauri-apps/api/core');
+  try {
+    token = (await invoke<string | null>('get_backend_token')) ?? null;
+  } catch {
+    token = null;
+  }
+}
+
+/**
+ * Start the shell's backend and pick up its address and token. Requests to
+ * the backend wait for this, so nothing goes out unauthenticated or to a stale
+ * port while it runs.
+ */
+export function connectBackend(): Promise<void> {
+  if (!ready) {
+    ready = (async () => {
+      if (!isTauri()) return;
+      const { invoke } = await import('@tauri-apps/api/core');
+      const { listen } = await import('@tauri-apps/api/event');
+      await listen<{ port: number; base_url: string }>('backend-endpoint-changed', (event) => {
+        setBase(event.payload.base_url);
+        void refreshToken();
+      });
+      try {
+        await invoke('start_backend');
+      } catch (e) {
+        console.warn('Backend start failed', e);
+      }
+      try {
+        setBase(await invoke<string>('get_backend_url'));
+      } catch (e) {
+        console.warn('Backend address lookup failed', e);
+      }
+      await refreshToken();
+    })();
+  }
+  return ready;
+}
+
+/** Resolves once `connectBackend` has finished (immediately when it was never called). */
+export function backendReady(): Promise<void> {
+  return ready ?? Promise.resolve();
+}
+
+/** Wrap window.fetch so requests to the backend carry the shell's token. */
+export function installBackendFetch(): void {
+  if (typeof window === 'undefined' || (window.fetch as any).__plattera) return;
+  const original = window.fetch.bind(window);
+  const wrapped = async (input: RequestInfo | URL, init?: RequestInit): Promise<Response> => {
+    const requestUrl = () => (typeof input === 'string' ? input : input instanceof URL ? input.href : input.url);
+    const path = backendPath(rebase(requestUrl()));
+    if (path !== null && !OPEN_PATHS.includes(path)) await backendReady();
+    const url = requestUrl();
+    const target = rebase(url);
+    if (target !== url) input = input instanceof Request ? new Request(target, input) : target;
+    const targetPath = backendPath(target);
+    if (targetPath === null || OPEN_PATHS.includes(targetPath) || !token) return original(input, init);
+    const headers = new Headers(init?.headers ?? (input instanceof Request ? input.headers : undefined));
+    headers.set(TOKEN_HEADER, token);
+    return original(input, { ...init, headers });
+  };
+  (wrapped as any).__plattera = true;
+  window.fetch = wrapped as typeof window.fetch;
+}
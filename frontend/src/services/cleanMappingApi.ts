@@ -3,7 +3,9 @@
  * Simple, fast mapping operations using new backend services
  */
 
-const API_BASE = 'http://localhost:8000/api/mapping';
+import { backendUrl } from '@/services/backendClient';
+
+const API_BASE = () => backendUrl('/api/mapping');
 
 export interface ProjectPolygonRequest {
   local_coordinates: Array<{ x: number; y: number }>;
@@ -61,7 +63,7 @@ export class CleanMappingApi {
     try {
       console.log('🗺️ Projecting polygon with clean API');
       
-      const response = await fetch(`${API_BASE}/project-polygon`, {
+      const response = await fetch(`${API_BASE()}/project-polygon`, {
         method: 'POST',
         headers: {
           'Content-Type': 'application/json',
@@ -150,7 +152,7 @@ export class CleanMappingApi {
    */
   async getTileCacheStats() {
     try {
-      const response = await fetch(`${API_BASE}/tile-cache/stats`);
+      const response = await fetch(`${API_BASE()}/tile-cache/stats`);
       return await response.json();
     } catch (error) {
       console.error('❌ Failed to get cache stats:', error);
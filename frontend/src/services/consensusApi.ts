@@ -5,6 +5,8 @@
  * Handles API calls for generating consensus drafts from alignment results.
  */
 
+import { backendUrl } from '@/services/backendClient';
+
 export interface ConsensusGenerationResponse {
   success: boolean;
   enhanced_alignment_results?: any;
@@ -21,7 +23,7 @@ export interface ConsensusGenerationResponse {
  */
 export async function generateConsensusDrafts(alignmentResults: any): Promise<ConsensusGenerationResponse> {
   try {
-    const response = await fetch('http://localhost:8000/api/consensus/generate-consensus', {  // ← FIX: Add full URL
+    const response = await fetch(backendUrl('/api/consensus/generate-consensus'), {  // ← FIX: Add full URL
       method: 'POST',
       headers: {
         'Content-Type': 'application/json',
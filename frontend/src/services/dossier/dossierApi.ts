@@ -6,6 +6,7 @@
 // ============================================================================
 
 import { Dossier, Segment, Run, Draft, DossierApiResponse, CreateDossierData, UpdateDossierData, CreateSegmentData, BulkAction } from '../../types/dossier';
+import { backendUrl } from '@/services/backendClient';
 
 class DossierApiError extends Error {
   constructor(message: string, public statusCode?: number, public details?: any) {
@@ -15,7 +16,9 @@ class DossierApiError extends Error {
 }
 
 class DossierApiClient {
-  private baseUrl = 'http://localhost:8000/api';
+  private get baseUrl() {
+    return backendUrl('/api');
+  }
   private retryAttempts = 5; // trimmed for quicker feedback
   private retryDelay = 400;  // slightly lower base delay
   private warmedUp = false;  // mark after first success
@@ -3,7 +3,9 @@
  * Provides endpoints to project polygons and resolve POB
  */
 
-const API_BASE = 'http://localhost:8000/api/mapping/georeference';
+import { backendUrl } from '@/services/backendClient';
+
+const API_BASE = () => backendUrl('/api/mapping/georeference');
 
 export interface LocalCoordinate {
   x: number;
@@ -87,7 +89,7 @@ export interface ResolvePOBResponse {
 class GeoreferenceApiService {
   async project(request: GeoreferenceProjectRequest): Promise<GeoreferenceProjectResponse> {
     try {
-      const res = await fetch(`${API_BASE}/project`, {
+      const res = await fetch(`${API_BASE()}/project`, {
         method: 'POST',
         headers: { 'Content-Type': 'application/json' },
         body: JSON.stringify(request)
@@ -108,7 +110,7 @@ class GeoreferenceApiService {
 
   async projectFromSchema(request: GeoreferenceProjectFromSchemaRequest): Promise<GeoreferenceProjectResponse> {
     try {
-      const res = await fetch(`${API_BASE}/project-from-schema`, {
+      const res = await fetch(`${API_BASE()}/project-from-schema`, {
         method: 'POST',
         headers: { 'Content-Type': 'application/json' },
         body: JSON.stringify(request)
@@ -129,7 +131,7 @@ class GeoreferenceApiService {
 
   async resolvePOB(request: ResolvePOBRequest): Promise<ResolvePOBResponse> {
     try {
-      const res = await fetch(`${API_BASE}/resolve-pob`, {
+      const res = await fetch(`${API_BASE()}/resolve-pob`, {
         method: 'POST',
         headers: { 'Content-Type': 'application/json' },
         body: JSON.stringify(request)
@@ -152,7 +154,7 @@ export const saveGeoreferenceForDossier = async (payload: {
   georef_result: any;
   metadata?: any;
 }) => {
-  const res = await fetch(`${API_BASE}/save`, {
+  const res = await fetch(`${API_BASE()}/save`, {
     method: 'POST',
     headers: { 'Content-Type': 'application/json' },
     body: JSON.stringify(payload)
@@ -165,25 +167,25 @@ export const saveGeoreferenceForDossier = async (payload: {
 };
 
 export const listGeoreferences = async (dossierId: string) => {
-  const res = await fetch(`${API_BASE}/list?dossier_id=${encodeURIComponent(dossierId)}`);
+  const res = await fetch(`${API_BASE()}/list?dossier_id=${encodeURIComponent(dossierId)}`);
   if (!res.ok) throw new Error(`HTTP ${res.status}: ${res.statusText}`);
   return res.json();
 };
 
 export const getGeoreference = async (dossierId: string, georefId: string) => {
-  const res = await fetch(`${API_BASE}/get?dossier_id=${encodeURIComponent(dossierId)}&georef_id=${encodeURIComponent(georefId)}`);
+  const res = await fetch(`${API_BASE()}/get?dossier_id=${encodeURIComponent(dossierId)}&georef_id=${encodeURIComponent(georefId)}`);
   if (!res.ok) throw new Error(`HTTP ${res.status}: ${res.statusText}`);
   return res.json();
 };
 
 export const deleteGeoreference = async (dossierId: string, georefId: string) => {
-  const res = await fetch(`${API_BASE}/delete?dossier_id=${encodeURIComponent(dossierId)}&georef_id=${encodeURIComponent(georefId)}`, { method: 'DELETE' });
+  const res = await fetch(`${API_BASE()}/delete?dossier_id=${encodeURIComponent(dossierId)}&georef_id=${encodeURIComponent(georefId)}`, { method: 'DELETE' });
   if (!res.ok) throw new Error(`HTTP ${res.status}: ${res.statusText}`);
   return res.json();
 };
 
 export const bulkDeleteGeoreferences = async (dossierId: string, georefIds: string[]) => {
-  const res = await fetch(`${API_BASE}/bulk-delete`, {
+  const res = await fetch(`${API_BASE()}/bulk-delete`, {
     method: 'POST',
     headers: { 'Content-Type': 'application/json' },
     body: JSON.stringify({ dossier_id: dossierId, georef_ids: georefIds })
@@ -193,7 +195,7 @@ export const bulkDeleteGeoreferences = async (dossierId: string, georefIds: stri
 };
 
 export const listAllGeoreferences = async () => {
-  const res = await fetch(`${API_BASE}/list-all`);
+  const res = await fetch(`${API_BASE()}/list-all`);
   if (!res.ok) throw new Error(`HTTP ${res.status}: ${res.statusText}`);
   return res.json();
 };
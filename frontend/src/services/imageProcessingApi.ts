@@ -1,4 +1,5 @@
 import { EnhancementSettings, ProcessingResult, RedundancySettings, ConsensusSettings, AlignmentDraft, AlignmentResult } from '../types/imageProcessing';
+import { backendUrl } from '@/services/backendClient';
 
 // --- API Calls for Image Processing Feature ---
 
@@ -36,7 +37,7 @@ export const processFilesAPI = async (
     if (userInstruction) form.append('user_instruction', userInstruction);
     // Do NOT send a single transcription_id for batch; server will generate per-file IDs
 
-    const resp = await fetch('http://localhost:8000/api/image-to-text/jobs', { method: 'POST', body: form });
+    const resp = await fetch(backendUrl('/api/image-to-text/jobs'), { method: 'POST', body: form });
     const data = await resp.json();
     if (!resp.ok) {
       throw new Error(data?.detail || data?.error || `HTTP ${resp.status}`);
@@ -71,7 +72,7 @@ export const processFilesAPI = async (
     if (transcriptionId) formData.append('transcription_id', transcriptionId);
     if (userInstruction) formData.append('user_instruction', userInstruction);
 
-    const endpoint = dossierId ? 'http://localhost:8000/api/dossier/process' : 'http://localhost:8000/api/process';
+    const endpoint = dossierId ? backendUrl('/api/dossier/process') : backendUrl('/api/process');
     const response = await fetch(endpoint, { method: 'POST', body: formData });
     const data = await response.json();
     if (data.status === 'success') {
@@ -88,7 +89,7 @@ export const processFilesAPI = async (
 
 export const fetchModelsAPI = async () => {
   try {
-    const response = await fetch('http://localhost:8000/api/models');
+    const response = await fetch(backendUrl('/api/models'));
     
     if (!response.ok) {
       throw new Error(`HTTP ${response.status}: ${response.statusText}`);
@@ -121,7 +122,7 @@ export const retryLlmConsensusAPI = async (params: {
   dossierId?: string;
   transcriptionId?: string;
 }): Promise<{ success: boolean; consensus_text?: string; consensus_title?: string; error?: string }> => {
-  const response = await fetch('http://localhost:8000/api/llm-consensus/generate', {
+  const response = await fetch(backendUrl('/api/llm-consensus/generate'), {
     method: 'POST',
     headers: { 'Content-Type': 'application/json' },
     body: JSON.stringify({
@@ -146,7 +147,7 @@ export const alignDraftsAPI = async (
     console.log(`Aligning ${drafts.length} drafts with strategy: ${consensusStrategy}`);
     
     const attempt = async () => {
-      const response = await fetch('http://localhost:8000/api/alignment/align-drafts', {
+      const response = await fetch(backendUrl('/api/alignment/align-drafts'), {
         method: 'POST',
         headers: {
           'Content-Type': 'application/json',
@@ -231,7 +232,7 @@ export const selectFinalDraftAPI = async (
 
   console.log('📤 Sending FormData with keys:', Array.from(formData.keys()));
 
-  const response = await fetch('http://localhost:8000/api/final-draft/select-final-draft', {
+  const response = await fetch(backendUrl('/api/final-draft/select-final-draft'), {
     method: 'POST',
     body: formData
   });
@@ -277,7 +278,7 @@ export const saveDossierEditAPI = async (params: {
     formData.append('alignment_draft_index', String(params.alignmentDraftIndex));
   }
 
-  const response = await fetch('http://localhost:8000/api/dossier/edits/save', {
+  const response = await fetch(backendUrl('/api/dossier/edits/save'), {
     method: 'POST',
     body: formData
   });
@@ -296,7 +297,7 @@ export const alignDraftsByIdsAPI = async (params: {
   versionPolicy?: 'prefer_v2_else_v1' | 'prefer_v1_else_v2';
   excludeAlignmentVersions?: boolean;
 }): Promise<AlignmentResult> => {
-  const response = await fetch('http://localhost:8000/api/alignment/align-drafts/by-ids', {
+  const response = await fetch(backendUrl('/api/alignment/align-drafts/by-ids'), {
     method: 'POST',
     headers: { 'Content-Type': 'application/json' },
     body: JSON.stringify({
@@ -336,7 +337,7 @@ export const revertToV1API = async (params: {
     formData.append('draft_index', String(params.draftIndex));
   }
 
-  const response = await fetch('http://localhost:8000/api/dossier/versions/revert-to-v1', {
+  const response = await fetch(backendUrl('/api/dossier/versions/revert-to-v1'), {
     method: 'POST',
     body: formData
   });
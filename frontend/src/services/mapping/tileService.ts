@@ -3,6 +3,8 @@
  * High-performance tile loading with backend integration and cache awareness
  */
 
+import { backendUrl } from '@/services/backendClient';
+
 export interface TileProvider {
   name: string;
   description: string;
@@ -29,7 +31,9 @@ export interface TileConfig {
 }
 
 class TileService {
-  private apiBase = 'http://localhost:8000/api/mapping';
+  private get apiBase() {
+    return backendUrl('/api/mapping');
+  }
   private providers: Map<string, TileProvider> = new Map();
   private config: TileConfig | null = null;
 
@@ -3,7 +3,9 @@
  * Handles API calls for geographic mapping functionality
  */
 
-const API_BASE = 'http://localhost:8000/api/mapping';
+import { backendUrl } from '@/services/backendClient';
+
+const API_BASE = () => backendUrl('/api/mapping');
 
 export interface PLSSDescription {
   state: string;
@@ -188,7 +190,7 @@ export async function extractPLSSInfo(schemaData: any): Promise<{
   try {
     console.log('🗺️ Extracting PLSS info for mapping:', schemaData);
     
-    const response = await fetch('http://localhost:8000/api/mapping/extract-plss-info', {
+    const response = await fetch(backendUrl('/api/mapping/extract-plss-info'), {
       method: 'POST',
       headers: { 'Content-Type': 'application/json' },
       body: JSON.stringify(schemaData)
@@ -214,7 +216,7 @@ class MappingApiService {
    */
   async projectPolygonToMap(request: ProjectPolygonRequest): Promise<ProjectPolygonResponse> {
     try {
-      const response = await fetch(`${API_BASE}/project-polygon`, {
+      const response = await fetch(`${API_BASE()}/project-polygon`, {
         method: 'POST',
         headers: {
           'Content-Type': 'application/json',
@@ -249,7 +251,7 @@ class MappingApiService {
    */
   async getMapTiles(request: TileRequest): Promise<TileResponse> {
     try {
-      const response = await fetch(`${API_BASE}/get-map-tiles`, {
+      const response = await fetch(`${API_BASE()}/get-map-tiles`, {
         method: 'POST',
         headers: {
           'Content-Type': 'application/json',
@@ -277,7 +279,7 @@ class MappingApiService {
    */
   async resolvePLSSCoordinates(request: PLSSResolveRequest): Promise<PLSSResolveResponse> {
     try {
-      const response = await fetch(`${API_BASE}/resolve-plss`, {
+      const response = await fetch(`${API_BASE()}/resolve-plss`, {
         method: 'POST',
         headers: {
           'Content-Type': 'application/json',
@@ -305,7 +307,7 @@ class MappingApiService {
    */
   async getTileProviders(): Promise<TileProvidersResponse> {
     try {
-      const response = await fetch(`${API_BASE}/tile-providers`);
+      const response = await fetch(`${API_BASE()}/tile-providers`);
 
       if (!response.ok) {
         const errorData = await response.json();
@@ -327,7 +329,7 @@ class MappingApiService {
    */
   async getPLSSStates(): Promise<PLSSStatesResponse> {
     try {
-      const response = await fetch(`${API_BASE}/plss-states`);
+      const response = await fetch(`${API_BASE()}/plss-states`);
 
       if (!response.ok) {
         const errorData = await response.json();
@@ -349,7 +351,7 @@ class MappingApiService {
     padding: number = 0.1
   ): Promise<{ success: boolean; center?: { lat: number; lon: number }; bounds?: GeographicBounds; error?: string }>{
     try {
-      const response = await fetch(`${API_BASE}/plss/section-view`, {
+      const response = await fetch(`${API_BASE()}/plss/section-view`, {
         method: 'POST',
         headers: { 'Content-Type': 'application/json' },
         body: JSON.stringify({ plss_description: plss, padding })
@@ -377,7 +379,7 @@ class MappingApiService {
     error?: string;
   }> {
     try {
-      const res = await fetch(`${API_BASE}/plss/overlay`, {
+      const res = await fetch(`${API_BASE()}/plss/overlay`, {
         method: 'POST',
         headers: { 'Content-Type': 'application/json' },
         body: JSON.stringify({ plss_description: plss }),
@@ -404,7 +406,7 @@ class MappingApiService {
     error?: string;
   }> {
     try {
-      const res = await fetch(`${API_BASE}/validate-georef`, {
+      const res = await fetch(`${API_BASE()}/validate-georef`, {
         method: 'POST',
         headers: { 'Content-Type': 'application/json' },
         body: JSON.stringify({ plss_description: plss, geographic_polygon: geographicPolygon }),
@@ -425,7 +427,7 @@ class MappingApiService {
    */
   async clearCache(cacheType: 'plss' | 'tiles' | 'all' = 'all'): Promise<{ success: boolean; error?: string }> {
     try {
-      const response = await fetch(`${API_BASE}/cache/clear`, {
+      const response = await fetch(`${API_BASE()}/cache/clear`, {
         method: 'POST',
         headers: {
           'Content-Type': 'application/json',
@@ -453,7 +455,7 @@ class MappingApiService {
    */
   async getCacheStats(): Promise<CacheStatsResponse> {
     try {
-      const response = await fetch(`${API_BASE}/cache/stats`);
+      const response = await fetch(`${API_BASE()}/cache/stats`);
 
       if (!response.ok) {
         const errorData = await response.json();
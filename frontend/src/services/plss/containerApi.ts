@@ -4,6 +4,8 @@
  * Clean, focused API for parcel-relative overlay data
  */
 
+import { backendUrl } from '@/services/backendClient';
+
 export type ContainerLayer = 
   | 'grid' | 'grid-labels'
   | 'township' | 'township-labels'
@@ -38,7 +40,9 @@ export interface ContainerResponse {
 }
 
 export class ContainerApi {
-  private baseUrl = 'http://localhost:8000/api/plss/container';
+  private get baseUrl() {
+    return backendUrl('/api/plss/container');
+  }
 
   /**
    * Get container overlay for a specific layer
@@ -4,6 +4,8 @@
  * Clean, focused API for parcel-relative overlay data
  */
 
+import { backendUrl } from '@/services/backendClient';
+
 export type ContainerOverlayLayer = 'township' | 'range' | 'grid' | 'sections' | 'quarter-sections';
 
 export interface ContainerOverlayRequest {
@@ -32,7 +34,9 @@ export interface ContainerOverlayResponse {
 }
 
 export class ContainerOverlayApi {
-  private baseUrl = 'http://localhost:8000/api/plss/container';
+  private get baseUrl() {
+    return backendUrl('/api/plss/container');
+  }
 
   /**
    * Get container overlay for a specific layer
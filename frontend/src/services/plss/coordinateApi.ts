@@ -7,6 +7,8 @@
  * Responsibility: Direct backend communication and caching for coordinate operations
  */
 
+import { backendUrl } from '@/services/backendClient';
+
 export interface PLSSCoordinateRequest {
   latitude: number;
   longitude: number;
@@ -43,7 +45,9 @@ export interface PLSSCoordinateResult {
  * Provides direct backend communication for PLSS coordinate operations
  */
 export class PLSSCoordinateService {
-  private readonly apiBase = 'http://localhost:8000/api/plss';
+  private get apiBase() {
+    return backendUrl('/api/plss');
+  }
 
   /**
    * Find nearest PLSS feature to given coordinates
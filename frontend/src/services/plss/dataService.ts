@@ -8,6 +8,8 @@
  * Responsibility: Data operations only - no UI state management
  */
 
+import { backendUrl } from '@/services/backendClient';
+
 // Note: we explicitly track a "canceled" status in the frontend hook so that
 // UX and logs can distinguish "never downloaded" from "user canceled an
 // in-flight job". This is important for restart semantics and diagnosis.
@@ -46,7 +48,9 @@ export interface PLSSDataDownloadResult {
  * - Extracting state information from various data sources
  */
 export class PLSSDataService {
-  private readonly apiBase = 'http://localhost:8000/api/mapping';
+  private get apiBase() {
+    return backendUrl('/api/mapping');
+  }
 
   /**
    * Check if PLSS data is available locally for a given state
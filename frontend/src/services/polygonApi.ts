@@ -3,6 +3,8 @@
  * Handles all API calls related to polygon generation from schema data
  */
 
+import { backendUrl } from '@/services/backendClient';
+
 export interface PolygonDrawingRequest {
   parcel_data: any;
   options?: {
@@ -56,7 +58,7 @@ export interface PolygonDrawingResponse {
   error?: string;
 }
 
-const API_BASE_URL = 'http://localhost:8000/api/polygon';
+const API_BASE_URL = () => backendUrl('/api/polygon');
 
 /**
  * Generate polygon coordinates from structured parcel data
@@ -68,7 +70,7 @@ export const drawPolygonFromSchema = async (request: PolygonDrawingRequest): Pro
       options: request.options
     });
 
-    const response = await fetch(`${API_BASE_URL}/draw`, {
+    const response = await fetch(`${API_BASE_URL()}/draw`, {
       method: 'POST',
       headers: {
         'Content-Type': 'application/json',
@@ -95,7 +97,7 @@ export const drawPolygonFromSchema = async (request: PolygonDrawingRequest): Pro
  */
 export const getPolygonDrawingOptions = async () => {
   try {
-    const response = await fetch(`${API_BASE_URL}/options`);
+    const response = await fetch(`${API_BASE_URL()}/options`);
     
     if (!response.ok) {
       throw new Error(`HTTP ${response.status}: ${response.statusText}`);
@@ -1,5 +1,6 @@
-const API_BASE = (typeof process !== 'undefined' && process.env && (process.env.NEXT_PUBLIC_API_BASE as string)) || 'http://localhost:8000';
-const API_BASE_URL = `${API_BASE}/api/text-to-schema`;
+import { backendUrl } from '@/services/backendClient';
+
+const API_BASE_URL = () => backendUrl('/api/text-to-schema');
 
 export interface SchemaListItem {
   dossier_id: string;
@@ -24,7 +25,7 @@ export interface SchemaArtifact {
 
 class SchemaApiClient {
   async listSchemas(dossierId: string): Promise<SchemaListItem[]> {
-    const url = `${API_BASE_URL}/list?dossier_id=${encodeURIComponent(dossierId)}&t=${Date.now()}`;
+    const url = `${API_BASE_URL()}/list?dossier_id=${encodeURIComponent(dossierId)}&t=${Date.now()}`;
     const res = await fetch(url, { cache: 'no-store' as RequestCache });
     const data = await res.json().catch(() => ({}));
     if (!res.ok) throw new Error(data?.detail || 'Failed to list schemas');
@@ -32,14 +33,14 @@ class SchemaApiClient {
   }
 
   async listAllSchemas(): Promise<SchemaListItem[]> {
-    const res = await fetch(`${API_BASE_URL}/list-all?t=${Date.now()}`, { cache: 'no-store' as RequestCache });
+    const res = await fetch(`${API_BASE_URL()}/list-all?t=${Date.now()}`, { cache: 'no-store' as RequestCache });
     const data = await res.json().catch(() => ({}));
     if (!res.ok) throw new Error(data?.detail || 'Failed to list schemas');
     return data?.schemas || [];
   }
 
   async getSchema(dossierId: string, schemaId: string): Promise<SchemaArtifact> {
-    const url = `${API_BASE_URL}/get?dossier_id=${encodeURIComponent(dossierId)}&schema_id=${encodeURIComponent(schemaId)}&t=${Date.now()}`;
+    const url = `${API_BASE_URL()}/get?dossier_id=${encodeURIComponent(dossierId)}&schema_id=${encodeURIComponent(schemaId)}&t=${Date.now()}`;
     const res = await fetch(url, { cache: 'no-store' as RequestCache });
     const data = await res.json().catch(() => ({}));
     if (!res.ok) throw new Error(data?.detail || 'Failed to get schema');
@@ -47,7 +48,7 @@ class SchemaApiClient {
   }
 
   async deleteSchema(dossierId: string, schemaId: string): Promise<{ status: string; success?: boolean }> {
-    const url = `${API_BASE_URL}/delete?dossier_id=${encodeURIComponent(dossierId)}&schema_id=${encodeURIComponent(schemaId)}`;
+    const url = `${API_BASE_URL()}/delete?dossier_id=${encodeURIComponent(dossierId)}&schema_id=${encodeURIComponent(schemaId)}`;
     const res = await fetch(url, { method: 'DELETE' });
     const data = await res.json().catch(() => ({}));
     if (!res.ok) throw new Error(data?.detail || 'Failed to delete schema');
@@ -55,7 +56,7 @@ class SchemaApiClient {
   }
 
   async purgeSchema(dossierId: string, schemaId: string): Promise<{ status: string; purged_georefs?: string[] }> {
-    const url = `${API_BASE_URL}/purge-schema`;
+    const url = `${API_BASE_URL()}/purge-schema`;
     const res = await fetch(url, {
       method: 'POST',
       headers: { 'Content-Type': 'application/json' },
@@ -67,7 +68,7 @@ class SchemaApiClient {
   }
 
   async renameSchema(dossierId: string, schemaId: string, newLabel: string): Promise<SchemaArtifact> {
-    const url = `${API_BASE_URL}/rename`;
+    const url = `${API_BASE_URL()}/rename`;
     const res = await fetch(url, {
       method: 'POST',
       headers: { 'Content-Type': 'application/json' },
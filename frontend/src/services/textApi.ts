@@ -2,7 +2,9 @@
 // TEXT API CLIENT - FETCH DRAFT CONTENT BY ID
 // ============================================================================
 
-const BASE_URL = 'http://localhost:8000/api';
+import { backendUrl } from '@/services/backendClient';
+
+const BASE_URL = () => backendUrl('/api');
 
 // Simple in-memory caches with TTL and in-flight request de-duplication
 type CacheEntry<T> = { value: T; expiresAt: number };
@@ -106,7 +108,7 @@ export const textApi = {
   },
 
   async getDraftJson(transcriptionId: string, draftId: string, dossierId?: string, options?: { signal?: AbortSignal }): Promise<any> {
-    const primary = `${BASE_URL}/dossier-management/drafts/${encodeURIComponent(draftId)}${dossierId ? `?dossier_id=${encodeURIComponent(dossierId)}` : ''}`;
+    const primary = `${BASE_URL()}/dossier-management/drafts/${encodeURIComponent(draftId)}${dossierId ? `?dossier_id=${encodeURIComponent(dossierId)}` : ''}`;
     const key = cacheKeyJson(draftId, dossierId);
     const cached = getFromCache<any>(JSON_CACHE, key);
     if (cached !== undefined) return cached;
@@ -3,6 +3,8 @@
  * Handles all API calls related to text-to-schema conversion
  */
 
+import { backendUrl } from '@/services/backendClient';
+
 export interface TextToSchemaRequest {
   text: string;
   parcel_id?: string;
@@ -28,15 +30,14 @@ export interface SchemaModelsResponse {
 }
 
 // Prefer environment-configured API base, fallback to localhost dev
-const API_BASE = (typeof process !== 'undefined' && process.env && (process.env.NEXT_PUBLIC_API_BASE as string)) || 'http://localhost:8000';
-const API_BASE_URL = `${API_BASE}/api/text-to-schema`;
+const API_BASE_URL = () => backendUrl('/api/text-to-schema');
 
 /**
  * Convert text to structured parcel schema
  */
 export const convertTextToSchema = async (request: TextToSchemaRequest): Promise<TextToSchemaResponse> => {
   const attempt = async () => {
-    const response = await fetch(`${API_BASE_URL}/convert`, {
+    const response = await fetch(`${API_BASE_URL()}/convert`, {
       method: 'POST',
       headers: {
         'Content-Type': 'application/json',
@@ -84,7 +85,7 @@ export const convertTextToSchema = async (request: TextToSchemaRequest): Promise
  */
 export const getTextToSchemaModels = async (): Promise<SchemaModelsResponse> => {
   try {
-    const response = await fetch(`${API_BASE_URL}/models`);
+    const response = await fetch(`${API_BASE_URL()}/models`);
     
     if (!response.ok) {
       throw new Error(`HTTP ${response.status}: ${response.statusText}`);
@@ -96,7 +97,7 @@ export const getTextToSchemaModels = async (): Promise<SchemaModelsResponse> =>
     console.warn('Failed to load text-to-schema models:', error);
     // Try same-origin fallback
     try {
-      const res = await fetch(`http://localhost:8000/api/text-to-schema/models`);
+      const res = await fetch(backendUrl(`/api/text-to-schema/models`));
       if (res.ok) {
         return await res.json();
       }
@@ -119,7 +120,7 @@ export const getTextToSchemaModels = async (): Promise<SchemaModelsResponse> =>
  */
 export const getParcelSchema = async () => {
   try {
-    const response = await fetch(`${API_BASE_URL}/schema`);
+    const response = await fetch(`${API_BASE_URL()}/schema`);
     
     if (!response.ok) {
       throw new Error(`HTTP ${response.status}: ${response.statusText}`);
@@ -143,7 +144,7 @@ export const saveSchemaForDossier = async (payload: {
   original_text: string;
   metadata?: any;
 }) => {
-  const response = await fetch(`${API_BASE_URL}/save`, {
+  const response = await fetch(`${API_BASE_URL()}/save`, {
     method: 'POST',
     headers: { 'Content-Type': 'application/json' },
     body: JSON.stringify(payload)
@@ -156,19 +157,19 @@ export const saveSchemaForDossier = async (payload: {
 };
 
 export const listSchemas = async (dossierId: string) => {
-  const res = await fetch(`${API_BASE_URL}/list?dossier_id=${encodeURIComponent(dossierId)}`);
+  const res = await fetch(`${API_BASE_URL()}/list?dossier_id=${encodeURIComponent(dossierId)}`);
   if (!res.ok) throw new Error(`HTTP ${res.status}: ${res.statusText}`);
   return res.json();
 };
 
 export const getSchema = async (dossierId: string, schemaId: string) => {
-  const res = await fetch(`${API_BASE_URL}/get?dossier_id=${encodeURIComponent(dossierId)}&schema_id=${encodeURIComponent(schemaId)}`);
+  const res = await fetch(`${API_BASE_URL()}/get?dossier_id=${encodeURIComponent(dossierId)}&schema_id=${encodeURIComponent(schemaId)}`);
   if (!res.ok) throw new Error(`HTTP ${res.status}: ${res.statusText}`);
   return res.json();
 };
 
 export const deleteSchema = async (dossierId: string, schemaId: string, force = false) => {
-  const res = await fetch(`${API_BASE_URL}/delete?dossier_id=${encodeURIComponent(dossierId)}&schema_id=${encodeURIComponent(schemaId)}&force=${force ? 'true' : 'false'}`, { method: 'DELETE' });
+  const res = await fetch(`${API_BASE_URL()}/delete?dossier_id=${encodeURIComponent(dossierId)}&schema_id=${encodeURIComponent(schemaId)}&force=${force ? 'true' : 'false'}`, { method: 'DELETE' });
   if (res.status === 409) {
     const body = await res.json().catch(() => ({}));
     const detail = (body && body.detail) || body;
@@ -179,7 +180,7 @@ export const deleteSchema = async (dossierId: string, schemaId: string, force =
 };
 
 export const bulkDeleteSchemas = async (items: Array<{ dossier_id: string; schema_id: string }>, force = false) => {
-  const res = await fetch(`${API_BASE_URL}/bulk-delete`, {
+  const res = await fetch(`${API_BASE_URL()}/bulk-delete`, {
     method: 'POST',
     headers: { 'Content-Type': 'application/json' },
     body: JSON.stringify({ items, force })
@@ -189,7 +190,7 @@ export const bulkDeleteSchemas = async (items: Array<{ dossier_id: string; schem
 };
 
 export const listAllSchemas = async () => {
-  const res = await fetch(`${API_BASE_URL}/list-all`);
+  const res = await fetch(`${API_BASE_URL()}/list-all`);
   if (!res.ok) throw new Error(`HTTP ${res.status}: ${res.statusText}`);
   return res.json();
 };
\ No newline at end of file
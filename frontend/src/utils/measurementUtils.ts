@@ -3,6 +3,8 @@
  * Mathematical functions for distance, bearing, and coordinate calculations
  */
 
+import { backendUrl } from '@/services/backendClient';
+
 export interface MeasurementPoint {
   lng: number;
   lat: number;
@@ -87,7 +89,7 @@ export async function calculateEndPointBackend(
   try {
     console.log(`🧮 Calling backend ${method} calculation...`);
 
-    const response = await fetch('http://localhost:8000/api/mapping/coordinates/calculate-endpoint', {
+    const response = await fetch(backendUrl('/api/mapping/coordinates/calculate-endpoint'), {
       method: 'POST',
       headers: {
         'Content-Type': 'application/json',